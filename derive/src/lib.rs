@@ -0,0 +1,190 @@
+//! # matetui-derive
+//!
+//! The derive macros of [matetui](https://docs.rs/matetui), re-exported by it with the `derive`
+//! feature. Use them through matetui rather than depending on this crate directly.
+
+use {
+    proc_macro::TokenStream,
+    proc_macro2::TokenStream as TokenStream2,
+    quote::{quote, ToTokens},
+    syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, LitStr, Member, Type},
+};
+
+/// Implements `ComponentAccessors` for a struct holding a `ComponentBase`, the state the framework
+/// keeps in every component, as an alternative to the `component!` macro for structs that need
+/// their own shape: generics, derives, doc comments, fields without a default...
+///
+/// The struct must have exactly one field of type `ComponentBase`. The optional `#[component]`
+/// attribute on the struct sets:
+/// - `actions = Type`: the custom actions of the app (see `UserAction`), `NoAction` by default.
+///   It can be a type parameter of the struct, to make a component generic over the actions.
+/// - `name = "name"`: the name of the component, the name of the struct by default.
+///
+/// ```ignore
+/// #[derive(ComponentAccessors)]
+/// #[component(actions = MyAction)]
+/// pub struct Editor {
+///     base: ComponentBase<MyAction>,
+///     path: PathBuf,
+/// }
+///
+/// impl Editor {
+///     pub fn new(path: PathBuf) -> Self {
+///         Self { base: ComponentBase::default(), path }
+///     }
+/// }
+/// ```
+#[proc_macro_derive(ComponentAccessors, attributes(component))]
+pub fn derive_component_accessors(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// The options of the `#[component]` attribute.
+struct Options {
+    actions: Option<Type>,
+    name: Option<LitStr>,
+}
+
+impl Options {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut options = Options {
+            actions: None,
+            name: None,
+        };
+        for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("component")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("actions") {
+                    options.actions = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("name") {
+                    options.name = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("expected `actions = Type` or `name = \"name\"`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+/// The member of the struct holding the `ComponentBase`.
+fn base_member(input: &DeriveInput) -> syn::Result<Member> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "ComponentAccessors can only be derived for structs",
+        ));
+    };
+    let is_base = |ty: &Type| match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|s| s.ident == "ComponentBase"),
+        _ => false,
+    };
+    let members: Vec<Member> = match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .filter(|field| is_base(&field.ty))
+            .filter_map(|field| field.ident.clone().map(Member::Named))
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| is_base(&field.ty))
+            .map(|(index, _)| Member::Unnamed(index.into()))
+            .collect(),
+        Fields::Unit => vec![],
+    };
+    match <[Member; 1]>::try_from(members) {
+        Ok([member]) => Ok(member),
+        Err(_) => Err(syn::Error::new(
+            input.ident.span(),
+            "ComponentAccessors needs exactly one field of type ComponentBase",
+        )),
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let options = Options::parse(&input)?;
+    let base = base_member(&input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let actions = match &options.actions {
+        Some(actions) => actions.to_token_stream(),
+        None => quote!(::matetui::NoAction),
+    };
+    let name = match &options.name {
+        Some(name) => name.value(),
+        None => ident.to_string(),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::matetui::ComponentAccessors<#actions> for #ident #ty_generics
+            #where_clause
+        {
+            fn name(&self) -> String {
+                #name.to_string()
+            }
+            fn is_active(&self) -> bool {
+                self.#base.is_active()
+            }
+            fn set_active(&mut self, active: bool) {
+                self.#base.set_active(active);
+                ::matetui::Component::<#actions>::on_active_changed(self, active);
+            }
+            fn is_focused(&self) -> bool {
+                self.#base.is_focused()
+            }
+            fn set_focused(&mut self, focused: bool) {
+                self.#base.set_focused(focused);
+                ::matetui::Component::<#actions>::on_focus_changed(self, focused);
+            }
+            fn is_visible(&self) -> bool {
+                self.#base.is_visible()
+            }
+            fn set_visible(&mut self, visible: bool) {
+                self.#base.set_visible(visible);
+            }
+            fn area(&self) -> Option<::matetui::ratatui::layout::Rect> {
+                self.#base.area()
+            }
+            fn set_area(&mut self, area: ::matetui::ratatui::layout::Rect) {
+                self.#base.set_area(area);
+            }
+            fn register_action_handler(
+                &mut self,
+                tx: ::tokio::sync::mpsc::UnboundedSender<::matetui::Action<#actions>>,
+            ) {
+                self.#base.set_action_sender(tx);
+            }
+            fn action_sender(
+                &self,
+            ) -> Option<::tokio::sync::mpsc::UnboundedSender<::matetui::Action<#actions>>> {
+                self.#base.action_sender()
+            }
+            fn register_state(&mut self, state: ::matetui::AppState) {
+                self.#base.set_state(state);
+            }
+            fn state(&self) -> &::matetui::AppState {
+                self.#base.state()
+            }
+            fn send(&self, action: &str) {
+                self.#base.send(action);
+            }
+            fn send_action(&self, action: ::matetui::Action<#actions>) {
+                self.#base.send_action(action);
+            }
+            fn as_active(mut self) -> Self {
+                ::matetui::ComponentAccessors::<#actions>::set_active(&mut self, true);
+                self
+            }
+            fn get_children(&mut self) -> Option<&mut ::matetui::Children<#actions>> {
+                Some(self.#base.children_mut())
+            }
+            fn get_children_ref(&self) -> Option<&::matetui::Children<#actions>> {
+                Some(self.#base.children())
+            }
+        }
+    })
+}
@@ -1,5 +1,5 @@
 use {
-    matetui::{component, Component},
+    matetui::{component, Component, ComponentError},
     ratatui::{
         layout::{Alignment, Constraint, Direction, Layout, Rect},
         style::Stylize,
@@ -58,7 +58,7 @@ impl Component for FpsComponent {
         None
     }
 
-    fn draw(&mut self, f: &mut matetui::Frame<'_>, area: Rect) {
+    fn draw(&mut self, f: &mut matetui::Frame<'_>, area: Rect) -> Result<(), ComponentError> {
         let rects = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![Constraint::Length(1), Constraint::Min(0)])
@@ -72,5 +72,6 @@ impl Component for FpsComponent {
         );
         let block = Block::default().title(Title::from(s.dim()).alignment(Alignment::Right));
         f.render_widget(block, rect);
+        Ok(())
     }
 }
@@ -1,14 +1,4 @@
-use {
-    crate::fps,
-    matetui::{
-        component,
-        ratatui::{
-            prelude::{Constraint, Layout, Line, Rect},
-            widgets::Paragraph,
-        },
-        Action, Component, ComponentAccessors,
-    },
-};
+use {crate::fps, matetui::prelude::*};
 
 component! {
     // mandatory struct definition
@@ -36,23 +26,20 @@ impl Component for Home {
     // implement the Component trait for the Home component
     fn receive_message(&mut self, message: String) {
         // react on drink-mate message received from the app
-        match message.as_str() {
-            "app:drink-mate" => {
-                self.drank_matetuis += 1;
-                // if I drank too much mate, we shound stop, too much mate, mate!
-
-                if self.drank_matetuis > 10 {
-                    self.send_action(Action::Quit);
-                    // we also have the
-                    // self.send("message") method to send a custom message to the event-bus
-                    // then other components can react to this message
-                }
+        if message == "app:drink-mate" {
+            self.drank_matetuis += 1;
+            // if I drank too much mate, we shound stop, too much mate, mate!
+
+            if self.drank_matetuis > 10 {
+                self.send_action(Action::Quit);
+                // we also have the
+                // self.send("message") method to send a custom message to the event-bus
+                // then other components can react to this message
             }
-            _ => {}
         }
     }
 
-    fn draw(&mut self, f: &mut matetui::Frame<'_>, area: ratatui::prelude::Rect) {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
         let [left, right] = self.layout(area);
         let lines = vec![
             Line::from("Hi!"),
@@ -65,8 +52,9 @@ impl Component for Home {
 
         // get the fps child component and draw it
         let fps = self.child_mut("fps-counter").unwrap();
-        fps.draw(f, right);
+        fps.render(f, right)?;
 
         f.render_widget(p, left);
+        Ok(())
     }
 }
@@ -14,8 +14,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_components(components![home])
         .with_frame_rate(24)
         .with_tick_rate(24)
+        .with_standard_bindings(true) // <ctrl-c> to quit, <ctrl-z> to suspend, <ctrl-l> to redraw
         .with_keybindings(kb![
-            "<ctrl-c>" => Action::Quit, // quit the app when pressing ctrl-c
             "<q>" => Action::Quit,      // quit the app when pressing q
             "<d>" => "app:drink-mate"   // send custom message when pressing d (all components will
                                         // receive this message and act accordingly)
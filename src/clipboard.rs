@@ -0,0 +1,168 @@
+//! # Clipboard
+//!
+//! The [`Clipboard`] copies text to the system clipboard, so the text copied in the app can be
+//! pasted in other programs. There is one clipboard per process, like the [`Theme`](crate::theme):
+//! components use it through
+//! [`ComponentAccessors::copy_to_clipboard`](crate::ComponentAccessors::copy_to_clipboard), and
+//! the textareas with the system clipboard (see
+//! [`TextArea::with_system_clipboard`](crate::widgets::textarea::TextArea::with_system_clipboard))
+//! copy, cut and paste through it.
+//!
+//! By default, the text is copied with the OSC 52 escape sequence (see [`Osc52`]), which most
+//! terminals support, also over ssh. Terminals don't let apps read the clipboard that way, so
+//! reading it returns the last text copied by the app, and the text copied in other programs comes
+//! in as [paste events](crate::App::with_paste). A [`ClipboardProvider`] reading and writing the
+//! system clipboard directly (e.g. on top of the `arboard` crate) can be set instead:
+//!
+//! ```ignore
+//! struct Arboard;
+//!
+//! impl ClipboardProvider for Arboard {
+//!     fn set_text(&self, text: &str) -> std::io::Result<()> {
+//!         let mut clipboard = arboard::Clipboard::new().map_err(std::io::Error::other)?;
+//!         clipboard.set_text(text).map_err(std::io::Error::other)
+//!     }
+//!
+//!     fn get_text(&self) -> Option<String> {
+//!         arboard::Clipboard::new().and_then(|mut c| c.get_text()).ok()
+//!     }
+//! }
+//!
+//! Clipboard::set_provider(Arboard);
+//! ```
+
+use std::{
+    io::{IsTerminal, Write},
+    sync::{Mutex, RwLock},
+};
+
+static PROVIDER: RwLock<Option<Box<dyn ClipboardProvider>>> = RwLock::new(None);
+static LAST_COPIED: Mutex<Option<String>> = Mutex::new(None);
+
+/// A system clipboard the [`Clipboard`] reads and writes text through.
+pub trait ClipboardProvider: Send + Sync {
+    /// Copy the text to the clipboard.
+    fn set_text(&self, text: &str) -> std::io::Result<()>;
+
+    /// The text of the clipboard, or `None` if it can't be read.
+    fn get_text(&self) -> Option<String>;
+}
+
+/// The [`ClipboardProvider`] writing the clipboard through the terminal, with the OSC 52 escape
+/// sequence. It can't read the clipboard. Nothing is written when stdout isn't a terminal.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Osc52;
+
+impl ClipboardProvider for Osc52 {
+    fn set_text(&self, text: &str) -> std::io::Result<()> {
+        let mut stdout = std::io::stdout();
+        if !stdout.is_terminal() {
+            return Ok(());
+        }
+        write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+        stdout.flush()
+    }
+
+    fn get_text(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The clipboard of the process.
+pub struct Clipboard;
+
+impl Clipboard {
+    /// Set the provider of the clipboard. Defaults to [`Osc52`].
+    pub fn set_provider(provider: impl ClipboardProvider + 'static) {
+        *PROVIDER.write().unwrap() = Some(Box::new(provider));
+    }
+
+    /// Copy the text to the clipboard.
+    pub fn copy(text: &str) -> std::io::Result<()> {
+        *LAST_COPIED.lock().unwrap() = Some(text.to_string());
+        match PROVIDER.read().unwrap().as_ref() {
+            Some(provider) => provider.set_text(text),
+            None => Osc52.set_text(text),
+        }
+    }
+
+    /// The text of the clipboard or, if the provider can't read it, the last text copied by the
+    /// app, if any.
+    pub fn text() -> Option<String> {
+        let text = PROVIDER.read().unwrap().as_ref().and_then(|provider| provider.get_text());
+        text.or_else(|| LAST_COPIED.lock().unwrap().clone())
+    }
+}
+
+/// Encode the bytes in base64 (with padding), as OSC 52 expects them.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let byte = |i: usize| u32::from(chunk.get(i).copied().unwrap_or(0));
+        let n = byte(0) << 16 | byte(1) << 8 | byte(2);
+        for i in 0..4 {
+            encoded.push(match i <= chunk.len() {
+                true => ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char,
+                false => '=',
+            });
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::sync::Arc};
+
+    #[derive(Clone, Default)]
+    struct Memory(Arc<Mutex<Vec<String>>>);
+
+    impl ClipboardProvider for Memory {
+        fn set_text(&self, text: &str) -> std::io::Result<()> {
+            self.0.lock().unwrap().push(text.to_string());
+            Ok(())
+        }
+
+        fn get_text(&self) -> Option<String> {
+            self.0.lock().unwrap().last().map(|text| format!("system {text}"))
+        }
+    }
+
+    #[test]
+    fn encodes_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"m"), "bQ==");
+        assert_eq!(base64(b"ma"), "bWE=");
+        assert_eq!(base64(b"mate"), "bWF0ZQ==");
+        assert_eq!(base64("mate cocido ☕".as_bytes()), "bWF0ZSBjb2NpZG8g4piV");
+    }
+
+    // the clipboard is global, so everything using it is tested here
+    #[test]
+    fn copies_through_the_provider() -> std::io::Result<()> {
+        Clipboard::copy("mate")?;
+        assert_eq!(Clipboard::text().as_deref(), Some("mate"));
+
+        let memory = Memory::default();
+        Clipboard::set_provider(memory.clone());
+        Clipboard::copy("cocido")?;
+        assert_eq!(*memory.0.lock().unwrap(), ["cocido"]);
+        assert_eq!(Clipboard::text().as_deref(), Some("system cocido"));
+
+        #[cfg(feature = "widget-textarea")]
+        {
+            use crate::widgets::textarea::TextArea;
+
+            let mut textarea = TextArea::from(["dulce"]).with_system_clipboard(true);
+            textarea.select_word();
+            textarea.copy();
+            assert_eq!(*memory.0.lock().unwrap(), ["cocido", "dulce"]);
+
+            assert!(textarea.paste());
+            assert_eq!(textarea.lines(), ["dulcesystem dulce"]);
+        }
+        Ok(())
+    }
+}
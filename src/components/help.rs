@@ -0,0 +1,214 @@
+//! # Help
+//!
+//! This module contains the [`Help`] component, which lists the keybindings of the app, global
+//! and scoped ones, with their descriptions, like the `?` screen of lazygit:
+//!
+//! ```ignore
+//! let mut app = App::default()
+//!     .with_keybindings(kb![
+//!         "<?>" => help::TOGGLE => "show the keybindings",
+//!         "<ctrl-s>" => "app:save" => "save the file",
+//!         "<q>" => Action::Quit
+//!     ])
+//!     .with_components(components![home])
+//!     .with_layer(Layer::Overlay, components![Help::default().as_active()]);
+//! ```
+//!
+//! The help starts hidden, and is shown and hidden with the [`TOGGLE`] message. When shown,
+//! `<up>`/`<down>` (or `<k>`/`<j>`) and `<pageup>`/`<pagedown>` scroll the list, and `<esc>` or
+//! `<q>` close it. Bindings without a description (see [`kb`](crate::kb)) show their action.
+
+use {
+    crate::{
+        component, styles,
+        theme::Theme,
+        utils::{keyboard::key_sequence_to_display, text},
+        Action, Component, ComponentAccessors, ComponentError, Frame, KeyBindingHelp, UserAction,
+    },
+    crossterm::event::{KeyCode, KeyEvent},
+    ratatui::{
+        layout::{Constraint, Flex, Layout, Rect},
+        text::{Line, Span},
+        widgets::{Block, Borders, Clear, Paragraph},
+    },
+};
+
+/// The message toggling the [`Help`], to bind to a key: `"<?>" => help::TOGGLE`.
+pub const TOGGLE: &str = "help:toggle";
+
+component! {
+    /// A panel, centered in its area, listing the keybindings of the app.
+    pub struct Help<A> {
+        title: Option<String>,
+        visible: bool,
+        scroll: usize,
+        page: usize,
+    }
+}
+
+impl<A: UserAction> Help<A> {
+    /// Set the title of the panel. Defaults to `keybindings`.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Show the help right away, instead of waiting for the [`TOGGLE`] message.
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Returns `true` if the help is shown.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        self.scroll = 0;
+        self.request_render();
+    }
+
+    /// The keybindings of the app (see [App::keybindings_help](crate::App::keybindings_help)).
+    fn bindings(&self) -> Vec<KeyBindingHelp> {
+        self.state().read(|help: &Vec<KeyBindingHelp>| help.clone()).unwrap_or_default()
+    }
+
+    /// The lines listing the bindings, grouped by scope.
+    fn lines(&self) -> Vec<Line<'static>> {
+        let bindings = self.bindings();
+        let glyphs = Theme::read(|t| t.key_glyphs);
+        let keys = bindings.iter().map(|b| key_sequence_to_display(&b.keys, glyphs));
+        let keys = keys.collect::<Vec<_>>();
+        let column = keys.iter().map(|k| text::width(k)).max().unwrap_or_default();
+
+        let mut lines = vec![];
+        let mut scope = None;
+        for (binding, keys) in bindings.iter().zip(keys) {
+            if lines.is_empty() || binding.scope != scope {
+                scope = binding.scope.clone();
+                if !lines.is_empty() {
+                    lines.push(Line::default());
+                }
+                let header = scope.as_deref().unwrap_or("global").to_string();
+                lines.push(Line::styled(header, styles::accent()));
+            }
+
+            let action = match &binding.description {
+                Some(description) => Span::styled(description.clone(), styles::text()),
+                None => Span::styled(binding.action.clone(), styles::hint()),
+            };
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(text::pad_right(&keys, column), styles::accent()),
+                Span::raw("  "),
+                action,
+            ]));
+        }
+
+        if lines.is_empty() {
+            lines.push(Line::styled("no keybindings", styles::hint()));
+        }
+        lines
+    }
+}
+
+impl<A: UserAction> Component<A> for Help<A> {
+    fn subscriptions(&self) -> Option<Vec<&str>> {
+        Some(vec![TOGGLE])
+    }
+
+    fn receive_message(&mut self, message: String) {
+        if message == TOGGLE {
+            self.set_visible(!self.visible);
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Option<Action<A>> {
+        if !self.visible {
+            return None;
+        }
+
+        let page = self.page.max(1);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll += 1,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(page),
+            KeyCode::PageDown => self.scroll += page,
+            KeyCode::Esc | KeyCode::Char('q') => self.set_visible(false),
+            _ => return None,
+        }
+        self.request_render();
+        None
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        let lines = self.lines();
+        let width = lines.iter().map(Line::width).max().unwrap_or_default() as u16 + 4;
+        let height = lines.len() as u16 + 2;
+        let [area] = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(area);
+
+        let title = self.title.as_deref().unwrap_or("keybindings");
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(styles::border_focused())
+            .title(format!(" {title} "));
+        let inner = block.inner(area);
+
+        self.page = inner.height as usize;
+        self.scroll = self.scroll.min(lines.len().saturating_sub(self.page));
+        let list = Paragraph::new(lines).scroll((self.scroll as u16, 0));
+
+        f.render_widget(Clear, area);
+        f.render_widget(list.block(block), area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{components, kb, kb_scoped, App, MatetuiError, NoAction},
+    };
+
+    #[tokio::test]
+    async fn lists_the_keybindings_by_scope() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb![
+                "<?>" => TOGGLE => "show the keybindings",
+                "<ctrl-s>" => "app:save" => "save the file",
+                "<ctrl-q>" => Action::Quit
+            ])
+            .with_scoped_keybindings(kb_scoped! {
+                "editor" => { "<ctrl-z>" => "app:undo" => "undo" }
+            })
+            .with_components(components![Help::<NoAction>::default().as_active()])
+            .test()?;
+
+        app.press("<?>")?;
+        let lines = app.lines().iter().map(|l| l.trim().to_string()).collect::<Vec<_>>();
+        let start = lines.iter().position(|l| l.contains("keybindings")).unwrap();
+        assert_eq!(
+            lines[start + 1..start + 7],
+            [
+                "│global                      │",
+                "│  ?   show the keybindings  │",
+                "│  ⌃Q  quit                  │",
+                "│  ⌃S  save the file         │",
+                "│                            │",
+                "│editor                      │",
+            ]
+        );
+
+        app.press("<esc>")?;
+        assert!(!app.contains("keybindings"));
+        Ok(())
+    }
+}
@@ -0,0 +1,217 @@
+//! # Component inspector
+//!
+//! This module contains the [`Inspector`] development overlay.
+//!
+//! The inspector draws the bounding area, name, path and active/focus state of the component under
+//! the mouse cursor, on top of the app. It is installed in the [`App`](crate::App):
+//!
+//! ```ignore
+//! let mut app = App::default()
+//!     .with_mouse(true)
+//!     .with_inspector(Inspector::default());
+//! ```
+//!
+//! The inspector reacts to the following keys:
+//! - `<alt-i>` shows/hides the overlay
+//! - `<alt-n>` inspects the next component of the tree (instead of the one under the cursor)
+//! - `<alt-b>` inspects the previous component of the tree
+//!
+//! Moving the mouse goes back to inspecting the component under the cursor. The area of a
+//! component is the one it was last [rendered](crate::Component::render) into.
+//!
+//! The top right corner shows the latency of the app (see [`InputLatency`]): how long it took to
+//! process the last input event, and to draw it.
+
+use {
+    crate::{
+        framework::component::ComponentHandler, Component, Event, FocusPath, Frame, InputLatency,
+        UserAction,
+    },
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEventKind},
+    ratatui::{
+        layout::{Position, Rect},
+        style::{Color, Style, Stylize},
+        text::Line,
+        widgets::{Block, Borders, Clear, Paragraph},
+    },
+};
+
+/// A component of the tree, as seen by the [`Inspector`].
+#[derive(Clone, Debug)]
+struct Inspected {
+    path: FocusPath,
+    name: String,
+    area: Option<Rect>,
+    active: bool,
+    focused: bool,
+}
+
+/// A development overlay that shows the area and the state of the components of the app.
+#[derive(Clone, Debug, Default)]
+pub struct Inspector {
+    hidden: bool,
+    mouse: Option<Position>,
+    cycled: Option<usize>,
+    step: isize,
+}
+
+impl Inspector {
+    /// Create a new, visible, [`Inspector`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the overlay is visible. It is visible by default.
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.hidden = !visible;
+        self
+    }
+
+    /// Returns `true` if the overlay is visible.
+    pub fn is_visible(&self) -> bool {
+        !self.hidden
+    }
+
+    pub(crate) fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::Mouse(mouse) if matches!(mouse.kind, MouseEventKind::Moved) => {
+                self.mouse = Some(Position::new(mouse.column, mouse.row));
+                self.cycled = None;
+                self.step = 0;
+            }
+            Event::Key(key) => self.handle_key(key),
+            _ => {}
+        }
+    }
+
+    fn handle_key(&mut self, key: &KeyEvent) {
+        if !key.modifiers.contains(KeyModifiers::ALT) {
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('i') => self.hidden = !self.hidden,
+            KeyCode::Char('n') => self.step += 1,
+            KeyCode::Char('b') => self.step -= 1,
+            _ => {}
+        }
+    }
+
+    pub(crate) fn draw<A: UserAction>(
+        &mut self,
+        f: &mut Frame<'_>,
+        handlers: &mut [ComponentHandler<A>],
+        latency: &InputLatency,
+    ) {
+        if self.hidden {
+            return;
+        }
+
+        draw_latency(f, latency);
+
+        let mut tree = vec![];
+        for (root, handler) in handlers.iter_mut().enumerate() {
+            collect(handler.component_mut(), FocusPath::root(root), &mut tree);
+        }
+
+        if self.step != 0 && !tree.is_empty() {
+            let len = tree.len() as isize;
+            let current = match self.cycled {
+                Some(i) => i as isize,
+                None if self.step > 0 => -1,
+                None => len,
+            };
+            self.cycled = Some((current + self.step).rem_euclid(len) as usize);
+            self.step = 0;
+        }
+
+        let inspected = match self.cycled {
+            Some(i) => tree.get(i),
+            // the deepest component under the cursor
+            None => self.mouse.and_then(|mouse| {
+                tree.iter().rev().find(|c| c.area.is_some_and(|area| area.contains(mouse)))
+            }),
+        };
+
+        if let Some(inspected) = inspected {
+            draw_inspected(f, inspected);
+        }
+    }
+}
+
+fn collect<A: UserAction>(c: &mut dyn Component<A>, path: FocusPath, out: &mut Vec<Inspected>) {
+    out.push(Inspected {
+        name: c.name(),
+        // the area of a hidden component is the one it had when last drawn
+        area: c.area().filter(|_| c.is_visible()),
+        active: c.is_active(),
+        focused: c.is_focused(),
+        path: path.clone(),
+    });
+
+    if let Some(children) = c.get_children() {
+        for (name, child) in children.iter_mut() {
+            collect(child.as_mut(), path.child(name.clone()), out);
+        }
+    }
+}
+
+fn draw_inspected(f: &mut Frame<'_>, inspected: &Inspected) {
+    let screen = f.area();
+    let path = std::iter::once(inspected.path.root.to_string())
+        .chain(inspected.path.children.iter().cloned())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let mut info = vec![inspected.name.clone().bold(), format!(" {path}").into()];
+    match inspected.area {
+        Some(a) => info.push(format!(" {}x{} @ ({}, {})", a.width, a.height, a.x, a.y).into()),
+        None => info.push(" not rendered".into()),
+    }
+    info.push(if inspected.active {
+        " active".green()
+    } else {
+        " inactive".red()
+    });
+    if inspected.focused {
+        info.push(" focused".yellow());
+    }
+
+    let line = Line::from(info);
+
+    if let Some(area) = inspected.area.map(|a| a.intersection(screen)) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta));
+        f.render_widget(block, area);
+    }
+
+    // the info label goes right above the component, or below it if there is no room
+    let width = (line.width() as u16).min(screen.width);
+    let y = match inspected.area {
+        Some(a) if a.y > screen.y => a.y - 1,
+        Some(a) if a.bottom() < screen.bottom() => a.bottom(),
+        _ => screen.y,
+    };
+    let x = inspected.area.map_or(screen.x, |a| a.x.min(screen.right().saturating_sub(width)));
+    let label = Rect::new(x, y, width, 1).intersection(screen);
+
+    f.render_widget(Clear, label);
+    f.render_widget(
+        Paragraph::new(line).style(Style::default().bg(Color::Magenta).fg(Color::Black)),
+        label,
+    );
+}
+
+fn draw_latency(f: &mut Frame<'_>, latency: &InputLatency) {
+    let screen = f.area();
+    let line = Line::from(format!(" {latency} "));
+    let width = (line.width() as u16).min(screen.width);
+    let area = Rect::new(screen.right() - width, screen.y, width, screen.height.min(1));
+
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(line).style(Style::default().bg(Color::DarkGray).fg(Color::White)),
+        area,
+    );
+}
@@ -0,0 +1,133 @@
+//! # Log console
+//!
+//! This module contains the [`LogConsole`] component, which shows the [`log`] records captured by
+//! a [`LogBuffer`], as stdout is taken over by the alternate screen while the app runs:
+//!
+//! ```ignore
+//! let logs = LogBuffer::default();
+//! logs.clone().install(LevelFilter::Debug)?;
+//!
+//! let mut app = App::default()
+//!     .with_keybindings(kb!["<f12>" => log_console::TOGGLE])
+//!     .with_components(components![home])
+//!     .with_layer(Layer::Overlay, components![LogConsole::new(logs).as_active()]);
+//! ```
+//!
+//! The console starts hidden, and is shown and hidden with the [`TOGGLE`] message.
+
+use {
+    crate::{
+        component, styles, Component, ComponentAccessors, ComponentError, Frame, LogBuffer,
+        UserAction,
+    },
+    log::{Level, LevelFilter},
+    ratatui::{
+        layout::{Constraint, Layout, Rect},
+        text::{Line, Span},
+        widgets::{Block, Borders, Clear, Paragraph},
+    },
+};
+
+/// The message toggling the [`LogConsole`], to bind to a key: `"<f12>" => log_console::TOGGLE`.
+pub const TOGGLE: &str = "logconsole:toggle";
+
+component! {
+    /// A console showing the latest log records at the bottom of its area.
+    pub struct LogConsole<A> {
+        logs: LogBuffer,
+        height: Option<u16>,
+        level: Option<LevelFilter>,
+        visible: bool,
+    }
+}
+
+impl<A: UserAction> LogConsole<A> {
+    /// Create a new [`LogConsole`] showing the records of the given [`LogBuffer`].
+    pub fn new(logs: LogBuffer) -> Self {
+        Self {
+            logs,
+            ..Self::default()
+        }
+    }
+
+    /// Set the number of rows of the console, borders included. Defaults to 10.
+    pub fn with_height(mut self, rows: u16) -> Self {
+        self.height = Some(rows);
+        self
+    }
+
+    /// Only show the records up to the given level. Defaults to all of the captured ones.
+    pub fn with_level(mut self, level: LevelFilter) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Show the console right away, instead of waiting for the [`TOGGLE`] message.
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Returns `true` if the console is shown.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn level_style(level: Level) -> ratatui::style::Style {
+        match level {
+            Level::Error => styles::error(),
+            Level::Warn => styles::warning(),
+            Level::Info => styles::accent(),
+            Level::Debug | Level::Trace => styles::hint(),
+        }
+    }
+}
+
+impl<A: UserAction> Component<A> for LogConsole<A> {
+    fn subscriptions(&self) -> Option<Vec<&str>> {
+        Some(vec![TOGGLE])
+    }
+
+    fn receive_message(&mut self, message: String) {
+        if message == TOGGLE {
+            self.visible = !self.visible;
+            self.request_render();
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        let height = self.height.unwrap_or(10);
+        let area =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(height)]).split(area)[1];
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(styles::border())
+            .title(format!(" logs · {} ", self.logs.len()));
+        let inner = block.inner(area);
+
+        let level = self.level.unwrap_or(LevelFilter::Trace);
+        let entries =
+            self.logs.entries().into_iter().filter(|e| e.level <= level).collect::<Vec<_>>();
+        let skip = entries.len().saturating_sub(inner.height as usize);
+        let lines = entries
+            .iter()
+            .skip(skip)
+            .map(|e| {
+                Line::from(vec![
+                    Span::styled(format!("{:>9.3}s ", e.elapsed.as_secs_f64()), styles::hint()),
+                    Span::styled(format!("{:<6}", e.level), Self::level_style(e.level)),
+                    Span::styled(format!("{} ", e.target), styles::hint()),
+                    Span::from(e.message.clone()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+        Ok(())
+    }
+}
@@ -0,0 +1,401 @@
+//! # Command palette
+//!
+//! This module contains the [`CommandPalette`], an overlay that fuzzy-searches the commands of the
+//! app and dispatches the action of the picked one, like the command palette of code editors. It
+//! is installed in the [`App`](crate::App), along with the commands of the app:
+//!
+//! ```ignore
+//! let mut app = App::default()
+//!     .with_command_palette(CommandPalette::default().with_key("<ctrl-k>"))
+//!     .with_commands(vec![Command::new("Quit", Action::Quit)])
+//!     .with_components(components![editor]);
+//! ```
+//!
+//! The active components add their own commands (see [`Component::commands`]). The palette opens
+//! with `<ctrl-p>` by default, before the keybindings of the app. Once open, it takes all the keys:
+//! typing filters the commands, `<up>`/`<down>` select one, `<enter>` dispatches its action, and
+//! `<esc>` or the key of the palette close it. The keys bound to the action of a command are shown
+//! next to it.
+
+use {
+    crate::{
+        styles,
+        utils::{
+            keyboard::parse_key_sequence,
+            text::{self, fuzzy_match},
+        },
+        Action, Command, Frame, KeyBindingConflict, NoAction, UserAction,
+    },
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    ratatui::{
+        layout::Rect,
+        style::Modifier,
+        text::{Line, Span},
+        widgets::{Block, Borders, Clear, Paragraph},
+    },
+};
+
+/// The number of commands listed at once.
+const VISIBLE: usize = 10;
+
+/// A command, with the keys bound to its action, as listed by the [`CommandPalette`].
+#[derive(Clone, Debug)]
+struct Entry<A: UserAction> {
+    command: Command<A>,
+    keys: String,
+}
+
+/// An overlay listing the commands of the app, to search and run them from the keyboard.
+#[derive(Clone, Debug)]
+pub struct CommandPalette<A: UserAction = NoAction> {
+    /// The key opening the palette, or why the one given to [CommandPalette::with_key] isn't.
+    key: Result<KeyEvent, KeyBindingConflict>,
+    title: Option<String>,
+    open: bool,
+    query: String,
+    entries: Vec<Entry<A>>,
+    /// The entries matching the query, best first, with the indices of their matched chars.
+    matches: Vec<(usize, Vec<usize>)>,
+    selected: usize,
+}
+
+impl<A: UserAction> Default for CommandPalette<A> {
+    fn default() -> Self {
+        Self {
+            key: Ok(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
+            title: None,
+            open: false,
+            query: String::new(),
+            entries: Vec::new(),
+            matches: Vec::new(),
+            selected: 0,
+        }
+    }
+}
+
+impl<A: UserAction> CommandPalette<A> {
+    /// Create a new [`CommandPalette`], opened with `<ctrl-p>`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the key opening the palette, e.g. `<ctrl-k>`. If `key` is not a single valid key (see
+    /// [parse_key_sequence]), the palette doesn't open with any key, and the key is reported by
+    /// [App::keybinding_conflicts](crate::App::keybinding_conflicts).
+    pub fn with_key(mut self, key: &str) -> Self {
+        let error = match parse_key_sequence(key).as_deref() {
+            Ok([parsed]) => {
+                self.key = Ok(*parsed);
+                return self;
+            }
+            Ok(_) => "the command palette opens with a single key".to_string(),
+            Err(err) => err.to_string(),
+        };
+        self.key = Err(KeyBindingConflict::Invalid {
+            keys: key.to_string(),
+            error,
+        });
+        self
+    }
+
+    /// Set the title of the palette. Defaults to `commands`.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Returns `true` if the palette is open.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// The query typed in the palette.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns `true` if the key opens the palette, while it's closed.
+    pub(crate) fn opens_with(&self, key: &KeyEvent) -> bool {
+        !self.open && self.key.as_ref() == Ok(key)
+    }
+
+    /// The key given to [CommandPalette::with_key], if it's invalid.
+    pub(crate) fn key_conflict(&self) -> Option<&KeyBindingConflict> {
+        self.key.as_ref().err()
+    }
+
+    /// Open the palette with the commands, and the keys bound to their actions.
+    pub(crate) fn open(&mut self, commands: Vec<(Command<A>, String)>) {
+        self.entries =
+            commands.into_iter().map(|(command, keys)| Entry { command, keys }).collect();
+        self.open = true;
+        self.query.clear();
+        self.filter();
+    }
+
+    fn close(&mut self) {
+        self.open = false;
+        self.entries.clear();
+        self.matches.clear();
+    }
+
+    /// Handle a key while the palette is open, returning the action of the picked command.
+    pub(crate) fn handle_key(&mut self, key: KeyEvent) -> Option<Action<A>> {
+        if self.key.as_ref() == Ok(&key) {
+            self.close();
+            return None;
+        }
+
+        let modified = key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Esc => self.close(),
+            KeyCode::Enter => {
+                let action = self.selected_command().map(|command| command.action().clone());
+                self.close();
+                return action;
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1));
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.filter();
+            }
+            KeyCode::Char(c) if !modified => {
+                self.query.push(c);
+                self.filter();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Append pasted text to the query.
+    pub(crate) fn paste(&mut self, text: &str) {
+        self.query.extend(text.chars().filter(|c| !c.is_control()));
+        self.filter();
+    }
+
+    /// The selected command, if any command matches the query.
+    pub fn selected_command(&self) -> Option<&Command<A>> {
+        let (index, _) = self.matches.get(self.selected)?;
+        Some(&self.entries[*index].command)
+    }
+
+    fn filter(&mut self) {
+        let mut matches = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| {
+                let (score, chars) = fuzzy_match(&self.query, entry.command.name())?;
+                Some((score, i, chars))
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by(|(a_score, a, _), (b_score, b, _)| {
+            let name = |i: &usize| self.entries[*i].command.name();
+            b_score.cmp(a_score).then_with(|| name(a).cmp(name(b)))
+        });
+
+        self.matches = matches.into_iter().map(|(_, i, chars)| (i, chars)).collect();
+        self.selected = 0;
+    }
+
+    pub(crate) fn draw(&self, f: &mut Frame<'_>) {
+        if !self.open {
+            return;
+        }
+
+        let screen = f.area();
+        let rows = self.matches.len().clamp(1, VISIBLE) as u16;
+        let width = screen.width.saturating_sub(4).min(64);
+        let height = (rows + 4).min(screen.height);
+        let x = screen.x + (screen.width - width) / 2;
+        let y = screen.y + (screen.height - height) / 5;
+        let area = Rect::new(x, y, width, height);
+
+        let title = self.title.as_deref().unwrap_or("commands");
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(styles::border_focused())
+            .title(format!(" {title} "));
+        let inner = block.inner(area);
+        let columns = inner.width as usize;
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("> ", styles::accent()),
+                Span::styled(self.query.clone(), styles::text()),
+                Span::styled(" ", styles::selection()),
+            ]),
+            Line::styled("─".repeat(columns), styles::border()),
+        ];
+
+        // keep the selected command in view
+        let offset = self.selected.saturating_sub(VISIBLE - 1);
+        for (row, (index, chars)) in self.matches.iter().enumerate().skip(offset).take(VISIBLE) {
+            let line = self.entry_line(&self.entries[*index], chars, columns);
+            lines.push(if row == self.selected {
+                line.patch_style(styles::selection())
+            } else {
+                line
+            });
+        }
+        if self.matches.is_empty() {
+            lines.push(Line::styled("no matching commands", styles::hint()));
+        }
+
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    /// The line of a command: its name, with the chars matching the query highlighted, its
+    /// description, and the keys bound to it on the right.
+    fn entry_line(&self, entry: &Entry<A>, matched: &[usize], columns: usize) -> Line<'static> {
+        let highlight = styles::accent().add_modifier(Modifier::BOLD);
+        let name = entry.command.name();
+        let mut spans = name
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let style = if matched.contains(&i) {
+                    highlight
+                } else {
+                    styles::text()
+                };
+                Span::styled(c.to_string(), style)
+            })
+            .collect::<Vec<_>>();
+
+        let keys = if entry.keys.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", entry.keys)
+        };
+        let used = text::width(name) + text::width(&keys);
+        if let Some(description) = entry.command.description() {
+            let room = columns.saturating_sub(used + 2);
+            if room > 1 {
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(text::truncate(description, room), styles::hint()));
+            }
+        }
+
+        let used = spans.iter().map(Span::width).sum::<usize>() + text::width(&keys);
+        spans.push(Span::raw(" ".repeat(columns.saturating_sub(used))));
+        spans.push(Span::styled(keys, styles::hint()));
+        Line::from(spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            component, components, App, Component, ComponentAccessors, ComponentError, MatetuiError,
+        },
+    };
+
+    component! {
+        struct Editor {
+            saved: usize,
+        }
+    }
+
+    impl Component for Editor {
+        fn commands(&self) -> Vec<Command> {
+            vec![Command::new("Save file", "editor:save").with_description("write to disk")]
+        }
+
+        fn receive_message(&mut self, message: String) {
+            if message == "editor:save" {
+                self.saved += 1;
+            }
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("saved {}", self.saved)), area);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn searches_and_dispatches_commands() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(crate::kb!["<ctrl-s>" => "editor:save"])
+            .with_command_palette(CommandPalette::default())
+            .with_commands(vec![
+                Command::new("Quit", Action::Quit),
+                Command::new("Split view", "view:split"),
+            ])
+            .with_components(components![Editor::default().as_active()])
+            .test()?;
+
+        app.press("<ctrl-p>")?;
+        assert!(app.contains("commands"));
+        assert!(app.contains("Quit"));
+
+        app.type_text("sf")?;
+        assert!(app.contains("Save file  write to disk"));
+        assert!(app.contains("⌃S"));
+        assert!(!app.contains("Quit"));
+        assert!(!app.contains("Split view"));
+
+        // the keys go to the palette while it's open
+        app.press("<enter>")?;
+        assert!(!app.contains("commands"));
+        assert!(app.contains("saved 1"));
+
+        app.press("<ctrl-p>")?;
+        app.type_text("zzz")?;
+        assert!(app.contains("no matching commands"));
+        app.press("<esc>")?;
+        assert!(!app.contains("commands"));
+        assert!(!app.has_quit());
+        Ok(())
+    }
+
+    #[test]
+    fn ranks_word_starts_first() {
+        let mut palette = CommandPalette::<NoAction>::new().with_key("<ctrl-k>");
+        palette.open(vec![
+            (Command::new("misfire", "a"), String::new()),
+            (Command::new("Save file", "b"), String::new()),
+        ]);
+        palette.paste("sf");
+        assert_eq!(palette.selected_command().map(Command::name), Some("Save file"));
+
+        palette.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        assert_eq!(palette.selected_command().map(Command::name), Some("misfire"));
+        palette.handle_key(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
+        assert!(!palette.is_open());
+    }
+
+    #[tokio::test]
+    async fn reports_the_keys_it_cant_open_with() -> Result<(), MatetuiError> {
+        let app = App::<NoAction>::default()
+            .with_command_palette(CommandPalette::default().with_key("<ctrl-k><ctrl-p>"));
+        assert_eq!(
+            app.keybinding_conflicts(),
+            [KeyBindingConflict::Invalid {
+                keys: "<ctrl-k><ctrl-p>".into(),
+                error: "the command palette opens with a single key".into()
+            }]
+        );
+
+        let app = App::default()
+            .with_command_palette(CommandPalette::default().with_key("<ctrl-k"))
+            .with_components(components![Editor::default().as_active()]);
+        assert!(matches!(
+            &app.keybinding_conflicts()[..],
+            [KeyBindingConflict::Invalid { keys, .. }] if keys == "<ctrl-k"
+        ));
+        // without a key, rather than with the default one
+        let mut app = app.test()?;
+        app.press("<ctrl-p>")?;
+        assert!(!app.contains("commands"));
+        Ok(())
+    }
+}
@@ -0,0 +1,451 @@
+//! # Passthrough region
+//!
+//! This module contains the [`Passthrough`] component, which embeds an external terminal program
+//! (e.g. `htop` or a shell) into a region of the app.
+//!
+//! The component doesn't spawn the program itself: it renders the raw bytes written by the program
+//! to its pseudo terminal, and forwards the keys pressed while it's focused back to it. This keeps
+//! the framework independent of the PTY implementation (e.g. `portable-pty`):
+//!
+//! ```ignore
+//! let (output_tx, output_rx) = mpsc::unbounded_channel(); // bytes read from the pty
+//! let (input_tx, mut input_rx) = mpsc::unbounded_channel(); // events for the pty
+//!
+//! let pane = Passthrough::new(output_rx, input_tx);
+//!
+//! // in the task that owns the pty
+//! match input_rx.recv().await {
+//!     Some(PassthroughEvent::Input(bytes)) => writer.write_all(&bytes)?,
+//!     Some(PassthroughEvent::Resize(cols, rows)) => pty.resize(PtySize { cols, rows, .. })?,
+//!     None => break,
+//! }
+//! ```
+//!
+//! The component understands the most common control sequences (cursor movement, erasing and
+//! colors), which is enough for shells and most line based programs. The keybindings of the app
+//! still apply while the component is focused.
+
+use {
+    crate::{component, Action, Component, ComponentAccessors, ComponentError, Frame, UserAction},
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    ratatui::{
+        layout::Rect,
+        style::{Color, Modifier, Style},
+    },
+    tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender},
+};
+
+/// An event sent by the [`Passthrough`] component to the program it embeds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PassthroughEvent {
+    /// Bytes of the keys pressed while the component is focused, to write to the pty.
+    Input(Vec<u8>),
+    /// The new size (columns, rows) of the region, to resize the pty.
+    Resize(u16, u16),
+}
+
+component! {
+    /// A component rendering the output of an external terminal program, forwarding the input to
+    /// it while focused.
+    pub struct Passthrough<A> {
+        output: Option<UnboundedReceiver<Vec<u8>>>,
+        input: Option<UnboundedSender<PassthroughEvent>>,
+        screen: Screen,
+    }
+}
+
+impl<A: UserAction> Passthrough<A> {
+    /// Create a new [`Passthrough`] rendering the bytes received from `output` and sending the
+    /// input and resize events to `input`.
+    pub fn new(
+        output: UnboundedReceiver<Vec<u8>>,
+        input: UnboundedSender<PassthroughEvent>,
+    ) -> Self {
+        Self {
+            output: Some(output),
+            input: Some(input),
+            ..Self::default()
+        }
+    }
+
+    fn send_event(&self, event: PassthroughEvent) {
+        if let Some(input) = &self.input {
+            // the program might have exited already
+            let _ = input.send(event);
+        }
+    }
+
+    /// Process the output written by the program since the last call.
+    fn read_output(&mut self) {
+        if let Some(output) = self.output.as_mut() {
+            while let Ok(bytes) = output.try_recv() {
+                self.screen.feed(&bytes);
+            }
+        }
+    }
+}
+
+impl<A: UserAction> Component<A> for Passthrough<A> {
+    fn tab_index(&self) -> Option<u16> {
+        Some(0)
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Option<Action<A>> {
+        if self.is_focused() {
+            if let Some(bytes) = key_to_bytes(key) {
+                self.send_event(PassthroughEvent::Input(bytes));
+            }
+        }
+        None
+    }
+
+    fn handle_paste_event(&mut self, message: String) -> Option<Action<A>> {
+        if self.is_focused() {
+            self.send_event(PassthroughEvent::Input(message.into_bytes()));
+        }
+        None
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+        if (area.width, area.height) != self.screen.size() {
+            self.screen.resize(area.width, area.height);
+            self.send_event(PassthroughEvent::Resize(area.width, area.height));
+        }
+
+        self.read_output();
+
+        let buf = f.buffer_mut();
+        for (y, row) in self.screen.rows.iter().enumerate() {
+            for (x, (symbol, style)) in row.iter().enumerate() {
+                let cell = &mut buf[(area.x + x as u16, area.y + y as u16)];
+                cell.set_symbol(symbol).set_style(*style);
+            }
+        }
+
+        if self.is_focused() {
+            let (x, y) = self.screen.cursor;
+            if x < area.width && y < area.height {
+                buf[(area.x + x, area.y + y)].modifier.insert(Modifier::REVERSED);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Encode a key the way a terminal sends it to the programs.
+fn key_to_bytes(key: KeyEvent) -> Option<Vec<u8>> {
+    let mut bytes = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            match c.to_ascii_lowercase() {
+                c @ 'a'..='z' => vec![c as u8 - b'a' + 1],
+                '@' | ' ' => vec![0],
+                '[' => vec![0x1b],
+                '\\' => vec![0x1c],
+                ']' => vec![0x1d],
+                _ => return None,
+            }
+        }
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        _ => return None,
+    };
+
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        bytes.insert(0, 0x1b);
+    }
+
+    Some(bytes)
+}
+
+/// State of the control sequences parser.
+#[derive(Debug, Default)]
+enum ParserState {
+    #[default]
+    Ground,
+    Escape,
+    /// control sequence, with the parameters read so far
+    Csi(String),
+    /// operating system command, skipped until its terminator
+    Osc,
+    OscEscape,
+}
+
+/// The grid of cells of the embedded terminal, and the parser that writes into it.
+#[derive(Debug, Default)]
+struct Screen {
+    /// symbol and style of each cell, by row
+    rows: Vec<Vec<(String, Style)>>,
+    /// position of the cursor (column, row)
+    cursor: (u16, u16),
+    saved_cursor: (u16, u16),
+    /// style of the text written from now on
+    style: Style,
+    state: ParserState,
+    /// bytes of an incomplete utf-8 character
+    pending: Vec<u8>,
+}
+
+impl Screen {
+    fn size(&self) -> (u16, u16) {
+        let height = self.rows.len() as u16;
+        let width = self.rows.first().map_or(0, Vec::len) as u16;
+        (width, height)
+    }
+
+    fn blank() -> (String, Style) {
+        (" ".to_string(), Style::default())
+    }
+
+    fn resize(&mut self, width: u16, height: u16) {
+        self.rows.resize_with(height as usize, Vec::new);
+        for row in self.rows.iter_mut() {
+            row.resize_with(width as usize, Self::blank);
+        }
+        self.cursor = (
+            self.cursor.0.min(width.saturating_sub(1)),
+            self.cursor.1.min(height.saturating_sub(1)),
+        );
+    }
+
+    fn feed(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+        let pending = std::mem::take(&mut self.pending);
+
+        let (valid, rest) = match std::str::from_utf8(&pending) {
+            Ok(text) => (text, &[][..]),
+            Err(err) if err.error_len().is_none() => {
+                // the last character is incomplete, keep it for the next call
+                let (valid, rest) = pending.split_at(err.valid_up_to());
+                (std::str::from_utf8(valid).unwrap(), rest)
+            }
+            Err(_) => {
+                let text = String::from_utf8_lossy(&pending).into_owned();
+                text.chars().for_each(|c| self.process(c));
+                return;
+            }
+        };
+
+        valid.chars().for_each(|c| self.process(c));
+        self.pending = rest.to_vec();
+    }
+
+    fn process(&mut self, c: char) {
+        match std::mem::take(&mut self.state) {
+            ParserState::Ground => match c {
+                '\x1b' => self.state = ParserState::Escape,
+                '\r' => self.cursor.0 = 0,
+                '\n' | '\x0b' | '\x0c' => self.line_feed(),
+                '\x08' => self.cursor.0 = self.cursor.0.saturating_sub(1),
+                '\t' => {
+                    self.cursor.0 =
+                        ((self.cursor.0 / 8 + 1) * 8).min(self.size().0.saturating_sub(1))
+                }
+                c if c.is_control() => {}
+                c => self.print(c),
+            },
+            ParserState::Escape => match c {
+                '[' => self.state = ParserState::Csi(String::new()),
+                ']' => self.state = ParserState::Osc,
+                '7' => self.saved_cursor = self.cursor,
+                '8' => self.cursor = self.saved_cursor,
+                'c' => {
+                    let (width, height) = self.size();
+                    *self = Screen::default();
+                    self.resize(width, height);
+                }
+                _ => {}
+            },
+            ParserState::Csi(mut params) => match c {
+                '0'..='9' | ';' | '?' | '>' | '=' => {
+                    params.push(c);
+                    self.state = ParserState::Csi(params);
+                }
+                c => self.control_sequence(&params, c),
+            },
+            ParserState::Osc => match c {
+                '\x07' => {}
+                '\x1b' => self.state = ParserState::OscEscape,
+                _ => self.state = ParserState::Osc,
+            },
+            // the terminator is `ESC \`
+            ParserState::OscEscape => {}
+        }
+    }
+
+    fn print(&mut self, c: char) {
+        let (width, height) = self.size();
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        if self.cursor.0 >= width {
+            self.cursor.0 = 0;
+            self.line_feed();
+        }
+
+        let (x, y) = self.cursor;
+        self.rows[y as usize][x as usize] = (c.to_string(), self.style);
+        self.cursor.0 += 1;
+    }
+
+    fn line_feed(&mut self) {
+        let (width, height) = self.size();
+        if self.cursor.1 + 1 < height {
+            self.cursor.1 += 1;
+        } else if height > 0 {
+            // scroll up
+            self.rows.remove(0);
+            self.rows.push(vec![Self::blank(); width as usize]);
+        }
+    }
+
+    fn control_sequence(&mut self, params: &str, action: char) {
+        if params.starts_with(['?', '>', '=']) {
+            // private modes (e.g. the alternate screen) are not supported
+            return;
+        }
+
+        let values = params.split(';').map(|p| p.parse::<u16>().ok()).collect::<Vec<_>>();
+        let arg = |i: usize, default: u16| values.get(i).copied().flatten().unwrap_or(default);
+        let (width, height) = self.size();
+        let (x, y) = self.cursor;
+        let max_x = width.saturating_sub(1);
+        let max_y = height.saturating_sub(1);
+
+        match action {
+            'A' => self.cursor.1 = y.saturating_sub(arg(0, 1).max(1)),
+            'B' => self.cursor.1 = (y + arg(0, 1).max(1)).min(max_y),
+            'C' => self.cursor.0 = (x + arg(0, 1).max(1)).min(max_x),
+            'D' => self.cursor.0 = x.saturating_sub(arg(0, 1).max(1)),
+            'G' => self.cursor.0 = (arg(0, 1).max(1) - 1).min(max_x),
+            'd' => self.cursor.1 = (arg(0, 1).max(1) - 1).min(max_y),
+            'H' | 'f' => {
+                self.cursor = ((arg(1, 1).max(1) - 1).min(max_x), (arg(0, 1).max(1) - 1).min(max_y))
+            }
+            'J' => match arg(0, 0) {
+                0 => {
+                    self.erase_line(y, x, width);
+                    (y + 1..height).for_each(|row| self.erase_line(row, 0, width));
+                }
+                1 => {
+                    (0..y).for_each(|row| self.erase_line(row, 0, width));
+                    self.erase_line(y, 0, x + 1);
+                }
+                _ => (0..height).for_each(|row| self.erase_line(row, 0, width)),
+            },
+            'K' => match arg(0, 0) {
+                0 => self.erase_line(y, x, width),
+                1 => self.erase_line(y, 0, x + 1),
+                _ => self.erase_line(y, 0, width),
+            },
+            'm' => self.select_graphic_rendition(&values),
+            _ => {}
+        }
+    }
+
+    fn erase_line(&mut self, row: u16, from: u16, to: u16) {
+        if let Some(row) = self.rows.get_mut(row as usize) {
+            let to = (to as usize).min(row.len());
+            for cell in row.iter_mut().take(to).skip(from as usize) {
+                *cell = Self::blank();
+            }
+        }
+    }
+
+    fn select_graphic_rendition(&mut self, values: &[Option<u16>]) {
+        let mut values = values.iter().map(|v| v.unwrap_or(0));
+
+        while let Some(value) = values.next() {
+            self.style = match value {
+                0 => Style::default(),
+                1 => self.style.add_modifier(Modifier::BOLD),
+                2 => self.style.add_modifier(Modifier::DIM),
+                3 => self.style.add_modifier(Modifier::ITALIC),
+                4 => self.style.add_modifier(Modifier::UNDERLINED),
+                7 => self.style.add_modifier(Modifier::REVERSED),
+                22 => self.style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+                23 => self.style.remove_modifier(Modifier::ITALIC),
+                24 => self.style.remove_modifier(Modifier::UNDERLINED),
+                27 => self.style.remove_modifier(Modifier::REVERSED),
+                30..=37 => self.style.fg(Color::Indexed((value - 30) as u8)),
+                39 => self.style.fg(Color::Reset),
+                40..=47 => self.style.bg(Color::Indexed((value - 40) as u8)),
+                49 => self.style.bg(Color::Reset),
+                90..=97 => self.style.fg(Color::Indexed((value - 90 + 8) as u8)),
+                100..=107 => self.style.bg(Color::Indexed((value - 100 + 8) as u8)),
+                38 | 48 => {
+                    let color = match values.next() {
+                        Some(5) => values.next().map(|i| Color::Indexed(i as u8)),
+                        Some(2) => match (values.next(), values.next(), values.next()) {
+                            (Some(r), Some(g), Some(b)) => {
+                                Some(Color::Rgb(r as u8, g as u8, b as u8))
+                            }
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    match color {
+                        Some(color) if value == 38 => self.style.fg(color),
+                        Some(color) => self.style.bg(color),
+                        None => self.style,
+                    }
+                }
+                _ => self.style,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(screen: &Screen) -> Vec<String> {
+        screen.rows.iter().map(|row| row.iter().map(|(s, _)| s.as_str()).collect()).collect()
+    }
+
+    #[test]
+    fn screen_interprets_control_sequences() {
+        let mut screen = Screen::default();
+        screen.resize(8, 3);
+
+        screen.feed(b"$ ls\r\n\x1b[1;34msrc\x1b[0m ");
+        // a character split between two reads
+        screen.feed(&"ñ".as_bytes()[..1]);
+        screen.feed(&"ñ".as_bytes()[1..]);
+        assert_eq!(text(&screen), ["$ ls    ", "src ñ   ", "        "]);
+        assert_eq!(
+            screen.rows[1][0].1,
+            Style::default().fg(Color::Indexed(4)).add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(screen.rows[1][3].1, Style::default());
+
+        // scrolls when the output reaches the bottom
+        screen.feed(b"\r\n1\r\n2");
+        assert_eq!(text(&screen), ["src ñ   ", "1       ", "2       "]);
+
+        screen.feed(b"\x1b[1;3Hxy\x1b[2;1H\x1b[K\x1b]0;title\x07!");
+        assert_eq!(text(&screen), ["srxyñ   ", "!       ", "2       "]);
+        assert_eq!(screen.cursor, (1, 1));
+
+        assert_eq!(
+            key_to_bytes(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(vec![3])
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! # Progress view
+//!
+//! This module contains the [`ProgressView`] component, which renders the tasks of a
+//! [`ProgressRegistry`] as a list of progress bars, like the parallel build output of cargo:
+//!
+//! ```ignore
+//! let progress = ProgressRegistry::default();
+//!
+//! let mut app = App::default()
+//!     .with_components(components![ProgressView::new(progress.clone()).with_summary(true)]);
+//! ```
+//!
+//! Each task gets a row with its label, a bar (or a spinner when its total isn't known), the
+//! units done and its last message. Finished tasks are marked as done or failed.
+
+use {
+    crate::{
+        component, styles, Component, ComponentAccessors, ComponentError, Frame, ProgressEntry,
+        ProgressRegistry, ProgressStatus, UserAction,
+    },
+    ratatui::{
+        layout::Rect,
+        text::{Line, Span},
+        widgets::Paragraph,
+    },
+};
+
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const ASCII_SPINNER: [&str; 4] = ["|", "/", "-", "\\"];
+
+component! {
+    /// A component rendering the tasks of a [`ProgressRegistry`] as progress bars.
+    pub struct ProgressView<A> {
+        registry: ProgressRegistry,
+        hide_finished: bool,
+        summary: bool,
+        bar_width: u16,
+    }
+}
+
+impl<A: UserAction> ProgressView<A> {
+    /// Create a new [`ProgressView`] rendering the tasks of the given registry.
+    pub fn new(registry: ProgressRegistry) -> Self {
+        Self {
+            registry,
+            bar_width: 24,
+            ..Self::default()
+        }
+    }
+
+    /// Hide the tasks once they are done. Failed tasks are always shown.
+    pub fn with_hide_finished(mut self, hide: bool) -> Self {
+        self.hide_finished = hide;
+        self
+    }
+
+    /// Show a last row with the number of finished tasks.
+    pub fn with_summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    /// Set the width of the bars, in columns. Defaults to 24.
+    pub fn with_bar_width(mut self, width: u16) -> Self {
+        self.bar_width = width;
+        self
+    }
+
+    fn bar(&self, entry: &ProgressEntry, unicode: bool) -> Span<'static> {
+        let width = self.bar_width as usize;
+        let Some(ratio) = entry.ratio() else {
+            let frames: &[&str] = if unicode { &SPINNER } else { &ASCII_SPINNER };
+            let frame = (entry.elapsed.as_millis() / 100) as usize % frames.len();
+            return Span::styled(format!("{:<width$}", frames[frame]), styles::accent());
+        };
+
+        let done = (ratio * width as f64).round() as usize;
+        let (full, empty) = if unicode { ("█", "░") } else { ("=", " ") };
+        let bar = format!("{}{}", full.repeat(done), empty.repeat(width - done));
+        if unicode {
+            Span::styled(bar, styles::accent())
+        } else {
+            Span::styled(format!("[{bar}]"), styles::accent())
+        }
+    }
+
+    fn row(&self, entry: &ProgressEntry, unicode: bool) -> Line<'static> {
+        let (mark, mark_style) = match (&entry.status, unicode) {
+            (ProgressStatus::Running, _) => (" ", styles::text()),
+            (ProgressStatus::Done, true) => ("✓", styles::success()),
+            (ProgressStatus::Done, false) => ("+", styles::success()),
+            (ProgressStatus::Failed(_), true) => ("✗", styles::error()),
+            (ProgressStatus::Failed(_), false) => ("x", styles::error()),
+        };
+
+        let count = match entry.total {
+            Some(total) => format!("{}/{total}", entry.current),
+            None => entry.current.to_string(),
+        };
+
+        let message = match &entry.status {
+            ProgressStatus::Failed(error) => Span::styled(error.clone(), styles::error()),
+            _ => Span::styled(entry.message.clone().unwrap_or_default(), styles::hint()),
+        };
+
+        Line::from(vec![
+            Span::styled(format!("{mark} "), mark_style),
+            Span::styled(format!("{} ", entry.label), styles::text()),
+            self.bar(entry, unicode),
+            Span::styled(format!(" {count} "), styles::hint()),
+            message,
+        ])
+    }
+}
+
+impl<A: UserAction> Component<A> for ProgressView<A> {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+        let unicode = self.terminal().unicode;
+        let entries = self
+            .registry
+            .entries()
+            .into_iter()
+            .filter(|e| !(self.hide_finished && e.status == ProgressStatus::Done))
+            .collect::<Vec<_>>();
+
+        // the labels are aligned, so the bars start at the same column
+        let label_width = entries.iter().map(|e| e.label.chars().count()).max().unwrap_or(0);
+        let mut lines = entries
+            .iter()
+            .map(|e| {
+                let mut entry = e.clone();
+                entry.label = format!("{:<label_width$}", e.label);
+                self.row(&entry, unicode)
+            })
+            .collect::<Vec<_>>();
+
+        if self.summary {
+            let (finished, total) = self.registry.overall();
+            lines.push(Line::styled(format!("{finished}/{total} tasks finished"), styles::hint()));
+        }
+
+        // keep the newest tasks in view
+        let skip = lines.len().saturating_sub(area.height as usize);
+        f.render_widget(Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>()), area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::NoAction,
+        ratatui::{backend::TestBackend, Terminal},
+    };
+
+    #[test]
+    fn renders_a_bar_per_task() {
+        let registry = ProgressRegistry::default();
+        registry.start("a", "fetch", Some(4)).advance(2);
+        registry.start("b", "build", Some(1)).fail("boom");
+
+        let mut view = ProgressView::<NoAction>::new(registry).with_bar_width(4).with_summary(true);
+        let mut terminal = Terminal::new(TestBackend::new(30, 3)).unwrap();
+        terminal.draw(|f| view.draw(f, f.area()).unwrap()).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let row = |y| (0..30).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+        assert_eq!(row(0).trim_end(), "  fetch [==  ] 2/4");
+        assert_eq!(row(1).trim_end(), "x build [    ] 0/1 boom");
+        assert_eq!(row(2).trim_end(), "1/2 tasks finished");
+    }
+}
@@ -0,0 +1,529 @@
+//! # Readline prompt
+//!
+//! This module contains the [`Prompt`] component, a single-line input emulating the readline
+//! behavior of shells, for REPL-style apps:
+//!
+//! ```ignore
+//! let prompt = Prompt::new("λ ")
+//!     .with_history(["help", "status"])
+//!     .with_completion(|word| commands.iter().filter(|c| c.starts_with(word)).cloned().collect())
+//!     .on_submit(|line| Some(Action::AppAction(format!("repl:{line}"))));
+//! ```
+//!
+//! While focused, the component reacts to the usual readline keys:
+//! - `<enter>` submits the line, adding it to the history
+//! - `<up>`/`<ctrl-p>` and `<down>`/`<ctrl-n>` browse the history
+//! - `<ctrl-r>` searches the history backwards, `<esc>`/`<ctrl-g>` cancels the search
+//! - `<tab>` completes the word before the cursor
+//! - `<ctrl-a>`, `<ctrl-e>`, `<ctrl-b>`, `<ctrl-f>`, `<alt-b>` and `<alt-f>` move the cursor
+//! - `<ctrl-k>`, `<ctrl-u>`, `<ctrl-w>` and `<alt-d>` kill text into the kill ring
+//! - `<ctrl-y>` yanks the last killed text, `<alt-y>` rotates it with the previous ones
+
+use {
+    crate::{
+        component, styles,
+        widgets::textarea::{CursorMove, TextArea},
+        Action, Component, ComponentAccessors, ComponentError, Frame, UserAction,
+    },
+    crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    ratatui::{
+        layout::{Constraint, Layout, Rect},
+        text::{Line, Span},
+        widgets::Paragraph,
+    },
+    unicode_width::UnicodeWidthStr,
+};
+
+/// Hook called with the submitted line, returning the action to send, if any.
+pub type SubmitHook<A> = Box<dyn FnMut(&str) -> Option<Action<A>>>;
+
+/// Hook called with the word before the cursor, returning the candidates to complete it with.
+pub type CompletionHook = Box<dyn Fn(&str) -> Vec<String>>;
+
+/// The number of killed texts kept by the [`Prompt`].
+const KILL_RING_SIZE: usize = 16;
+
+/// An ongoing `<ctrl-r>` history search.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Search {
+    query: String,
+    /// Index of the matching history entry.
+    found: Option<usize>,
+    /// The line being edited when the search started, restored when it's cancelled.
+    original: String,
+}
+
+/// The last yank, so `<alt-y>` can replace it with an older killed text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Yank {
+    start: usize,
+    len: usize,
+    ring_index: usize,
+}
+
+component! {
+    /// A single-line input with history, reverse search, completion and a kill ring.
+    pub struct Prompt<A> {
+        textarea: TextArea<'static>,
+        prompt: String,
+        history: Vec<String>,
+        max_history: Option<usize>,
+        browsing: Option<usize>,
+        draft: String,
+        search: Option<Search>,
+        kill_ring: Vec<String>,
+        last_yank: Option<Yank>,
+        completer: Option<CompletionHook>,
+        candidates: Vec<String>,
+        on_submit: Option<SubmitHook<A>>,
+    }
+}
+
+impl<A: UserAction> Prompt<A> {
+    /// Create a new [`Prompt`] showing the given text before the input.
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            textarea: TextArea::default().with_single_line(true),
+            prompt: prompt.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Start with the given history, from the oldest to the newest entry.
+    pub fn with_history(mut self, history: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.history = history.into_iter().map(Into::into).collect();
+        self.trim_history();
+        self
+    }
+
+    /// Keep at most `max` history entries, dropping the oldest ones. Unlimited by default.
+    pub fn with_max_history(mut self, max: usize) -> Self {
+        self.max_history = Some(max);
+        self.trim_history();
+        self
+    }
+
+    /// Set the hook completing the word before the cursor on `<tab>`. A single candidate replaces
+    /// the word, multiple candidates complete their common prefix and are listed below the input.
+    pub fn with_completion(mut self, completer: impl Fn(&str) -> Vec<String> + 'static) -> Self {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    /// Set the hook called when a line is submitted with `<enter>`.
+    pub fn on_submit(mut self, hook: impl FnMut(&str) -> Option<Action<A>> + 'static) -> Self {
+        self.on_submit = Some(Box::new(hook));
+        self
+    }
+
+    /// The line being edited.
+    pub fn line(&self) -> &str {
+        &self.textarea.lines()[0]
+    }
+
+    /// The submitted lines, from the oldest to the newest.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// The killed texts, from the oldest to the newest.
+    pub fn kill_ring(&self) -> &[String] {
+        &self.kill_ring
+    }
+
+    /// Returns `true` while a `<ctrl-r>` history search is ongoing.
+    pub fn is_searching(&self) -> bool {
+        self.search.is_some()
+    }
+
+    /// Replace the line being edited, moving the cursor to its end.
+    pub fn set_line(&mut self, line: impl AsRef<str>) {
+        self.textarea.set_text(line);
+    }
+
+    fn trim_history(&mut self) {
+        if let Some(max) = self.max_history {
+            let excess = self.history.len().saturating_sub(max);
+            self.history.drain(..excess);
+        }
+    }
+
+    fn cursor(&self) -> usize {
+        self.textarea.cursor().1
+    }
+
+    fn chars(&self) -> Vec<char> {
+        self.line().chars().collect()
+    }
+
+    /// Replace the line and move the cursor to the given char position.
+    fn replace(&mut self, chars: &[char], cursor: usize) {
+        self.textarea.set_text(chars.iter().collect::<String>());
+        self.textarea.move_cursor(CursorMove::Jump(0, cursor as u16));
+    }
+
+    fn submit(&mut self) -> Option<Action<A>> {
+        let line = self.line().to_string();
+        if !line.trim().is_empty() && self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+            self.trim_history();
+        }
+
+        self.textarea.set_text("");
+        self.browsing = None;
+        self.draft.clear();
+        self.on_submit.as_mut().and_then(|hook| hook(&line))
+    }
+
+    /// Browse the history, `older` going back in time.
+    fn browse(&mut self, older: bool) {
+        let next = match (self.browsing, older) {
+            (None, true) => self.history.len().checked_sub(1),
+            (None, false) => return,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) => (i + 1 < self.history.len()).then_some(i + 1),
+        };
+
+        if self.browsing.is_none() {
+            self.draft = self.line().to_string();
+        }
+
+        match next {
+            Some(i) => {
+                let entry = self.history[i].clone();
+                self.textarea.set_text(entry);
+            }
+            None => {
+                let draft = std::mem::take(&mut self.draft);
+                self.textarea.set_text(draft);
+            }
+        }
+        self.browsing = next;
+    }
+
+    /// Find the newest history entry containing the query, starting at `before` (excluded).
+    fn find(&self, query: &str, before: usize) -> Option<usize> {
+        self.history[..before.min(self.history.len())]
+            .iter()
+            .rposition(|entry| entry.contains(query))
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        let Some(mut search) = self.search.take() else {
+            return;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Char('r') if ctrl => {
+                let before = search.found.unwrap_or(self.history.len());
+                if let Some(found) = self.find(&search.query, before) {
+                    search.found = Some(found);
+                }
+            }
+            KeyCode::Char('g') if ctrl => {
+                self.textarea.set_text(&search.original);
+                return;
+            }
+            KeyCode::Esc => {
+                self.textarea.set_text(&search.original);
+                return;
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+                search.found = self.find(&search.query, self.history.len());
+            }
+            KeyCode::Char(c) if !ctrl => {
+                search.query.push(c);
+                search.found = self.find(&search.query, self.history.len());
+            }
+            _ => {
+                // any other key accepts the match and ends the search
+                if let Some(found) = search.found {
+                    let entry = self.history[found].clone();
+                    self.textarea.set_text(entry);
+                }
+                return;
+            }
+        }
+
+        self.search = Some(search);
+    }
+
+    fn kill(&mut self, from: usize, to: usize) {
+        let mut chars = self.chars();
+        if from >= to {
+            return;
+        }
+
+        let killed: String = chars.drain(from..to).collect();
+        self.kill_ring.push(killed);
+        if self.kill_ring.len() > KILL_RING_SIZE {
+            self.kill_ring.remove(0);
+        }
+        self.replace(&chars, from);
+    }
+
+    fn yank(&mut self, ring_index: usize) {
+        let Some(text) = self.kill_ring.get(ring_index) else {
+            return;
+        };
+
+        let start = self.cursor();
+        let len = text.chars().count();
+        let mut chars = self.chars();
+        chars.splice(start..start, text.chars());
+        self.replace(&chars, start + len);
+        self.last_yank = Some(Yank {
+            start,
+            len,
+            ring_index,
+        });
+    }
+
+    fn yank_pop(&mut self, last: Yank) {
+        let mut chars = self.chars();
+        chars.drain(last.start..last.start + last.len);
+        self.replace(&chars, last.start);
+
+        let ring_index = last.ring_index.checked_sub(1).unwrap_or(self.kill_ring.len() - 1);
+        self.yank(ring_index);
+    }
+
+    fn complete(&mut self) {
+        let Some(completer) = &self.completer else {
+            return;
+        };
+
+        let chars = self.chars();
+        let cursor = self.cursor();
+        let start = word_start(&chars, cursor);
+        let word: String = chars[start..cursor].iter().collect();
+        let candidates = completer(&word);
+
+        let completion = match candidates.as_slice() {
+            [] => return,
+            [single] => format!("{single} "),
+            [first, rest @ ..] => rest.iter().fold(first.clone(), |prefix, candidate| {
+                prefix
+                    .chars()
+                    .zip(candidate.chars())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect()
+            }),
+        };
+
+        if candidates.len() > 1 {
+            self.candidates = candidates;
+        }
+
+        if completion.chars().count() >= word.chars().count() {
+            let mut chars = chars;
+            chars.splice(start..cursor, completion.chars());
+            self.replace(&chars, start + completion.chars().count());
+        }
+    }
+}
+
+/// The start of the word ending at `cursor`, words being separated by whitespace.
+fn word_start(chars: &[char], cursor: usize) -> usize {
+    let trailing = chars[..cursor].iter().rev().take_while(|c| c.is_whitespace()).count();
+    let word = chars[..cursor - trailing].iter().rev().take_while(|c| !c.is_whitespace()).count();
+    cursor - trailing - word
+}
+
+/// The end of the word starting at `cursor`, words being separated by whitespace.
+fn word_end(chars: &[char], cursor: usize) -> usize {
+    let leading = chars[cursor..].iter().take_while(|c| c.is_whitespace()).count();
+    let word = chars[cursor + leading..].iter().take_while(|c| !c.is_whitespace()).count();
+    cursor + leading + word
+}
+
+impl<A: UserAction> Component<A> for Prompt<A> {
+    fn tab_index(&self) -> Option<u16> {
+        Some(0)
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Option<Action<A>> {
+        if !self.is_focused() || key.kind == KeyEventKind::Release {
+            return None;
+        }
+
+        if self.search.is_some() {
+            self.handle_search_key(key);
+            return None;
+        }
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let last_yank = self.last_yank.take();
+        self.candidates.clear();
+
+        match key.code {
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Up => self.browse(true),
+            KeyCode::Down => self.browse(false),
+            KeyCode::Tab => self.complete(),
+            KeyCode::Char('p') if ctrl => self.browse(true),
+            KeyCode::Char('n') if ctrl => self.browse(false),
+            KeyCode::Char('r') if ctrl => {
+                self.search = Some(Search {
+                    original: self.line().to_string(),
+                    ..Search::default()
+                });
+            }
+            KeyCode::Char('a') if ctrl => self.textarea.move_cursor(CursorMove::Head),
+            KeyCode::Char('e') if ctrl => self.textarea.move_cursor(CursorMove::End),
+            KeyCode::Char('b') if ctrl => self.textarea.move_cursor(CursorMove::Back),
+            KeyCode::Char('f') if ctrl => self.textarea.move_cursor(CursorMove::Forward),
+            KeyCode::Char('b') if alt => {
+                let start = word_start(&self.chars(), self.cursor());
+                self.textarea.move_cursor(CursorMove::Jump(0, start as u16));
+            }
+            KeyCode::Char('f') if alt => {
+                let end = word_end(&self.chars(), self.cursor());
+                self.textarea.move_cursor(CursorMove::Jump(0, end as u16));
+            }
+            KeyCode::Char('k') if ctrl => self.kill(self.cursor(), self.chars().len()),
+            KeyCode::Char('u') if ctrl => self.kill(0, self.cursor()),
+            KeyCode::Char('w') if ctrl => {
+                let start = word_start(&self.chars(), self.cursor());
+                self.kill(start, self.cursor());
+            }
+            KeyCode::Char('d') if alt => {
+                let end = word_end(&self.chars(), self.cursor());
+                self.kill(self.cursor(), end);
+            }
+            KeyCode::Char('d') if ctrl => {
+                self.textarea.delete_next_char();
+            }
+            KeyCode::Char('h') if ctrl => {
+                self.textarea.delete_char();
+            }
+            KeyCode::Char('y') if ctrl => self.yank(self.kill_ring.len().wrapping_sub(1)),
+            KeyCode::Char('y') if alt => {
+                if let Some(last) = last_yank {
+                    self.yank_pop(last);
+                }
+            }
+            _ if ctrl || alt => {}
+            _ => {
+                self.textarea.input(key);
+            }
+        }
+
+        None
+    }
+
+    fn handle_paste_event(&mut self, message: String) -> Option<Action<A>> {
+        if self.is_focused() && self.search.is_none() {
+            self.textarea.insert_str(message);
+        }
+        None
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+        let [input, below] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+
+        if let Some(search) = &self.search {
+            let found = search.found.map(|i| self.history[i].as_str()).unwrap_or_default();
+            let line = Line::from(vec![
+                Span::styled(format!("(reverse-i-search)`{}': ", search.query), styles::hint()),
+                Span::styled(found.to_string(), styles::text()),
+            ]);
+            f.render_widget(Paragraph::new(line), input);
+            return Ok(());
+        }
+
+        let width = (self.prompt.width() as u16).min(input.width);
+        let [label, text] =
+            Layout::horizontal([Constraint::Length(width), Constraint::Fill(1)]).areas(input);
+
+        f.render_widget(Span::styled(self.prompt.as_str(), styles::accent()), label);
+        let cursor_style = if self.is_focused() {
+            styles::selection()
+        } else {
+            styles::text()
+        };
+        self.textarea.cursor_style = cursor_style;
+        f.render_widget(&self.textarea, text);
+
+        if !self.candidates.is_empty() && below.height > 0 {
+            let candidates = Line::styled(self.candidates.join("  "), styles::hint());
+            f.render_widget(Paragraph::new(candidates), below);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::NoAction};
+
+    fn press(prompt: &mut Prompt<NoAction>, code: KeyCode, modifiers: KeyModifiers) {
+        prompt.handle_key_events(KeyEvent::new(code, modifiers));
+    }
+
+    fn typed(prompt: &mut Prompt<NoAction>, text: &str) {
+        for c in text.chars() {
+            press(prompt, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn readline_editing() {
+        let mut prompt = Prompt::<NoAction>::new("> ")
+            .with_history(["cargo build", "git status", "cargo test"])
+            .with_completion(|word| {
+                ["status", "stash"]
+                    .into_iter()
+                    .filter(|c| c.starts_with(word))
+                    .map(Into::into)
+                    .collect()
+            });
+        prompt.set_focused(true);
+
+        // kill ring
+        typed(&mut prompt, "echo hello world");
+        press(&mut prompt, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(prompt.line(), "echo hello ");
+        press(&mut prompt, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(prompt.line(), "");
+        press(&mut prompt, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(prompt.line(), "echo hello ");
+        press(&mut prompt, KeyCode::Char('y'), KeyModifiers::ALT);
+        assert_eq!(prompt.line(), "world");
+
+        // history
+        press(&mut prompt, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut prompt, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(prompt.line(), "git status");
+        press(&mut prompt, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut prompt, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(prompt.line(), "world");
+
+        // reverse search
+        press(&mut prompt, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        typed(&mut prompt, "cargo");
+        press(&mut prompt, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        press(&mut prompt, KeyCode::Right, KeyModifiers::NONE);
+        assert!(!prompt.is_searching());
+        assert_eq!(prompt.line(), "cargo build");
+
+        // completion
+        press(&mut prompt, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        typed(&mut prompt, "git st");
+        press(&mut prompt, KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(prompt.line(), "git sta");
+        typed(&mut prompt, "t");
+        press(&mut prompt, KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(prompt.line(), "git status ");
+
+        press(&mut prompt, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(prompt.line(), "");
+        assert_eq!(prompt.history().last().map(String::as_str), Some("git status "));
+    }
+}
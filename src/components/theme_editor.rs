@@ -0,0 +1,324 @@
+//! # Theme editor
+//!
+//! This module contains the [`ThemeEditor`] development component, which lists the styles of the
+//! current [`Theme`] and changes their colors live, to design the look of an app while it runs:
+//!
+//! ```ignore
+//! let mut app = App::default()
+//!     .with_keybindings(kb!["<f11>" => theme_editor::TOGGLE])
+//!     .with_components(components![
+//!         home,
+//!         ThemeEditor::default().with_export_path("theme.toml").as_active()
+//!     ]);
+//! ```
+//!
+//! The editor starts hidden, and is shown and hidden with the [`TOGGLE`] message. When shown, it
+//! starts from the current theme and reacts to the following keys:
+//! - `<up>`/`<down>` select a style
+//! - `<tab>` switches between the foreground and the background color
+//! - `<left>`/`<right>` pick the previous/next color of the palette
+//! - `[`/`]` darken/lighten the color
+//! - `<ctrl-s>` exports the theme to the export path, as the `[theme]` table of a
+//!   [config file](crate::Config)
+//!
+//! Every change is applied to the current theme right away (see [`Theme::set`]), so the whole app
+//! is restyled on the next frame.
+
+use {
+    crate::{
+        component, framework::config::style_to_string, styles, theme::Theme, utils::color::lerp,
+        Action, Component, ComponentAccessors, ComponentError, Config, Frame, UserAction,
+    },
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    ratatui::{
+        layout::{Constraint, Layout, Rect},
+        style::{Color, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Clear, Paragraph},
+    },
+    std::{
+        fs, io,
+        path::{Path, PathBuf},
+    },
+};
+
+/// The message toggling the [`ThemeEditor`], to bind to a key: `"<f11>" => theme_editor::TOGGLE`.
+pub const TOGGLE: &str = "themeeditor:toggle";
+
+/// The styles of the theme, in the order they are listed.
+const TOKENS: [&str; 10] = [
+    "text",
+    "hint",
+    "accent",
+    "error",
+    "warning",
+    "success",
+    "border",
+    "border_focused",
+    "selection",
+    "shadow",
+];
+
+/// The colors of the picker: the default color of the terminal and the 16 ANSI colors.
+const PALETTE: [Color; 17] = [
+    Color::Reset,
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+component! {
+    /// A panel, at the right of its area, to edit the colors of the current theme.
+    pub struct ThemeEditor<A> {
+        theme: Option<Theme>,
+        export_path: Option<PathBuf>,
+        selected: usize,
+        background: bool,
+        visible: bool,
+        status: Option<String>,
+    }
+}
+
+impl<A: UserAction> ThemeEditor<A> {
+    /// Set the file `<ctrl-s>` exports the theme to. Defaults to `theme.toml`.
+    pub fn with_export_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.export_path = Some(path.into());
+        self
+    }
+
+    /// Show the editor right away, instead of waiting for the [`TOGGLE`] message.
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.set_visible(visible);
+        self
+    }
+
+    /// Returns `true` if the editor is shown.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// The edited theme: the current theme, with the changes made in the editor.
+    pub fn theme(&self) -> Theme {
+        self.theme.clone().unwrap_or_else(Theme::current)
+    }
+
+    /// Write the edited theme to `path`, as the `[theme]` table of a [config file](Config).
+    pub fn export(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let config = Config {
+            theme: Some(self.theme()),
+            ..Config::default()
+        };
+        fs::write(path, config.to_toml())
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        self.status = None;
+        // the theme may have been changed by the app since the editor was last shown
+        self.theme = visible.then(Theme::current);
+    }
+
+    /// Apply an editing key to the edited theme, returning `true` if the theme changed.
+    fn edit(&mut self, key: KeyEvent) -> bool {
+        let mut theme = self.theme();
+        let background = self.background;
+        let style = token_mut(&mut theme, self.selected);
+        let color = if background { style.bg } else { style.fg };
+        let color = color.unwrap_or(Color::Reset);
+
+        let position = PALETTE.iter().position(|c| *c == color);
+        let picked = match (key.code, key.modifiers) {
+            (KeyCode::Up, _) => {
+                self.selected = (self.selected + TOKENS.len() - 1) % TOKENS.len();
+                return false;
+            }
+            (KeyCode::Down, _) => {
+                self.selected = (self.selected + 1) % TOKENS.len();
+                return false;
+            }
+            (KeyCode::Tab, _) => {
+                self.background = !self.background;
+                return false;
+            }
+            (KeyCode::Right, _) => PALETTE[position.map_or(0, |i| (i + 1) % PALETTE.len())],
+            (KeyCode::Left, _) => {
+                PALETTE[position.map_or(0, |i| (i + PALETTE.len() - 1) % PALETTE.len())]
+            }
+            (KeyCode::Char('['), KeyModifiers::NONE) => lerp(color, Color::Black, 0.1),
+            (KeyCode::Char(']'), KeyModifiers::NONE) => lerp(color, Color::White, 0.1),
+            _ => return false,
+        };
+
+        if picked == color {
+            return false;
+        }
+        *style = if background {
+            style.bg(picked)
+        } else {
+            style.fg(picked)
+        };
+        self.theme = Some(theme);
+        true
+    }
+
+    fn save(&mut self) {
+        let path = self.export_path.clone().unwrap_or_else(|| "theme.toml".into());
+        self.status = Some(match self.export(&path) {
+            Ok(()) => format!("exported to {}", path.display()),
+            Err(e) => format!("export failed: {e}"),
+        });
+    }
+}
+
+impl<A: UserAction> Component<A> for ThemeEditor<A> {
+    fn subscriptions(&self) -> Option<Vec<&str>> {
+        Some(vec![TOGGLE])
+    }
+
+    fn receive_message(&mut self, message: String) {
+        if message == TOGGLE {
+            self.set_visible(!self.visible);
+            self.request_render();
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Option<Action<A>> {
+        if !self.visible {
+            return None;
+        }
+
+        if key.code == KeyCode::Char('s') && key.modifiers == KeyModifiers::CONTROL {
+            self.save();
+        } else if self.edit(key) {
+            Theme::set(self.theme());
+            self.status = None;
+        }
+        self.request_render();
+        None
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        let area = Layout::horizontal([Constraint::Fill(1), Constraint::Length(46)]).split(area)[1];
+        let theme = self.theme();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(styles::border_focused())
+            .title(format!(" theme · {} ", theme.name));
+        let inner = block.inner(area);
+
+        let mut lines = TOKENS
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let mut theme = theme.clone();
+                let style = *token_mut(&mut theme, i);
+                let marker = if i == self.selected { "▸ " } else { "  " };
+                Line::from(vec![
+                    Span::styled(marker, styles::accent()),
+                    Span::styled(format!("{name:<15}"), styles::text()),
+                    Span::styled(" sample ", style),
+                    Span::styled(format!(" {}", style_to_string(style)), styles::hint()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let mut theme = theme.clone();
+        let style = *token_mut(&mut theme, self.selected);
+        let color = if self.background { style.bg } else { style.fg };
+        let color = color.unwrap_or(Color::Reset);
+        let target = if self.background {
+            "background"
+        } else {
+            "foreground"
+        };
+
+        let mut swatches = vec![Span::styled(format!("{target:<11}"), styles::hint())];
+        for c in PALETTE {
+            let symbol = if c == color { "◆" } else { "■" };
+            swatches.push(Span::styled(symbol, Style::default().fg(c)));
+            swatches.push(Span::raw(" "));
+        }
+        lines.push(Line::default());
+        lines.push(Line::from(swatches));
+        lines.push(Line::styled(
+            "↑↓ style · tab fg/bg · ←→ pick · [] shade · ^s export",
+            styles::hint(),
+        ));
+        if let Some(status) = &self.status {
+            lines.push(Line::styled(status.clone(), styles::success()));
+        }
+
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        f.render_widget(Paragraph::new(lines), inner);
+        Ok(())
+    }
+}
+
+/// The style of the theme listed at `index` of [`TOKENS`].
+fn token_mut(theme: &mut Theme, index: usize) -> &mut Style {
+    match index {
+        0 => &mut theme.text,
+        1 => &mut theme.hint,
+        2 => &mut theme.accent,
+        3 => &mut theme.error,
+        4 => &mut theme.warning,
+        5 => &mut theme.success,
+        6 => &mut theme.border,
+        7 => &mut theme.border_focused,
+        8 => &mut theme.selection,
+        _ => &mut theme.shadow,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::NoAction};
+
+    #[test]
+    fn edits_the_colors_of_the_selected_style() {
+        let mut editor = ThemeEditor::<NoAction> {
+            theme: Some(Theme::dark()),
+            ..Default::default()
+        };
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        // accent: cyan -> gray
+        editor.edit(key(KeyCode::Down));
+        editor.edit(key(KeyCode::Down));
+        assert!(editor.edit(key(KeyCode::Right)));
+        assert_eq!(editor.theme().accent, Style::default().fg(Color::Gray));
+
+        // the background of the accent
+        editor.edit(key(KeyCode::Tab));
+        assert!(editor.edit(key(KeyCode::Left)));
+        assert_eq!(editor.theme().accent.bg, Some(Color::White));
+
+        assert!(editor.edit(key(KeyCode::Char('['))));
+        assert_eq!(editor.theme().accent.bg, Some(Color::Rgb(230, 230, 230)));
+        assert_eq!(editor.theme().hint, Theme::dark().hint);
+
+        let path = std::env::temp_dir().join("matetui-theme-editor.toml");
+        editor.export(&path).unwrap();
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.theme.unwrap().accent, editor.theme().accent);
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,247 @@
+//! # Event trace viewer
+//!
+//! This module contains the [`EventTrace`] development component.
+//!
+//! The component shows the live stream of [`Event`](crate::Event)s and [`Action`]s processed by the
+//! [`App`](crate::App) loop, reading them from a [`History`] shared with the app:
+//!
+//! ```ignore
+//! let history = History::default();
+//!
+//! let mut app = App::default()
+//!     .with_history(history.clone())
+//!     .with_components(components![
+//!         home,
+//!         EventTrace::new(history).with_dock(Dock::Bottom(12)).as_active()
+//!     ]);
+//! ```
+//!
+//! While active, the component reacts to the following keys:
+//! - `<alt-p>` pauses/resumes the live stream
+//! - `<alt-f>` cycles the kind filter (all, events, actions)
+//! - `<alt-t>` shows/hides the high frequency tick and render entries
+
+use {
+    crate::{
+        component, styles, Action, Component, ComponentError, Frame, History, HistoryEntry,
+        HistoryKind, UserAction,
+    },
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    ratatui::{
+        layout::{Constraint, Layout, Rect},
+        text::{Line, Span},
+        widgets::{Block, Borders, Clear, Paragraph},
+    },
+};
+
+/// Where the [`EventTrace`] draws itself inside the area it receives.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dock {
+    /// Use the whole area.
+    #[default]
+    Fill,
+    /// Use the given number of rows at the top of the area.
+    Top(u16),
+    /// Use the given number of rows at the bottom of the area.
+    Bottom(u16),
+    /// Use the given number of columns at the left of the area.
+    Left(u16),
+    /// Use the given number of columns at the right of the area.
+    Right(u16),
+}
+
+impl Dock {
+    /// Compute the docked area inside `area`.
+    pub fn area(&self, area: Rect) -> Rect {
+        match *self {
+            Dock::Fill => area,
+            Dock::Top(rows) => {
+                Layout::vertical([Constraint::Length(rows), Constraint::Fill(1)]).split(area)[0]
+            }
+            Dock::Bottom(rows) => {
+                Layout::vertical([Constraint::Fill(1), Constraint::Length(rows)]).split(area)[1]
+            }
+            Dock::Left(cols) => {
+                Layout::horizontal([Constraint::Length(cols), Constraint::Fill(1)]).split(area)[0]
+            }
+            Dock::Right(cols) => {
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(cols)]).split(area)[1]
+            }
+        }
+    }
+}
+
+/// Kind filter of the [`EventTrace`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TraceFilter {
+    #[default]
+    All,
+    Events,
+    Actions,
+}
+
+impl TraceFilter {
+    fn next(self) -> Self {
+        match self {
+            TraceFilter::All => TraceFilter::Events,
+            TraceFilter::Events => TraceFilter::Actions,
+            TraceFilter::Actions => TraceFilter::All,
+        }
+    }
+
+    fn accepts(&self, kind: HistoryKind) -> bool {
+        match self {
+            TraceFilter::All => true,
+            TraceFilter::Events => kind == HistoryKind::Event,
+            TraceFilter::Actions => kind == HistoryKind::Action,
+        }
+    }
+}
+
+component! {
+    /// A development component that displays the live stream of events and actions of the app.
+    pub struct EventTrace<A> {
+        history: History,
+        dock: Dock,
+        filter: TraceFilter,
+        query: String,
+        show_frames: bool,
+        paused: Option<Vec<HistoryEntry>>,
+    }
+}
+
+impl<A: UserAction> EventTrace<A> {
+    /// Create a new [`EventTrace`] reading from the given [`History`].
+    pub fn new(history: History) -> Self {
+        Self {
+            history,
+            ..Self::default()
+        }
+    }
+
+    /// Set where the component draws itself inside its area. Defaults to [`Dock::Fill`].
+    pub fn with_dock(mut self, dock: Dock) -> Self {
+        self.dock = dock;
+        self
+    }
+
+    /// Set the kind filter. Defaults to [`TraceFilter::All`].
+    pub fn with_filter(mut self, filter: TraceFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Only show the entries containing the given text (case insensitive).
+    pub fn with_query(mut self, query: impl Into<String>) -> Self {
+        self.query = query.into().to_lowercase();
+        self
+    }
+
+    /// Show the tick and render entries, which are hidden by default as they flood the trace.
+    pub fn with_frames(mut self, show: bool) -> Self {
+        self.show_frames = show;
+        self
+    }
+
+    /// Freeze the trace, keeping a snapshot of the current entries.
+    pub fn pause(&mut self) {
+        if self.paused.is_none() {
+            self.paused = Some(self.history.entries());
+        }
+    }
+
+    /// Go back to following the live stream.
+    pub fn resume(&mut self) {
+        self.paused = None;
+    }
+
+    /// Returns `true` if the trace is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    fn is_frame(entry: &HistoryEntry) -> bool {
+        matches!(entry.value.as_str(), "Tick" | "Render")
+    }
+
+    fn visible_entries(&self) -> Vec<HistoryEntry> {
+        let entries = match &self.paused {
+            Some(snapshot) => snapshot.clone(),
+            None => self.history.entries(),
+        };
+
+        entries
+            .into_iter()
+            .filter(|e| self.filter.accepts(e.kind))
+            .filter(|e| self.show_frames || !Self::is_frame(e))
+            .filter(|e| self.query.is_empty() || e.value.to_lowercase().contains(&self.query))
+            .collect()
+    }
+
+    fn title(&self) -> String {
+        let mut title = format!(" trace · {:?}", self.filter).to_lowercase();
+        if !self.query.is_empty() {
+            title.push_str(&format!(" · \"{}\"", self.query));
+        }
+        if self.is_paused() {
+            title.push_str(" · paused");
+        }
+        title.push(' ');
+        title
+    }
+}
+
+impl<A: UserAction> Component<A> for EventTrace<A> {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Option<Action<A>> {
+        if !key.modifiers.contains(KeyModifiers::ALT) {
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Char('p') => {
+                if self.is_paused() {
+                    self.resume();
+                } else {
+                    self.pause();
+                }
+            }
+            KeyCode::Char('f') => self.filter = self.filter.next(),
+            KeyCode::Char('t') => self.show_frames = !self.show_frames,
+            _ => {}
+        }
+
+        None
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+        let area = self.dock.area(area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(styles::border())
+            .title(self.title());
+        let inner = block.inner(area);
+
+        let entries = self.visible_entries();
+        let skip = entries.len().saturating_sub(inner.height as usize);
+        let lines = entries
+            .iter()
+            .skip(skip)
+            .map(|e| {
+                let kind_style = match e.kind {
+                    HistoryKind::Event => styles::accent(),
+                    HistoryKind::Action => styles::warning(),
+                };
+
+                Line::from(vec![
+                    Span::styled(format!("{:>9.3}s ", e.elapsed.as_secs_f64()), styles::hint()),
+                    Span::from(format!("{:<7}", e.kind.to_string())).style(kind_style),
+                    Span::from(e.value.clone()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+use {
+    crate::utils::color::lerp,
+    ratatui::style::Color,
+    std::time::{Duration, Instant},
+};
+
+/// The pace of a [Tween]: how its progress, from `0.0` to `1.0`, is spread over its duration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// A constant pace.
+    #[default]
+    Linear,
+    /// Starts slowly and accelerates (cubic).
+    EaseIn,
+    /// Starts fast and decelerates (cubic), the usual choice for things entering the screen.
+    EaseOut,
+    /// Accelerates, then decelerates (cubic).
+    EaseInOut,
+}
+
+impl Easing {
+    /// The eased progress for the linear progress `t`, clamped between `0.0` and `1.0`.
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+        }
+    }
+}
+
+/// A value a [Tween] can animate.
+pub trait Interpolate {
+    /// The value between `self` (at `t = 0.0`) and `to` (at `t = 1.0`).
+    fn interpolate(&self, to: &Self, t: f64) -> Self;
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        self + (to - self) * t as f32
+    }
+}
+
+impl Interpolate for u16 {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        (*self as f64).interpolate(&(*to as f64), t).round() as u16
+    }
+}
+
+/// Colors are interpolated through their RGB components (see [lerp]).
+impl Interpolate for Color {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        lerp(*self, *to, t)
+    }
+}
+
+/// The animation of a value from `from` to `to` over a duration, advanced by the component
+/// showing it on every frame:
+///
+/// ```ignore
+/// component! {
+///     pub struct Drawer {
+///         width: Tween<u16>,
+///     }
+/// }
+///
+/// impl Component for Drawer {
+///     fn receive_message(&mut self, message: String) {
+///         if message == "drawer:open" {
+///             self.width.animate_to(30);
+///             self.request_render();
+///         }
+///     }
+///
+///     fn handle_frame_event(&mut self) -> Option<Action> {
+///         if self.width.advance() {
+///             // keep drawing frames until the animation ends
+///             self.request_render();
+///         }
+///         None
+///     }
+///
+///     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+///         let width = self.width.value();
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// The progress is measured in time, not frames, so animations last the same whatever the frame
+/// rate of the app.
+#[derive(Clone, Debug)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    duration: Duration,
+    easing: Easing,
+    elapsed: Duration,
+    last_frame: Option<Instant>,
+}
+
+impl<T: Interpolate + Clone> Tween<T> {
+    /// Create a new tween from `from` to `to`, starting on the next [Tween::advance].
+    pub fn new(from: T, to: T, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            easing: Easing::default(),
+            elapsed: Duration::ZERO,
+            last_frame: None,
+        }
+    }
+
+    /// Set the easing of the tween. Defaults to [Easing::Linear].
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Set the duration of the tween.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// The current value of the tween.
+    pub fn value(&self) -> T {
+        self.from.interpolate(&self.to, self.progress())
+    }
+
+    /// The value the tween ends at.
+    pub fn target(&self) -> &T {
+        &self.to
+    }
+
+    /// The eased progress of the tween, from `0.0` to `1.0`.
+    pub fn progress(&self) -> f64 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        self.easing.apply(self.elapsed.as_secs_f64() / self.duration.as_secs_f64())
+    }
+
+    /// Returns `true` until the tween reaches its target.
+    pub fn is_running(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    /// Advance the tween by the time elapsed since it was last advanced, returning `true` while
+    /// it's running. The first call after the tween (re)starts only starts the clock.
+    pub fn advance(&mut self) -> bool {
+        let now = Instant::now();
+        let delta = self.last_frame.map_or(Duration::ZERO, |last| now - last);
+        self.last_frame = Some(now);
+        self.advance_by(delta)
+    }
+
+    /// Advance the tween by `delta`, returning `true` while it's running.
+    pub fn advance_by(&mut self, delta: Duration) -> bool {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+        if !self.is_running() {
+            self.last_frame = None;
+        }
+        self.is_running()
+    }
+
+    /// Animate from the current value to `to`. Retargeting a running tween (e.g. a switch toggled
+    /// back while sliding) continues from where it was, without jumping.
+    pub fn animate_to(&mut self, to: T) {
+        self.from = self.value();
+        self.to = to;
+        self.restart();
+    }
+
+    /// Start the tween over, from its initial value.
+    pub fn restart(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.last_frame = None;
+    }
+
+    /// Jump to the end of the tween.
+    pub fn finish(&mut self) {
+        self.elapsed = self.duration;
+        self.last_frame = None;
+    }
+}
+
+/// A finished tween at the default value, so tweens can be fields of components.
+impl<T: Interpolate + Clone + Default> Default for Tween<T> {
+    fn default() -> Self {
+        Self::new(T::default(), T::default(), Duration::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eases_the_progress() {
+        for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(easing.apply(2.0), 1.0);
+        }
+        assert_eq!(Easing::EaseIn.apply(0.5), 0.125);
+        assert_eq!(Easing::EaseOut.apply(0.5), 0.875);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn advances_and_retargets() {
+        let ms = Duration::from_millis;
+        let mut tween = Tween::new(0u16, 100, ms(100));
+        assert_eq!(tween.value(), 0);
+
+        assert!(tween.advance_by(ms(40)));
+        assert_eq!(tween.value(), 40);
+
+        // back to 0, from 40
+        tween.animate_to(0);
+        assert!(tween.advance_by(ms(50)));
+        assert_eq!(tween.value(), 20);
+        assert!(!tween.advance_by(ms(80)));
+        assert_eq!(tween.value(), 0);
+
+        let mut fade = Tween::new(Color::Black, Color::White, ms(10)).with_easing(Easing::EaseOut);
+        fade.finish();
+        assert!(!fade.is_running());
+        assert_eq!(fade.value(), Color::Rgb(255, 255, 255));
+        assert_eq!(Tween::<f64>::default().progress(), 1.0);
+    }
+}
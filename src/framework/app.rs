@@ -1,17 +1,74 @@
+#[cfg(feature = "cli")]
+use super::cli::AppOptions;
+#[cfg(feature = "trace")]
+use super::instrument;
+#[cfg(feature = "metrics")]
+use super::metrics::{self, AppMetrics};
+#[cfg(feature = "persistence")]
+use super::persistence::{config_dir, Snapshot};
+#[cfg(feature = "devtools")]
+use crate::components::inspector::Inspector;
+#[cfg(feature = "palette")]
+use {
+    super::{command::Command, keyboard::key_sequence_to_display},
+    crate::components::palette::CommandPalette,
+};
 use {
     super::{
-        component::{Component, ComponentHandler},
-        events::{Action, ActionKind, Event},
-        keyboard::KeyBindings,
-        tui::Tui,
+        component::{deliver_message, Component, ComponentError, ComponentHandler, QuitDecision},
+        events::{
+            Action, ActionKind, DragPhase, Event, MouseClick, MouseDrag, NoAction, ScrollDirection,
+            UserAction,
+        },
+        external::External,
+        focus::{FocusManager, FocusPath},
+        history::{History, HistoryKind},
+        keyboard::{
+            key_sequence_to_string, parse_key_sequence, KeyBindingConflict, KeyBindingHelp,
+            KeyBindings, ScopedKeyBindings,
+        },
+        latency::InputLatency,
+        layer::{Float, Layer, Layers},
+        modal::Modals,
+        recording::{Recorder, Recording},
+        router::Router,
+        schedule::Schedule,
+        state::{AppCancellation, AppState, ExitValue, RenderRequest, StatusText},
+        terminal::{Background, TerminalInfo},
+        testing::TestApp,
+        tui::{restore_terminal, Frame, Tui, DEFAULT_CLICK_INTERVAL},
+    },
+    crate::{styles, theme::Theme, utils::color::ColorDepth},
+    crossterm::{
+        cursor::SetCursorStyle,
+        event::{KeyEvent, KeyEventKind, MouseEvent, MouseEventKind},
+    },
+    ratatui::{
+        backend::{Backend, TestBackend},
+        buffer::Buffer,
+        layout::{Layout, Position, Rect, Size},
+        text::Line,
+        widgets::{Block, Borders, Clear, Paragraph},
+    },
+    std::{
+        io::{IsTerminal, Write},
+        marker::PhantomData,
+        path::PathBuf,
+        sync::atomic::{AtomicBool, Ordering},
+        time::{Duration, Instant},
     },
-    crossterm::event::{KeyCode, KeyEvent},
-    std::str::FromStr,
     thiserror::Error,
     tokio::sync::mpsc::{
         self,
         error::{SendError, TryRecvError},
     },
+    tokio_util::sync::CancellationToken,
+    unicode_width::UnicodeWidthStr,
+};
+#[cfg(feature = "config")]
+use {
+    super::{config::Config, keyboard::action_to_config},
+    std::path::Path,
 };
 
 #[derive(Error, Debug)]
@@ -19,217 +76,2927 @@ pub enum MatetuiError {
     #[error("io error: {0}")]
     IoError(#[from] std::io::Error),
     #[error("error sending message: {0}")]
-    SendError(#[from] SendError<String>),
+    SendError(String),
+    #[error("component `{name}` failed: {source}")]
+    ComponentError {
+        name: String,
+        source: ComponentError,
+    },
+    /// The app was run with its output redirected (e.g. to a pipe or a file), see
+    /// [App::with_print_fallback].
+    #[error("stdout is not a terminal")]
+    NotATerminal,
+    #[cfg(feature = "cron")]
+    #[error("invalid cron expression: {0}")]
+    InvalidCron(#[from] croner::errors::CronError),
+}
+
+impl<A: UserAction> From<SendError<Action<A>>> for MatetuiError {
+    fn from(err: SendError<Action<A>>) -> Self {
+        MatetuiError::SendError(err.to_string())
+    }
+}
+
+/// The most actions processed while draining the queue on quit (see [App::drain_actions]).
+const DRAIN_LIMIT: usize = 1000;
+
+/// How much a turn of the mouse wheel scrolls by default (see [App::with_scroll_amount]).
+pub(crate) const SCROLL_AMOUNT: u16 = 3;
+
+/// A callback run at a point of the lifecycle of the app (see [App::on_start] and [App::on_quit]).
+type LifecycleHook = Box<dyn FnMut(&AppState)>;
+
+/// A callback run instead of the terminal bell (see [App::on_bell]).
+type BellHook = Box<dyn FnMut()>;
+
+/// A callback computing the regions of the root components from the frame area (see
+/// [App::with_layout_fn]).
+type LayoutFn = Box<dyn Fn(Rect) -> Vec<Rect>>;
+
+/// The application, generic over its custom actions (see [UserAction]) and the type of the value
+/// it returns when it exits (see [App::returning]).
+pub struct App<A: UserAction = NoAction, R = ()> {
+    core: Core<A>,
+    output: PhantomData<fn() -> R>,
 }
 
-pub struct App {
+/// The settings and the state of an [App], apart from the type of its output, so they are moved
+/// as one value when it changes (see [App::returning]).
+struct Core<A: UserAction> {
     tick_rate: f64,
     frame_rate: f64,
+    idle_frame_rate: Option<(f64, Duration)>,
+    adaptive_frame_rate: Option<(f64, f64)>,
     should_quit: bool,
-    // pub should_suspend: bool,
-    keybindings: KeyBindings,
+    should_suspend: bool,
+    standard_bindings: bool,
+    focus_bindings: bool,
+    /// the panic escape set with `with_panic_escape`, or why it's invalid
+    panic_escape: Option<Result<Vec<KeyEvent>, KeyBindingConflict>>,
+    keybindings: KeyBindings<A>,
+    /// keybindings that still apply while a modal is open
+    modal_keybindings: KeyBindings<A>,
+    scoped_keybindings: ScopedKeyBindings<A>,
+    /// the duplicated and the invalid keybindings found when they were set
+    invalid_keybindings: Vec<KeyBindingConflict>,
     last_tick_key_events: Vec<KeyEvent>,
     mouse: bool,
     paste: bool,
-    component_handlers: Vec<ComponentHandler>,
-    action_tx: mpsc::UnboundedSender<String>,
-    action_rx: mpsc::UnboundedReceiver<String>,
+    keyboard_enhancements: bool,
+    key_releases: bool,
+    title: Option<String>,
+    cursor_style: Option<SetCursorStyle>,
+    named_ticks: Vec<(String, f64)>,
+    component_handlers: Vec<ComponentHandler<A>>,
+    /// number of root components set with `with_components`, the first component handlers
+    roots: usize,
+    /// regions of the root components
+    layout: Option<LayoutFn>,
+    focus: FocusManager,
+    /// the component a mouse button was pressed on, getting the drags and the release of the
+    /// button
+    mouse_capture: Option<FocusPath>,
+    /// the component a drag started on, getting its steps until it ends
+    drag_target: Option<FocusPath>,
+    /// how much a turn of the mouse wheel scrolls
+    scroll_amount: u16,
+    click_interval: Duration,
+    screens: Vec<(String, Box<dyn Component<A>>)>,
+    router: Router,
+    modals: Vec<(String, Box<dyn Component<A>>)>,
+    open_modals: Modals,
+    layered: Vec<(Layer, Box<dyn Component<A>>)>,
+    floating: Vec<(Float, Box<dyn Component<A>>)>,
+    layers: Layers,
+    history: Option<History>,
+    #[cfg(feature = "config")]
+    config: Option<Config>,
+    recording: Option<PathBuf>,
+    recorder: Option<Recorder>,
+    replay: Option<Recording>,
+    /// where the state of the components is persisted between runs
+    #[cfg(feature = "persistence")]
+    persistence: Option<PathBuf>,
+    #[cfg(feature = "devtools")]
+    inspector: Option<Inspector>,
+    #[cfg(feature = "palette")]
+    palette: Option<CommandPalette<A>>,
+    #[cfg(feature = "palette")]
+    commands: Vec<Command<A>>,
+    render_on_demand: bool,
+    color_depth: Option<ColorDepth>,
+    /// when the last input event was read, until its actions are processed
+    input_at: Option<Instant>,
+    /// when the oldest input event not drawn yet was read
+    undrawn_input_at: Option<Instant>,
+    /// the longest a frame should take to draw, warning about the ones taking longer
+    #[cfg(feature = "metrics")]
+    frame_budget: Option<Duration>,
+    error_overlay: bool,
+    /// errors of the components shown in the error overlay
+    errors: Vec<String>,
+    panic_hook: bool,
+    print_fallback: bool,
+    on_start: Vec<LifecycleHook>,
+    on_quit: Vec<LifecycleHook>,
+    bell: bool,
+    status_bar: bool,
+    bell_hook: Option<BellHook>,
+    state: AppState,
+    schedules: Vec<Schedule<A>>,
+    themes: Vec<Theme>,
+    auto_theme: bool,
+    /// the Tui of an app driven by a host loop (see [App::init])
+    tui: Option<Tui>,
+    cancellation_token: CancellationToken,
+    action_tx: mpsc::UnboundedSender<Action<A>>,
+    action_rx: mpsc::UnboundedReceiver<Action<A>>,
 }
 
-impl Default for App {
+impl<A: UserAction> Default for App<A> {
     fn default() -> Self {
-        let (action_tx, action_rx) = mpsc::unbounded_channel::<String>();
+        let (action_tx, action_rx) = mpsc::unbounded_channel::<Action<A>>();
         Self {
-            last_tick_key_events: Vec::default(),
-            keybindings: KeyBindings::default(),
-            component_handlers: Vec::new(),
-            frame_rate: 4.into(),
-            tick_rate: 1.into(),
-            should_quit: false,
-            // should_suspend: false,
-            mouse: false,
-            paste: false,
-            action_tx,
-            action_rx,
+            core: Core {
+                last_tick_key_events: Vec::default(),
+                keybindings: KeyBindings::default(),
+                modal_keybindings: KeyBindings::default(),
+                scoped_keybindings: ScopedKeyBindings::default(),
+                invalid_keybindings: Vec::new(),
+                component_handlers: Vec::new(),
+                roots: 0,
+                layout: None,
+                focus: FocusManager::default(),
+                mouse_capture: None,
+                drag_target: None,
+                scroll_amount: SCROLL_AMOUNT,
+                click_interval: DEFAULT_CLICK_INTERVAL,
+                screens: Vec::new(),
+                router: Router::default(),
+                modals: Vec::new(),
+                open_modals: Modals::default(),
+                layered: Vec::new(),
+                floating: Vec::new(),
+                layers: Layers::default(),
+                history: None,
+                #[cfg(feature = "config")]
+                config: None,
+                recording: None,
+                recorder: None,
+                replay: None,
+                #[cfg(feature = "persistence")]
+                persistence: None,
+                #[cfg(feature = "devtools")]
+                inspector: None,
+                #[cfg(feature = "palette")]
+                palette: None,
+                #[cfg(feature = "palette")]
+                commands: Vec::new(),
+                render_on_demand: false,
+                color_depth: None,
+                input_at: None,
+                undrawn_input_at: None,
+                #[cfg(feature = "metrics")]
+                frame_budget: None,
+                error_overlay: false,
+                errors: Vec::new(),
+                panic_hook: false,
+                print_fallback: false,
+                on_start: Vec::new(),
+                on_quit: Vec::new(),
+                bell: true,
+                status_bar: false,
+                bell_hook: None,
+                state: AppState::default(),
+                schedules: Vec::new(),
+                themes: Vec::new(),
+                auto_theme: false,
+                tui: None,
+                cancellation_token: CancellationToken::new(),
+                frame_rate: 4.into(),
+                idle_frame_rate: None,
+                adaptive_frame_rate: None,
+                tick_rate: 1.into(),
+                should_quit: false,
+                should_suspend: false,
+                standard_bindings: false,
+                focus_bindings: false,
+                panic_escape: None,
+                mouse: false,
+                paste: false,
+                keyboard_enhancements: false,
+                key_releases: false,
+                title: None,
+                cursor_style: None,
+                named_ticks: Vec::new(),
+                action_tx,
+                action_rx,
+            },
+            output: PhantomData,
         }
     }
 }
 
-impl App {
+impl<A: UserAction> App<A> {
     // pub fn with_keybindings<const N: usize>(mut self, kb: [(&str, &str); N]) -> Self
-    pub fn new<const N: usize>(kb: [(&str, &str); N], components: Vec<Box<dyn Component>>) -> Self {
-        let keybindings = KeyBindings::new(kb);
-
-        let component_handlers =
-            components.into_iter().map(ComponentHandler::for_).collect::<Vec<_>>();
+    pub fn new<const N: usize>(
+        kb: [(&str, &str); N],
+        components: Vec<Box<dyn Component<A>>>,
+    ) -> Self {
+        let mut app = Self::default().with_components(components);
+        app.core.keybindings = KeyBindings::new(kb);
+        app
+    }
 
-        Self {
-            component_handlers,
-            keybindings,
-            ..Self::default()
+    /// Make [App::run] return the value of type `R` passed by a component to
+    /// [ComponentAccessors::exit_with](crate::ComponentAccessors::exit_with), e.g. the answer of
+    /// an interactive prompt:
+    ///
+    /// ```ignore
+    /// let mut app = App::default().returning::<String>().with_components(components![prompt]);
+    ///
+    /// // later, in the prompt component
+    /// self.exit_with(self.input.clone());
+    ///
+    /// let answer: Option<String> = app.run().await?;
+    /// ```
+    pub fn returning<R: Send + Sync + 'static>(self) -> App<A, R> {
+        App {
+            core: self.core,
+            output: PhantomData,
         }
     }
+}
 
+impl<A: UserAction, R: Send + Sync + 'static> App<A, R> {
     /// Set the components
-    pub fn with_components(mut self, components: Vec<Box<dyn Component>>) -> Self {
-        self.component_handlers =
+    pub fn with_components(mut self, components: Vec<Box<dyn Component<A>>>) -> Self {
+        self.core.component_handlers =
             components.into_iter().map(ComponentHandler::for_).collect::<Vec<_>>();
+        self.core.roots = self.core.component_handlers.len();
+        self
+    }
+
+    /// Split the frame between the root components set with [App::with_components], in order,
+    /// instead of drawing all of them over the whole frame. Makes multi-pane apps possible without
+    /// a wrapper component:
+    ///
+    /// ```ignore
+    /// let app = App::default()
+    ///     .with_components(components![sidebar, editor, status])
+    ///     .with_layout(Layout::horizontal([
+    ///         Constraint::Length(24),
+    ///         Constraint::Fill(1),
+    ///         Constraint::Length(16),
+    ///     ]));
+    /// ```
+    ///
+    /// Root components without a region of their own (if there are more components than
+    /// constraints) are drawn over the whole frame, like the screens, modals and layers.
+    pub fn with_layout(self, layout: Layout) -> Self {
+        self.with_layout_fn(move |area| layout.split(area).to_vec())
+    }
+
+    /// Compute the regions of the root components (see [App::with_layout]) from the frame area,
+    /// e.g. to change the layout when the terminal is narrow.
+    pub fn with_layout_fn(mut self, layout: impl Fn(Rect) -> Vec<Rect> + 'static) -> Self {
+        self.core.layout = Some(Box::new(layout));
+        self
+    }
+
+    /// Register a screen. Screens are root components managed by a navigation stack: only the
+    /// current screen (the one at the top of the stack) is active, so it's the only one drawn and
+    /// receiving events. The first registered screen is the initial one.
+    ///
+    /// The stack is managed with the [Action::PushScreen], [Action::PopScreen] and
+    /// [Action::ReplaceScreen] actions, and screens are notified of the transitions through
+    /// [Component::on_screen_enter] and [Component::on_screen_leave].
+    ///
+    /// ```ignore
+    /// let app = App::default()
+    ///     .with_screen("welcome", Welcome::default())
+    ///     .with_screen("settings", Settings::default());
+    ///
+    /// // later, in the welcome screen
+    /// self.send_action(Action::PushScreen("settings".into()));
+    /// ```
+    ///
+    /// Screens are drawn after the components set with [App::with_components], which stay active
+    /// regardless of the current screen (e.g. for a status bar).
+    pub fn with_screen(mut self, name: impl Into<String>, screen: impl Component<A>) -> Self {
+        self.core.screens.push((name.into(), Box::new(screen)));
         self
     }
 
-    // Set the keybindings
+    /// Register a modal (e.g. a confirm dialog or a popup). Modals are root components that are
+    /// only active while open. Open modals are drawn on top of everything else (but the
+    /// [Overlay](Layer::Overlay) layer) and the top one
+    /// captures all the key, mouse and paste events: only the standard keybindings (see
+    /// [App::with_standard_bindings]) and the panic escape (see [App::with_panic_escape]) still
+    /// apply while it's open.
+    ///
+    /// Modals are opened with [Action::OpenModal] and closed with [Action::CloseModal]. The focus
+    /// moves to the modal when it's opened, and is restored when it's closed.
+    ///
+    /// Modals receive the whole frame area when drawn, so they are responsible for drawing
+    /// themselves in the right place (e.g. clearing and centering a smaller area).
+    ///
+    /// ```ignore
+    /// let app = App::default()
+    ///     .with_components(components![home])
+    ///     .with_modal("confirm-quit", ConfirmQuit::default());
+    ///
+    /// // later, in the home component
+    /// self.send_action(Action::OpenModal("confirm-quit".into()));
+    /// ```
+    pub fn with_modal(mut self, name: impl Into<String>, modal: impl Component<A>) -> Self {
+        self.core.modals.push((name.into(), Box::new(modal)));
+        self
+    }
+
+    /// Add root components to the given [Layer], drawn above the components of the layers
+    /// below it and receiving the whole frame area. Unlike the components of the main layer, the
+    /// ones of the other layers don't receive the input events (keys, mouse and paste) unless
+    /// enabled with [App::with_layer_input].
+    ///
+    /// ```ignore
+    /// // a floating FPS counter, drawn over every screen and modal
+    /// let app = App::default()
+    ///     .with_components(components![home])
+    ///     .with_layer(Layer::Overlay, components![FpsCounter::default()]);
+    /// ```
+    pub fn with_layer(mut self, layer: Layer, components: Vec<Box<dyn Component<A>>>) -> Self {
+        self.core.layered.extend(components.into_iter().map(|component| (layer, component)));
+        self
+    }
+
+    /// Add a floating component, drawn in the area given by the [Float] (e.g. centered, or
+    /// anchored to a corner) on top of the root components, the screens and the modals. Useful
+    /// for the global toasts, palettes and dialogs that don't belong to a specific screen.
+    ///
+    /// ```ignore
+    /// let app = App::default()
+    ///     .with_components(components![home])
+    ///     .with_floating(
+    ///         Float::new(Anchor::BottomRight, Constraint::Length(32), Constraint::Length(3)),
+    ///         Toasts::default().as_active(),
+    ///     );
+    /// ```
+    ///
+    /// Floating components are in the [Overlay](Layer::Overlay) layer, drawn in the order they
+    /// were added, so they only receive the input events if it's enabled with
+    /// [App::with_layer_input].
+    pub fn with_floating(mut self, float: Float, component: impl Component<A>) -> Self {
+        self.core.floating.push((float, Box::new(component)));
+        self
+    }
+
+    /// Set whether the components of the given [Layer] receive the input events (see
+    /// [Layer::receives_input] for the defaults). An open modal captures them anyway.
+    pub fn with_layer_input(mut self, layer: Layer, input: bool) -> Self {
+        self.core.layers.set_input(layer, input);
+        self
+    }
+
+    /// Set the keybindings. Conflicting keybindings (see [KeyBindingConflict]) are reported by
+    /// [App::keybinding_conflicts].
     pub fn with_keybindings<const N: usize>(
         mut self,
-        kb: [(&str, impl Into<ActionKind>); N],
+        kb: [(&str, impl Into<ActionKind<A>>); N],
     ) -> Self {
-        self.keybindings = KeyBindings::new(kb);
+        let (keybindings, conflicts) = KeyBindings::checked(kb);
+        self.core.keybindings = keybindings;
+        self.core.invalid_keybindings = conflicts
+            .into_iter()
+            .filter(|c| !matches!(c, KeyBindingConflict::Shadowed { .. }))
+            .collect();
+        self
+    }
+
+    /// The conflicts between the keybindings of the app: the key sequences bound more than once
+    /// in [App::with_keybindings] (only the last binding is kept) or that can't be parsed, like an
+    /// invalid panic escape or key of the command palette, and the ones that never fire because a
+    /// shorter sequence they start with is bound too (e.g. `<g>` and `<g><g>`).
+    ///
+    /// ```ignore
+    /// let app = App::default().with_keybindings(kb!["<g>" => "app:top", "<g><g>" => "app:end"]);
+    /// for conflict in app.keybinding_conflicts() {
+    ///     eprintln!("warning: {conflict}");
+    /// }
+    /// ```
+    pub fn keybinding_conflicts(&self) -> Vec<KeyBindingConflict> {
+        let mut conflicts = self.core.invalid_keybindings.clone();
+        conflicts.extend(self.core.keybindings.conflicts());
+        conflicts.extend(self.core.panic_escape.clone().and_then(Result::err));
+        #[cfg(feature = "palette")]
+        conflicts.extend(self.core.palette.iter().filter_map(|p| p.key_conflict()).cloned());
+        conflicts
+    }
+
+    /// The keybindings of the app as listed by help screens: the global ones, built-in and config
+    /// ones included, then the scoped ones, each sorted by key sequence. Once the app runs, they're
+    /// also in the [AppState], for components to list them (e.g. the `Help` component):
+    ///
+    /// ```ignore
+    /// let bindings = self.state().read(|help: &Vec<KeyBindingHelp>| help.clone());
+    /// ```
+    pub fn keybindings_help(&self) -> Vec<KeyBindingHelp> {
+        let mut help = self.effective_keybindings().help(None);
+        for (scope, keybindings) in self.core.scoped_keybindings.iter() {
+            help.extend(keybindings.help(Some(scope)));
+        }
+        help
+    }
+
+    /// Set keybindings that only fire while the component named by their scope is focused (or
+    /// one of its children is), see [ScopedKeyBindings]. They take precedence over the global
+    /// keybindings, and the innermost scope wins.
+    ///
+    /// ```ignore
+    /// let app = App::default()
+    ///     .with_keybindings(kb!["<q>" => Action::Quit])
+    ///     .with_scoped_keybindings(kb_scoped! {
+    ///         "editor" => { "<ctrl-s>" => "app:save" },
+    ///         "list" => { "<d>" => "app:delete" }
+    ///     });
+    /// ```
+    pub fn with_scoped_keybindings(mut self, keybindings: ScopedKeyBindings<A>) -> Self {
+        for (scope, kb) in keybindings.0 {
+            self.core.scoped_keybindings.insert(scope, kb);
+        }
+        self
+    }
+
+    /// Install the standard keybindings (see [`KeyBindings::standard`]): `<ctrl-c>` to quit,
+    /// `<ctrl-z>` to suspend (unix only) and `<ctrl-l>` to force a redraw.
+    ///
+    /// User defined keybindings always take precedence over the standard ones.
+    pub fn with_standard_bindings(mut self, enabled: bool) -> Self {
+        self.core.standard_bindings = enabled;
+        self
+    }
+
+    /// Install the focus navigation keybindings (see [`KeyBindings::focus`]): `<tab>` and
+    /// `<backtab>` to move through the focusable components and `<esc>` to move the focus up one
+    /// level. It also sets `<ctrl-g>` as the panic escape, unless one was set with
+    /// [`App::with_panic_escape`].
+    ///
+    /// User defined keybindings always take precedence over the focus ones.
+    pub fn with_focus_bindings(mut self, enabled: bool) -> Self {
+        self.core.focus_bindings = enabled;
+        self
+    }
+
+    /// Set the "panic escape" key sequence, which always returns the focus to the root component,
+    /// even if the same keys are bound to something else, so users never get stuck inside nested
+    /// components.
+    ///
+    /// If `keys` is not a valid key sequence (see [parse_key_sequence]), there's no panic escape,
+    /// and the keys are reported by [App::keybinding_conflicts].
+    ///
+    /// [parse_key_sequence]: crate::utils::keyboard::parse_key_sequence
+    pub fn with_panic_escape(mut self, keys: &str) -> Self {
+        let escape = parse_key_sequence(keys).map_err(|err| KeyBindingConflict::Invalid {
+            keys: keys.to_string(),
+            error: err.to_string(),
+        });
+        self.core.panic_escape = Some(escape);
         self
     }
 
     /// Set the tick rate
     pub fn with_tick_rate(mut self, tick_rate: impl Into<f64>) -> Self {
-        self.tick_rate = tick_rate.into();
+        self.core.tick_rate = tick_rate.into();
+        self
+    }
+
+    /// Add a named tick stream with its own rate (ticks per second). Its ticks are delivered to
+    /// the components as [Event::NamedTick] through [Component::handle_named_tick_event], so
+    /// polling-heavy components don't force the global tick rate up.
+    ///
+    /// ```ignore
+    /// let app = App::default().with_named_tick("slow", 0.2); // once every 5 seconds
+    /// ```
+    pub fn with_named_tick(mut self, name: impl Into<String>, rate: impl Into<f64>) -> Self {
+        self.core.named_ticks.push((name.into(), rate.into()));
         self
     }
 
     /// Set the frame rate
     pub fn with_frame_rate(mut self, frame_rate: impl Into<f64>) -> Self {
-        self.frame_rate = frame_rate.into();
+        self.core.frame_rate = frame_rate.into();
+        self
+    }
+
+    /// Drop the frame rate to `idle_rate` after `after` without input events, and restore it on
+    /// the next one:
+    ///
+    /// ```ignore
+    /// let app = App::default().with_frame_rate(60).with_idle_frame_rate(1, Duration::from_secs(5));
+    /// ```
+    pub fn with_idle_frame_rate(mut self, idle_rate: impl Into<f64>, after: Duration) -> Self {
+        self.core.idle_frame_rate = Some((idle_rate.into(), after));
+        self
+    }
+
+    /// Adapt the frame rate to the activity of the app, between `min` and `max` frames per
+    /// second: it jumps to `max` on input events, actions and
+    /// [render requests](crate::ComponentAccessors::request_render) (e.g. from animations), then
+    /// halves every second without activity, down to `min`. Responsive while in use, cheap on
+    /// batteries while idle. Takes precedence over [App::with_frame_rate] and
+    /// [App::with_idle_frame_rate].
+    pub fn with_adaptive_frame_rate(mut self, min: impl Into<f64>, max: impl Into<f64>) -> Self {
+        self.core.adaptive_frame_rate = Some((min.into(), max.into()));
+        self
+    }
+
+    /// Warn, through the `log` crate, about the frames taking longer than the budget to draw,
+    /// naming the components that took the longest (see [AppMetrics]). E.g. a budget of 16ms
+    /// keeps up with 60 frames per second.
+    #[cfg(feature = "metrics")]
+    pub fn with_frame_budget(mut self, budget: Duration) -> Self {
+        self.core.frame_budget = Some(budget);
         self
     }
 
     /// Set the mouse
     pub fn with_mouse(mut self, mouse: bool) -> Self {
-        self.mouse = mouse;
+        self.core.mouse = mouse;
+        self
+    }
+
+    /// Set how much a turn of the mouse wheel scrolls the component under the pointer, e.g. in
+    /// lines (see [Component::handle_scroll_event]). Defaults to 3.
+    pub fn with_scroll_amount(mut self, amount: u16) -> Self {
+        self.core.scroll_amount = amount;
+        self
+    }
+
+    /// Set the longest time between two clicks of a mouse button on the same cell for them to
+    /// count as a double (or triple) click (see [Component::handle_mouse_click]). Defaults to 500
+    /// milliseconds.
+    pub fn with_click_interval(mut self, interval: Duration) -> Self {
+        self.core.click_interval = interval;
         self
     }
 
     /// Set the paste
     pub fn with_paste(mut self, paste: bool) -> Self {
-        self.paste = paste;
+        self.core.paste = paste;
+        self
+    }
+
+    /// Ask the terminal to report the keys with the kitty keyboard protocol, so the keybindings
+    /// and the components can tell apart `<shift-enter>`, `<ctrl-enter>` and `<enter>`, e.g. to
+    /// submit on enter and add a newline on shift+enter (see [Tui::keyboard_enhancements]).
+    /// Terminals without support for the protocol keep sending the keys the legacy way.
+    pub fn with_keyboard_enhancements(mut self, enabled: bool) -> Self {
+        self.core.keyboard_enhancements = enabled;
+        self
+    }
+
+    /// Send the repeats of the keys held down and their releases to the components too, e.g. for
+    /// games and hold-to-scroll interactions: the repeats as key events of the
+    /// [Repeat](KeyEventKind::Repeat) kind, which trigger the keybindings like presses, and the
+    /// releases through [Component::handle_key_release_events]. Most terminals only report the
+    /// releases with the kitty keyboard protocol, which this enables too (see
+    /// [App::with_keyboard_enhancements]).
+    pub fn with_key_releases(mut self, enabled: bool) -> Self {
+        self.core.key_releases = enabled;
+        self
+    }
+
+    /// Set the title of the terminal window while the app runs (see [Tui::title]).
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.core.title = Some(title.into());
+        self
+    }
+
+    /// Set the shape of the terminal cursor while the app runs (see [Tui::cursor_style]). The
+    /// cursor is only shown where a component puts it while drawing, with
+    /// `Frame::set_cursor_position`, e.g. at the caret of a text input.
+    pub fn with_cursor_style(mut self, style: SetCursorStyle) -> Self {
+        self.core.cursor_style = Some(style);
+        self
+    }
+
+    /// Draw the [Inspector] development overlay on top of the components. Enable the mouse (see
+    /// [App::with_mouse]) to inspect the component under the cursor.
+    #[cfg(feature = "devtools")]
+    pub fn with_inspector(mut self, inspector: Inspector) -> Self {
+        self.core.inspector = Some(inspector);
+        self
+    }
+
+    /// Open the [CommandPalette] with its key (`<ctrl-p>` by default), to search the commands of
+    /// the app (see [App::with_commands]) and of its active components (see
+    /// [Component::commands]), and dispatch the action of the picked one.
+    #[cfg(feature = "palette")]
+    pub fn with_command_palette(mut self, palette: CommandPalette<A>) -> Self {
+        self.core.palette = Some(palette);
+        self
+    }
+
+    /// Add commands to the command palette (see [App::with_command_palette]), whatever the
+    /// active components.
+    #[cfg(feature = "palette")]
+    pub fn with_commands(mut self, commands: Vec<Command<A>>) -> Self {
+        self.core.commands.extend(commands);
+        self
+    }
+
+    /// Only draw a frame when something might have changed, instead of on every frame (see
+    /// [App::with_frame_rate]): after input events and actions, or when a component asks for it with
+    /// [ComponentAccessors::request_render](crate::ComponentAccessors::request_render) (e.g. after
+    /// updating on a tick). Saves CPU on mostly static screens, like forms.
+    pub fn with_render_on_demand(mut self, enabled: bool) -> Self {
+        self.core.render_on_demand = enabled;
+        self
+    }
+
+    /// Show the errors of the components (see [ComponentError]) in an overlay at the bottom of the
+    /// screen, dismissed by the next key press, instead of stopping the app with the first one.
+    pub fn with_error_overlay(mut self, enabled: bool) -> Self {
+        self.core.error_overlay = enabled;
+        self
+    }
+
+    /// Share a value between all the components of the app. Components reach it through
+    /// [ComponentAccessors::state](crate::ComponentAccessors::state), see [AppState].
+    pub fn with_state<T: Send + Sync + 'static>(self, value: T) -> Self {
+        self.core.state.insert(value);
+        self
+    }
+
+    /// Style the app with the given [Theme], making it the current theme of the process (see
+    /// [Theme::set]).
+    pub fn with_theme(self, theme: Theme) -> Self {
+        Theme::set(theme);
+        self
+    }
+
+    /// Add themes the app can switch to by name while running, with [Action::SetTheme]. The
+    /// built-in `dark` and `light` themes are always available, unless replaced by themes with
+    /// the same names.
+    pub fn with_themes(mut self, themes: impl IntoIterator<Item = Theme>) -> Self {
+        self.core.themes.extend(themes);
+        self
+    }
+
+    /// Switch to the `light` or the `dark` theme (see [App::with_themes]) when the app starts,
+    /// following the background of the terminal, if it's known (see [TerminalInfo::background]).
+    pub fn with_auto_theme(mut self, enabled: bool) -> Self {
+        self.core.auto_theme = enabled;
+        self
+    }
+
+    /// Install a panic hook that restores the terminal (see [restore_terminal]) before printing
+    /// the panic, so a panicking component doesn't leave the terminal in raw mode and the
+    /// alternate screen. The hook wraps the previously installed one.
+    pub fn with_panic_hook(mut self, enabled: bool) -> Self {
+        self.core.panic_hook = enabled;
+        self
+    }
+
+    /// When stdout is not a terminal (e.g. the output is piped to another program or redirected
+    /// to a file), print a single frame as plain text and exit, instead of failing with
+    /// [MatetuiError::NotATerminal]. The frame is as wide as the `COLUMNS` variable and as tall
+    /// as the `LINES` one (80x24 by default), and the components see a terminal without colors
+    /// (see [TerminalInfo]).
+    pub fn with_print_fallback(mut self, enabled: bool) -> Self {
+        self.core.print_fallback = enabled;
+        self
+    }
+
+    /// Run the given callback when the app starts, once the terminal is set up and the components
+    /// are initialized, right before the first event is processed.
+    pub fn on_start(mut self, hook: impl FnMut(&AppState) + 'static) -> Self {
+        self.core.on_start.push(Box::new(hook));
         self
     }
 
-    fn send(&self, action: Action) -> Result<(), MatetuiError> {
-        match action {
-            Action::AppAction(cmd) => self.action_tx.send(cmd)?,
-            Action::Key(key) => self.action_tx.send(key)?,
-            action => self.action_tx.send(action.to_string())?,
+    /// Run the given callback when the app quits, once the terminal is restored (so it can print
+    /// to the normal screen). The callback runs even if the app stops with an error.
+    pub fn on_quit(mut self, hook: impl FnMut(&AppState) + 'static) -> Self {
+        self.core.on_quit.push(Box::new(hook));
+        self
+    }
+
+    /// Enable or disable the bell (see [Action::Bell]). Enabled by default.
+    pub fn with_bell(mut self, bell: bool) -> Self {
+        self.core.bell = bell;
+        self
+    }
+
+    /// Reserve the last line of the screen for a status bar, showing the status set by the
+    /// components with [ComponentAccessors::set_status](crate::ComponentAccessors::set_status).
+    /// The components, layers and modals are drawn above it. Disabled by default.
+    pub fn with_status_bar(mut self, enabled: bool) -> Self {
+        self.core.status_bar = enabled;
+        self
+    }
+
+    /// Run the given callback instead of ringing the terminal bell when a component calls
+    /// [ComponentAccessors::bell](crate::ComponentAccessors::bell), e.g. to play a sound.
+    pub fn on_bell(mut self, hook: impl FnMut() + 'static) -> Self {
+        self.core.bell_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Get the state shared by the components of the app, e.g. to read it after the app quits.
+    pub fn state(&self) -> &AppState {
+        &self.core.state
+    }
+
+    /// Record every event and action processed by the app loop in the given [History].
+    pub fn with_history(mut self, history: History) -> Self {
+        self.core.history = Some(history);
+        self
+    }
+
+    /// Let users override the settings of the app with the config file at `path`, in TOML or JSON
+    /// (see [Config]): the tick and frame rates, the mouse and paste flags and the keybindings.
+    /// The settings of the file take precedence over the ones set in code, whatever the order of
+    /// the calls. A missing file is not an error, as users might not have one.
+    ///
+    /// ```ignore
+    /// let app = App::default().with_frame_rate(60).with_config_file("~/.config/mate/config.toml")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a valid config.
+    #[cfg(feature = "config")]
+    pub fn with_config_file(self, path: impl AsRef<Path>) -> Result<Self, MatetuiError> {
+        match Config::load(path) {
+            Ok(config) => Ok(self.with_config(config)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(self),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Override the settings of the app with the given [Config] (see [App::with_config_file]).
+    #[cfg(feature = "config")]
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.core.config = Some(config);
+        self
+    }
+
+    /// Write the effective settings of the app to a TOML config file at `path`: the tick and
+    /// frame rates, the mouse and paste flags, the current [Theme] and the keybindings, including
+    /// the built-in ones and the overrides of the config file, if any. Users can take it as a
+    /// starting point to customize the app (see [App::with_config_file]).
+    ///
+    /// ```ignore
+    /// if std::env::args().any(|arg| arg == "--dump-config") {
+    ///     return app.export_config("config.toml");
+    /// }
+    /// ```
+    ///
+    /// The keybindings to actions that can't be written as strings, like
+    /// [Action::PushScreen], are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    #[cfg(feature = "config")]
+    pub fn export_config(&self, path: impl AsRef<Path>) -> Result<(), MatetuiError> {
+        let overrides = self.core.config.clone().unwrap_or_default();
+        let keybindings = self.effective_keybindings();
+        let config = Config {
+            tick_rate: Some(overrides.tick_rate.unwrap_or(self.core.tick_rate)),
+            frame_rate: Some(overrides.frame_rate.unwrap_or(self.core.frame_rate)),
+            mouse: Some(overrides.mouse.unwrap_or(self.core.mouse)),
+            paste: Some(overrides.paste.unwrap_or(self.core.paste)),
+            theme: Some(overrides.theme.unwrap_or_else(Theme::current)),
+            keybindings: keybindings
+                .iter()
+                .filter_map(|(keys, action)| {
+                    Some((key_sequence_to_string(keys), action_to_config(action)?))
+                })
+                .collect(),
         };
+        std::fs::write(path, config.to_toml())?;
         Ok(())
     }
 
-    fn try_recv(&mut self) -> Result<String, TryRecvError> {
-        self.action_rx.try_recv()
+    /// Record the input events processed by the app to the file at `path` (see [Recording]). The
+    /// file is written as the events happen, so it's complete even if the app crashes.
+    pub fn with_recording(mut self, path: impl Into<PathBuf>) -> Self {
+        self.core.recording = Some(path.into());
+        self
+    }
+
+    /// Replay the events of the given [Recording] through the app, at their recorded times. The
+    /// terminal input keeps working while the recording plays.
+    pub fn with_replay(mut self, recording: Recording) -> Self {
+        self.core.replay = Some(recording);
+        self
     }
 
-    pub async fn run(&mut self) -> Result<(), MatetuiError> {
-        let mut tui = Tui::new()?
-            .tick_rate(self.tick_rate)
-            .frame_rate(self.frame_rate)
-            .mouse(self.mouse)
-            .paste(self.paste);
+    /// Remember the state of the components between runs (see [Component::save_state]): it's
+    /// stored on quit in the `state.toml` file of the `app_name` directory, in the config
+    /// directory of the user (`$XDG_CONFIG_HOME` or `~/.config` on linux, `~/Library/Application
+    /// Support` on macOS and `%APPDATA%` on windows), and restored on start.
+    ///
+    /// The components are found by their path: the name of the root component, then the keys of
+    /// the children down to them (e.g. `main/sidebar`), so they should keep the same names across
+    /// versions of the app to find their state.
+    #[cfg(feature = "persistence")]
+    pub fn with_persistence(mut self, app_name: &str) -> Self {
+        self.core.persistence = config_dir().map(|dir| dir.join(app_name).join("state.toml"));
+        self
+    }
 
-        tui.enter()?;
+    /// Remember the state of the components between runs in the file at `path`, instead of the
+    /// config directory of the user (see [App::with_persistence]).
+    #[cfg(feature = "persistence")]
+    pub fn with_persistence_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.core.persistence = Some(path.into());
+        self
+    }
 
-        for handler in self.component_handlers.iter_mut() {
-            handler.receive_action_handler(self.action_tx.clone());
+    /// Draw with the colors of the given [ColorDepth] instead of the ones the terminal supports:
+    /// the colors of every frame are degraded to it (e.g. [ColorDepth::Mono] draws without
+    /// colors), and the [TerminalInfo] of the app reports it.
+    pub fn with_color_depth(mut self, depth: ColorDepth) -> Self {
+        self.core.color_depth = Some(depth);
+        self
+    }
+
+    /// Apply the runtime switches parsed from the command-line arguments (see [AppOptions]). The
+    /// config file is loaded right away and, unlike [App::with_config_file], a missing one is an
+    /// error, as the user asked for it. The other flags take precedence over the config file.
+    ///
+    /// ```ignore
+    /// let app = App::default().with_frame_rate(60).with_options(AppOptions::from_env()?)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config file or the recording to replay can't be loaded.
+    #[cfg(feature = "cli")]
+    pub fn with_options(mut self, options: AppOptions) -> Result<Self, MatetuiError> {
+        if let Some(path) = &options.config {
+            self = self.with_config(Config::load(path)?);
+        }
+        if let Some(rate) = options.frame_rate {
+            self.core.frame_rate = rate;
+            if let Some(config) = self.core.config.as_mut() {
+                config.frame_rate = Some(rate);
+            }
+        }
+        if options.no_color {
+            self.core.color_depth = Some(ColorDepth::Mono);
+        }
+        if let Some(path) = options.record {
+            self = self.with_recording(path);
+        }
+        if let Some(path) = options.replay {
+            self = self.with_replay(Recording::load(path)?);
         }
+        Ok(self)
+    }
+
+    /// Send the given action once, `delay` after the app starts running. The action isn't sent if
+    /// the app quits before.
+    ///
+    /// ```ignore
+    /// let app = App::default().after(Duration::from_secs(3), "splash:hide");
+    /// ```
+    pub fn after(mut self, delay: Duration, action: impl Into<ActionKind<A>>) -> Self {
+        self.core.schedules.push(Schedule::After(delay, Action::from(action.into())));
+        self
+    }
+
+    /// Send the given action every time the `period` elapses, starting one `period` after the app
+    /// starts running. Useful for recurring refreshes that would otherwise be implemented in the
+    /// tick handler of a component.
+    ///
+    /// ```ignore
+    /// let app = App::default().every(Duration::from_secs(30), "app:refresh");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn every(mut self, period: Duration, action: impl Into<ActionKind<A>>) -> Self {
+        assert!(!period.is_zero(), "`period` must be non-zero");
+        self.core.schedules.push(Schedule::Every(period, Action::from(action.into())));
+        self
+    }
+
+    /// Send the given action every time the cron expression matches (in local time). Standard
+    /// five fields expressions are supported (`minute hour day-of-month month day-of-week`).
+    ///
+    /// ```ignore
+    /// let app = App::default().schedule_action("0 * * * *", "app:hourly-refresh")?;
+    /// ```
+    #[cfg(feature = "cron")]
+    pub fn schedule_action(
+        mut self,
+        expression: &str,
+        action: impl Into<ActionKind<A>>,
+    ) -> Result<Self, MatetuiError> {
+        let cron = croner::Cron::new(expression).parse()?;
+        self.core.schedules.push(Schedule::Cron(Box::new(cron), Action::from(action.into())));
+        Ok(self)
+    }
 
-        for handler in self.component_handlers.iter_mut() {
-            handler.handle_init(tui.size()?);
+    fn record(&self, kind: HistoryKind, value: impl Into<String>) {
+        if let Some(history) = &self.core.history {
+            history.record(kind, value);
         }
+    }
 
-        loop {
-            if let Some(e) = tui.next().await {
-                match e {
-                    // Event::Resize(x, y) => self.send(Action::Resize(x, y))?,
-                    Event::Render => self.send(Action::Render)?,
-                    Event::Tick => self.send(Action::Tick)?,
-                    Event::Quit => self.send(Action::Quit)?,
-                    Event::Key(key) => {
-                        if let Some(action) = self.keybindings.get(&[key]) {
-                            self.send(action.clone())?;
-                        } else {
-                            // If the key was not handled as a single key action,
-                            // then consider it for multi-key combinations.
-                            self.last_tick_key_events.push(key);
-
-                            // Check for multi-key combinations
-                            if let Some(action) = self.keybindings.get(&self.last_tick_key_events) {
-                                self.send(action.clone())?;
-                            }
-                        }
+    fn send(&self, action: Action<A>) -> Result<(), MatetuiError> {
+        self.core.action_tx.send(action)?;
+        Ok(())
+    }
 
-                        // send the key event as simple key event too (not as action) if it's a
-                        // single alphanumeric char key
-                        if let KeyCode::Char(c) = key.code {
-                            if c.is_alphanumeric() {
-                                self.send(Action::Key(c.to_string()))?;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-                let mut actions = Vec::new();
+    fn try_recv(&mut self) -> Result<Action<A>, TryRecvError> {
+        self.core.action_rx.try_recv()
+    }
 
-                for handler in self.component_handlers.iter_mut() {
-                    let component_actions = handler.handle_events(Some(e.clone()));
-                    actions.extend(component_actions);
-                }
+    /// The keybindings that apply right now.
+    fn active_keybindings(&self) -> &KeyBindings<A> {
+        if self.core.open_modals.top().is_some() {
+            &self.core.modal_keybindings
+        } else {
+            &self.core.keybindings
+        }
+    }
 
-                for action in actions {
-                    self.send(action)?;
-                }
+    /// The action bound to the key sequence right now: the scoped keybindings of the focused
+    /// components first (innermost first), then the global ones.
+    fn binding(&mut self, keys: &[KeyEvent]) -> Option<Action<A>> {
+        if !self.core.scoped_keybindings.is_empty() {
+            let scopes = self.scopes();
+            if let Some(action) = self.core.scoped_keybindings.get(&scopes, keys) {
+                return Some(action.clone());
             }
+        }
+        self.active_keybindings().get(keys).cloned()
+    }
 
-            while let Ok(action) = self.try_recv() {
-                let enum_action = Action::from_str(&action).ok();
-                if let Some(a) = enum_action {
-                    match a {
-                        Action::Quit => self.should_quit = true,
-                        Action::Render => {
-                            tui.draw(|f| {
-                                for handler in self.component_handlers.iter_mut() {
-                                    handler.handle_draw(f, f.area());
-                                }
-                            })?;
-                        }
-                        Action::Tick => {
-                            self.last_tick_key_events.drain(..);
-                        }
+    /// Returns `true` if the keys start a longer key sequence bound right now (see
+    /// [App::binding]).
+    fn starts_binding(&mut self, keys: &[KeyEvent]) -> bool {
+        if !self.core.scoped_keybindings.is_empty() {
+            let scopes = self.scopes();
+            if self.core.scoped_keybindings.is_prefix(&scopes, keys) {
+                return true;
+            }
+        }
+        self.active_keybindings().is_prefix(keys)
+    }
 
-                        // Action::Resize(w, h) => {
-                        //     tui.resize(Rect::new(0, 0, w, h))?;
-                        //     tui.draw(|f| {
-                        //         for component in self.components.iter_mut() {
-                        //             if component.is_active() {
-                        //                 component.draw(f, f.area());
-                        //             }
-                        //         }
-                        //     })?;
-                        // }
-                        _ => {}
-                    }
+    /// Send the action bound to the key, or to the key sequence it completes. The keys starting a
+    /// longer bound sequence are held back until the sequence is complete, and go to the focused
+    /// component like the keys no binding handles (see [Action::Key]) if it doesn't complete.
+    fn handle_key(&mut self, key: KeyEvent) -> Result<(), MatetuiError> {
+        let held = std::mem::take(&mut self.core.last_tick_key_events);
+        let keys = [held.as_slice(), &[key]].concat();
+        if let Some(action) = self.binding(&keys) {
+            return self.send(action);
+        }
+        if self.starts_binding(&keys) {
+            self.core.last_tick_key_events = keys;
+            return Ok(());
+        }
 
-                    for handler in self.component_handlers.iter_mut() {
-                        handler.handle_update(a.clone());
-                    }
-                } else {
-                    // unrecognized action, might be a custom component action
-                    // send it to all components as a raw string
-                    for handler in self.component_handlers.iter_mut() {
-                        handler.handle_message(action.clone());
-                    }
-                }
+        if held.is_empty() {
+            return self.send(Action::Key(key_sequence_to_string(&[key])));
+        }
+        for held in held {
+            self.send(Action::Key(key_sequence_to_string(&[held])))?;
+        }
+        // the key might be bound on its own, or start another sequence
+        self.handle_key(key)
+    }
+
+    /// The scopes whose keybindings apply right now: the names of the focused component and its
+    /// ancestors or, when nothing is focused, of the active components (only those of the top
+    /// modal, if one is open).
+    fn scopes(&mut self) -> Vec<String> {
+        let names = self.core.focus.focused_names(&mut self.core.component_handlers);
+        if !names.is_empty() {
+            return names;
+        }
+
+        let mut names = vec![];
+        for (index, handler) in self.core.component_handlers.iter_mut().enumerate() {
+            if self.core.open_modals.top().is_none_or(|modal| modal == index) {
+                active_names(handler.component_mut(), None, &mut names);
+            }
+        }
+        names
+    }
+
+    /// Deliver a key no binding handled to the focused component (see [Action::Key]), unless it's
+    /// outside of the modal capturing the input or in a layer that doesn't receive it.
+    fn deliver_key(&mut self, key: String) {
+        if self.focused_input().is_some() {
+            if let Some(c) = self.core.focus.focused_component(&mut self.core.component_handlers) {
+                deliver_message(c, key);
             }
+        }
+    }
 
-            if self.should_quit {
-                tui.stop();
-                break;
+    /// Scroll the focused component (see [Action::Scroll]), under the same conditions as the keys
+    /// delivered to it (see [App::deliver_key]).
+    fn scroll_focused(
+        &mut self,
+        direction: ScrollDirection,
+        amount: u16,
+    ) -> Result<(), MatetuiError> {
+        let Some(target) = self.focused_input() else {
+            return Ok(());
+        };
+        let handler = &mut self.core.component_handlers[target.root];
+        if let Some(action) = handler.handle_scroll(&target.children, direction, amount) {
+            self.send(action)?;
+        }
+        Ok(())
+    }
+
+    /// Handle the result of a component call: errors either stop the app or are shown in the
+    /// error overlay (see [App::with_error_overlay]).
+    fn check(&mut self, result: Result<(), MatetuiError>) -> Result<(), MatetuiError> {
+        match result {
+            Err(err @ MatetuiError::ComponentError { .. }) if self.core.error_overlay => {
+                self.record(HistoryKind::Action, format!("Error({err})"));
+                self.core.errors.push(err.to_string());
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Draw a frame, unless the app renders on demand and nothing asked for it.
+    pub(crate) fn render_frame<B: Backend>(
+        &mut self,
+        tui: &mut Tui<B>,
+    ) -> Result<(), MatetuiError> {
+        let requested = self
+            .core
+            .state
+            .read(|request: &RenderRequest| request.0.swap(false, Ordering::Relaxed))
+            .unwrap_or(true);
+
+        if requested {
+            tui.notify_activity();
+        }
+        if !self.core.render_on_demand || requested {
+            self.render(tui)?;
+        }
+        Ok(())
+    }
+
+    /// The indexes of the root components in the order they are drawn, from the bottom to the
+    /// top: layer by layer, with the open modals on top of the main layer, in the order they
+    /// were opened.
+    fn draw_order(&self) -> Vec<usize> {
+        let mut order = vec![];
+        for layer in Layer::ALL {
+            order.extend((0..self.core.component_handlers.len()).filter(|index| {
+                self.core.layers.layer(*index) == layer && !self.core.open_modals.is_modal(*index)
+            }));
+            if layer == Layer::Main {
+                order.extend(self.core.open_modals.open_modals());
+            }
+        }
+        order
+    }
+
+    /// Mark the app as needing a new frame (see [App::with_render_on_demand]).
+    fn request_render(&self) {
+        self.core.state.read(|request: &RenderRequest| request.0.store(true, Ordering::Relaxed));
+    }
+
+    pub(crate) fn render<B: Backend>(&mut self, tui: &mut Tui<B>) -> Result<(), MatetuiError> {
+        #[cfg(feature = "trace")]
+        let _span = instrument::render();
+        let mut results = vec![];
+        #[cfg(feature = "devtools")]
+        let latency = self.core.state.read(InputLatency::clone).unwrap_or_default();
+
+        let frame = |f: &mut Frame<'_>| {
+            let mut screen = f.area();
+            if self.core.status_bar && screen.height > 1 {
+                screen.height -= 1;
+                let status = self.core.state.read(|text: &StatusText| text.0.clone()).flatten();
+                let bar = Rect::new(screen.x, screen.bottom(), screen.width, 1);
+                f.render_widget(
+                    Paragraph::new(status.unwrap_or_default()).style(styles::hint()),
+                    bar,
+                );
+            }
+            let regions =
+                self.core.layout.as_ref().map(|layout| layout(screen)).unwrap_or_default();
+
+            for index in self.draw_order() {
+                let area = match self.core.layers.float(index) {
+                    Some(float) => float.area(screen),
+                    None => regions
+                        .get(index)
+                        .filter(|_| {
+                            index < self.core.roots && !self.core.open_modals.is_modal(index)
+                        })
+                        .copied()
+                        .unwrap_or(screen),
+                };
+                results.push(self.core.component_handlers[index].handle_draw(f, area));
+            }
+
+            if !self.core.errors.is_empty() {
+                draw_errors(f, &self.core.errors);
             }
+
+            #[cfg(feature = "palette")]
+            if let Some(palette) = self.core.palette.as_ref() {
+                palette.draw(f);
+            }
+
+            #[cfg(feature = "devtools")]
+            if let Some(inspector) = self.core.inspector.as_mut() {
+                inspector.draw(f, &mut self.core.component_handlers, &latency);
+            }
+
+            if let Some(depth) =
+                self.core.color_depth.filter(|depth| *depth != ColorDepth::TrueColor)
+            {
+                for cell in f.buffer_mut().content.iter_mut() {
+                    cell.fg = depth.degrade(cell.fg);
+                    cell.bg = depth.degrade(cell.bg);
+                }
+            }
+        };
+        #[cfg(not(feature = "metrics"))]
+        tui.draw(frame)?;
+        #[cfg(feature = "metrics")]
+        {
+            let started = Instant::now();
+            let (drawn, components) = metrics::collect_draws(|| tui.draw(frame));
+            drawn?;
+            self.frame_drawn(started.elapsed(), components);
         }
-        tui.exit()?;
+
+        if let Some(at) = self.core.undrawn_input_at.take() {
+            self.core.state.write(|latency: &mut InputLatency| latency.drawn(at));
+        }
+
+        for result in results {
+            self.check(result)?;
+        }
+        Ok(())
+    }
+
+    /// Keep the [AppMetrics] of a frame, warning about it if it took longer than the budget.
+    #[cfg(feature = "metrics")]
+    fn frame_drawn(&mut self, took: Duration, components: Vec<(String, Duration)>) {
+        let budget = self.core.frame_budget.filter(|budget| took > *budget);
+        if let Some(budget) = budget {
+            metrics::warn_slow_frame(took, budget, &components);
+        }
+        let slow = budget.is_some();
+        self.core.state.write(|metrics: &mut AppMetrics| metrics.drawn(took, slow, components));
+    }
+
+    /// Run the app until it quits. Returns the value passed to
+    /// [ComponentAccessors::exit_with](crate::ComponentAccessors::exit_with), if the app exited
+    /// through it (see [App::returning]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [MatetuiError::NotATerminal] if stdout is not a terminal, unless the app prints a
+    /// frame instead (see [App::with_print_fallback]), so no escape sequences end up in a pipe.
+    pub async fn run(&mut self) -> Result<Option<R>, MatetuiError> {
+        self.prepare();
+        if !self.claim_terminal()? {
+            return Ok(self.exit_value());
+        }
+        self.run_on(&mut Tui::new()?).await
+    }
+
+    /// Check that stdout is a terminal and install the panic hook. Returns `false` if stdout is
+    /// not a terminal and the app printed a frame instead (see [App::with_print_fallback]).
+    pub(crate) fn claim_terminal(&mut self) -> Result<bool, MatetuiError> {
+        if !std::io::stdout().is_terminal() {
+            if !self.core.print_fallback {
+                return Err(MatetuiError::NotATerminal);
+            }
+            self.print_frame()?;
+            return Ok(false);
+        }
+
+        if self.core.panic_hook {
+            let hook = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                // best effort, the panic is reported anyway
+                let _ = restore_terminal();
+                hook(info);
+            }));
+        }
+        Ok(true)
+    }
+
+    /// Run the app until it quits, drawing to the given [Tui], e.g. one created with
+    /// [Tui::with_backend] to draw through a termwiz or custom backend. The tick, frame and idle
+    /// rates, the named ticks, the mouse and the paste of the app are applied to it.
+    ///
+    /// A [headless](Tui::headless) Tui (e.g. over ratatui's `TestBackend`) runs without touching
+    /// the terminal: the app only gets the events sent through the `event_tx` of the Tui, and its
+    /// last frame stays in the backend:
+    ///
+    /// ```ignore
+    /// let mut tui = Tui::headless(TestBackend::new(80, 24))?;
+    /// tui.event_tx.send(Event::Key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)))?;
+    /// app.run_with(&mut tui).await?;
+    /// let last_frame = tui.backend().buffer();
+    /// ```
+    ///
+    /// Unlike [App::run], it doesn't check that stdout is a terminal nor install the panic hook.
+    pub async fn run_with<B: Backend>(
+        &mut self,
+        tui: &mut Tui<B>,
+    ) -> Result<Option<R>, MatetuiError> {
+        self.prepare();
+        self.run_on(tui).await
+    }
+
+    /// Set the app up and enter the terminal, to drive the app from the loop of a host application
+    /// (e.g. alongside a network client) with [App::step], instead of handing the control flow over
+    /// to [App::run]. [App::shutdown] restores the terminal once the app quit:
+    ///
+    /// ```ignore
+    /// app.init()?;
+    /// let actions = app.action_sender();
+    /// loop {
+    ///     tokio::select! {
+    ///         running = app.step() => if !running? { break },
+    ///         Some(message) = client.next() => actions.send(Action::AppAction(message))?,
+    ///     }
+    /// }
+    /// let exit_value = app.shutdown()?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [MatetuiError::NotATerminal] if stdout is not a terminal, unless the app prints a
+    /// frame instead (see [App::with_print_fallback]), in which case the app is done right away.
+    pub fn init(&mut self) -> Result<(), MatetuiError> {
+        self.prepare();
+        if !self.claim_terminal()? {
+            self.core.should_quit = true;
+            return Ok(());
+        }
+
+        let mut tui = Tui::new()?;
+        self.enter_tui(&mut tui)?;
+        self.start(&mut tui)?;
+        self.core.tui = Some(tui);
+        Ok(())
+    }
+
+    /// Wait for the next event and handle it, along with the actions sent meanwhile (see
+    /// [App::init]). Returns `false` once the app quit, after which [App::shutdown] should be
+    /// called, and `step` shouldn't be called anymore.
+    ///
+    /// Waiting for the event is cancel safe, so `step` can be raced against the other work of the
+    /// host loop in a `tokio::select!`. On errors, [App::shutdown] still restores the terminal.
+    pub async fn step(&mut self) -> Result<bool, MatetuiError> {
+        let Some(tui) = self.core.tui.as_mut() else {
+            return Ok(false);
+        };
+        let event = if self.core.should_quit {
+            None
+        } else {
+            tui.next_stamped().await
+        };
+
+        let mut tui = self.core.tui.take().expect("the tui is set until the shutdown");
+        let running = self.complete_step(event, &mut tui).await;
+        self.core.tui = Some(tui);
+        running
+    }
+
+    /// Cancel the tasks of the app, restore the terminal and run the quit hooks, once the app
+    /// driven by a host loop quit (see [App::init]). Returns the value passed to
+    /// [ComponentAccessors::exit_with](crate::ComponentAccessors::exit_with), if any.
+    pub fn shutdown(&mut self) -> Result<Option<R>, MatetuiError> {
+        match self.core.tui.take() {
+            Some(mut tui) => self.leave_tui(&mut tui, Ok(())),
+            None => Ok(self.exit_value()),
+        }
+    }
+
+    async fn run_on<B: Backend>(&mut self, tui: &mut Tui<B>) -> Result<Option<R>, MatetuiError> {
+        self.enter_tui(tui)?;
+        // the terminal is restored and the tasks are cancelled even if the app stops with an error
+        let result = self.main_loop(tui).await;
+        self.leave_tui(tui, result)
+    }
+
+    /// Apply the rates, the mouse and the paste of the app to the Tui, and enter it.
+    pub(crate) fn enter_tui<B: Backend>(&mut self, tui: &mut Tui<B>) -> Result<(), MatetuiError> {
+        tui.tick_rate = self.core.tick_rate;
+        tui.frame_rate = self.core.frame_rate;
+        tui.mouse = self.core.mouse;
+        tui.paste = self.core.paste;
+        tui.keyboard_enhancements = self.core.keyboard_enhancements;
+        tui.key_releases = self.core.key_releases;
+        tui.title = self.core.title.clone().or(tui.title.take());
+        tui.cursor_style = self.core.cursor_style.or(tui.cursor_style);
+        tui.click_interval = self.core.click_interval;
+        tui.idle_frame_rate = self.core.idle_frame_rate.or(tui.idle_frame_rate);
+        tui.adaptive_frame_rate = self.core.adaptive_frame_rate.or(tui.adaptive_frame_rate);
+        tui.named_ticks.extend(self.core.named_ticks.iter().cloned());
+
+        tui.enter()?;
+        let size = tui.size()?;
+        let mut terminal = if tui.is_headless() {
+            TerminalInfo::plain(size)
+        } else {
+            TerminalInfo::detect(size)
+        };
+        terminal.color_depth = self.core.color_depth.unwrap_or(terminal.color_depth);
+        if self.core.auto_theme {
+            match terminal.background {
+                Some(Background::Dark) => self.switch_theme("dark"),
+                Some(Background::Light) => self.switch_theme("light"),
+                None => false,
+            };
+        }
+        self.core.state.insert(terminal);
+        Ok(())
+    }
+
+    /// Make the theme with the given name the current one, returning `false` if there is none.
+    /// The themes of the app take precedence over the built-in ones.
+    fn switch_theme(&mut self, name: &str) -> bool {
+        let theme = self.core.themes.iter().rev().find(|theme| theme.name == name).cloned();
+        match theme.or_else(|| Theme::builtin(name)) {
+            Some(theme) => {
+                Theme::set(theme);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancel the tasks of the app, exit the Tui and run the quit hooks, once the app stopped with
+    /// the given result. The hooks run even if the Tui fails to exit, and the error the app
+    /// stopped with, if any, is returned over the one of the exit.
+    pub(crate) fn leave_tui<B: Backend>(
+        &mut self,
+        tui: &mut Tui<B>,
+        result: Result<(), MatetuiError>,
+    ) -> Result<Option<R>, MatetuiError> {
+        self.core.cancellation_token.cancel();
+        let exited = tui.exit();
+
+        for hook in self.core.on_quit.iter_mut() {
+            hook(&self.core.state);
+        }
+        result?;
+        exited?;
+
+        Ok(self.exit_value())
+    }
+
+    /// The token cancelled when the app quits, so the work spawned outside the components can
+    /// stop gracefully. Components get a child token through
+    /// [ComponentAccessors::cancellation_token](crate::ComponentAccessors::cancellation_token).
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.core.cancellation_token.clone()
+    }
+
+    /// A sender of actions to the app, for the work done outside the components, like the host
+    /// loop of an embedded app (see [App::init]).
+    pub fn action_sender(&self) -> mpsc::UnboundedSender<Action<A>> {
+        self.core.action_tx.clone()
+    }
+
+    /// Run an external program (e.g. the editor of the user) in the terminal once the app handles
+    /// its pending actions, handing the terminal over until the program exits (see [External]).
+    /// Components use [ComponentAccessors::run_external](crate::ComponentAccessors::run_external).
+    pub fn run_external(&self, external: External) -> Result<(), MatetuiError> {
+        self.send(Action::RunExternal(external))
+    }
+
+    /// Start the app headless, drawing to a ratatui `TestBackend` of 80x24 cells instead of the
+    /// terminal, so it can be driven from unit tests (see [TestApp]).
+    pub fn test(self) -> Result<TestApp<A, R>, MatetuiError> {
+        TestApp::new(self, 80, 24)
+    }
+
+    /// Take the value passed to
+    /// [ComponentAccessors::exit_with](crate::ComponentAccessors::exit_with), if any.
+    pub(crate) fn exit_value(&self) -> Option<R> {
+        self.core.state.write(|exit: &mut ExitValue<R>| exit.0.take()).flatten()
+    }
+
+    /// Returns `true` if an active component vetoes quitting the app (see
+    /// [Component::on_quit_requested]).
+    fn quit_vetoed(&mut self) -> bool {
+        self.core
+            .component_handlers
+            .iter_mut()
+            .any(|handler| handler.handle_quit_request() == QuitDecision::Veto)
+    }
+
+    /// Returns `true` once the app processed an [Action::Quit].
+    pub(crate) fn should_quit(&self) -> bool {
+        self.core.should_quit
+    }
+
+    /// Apply the config overrides and merge the built-in keybindings into the ones of the app.
+    pub(crate) fn prepare(&mut self) {
+        self.core.keybindings = self.effective_keybindings();
+
+        #[cfg(feature = "config")]
+        if let Some(config) = self.core.config.take() {
+            self.core.tick_rate = config.tick_rate.unwrap_or(self.core.tick_rate);
+            self.core.frame_rate = config.frame_rate.unwrap_or(self.core.frame_rate);
+            self.core.mouse = config.mouse.unwrap_or(self.core.mouse);
+            self.core.paste = config.paste.unwrap_or(self.core.paste);
+            if let Some(theme) = config.theme {
+                Theme::set(theme);
+            }
+        }
+
+        if self.core.standard_bindings {
+            self.core.modal_keybindings.merge_under(KeyBindings::standard());
+        }
+
+        if let Some(keys) = self.effective_panic_escape() {
+            self.core.modal_keybindings.0.insert(keys, Action::FocusRoot);
+        }
+    }
+
+    fn effective_panic_escape(&self) -> Option<Vec<KeyEvent>> {
+        match (&self.core.panic_escape, self.core.focus_bindings) {
+            (None, true) => parse_key_sequence("<ctrl-g>").ok(),
+            // an invalid escape isn't replaced by the default one
+            (escape, _) => escape.clone().and_then(Result::ok),
+        }
+    }
+
+    /// The keybindings of the app with the ones of the config and the built-in ones merged in,
+    /// as they are once the app runs.
+    fn effective_keybindings(&self) -> KeyBindings<A> {
+        let mut keybindings = self.core.keybindings.clone();
+        #[cfg(feature = "config")]
+        for (keys, action) in self.core.config.iter().flat_map(|config| &config.keybindings) {
+            // the key sequences were validated when the config was loaded
+            if let Ok(keys) = parse_key_sequence(keys) {
+                keybindings.insert(keys, Action::from(ActionKind::from(action.as_str())));
+            }
+        }
+
+        if self.core.standard_bindings {
+            keybindings.merge_under(KeyBindings::standard());
+        }
+        if self.core.focus_bindings {
+            keybindings.merge_under(KeyBindings::focus());
+        }
+        if let Some(keys) = self.effective_panic_escape() {
+            keybindings.insert(keys.clone(), Action::FocusRoot);
+            keybindings.describe(&keys, "focus the root component");
+        }
+        keybindings
+    }
+
+    async fn main_loop<B: Backend>(&mut self, tui: &mut Tui<B>) -> Result<(), MatetuiError> {
+        self.start(tui)?;
+        while self.step_on(tui).await? {}
+        Ok(())
+    }
+
+    /// Wait for the next event of the Tui and handle it, along with the queued actions. Returns
+    /// `false` once the app quit, after processing the actions left.
+    async fn step_on<B: Backend>(&mut self, tui: &mut Tui<B>) -> Result<bool, MatetuiError> {
+        let event = if self.core.should_quit {
+            None
+        } else {
+            tui.next_stamped().await
+        };
+        self.complete_step(event, tui).await
+    }
+
+    /// Handle the event read by a step and the queued actions, and stop the event loop of the Tui
+    /// once the app quit. Returns `false` once the app quit, after processing the actions left.
+    async fn complete_step<B: Backend>(
+        &mut self,
+        event: Option<(Instant, Event)>,
+        tui: &mut Tui<B>,
+    ) -> Result<bool, MatetuiError> {
+        if self.handle_step(event, tui)? {
+            return Ok(true);
+        }
+        // the app finishes quitting (e.g. saving its state) even if the event loop broke
+        let stopped = tui.stop().await;
+        let running = self.finish_quit().await?;
+        stopped?;
+        Ok(running)
+    }
+
+    /// Handle the event read by a step and the queued actions. Returns `false` once the app quit.
+    fn handle_step<B: Backend>(
+        &mut self,
+        event: Option<(Instant, Event)>,
+        tui: &mut Tui<B>,
+    ) -> Result<bool, MatetuiError> {
+        if !self.core.should_quit {
+            if let Some((at, e)) = event {
+                self.handle_event(e, at, tui)?;
+            }
+
+            self.handle_actions(tui)?;
+
+            if self.core.should_suspend {
+                self.core.should_suspend = false;
+                tui.suspend()?;
+                tui.resume()?;
+                self.send(Action::ForceRedraw)?;
+            }
+        }
+
+        Ok(!self.core.should_quit)
+    }
+
+    /// Process the actions left once the app quit. Always returns `false`, as the last step.
+    async fn finish_quit(&mut self) -> Result<bool, MatetuiError> {
+        // the tasks stopping on the cancellation might have last actions to send
+        tokio::task::yield_now().await;
+        self.drain_actions()?;
+        Ok(false)
+    }
+
+    /// Process the actions still queued when the app quits, so the work they carry (e.g. saving a
+    /// file) isn't lost, then give the components a final [Action::Quit] update and persist their
+    /// state (see [App::with_persistence]).
+    ///
+    /// The custom actions and messages are delivered to the components as usual. The ones acting
+    /// on the terminal or the UI (rendering, focus, screens, keys, ...) are discarded, as the app
+    /// is going away, and recorded in the history as such.
+    pub(crate) fn drain_actions(&mut self) -> Result<(), MatetuiError> {
+        // components answering every action with another one can't keep the app from quitting
+        let mut remaining = DRAIN_LIMIT;
+
+        while let Ok(action) = self.try_recv() {
+            let a = match action {
+                Action::AppAction(ref message) => Action::parse(message).unwrap_or(action),
+                action => action,
+            };
+
+            match a {
+                Action::AppAction(message) if remaining > 0 => {
+                    remaining -= 1;
+                    self.record(HistoryKind::Action, format!("AppAction({message:?})"));
+                    for handler in self.core.component_handlers.iter_mut() {
+                        handler.handle_message(message.clone());
+                    }
+                }
+                Action::Message(message) if remaining > 0 => {
+                    remaining -= 1;
+                    self.record(HistoryKind::Action, format!("Message({message:?})"));
+                    for handler in self.core.component_handlers.iter_mut() {
+                        handler.handle_typed_message(&message);
+                    }
+                }
+                Action::SendTo(target, message) if remaining > 0 => {
+                    remaining -= 1;
+                    self.record(HistoryKind::Action, format!("SendTo({target:?}, {message:?})"));
+                    for handler in self.core.component_handlers.iter_mut() {
+                        handler.handle_addressed_message(&target, &message);
+                    }
+                }
+                Action::User(_) if remaining > 0 => {
+                    remaining -= 1;
+                    self.record(HistoryKind::Action, a.to_string());
+                    for index in 0..self.core.component_handlers.len() {
+                        let result = self.core.component_handlers[index].handle_update(a.clone());
+                        self.check(result)?;
+                    }
+                }
+                a => self.record(HistoryKind::Action, format!("{a} (discarded on quit)")),
+            }
+        }
+
+        for index in 0..self.core.component_handlers.len() {
+            let result = self.core.component_handlers[index].handle_update(Action::Quit);
+            self.check(result)?;
+        }
+        #[cfg(feature = "persistence")]
+        self.persist_state()?;
+        Ok(())
+    }
+
+    /// Store the state of the components, if the app persists it (see [App::with_persistence]).
+    #[cfg(feature = "persistence")]
+    fn persist_state(&mut self) -> Result<(), MatetuiError> {
+        let Some(path) = self.core.persistence.clone() else {
+            return Ok(());
+        };
+
+        let mut snapshot = Snapshot::default();
+        for handler in self.core.component_handlers.iter_mut() {
+            handler.handle_save_state(&mut snapshot);
+        }
+        snapshot.store(&path)?;
+        Ok(())
+    }
+
+    /// Draw a single frame of the app and print it to stdout as plain text (see
+    /// [App::with_print_fallback]).
+    fn print_frame(&mut self) -> Result<(), MatetuiError> {
+        let size = |var, default| {
+            std::env::var(var)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(default)
+        };
+        let text = self.frame_text(size("COLUMNS", 80), size("LINES", 24))?;
+        writeln!(std::io::stdout(), "{}", text.trim_end())?;
+        Ok(())
+    }
+
+    /// Start the app, draw a single frame of the given size and stop it, returning the frame as
+    /// plain text.
+    fn frame_text(&mut self, width: u16, height: u16) -> Result<String, MatetuiError> {
+        let mut tui = Tui::headless(TestBackend::new(width, height))?;
+        self.core.state.insert(TerminalInfo::plain(Size::new(width, height)));
+        let result = self.start(&mut tui).and_then(|_| {
+            self.handle_actions(&mut tui)?;
+            self.render(&mut tui)
+        });
+        self.core.cancellation_token.cancel();
+
+        for hook in self.core.on_quit.iter_mut() {
+            hook(&self.core.state);
+        }
+        result?;
+
+        Ok(buffer_to_text(tui.backend().buffer()))
+    }
+
+    /// Register the screens and modals, initialize the components and start the background tasks
+    /// of the app.
+    pub(crate) fn start<B: Backend>(&mut self, tui: &mut Tui<B>) -> Result<(), MatetuiError> {
+        let mut initial_screen = None;
+        for (name, mut screen) in self.core.screens.drain(..) {
+            // only the current screen is shown, once entered
+            if screen.is_active() {
+                screen.set_active(false);
+            }
+            screen.set_visible(false);
+            initial_screen.get_or_insert(name.clone());
+            self.core.router.register(name, self.core.component_handlers.len());
+            self.core.component_handlers.push(ComponentHandler::for_(screen));
+        }
+
+        for (name, mut modal) in self.core.modals.drain(..) {
+            if modal.is_active() {
+                modal.set_active(false);
+            }
+            self.core.open_modals.register(name, self.core.component_handlers.len());
+            self.core.component_handlers.push(ComponentHandler::for_(modal));
+        }
+
+        for (layer, component) in self.core.layered.drain(..) {
+            self.core.layers.register(layer, self.core.component_handlers.len());
+            self.core.component_handlers.push(ComponentHandler::for_(component));
+        }
+
+        for (float, component) in self.core.floating.drain(..) {
+            self.core.layers.register_float(float, self.core.component_handlers.len());
+            self.core.component_handlers.push(ComponentHandler::for_(component));
+        }
+
+        self.core.state.insert(AppCancellation(self.core.cancellation_token.clone()));
+        self.core.state.insert(self.keybindings_help());
+        self.core.state.insert(RenderRequest(AtomicBool::new(true)));
+        self.core.state.insert(StatusText::default());
+        self.core.state.insert(InputLatency::default());
+        #[cfg(feature = "metrics")]
+        self.core.state.insert(AppMetrics::default());
+        for handler in self.core.component_handlers.iter_mut() {
+            handler.receive_action_handler(self.core.action_tx.clone());
+            handler.receive_state(self.core.state.clone());
+        }
+
+        let size = tui.size()?;
+        for index in 0..self.core.component_handlers.len() {
+            let result = self.core.component_handlers[index].handle_init(size);
+            self.check(result)?;
+        }
+
+        #[cfg(feature = "persistence")]
+        if let Some(path) = &self.core.persistence {
+            // a broken snapshot (e.g. edited by hand) is dropped rather than keeping the app from
+            // starting, and replaced on quit
+            let snapshot = Snapshot::load(path).unwrap_or_default();
+            for handler in self.core.component_handlers.iter_mut() {
+                handler.handle_restore_state(&snapshot);
+            }
+        }
+
+        if let Some(name) = initial_screen {
+            self.core.router.push(&name, &mut self.core.component_handlers, &mut self.core.focus);
+        }
+
+        for schedule in self.core.schedules.iter() {
+            schedule.spawn(self.core.action_tx.clone(), self.core.cancellation_token.clone());
+        }
+
+        self.core.recorder = self.core.recording.as_ref().map(Recorder::create).transpose()?;
+        if let Some(replay) = &self.core.replay {
+            replay.replay(tui.event_tx.clone(), self.core.cancellation_token.clone());
+        }
+
+        for hook in self.core.on_start.iter_mut() {
+            hook(&self.core.state);
+        }
+        Ok(())
+    }
+
+    /// Dispatch an event, read at the given instant, to the keybindings and the components.
+    pub(crate) fn handle_event<B: Backend>(
+        &mut self,
+        e: Event,
+        at: Instant,
+        tui: &mut Tui<B>,
+    ) -> Result<(), MatetuiError> {
+        #[cfg(feature = "trace")]
+        let _span = instrument::event(&e);
+        self.record(HistoryKind::Event, format!("{e:?}"));
+        if let Some(recorder) = self.core.recorder.as_mut() {
+            recorder.record(&e)?;
+        }
+
+        if !matches!(e, Event::Tick | Event::Render) {
+            self.request_render();
+        }
+
+        if matches!(e, Event::Key(_) | Event::Mouse(_) | Event::Paste(_) | Event::Resize(..)) {
+            self.core.input_at = Some(at);
+            self.core.undrawn_input_at.get_or_insert(at);
+        }
+
+        if matches!(e, Event::Key(_)) && !self.core.errors.is_empty() {
+            // the key press dismisses the error overlay
+            self.core.errors.clear();
+            return Ok(());
+        }
+
+        #[cfg(feature = "palette")]
+        if self.palette_event(&e)? {
+            return Ok(());
+        }
+
+        #[cfg(feature = "devtools")]
+        if let Some(inspector) = self.core.inspector.as_mut() {
+            inspector.handle_event(&e);
+        }
+
+        match e {
+            Event::Resize(w, h) => self.send(Action::Resize(w, h))?,
+            // other programs writing to the terminal might leave the screen in a corrupted
+            // state, so we redraw everything from scratch
+            Event::FocusGained => self.send(Action::ForceRedraw)?,
+            // the terminal might have been reset while the process was stopped
+            Event::Resume => {
+                tui.restore()?;
+                self.send(Action::ForceRedraw)?;
+            }
+            Event::Render => self.send(Action::Render)?,
+            Event::Tick => self.send(Action::Tick)?,
+            Event::Quit => self.send(Action::Quit)?,
+            Event::Key(key) => {
+                // a key held down repeats the action bound to it
+                let key = KeyEvent {
+                    kind: KeyEventKind::Press,
+                    ..key
+                };
+                self.handle_key(key)?;
+            }
+            _ => {}
+        }
+        let mut actions = Vec::new();
+
+        // the top modal captures all the input events, which only go to the layers receiving
+        // them otherwise
+        let input = matches!(
+            e,
+            Event::Key(_)
+                | Event::KeyRelease(_)
+                | Event::Mouse(_)
+                | Event::MouseClick(_)
+                | Event::MouseDrag(_)
+                | Event::Paste(_)
+        );
+        let capturing = self.core.open_modals.top().filter(|_| input);
+
+        match e {
+            Event::Mouse(mouse) => actions.extend(self.route_mouse(mouse)),
+            Event::MouseClick(click) => actions.extend(self.route_click(click)),
+            Event::MouseDrag(drag) => actions.extend(self.route_drag(drag)),
+            e => {
+                for (index, handler) in self.core.component_handlers.iter_mut().enumerate() {
+                    if capturing.is_some_and(|modal| modal != index)
+                        || (input && capturing.is_none() && !self.core.layers.receives_input(index))
+                    {
+                        continue;
+                    }
+                    let component_actions = handler.handle_events(Some(e.clone()));
+                    actions.extend(component_actions);
+                }
+            }
+        }
+
+        for action in actions {
+            self.send(action)?;
+        }
+        Ok(())
+    }
+
+    /// Deliver a mouse event to the component under the pointer only (see
+    /// [App::component_under]), with the position of the event relative to its area.
+    ///
+    /// The component a button is pressed on keeps getting the drags and the release of the button,
+    /// even outside of its area, so drags work past its borders. The wheel scrolls the component
+    /// under the pointer or, if there is none, the focused one (see
+    /// [Component::handle_scroll_event]).
+    fn route_mouse(&mut self, mouse: MouseEvent) -> Option<Action<A>> {
+        let position = Position::new(mouse.column, mouse.row);
+        if let Some(direction) = ScrollDirection::from_mouse(mouse.kind) {
+            let target = self.component_under(position).or_else(|| self.focused_input())?;
+            return self.core.component_handlers[target.root].handle_scroll(
+                &target.children,
+                direction,
+                self.core.scroll_amount,
+            );
+        }
+
+        let captured = match mouse.kind {
+            MouseEventKind::Drag(_) => self.core.mouse_capture.clone(),
+            MouseEventKind::Up(_) => self.core.mouse_capture.take(),
+            _ => None,
+        };
+        let target = captured.or_else(|| self.component_under(position))?;
+        if let MouseEventKind::Down(_) = mouse.kind {
+            self.core.mouse_capture = Some(target.clone());
+        }
+        self.core.component_handlers[target.root].handle_mouse(&target.children, mouse)
+    }
+
+    /// Deliver a click to the component under the pointer only (see [App::component_under]),
+    /// with its position relative to the area of the component.
+    fn route_click(&mut self, click: MouseClick) -> Option<Action<A>> {
+        let target = self.component_under(Position::new(click.column, click.row))?;
+        self.core.component_handlers[target.root].handle_click(&target.children, click)
+    }
+
+    /// Deliver the steps of a drag to the component it started on: the one the button was pressed
+    /// on or, if the press went unseen, the one under the origin of the drag. The positions are
+    /// relative to the area of the component.
+    fn route_drag(&mut self, drag: MouseDrag) -> Option<Action<A>> {
+        let target = match drag.phase {
+            DragPhase::Start => {
+                let origin = Position::new(drag.origin_column, drag.origin_row);
+                self.core.drag_target =
+                    self.core.mouse_capture.clone().or_else(|| self.component_under(origin));
+                self.core.drag_target.clone()
+            }
+            DragPhase::Update => self.core.drag_target.clone(),
+            DragPhase::End => self.core.drag_target.take(),
+        }?;
+        self.core.component_handlers[target.root].handle_drag(&target.children, drag)
+    }
+
+    /// The deepest active component under the position, by the area it was last drawn into, in
+    /// the topmost root component receiving the input drawn there.
+    fn component_under(&mut self, position: Position) -> Option<FocusPath> {
+        self.draw_order().into_iter().rev().find_map(|root| {
+            let children = self
+                .receives_input(root)
+                .then(|| self.core.component_handlers[root].component_at(position));
+            children.flatten().map(|children| FocusPath { root, children })
+        })
+    }
+
+    /// The path of the focused component, if it receives the input events.
+    fn focused_input(&self) -> Option<FocusPath> {
+        self.core.focus.focused().filter(|path| self.receives_input(path.root)).cloned()
+    }
+
+    /// Returns `true` if the root component at `index` receives the input events: only the top
+    /// modal does while one is open, and otherwise the ones of the layers receiving the input.
+    fn receives_input(&self, index: usize) -> bool {
+        match self.core.open_modals.top() {
+            Some(modal) => modal == index,
+            None => self.core.layers.receives_input(index),
+        }
+    }
+
+    /// Open the command palette with its key and hand it the input while it's open, dispatching
+    /// the action of the picked command. Returns `true` if the palette took the event.
+    #[cfg(feature = "palette")]
+    fn palette_event(&mut self, e: &Event) -> Result<bool, MatetuiError> {
+        let Some(palette) = self.core.palette.as_ref() else {
+            return Ok(false);
+        };
+
+        match e {
+            Event::Key(key) if palette.opens_with(key) => {
+                let commands = self.palette_commands();
+                self.core.palette.as_mut().unwrap().open(commands);
+            }
+            Event::Key(key) if palette.is_open() => {
+                if let Some(action) = self.core.palette.as_mut().unwrap().handle_key(*key) {
+                    self.send(action)?;
+                }
+            }
+            Event::Paste(text) if palette.is_open() => {
+                self.core.palette.as_mut().unwrap().paste(text)
+            }
+            Event::KeyRelease(_) if palette.is_open() => {}
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// The commands of the app and of its active components, with the keys bound to their
+    /// actions.
+    #[cfg(feature = "palette")]
+    fn palette_commands(&mut self) -> Vec<(Command<A>, String)> {
+        let mut commands = self.core.commands.clone();
+        for handler in self.core.component_handlers.iter_mut() {
+            handler.handle_commands(&mut commands);
+        }
+
+        let glyphs = Theme::read(|t| t.key_glyphs);
+        let keybindings = self.active_keybindings();
+        commands
+            .into_iter()
+            .map(|command| {
+                let keys = keybindings.bindings_for_action(command.action());
+                let keys = keys.first().map(|keys| key_sequence_to_display(keys, glyphs));
+                (command, keys.unwrap_or_default())
+            })
+            .collect()
+    }
+
+    /// Process the pending actions, until the app quits (the actions left are processed by
+    /// [App::drain_actions]).
+    pub(crate) fn handle_actions<B: Backend>(
+        &mut self,
+        tui: &mut Tui<B>,
+    ) -> Result<(), MatetuiError> {
+        while !self.core.should_quit {
+            let Ok(action) = self.try_recv() else {
+                break;
+            };
+            #[cfg(feature = "trace")]
+            let _span = instrument::action(&action);
+            self.record(HistoryKind::Action, action.to_string());
+            if !matches!(action, Action::Tick | Action::Render) {
+                self.request_render();
+                tui.notify_activity();
+            }
+
+            // string based actions might still represent a built-in or custom action (e.g. a
+            // component calling `self.send("quit")`), otherwise they are treated as raw
+            // messages
+            let a = match action {
+                Action::Key(key) => {
+                    self.deliver_key(key);
+                    continue;
+                }
+                Action::AppAction(message) => {
+                    match Action::parse(&message) {
+                        Some(a) => a,
+                        None => {
+                            // unrecognized action, might be a custom component action
+                            // send it to all components as a raw string
+                            for handler in self.core.component_handlers.iter_mut() {
+                                handler.handle_message(message.clone());
+                            }
+                            continue;
+                        }
+                    }
+                }
+                Action::Message(message) => {
+                    for handler in self.core.component_handlers.iter_mut() {
+                        handler.handle_typed_message(&message);
+                    }
+                    continue;
+                }
+                Action::SendTo(target, message) => {
+                    for handler in self.core.component_handlers.iter_mut() {
+                        handler.handle_addressed_message(&target, &message);
+                    }
+                    continue;
+                }
+                action => action,
+            };
+
+            match a {
+                // a vetoed quit is dropped, the components don't see it, and neither is the value
+                // it was sent with kept to be returned by a later quit
+                Action::Quit if self.quit_vetoed() => {
+                    self.exit_value();
+                    continue;
+                }
+                // the components get the quit once the pending actions are drained
+                Action::Quit | Action::ForceQuit => {
+                    self.core.should_quit = true;
+                    // let the tasks know the app is quitting before they are dropped
+                    self.core.cancellation_token.cancel();
+                    continue;
+                }
+                Action::Suspend => self.core.should_suspend = true,
+                Action::Bell if self.core.bell => match self.core.bell_hook.as_mut() {
+                    Some(hook) => hook(),
+                    None => tui.bell()?,
+                },
+                Action::Render => self.render_frame(tui)?,
+                Action::RunExternal(ref external) => {
+                    tui.exit()?;
+                    let message = external.run();
+                    tui.enter()?;
+                    tui.clear()?;
+                    self.render(tui)?;
+                    self.send(Action::AppAction(message))?;
+                }
+                Action::ForceRedraw => {
+                    tui.clear()?;
+                    self.render(tui)?;
+                }
+                Action::Tick => {
+                    // the sequence the held back keys started wasn't completed in time
+                    for key in std::mem::take(&mut self.core.last_tick_key_events) {
+                        self.deliver_key(key_sequence_to_string(&[key]));
+                    }
+                }
+                Action::Scroll(direction, amount) => self.scroll_focused(direction, amount)?,
+                Action::FocusNext => self.core.focus.focus_next(&mut self.core.component_handlers),
+                Action::FocusPrev => self.core.focus.focus_prev(&mut self.core.component_handlers),
+                Action::FocusParent => {
+                    self.core.focus.focus_parent(&mut self.core.component_handlers)
+                }
+                Action::FocusRoot => self.core.focus.focus_root(&mut self.core.component_handlers),
+                Action::PushScreen(ref name) => self.core.router.push(
+                    name,
+                    &mut self.core.component_handlers,
+                    &mut self.core.focus,
+                ),
+                Action::PopScreen => {
+                    self.core.router.pop(&mut self.core.component_handlers, &mut self.core.focus)
+                }
+                Action::ReplaceScreen(ref name) => self.core.router.replace(
+                    name,
+                    &mut self.core.component_handlers,
+                    &mut self.core.focus,
+                ),
+                Action::OpenModal(ref name) => self.core.open_modals.open(
+                    name,
+                    &mut self.core.component_handlers,
+                    &mut self.core.focus,
+                ),
+                Action::CloseModal => self
+                    .core
+                    .open_modals
+                    .close(&mut self.core.component_handlers, &mut self.core.focus),
+                Action::SetTheme(ref name) => {
+                    let switched = self.switch_theme(name);
+                    if !switched {
+                        self.record(HistoryKind::Action, format!("unknown theme {name:?}"));
+                    }
+                }
+                Action::SetTickRate(rate) => {
+                    if tui.set_tick_rate(rate) {
+                        self.core.tick_rate = rate;
+                    } else {
+                        self.record(HistoryKind::Action, format!("invalid tick rate {rate}"));
+                    }
+                }
+                Action::SetFrameRate(rate) => {
+                    if tui.set_frame_rate(rate) {
+                        self.core.frame_rate = rate;
+                    } else {
+                        self.record(HistoryKind::Action, format!("invalid frame rate {rate}"));
+                    }
+                }
+                Action::Resize(w, h) => {
+                    self.core.state.write(|info: &mut TerminalInfo| info.size = Size::new(w, h));
+                    // resizing clears the screen, so everything is redrawn from scratch
+                    tui.resize(Rect::new(0, 0, w, h))?;
+                    self.render(tui)?;
+                }
+                _ => {}
+            }
+
+            for index in 0..self.core.component_handlers.len() {
+                let result = self.core.component_handlers[index].handle_update(a.clone());
+                self.check(result)?;
+            }
+        }
+
+        if let Some(at) = self.core.input_at.take() {
+            self.core.state.write(|latency: &mut InputLatency| latency.handled(at));
+        }
+        Ok(())
+    }
+}
+
+/// The text of the buffer, one line per row. The cells covered by wide chars are skipped.
+fn buffer_to_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut lines = vec![];
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut x = area.left();
+        while x < area.right() {
+            let symbol = buffer[(x, y)].symbol();
+            line.push_str(symbol);
+            x += symbol.width().max(1) as u16;
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+/// Collect the names of the active components of the tree (see [ScopedKeyBindings]).
+fn active_names<A: UserAction>(
+    c: &mut dyn Component<A>,
+    child_name: Option<&str>,
+    out: &mut Vec<String>,
+) {
+    if !c.is_active() {
+        return;
+    }
+
+    out.extend(child_name.map(str::to_string));
+    out.push(c.name());
+    if let Some(children) = c.get_children() {
+        for (name, child) in children.iter_mut() {
+            active_names(child.as_mut(), Some(name), out);
+        }
+    }
+}
+
+/// Draw the errors of the components at the bottom of the screen, the most recent last.
+fn draw_errors(f: &mut Frame<'_>, errors: &[String]) {
+    let area = f.area();
+    let height = (errors.len() as u16 + 2).min(area.height);
+    let area = Rect::new(area.x, area.bottom() - height, area.width, height);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(styles::error())
+        .title(" errors · press any key to dismiss ");
+
+    let skip = errors.len().saturating_sub(block.inner(area).height as usize);
+    let lines = errors.iter().skip(skip).map(|e| Line::styled(e.as_str(), styles::error()));
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines.collect::<Vec<_>>()).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{component, components, kb, ComponentAccessors, DragPhase, Frame},
+        crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
+        ratatui::{
+            buffer::Cell,
+            layout::{Constraint, Layout},
+            widgets::Paragraph,
+        },
+        std::sync::{Arc, Mutex},
+    };
+
+    component! {
+        struct Counter {
+            mates: u32
+        }
+    }
+
+    impl Component for Counter {
+        fn receive_message(&mut self, message: String) {
+            if message == "drink" {
+                self.mates += 1;
+            }
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("mates: {}", self.mates)), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Bar {},
+        children => {
+            "left" => Counter::default().as_active(),
+            "right" => Counter::default().as_active()
+        }
+    }
+
+    impl Component for Bar {
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            for (row, name) in ["left", "right"].into_iter().enumerate() {
+                let area = Rect::new(area.x, area.y + row as u16, area.width, 1);
+                self.child_mut(name).unwrap().render(f, area)?;
+            }
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Notes {
+            log: Arc<Mutex<Vec<String>>>
+        }
+    }
+
+    impl Component for Notes {
+        fn receive_message(&mut self, message: String) {
+            if message == "quit-soon" {
+                self.send_action(Action::Quit);
+                self.send("save");
+                self.send_action(Action::FocusNext);
+            }
+            self.log.lock().unwrap().push(message);
+        }
+
+        fn update(&mut self, action: &Action) -> Result<(), ComponentError> {
+            if *action == Action::Quit {
+                self.log.lock().unwrap().push("quit".into());
+            }
+            Ok(())
+        }
+
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Field {
+            heard: Vec<String>
+        }
+    }
+
+    impl Component for Field {
+        fn tab_index(&self) -> Option<u16> {
+            Some(0)
+        }
+
+        fn receive_message(&mut self, message: String) {
+            self.heard.push(message);
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("[{}]", self.heard.join(","))), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Pointer {
+            clicks: Vec<String>
+        }
+    }
+
+    impl Component for Pointer {
+        fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Option<Action> {
+            let kind = match mouse.kind {
+                MouseEventKind::Down(_) => "down",
+                MouseEventKind::Drag(_) => "drag",
+                MouseEventKind::Up(_) => "up",
+                _ => "moved",
+            };
+            self.clicks.push(format!("{kind}:{},{}", mouse.column, mouse.row));
+            None
+        }
+
+        fn handle_mouse_click(&mut self, click: MouseClick) -> Option<Action> {
+            self.clicks.push(format!("click{}:{},{}", click.count, click.column, click.row));
+            None
+        }
+
+        fn handle_mouse_drag(&mut self, drag: MouseDrag) -> Option<Action> {
+            let phase = format!("{:?}", drag.phase).to_lowercase();
+            self.clicks.push(format!("{phase}:{},{}", drag.column, drag.row));
+            None
+        }
+
+        fn handle_scroll_event(
+            &mut self,
+            direction: ScrollDirection,
+            amount: u16,
+        ) -> Option<Action> {
+            self.clicks.push(format!("{direction:?}:{amount}").to_lowercase());
+            None
+        }
+
+        fn tab_index(&self) -> Option<u16> {
+            Some(0)
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("[{}]", self.clicks.join(","))), area);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn drains_the_pending_actions_on_quit() -> Result<(), MatetuiError> {
+        let log = Arc::new(Mutex::new(vec![]));
+        let history = History::default();
+        let mut app = App::default()
+            .with_keybindings(kb!["<x>" => "quit-soon"])
+            .with_history(history.clone())
+            .with_components(components![Notes {
+                log: log.clone(),
+                ..Notes::default()
+            }
+            .as_active()])
+            .test()?;
+
+        app.press("<x>")?;
+        assert!(app.has_quit());
+        assert_eq!(*log.lock().unwrap(), ["quit-soon", "save", "quit"]);
+
+        let last = history.entries().pop().unwrap();
+        assert_eq!(last.value, "FocusNext (discarded on quit)");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn finishes_quitting_when_the_event_loop_broke() -> Result<(), MatetuiError> {
+        let log = Arc::new(Mutex::new(vec![]));
+        let mut app = App::default().with_keybindings(kb!["<x>" => "quit-soon"]).with_components(
+            components![Notes {
+                log: log.clone(),
+                ..Notes::default()
+            }
+            .as_active()],
+        );
+
+        let mut tui = Tui::headless(TestBackend::new(20, 2))?;
+        tui.task = tokio::spawn(async { panic!("the loop broke") });
+        tui.event_tx.send(Event::Key(parse_key_sequence("<x>")?[0])).unwrap();
+        assert!(app.run_with(&mut tui).await.is_err());
+        assert_eq!(*log.lock().unwrap(), ["quit-soon", "save", "quit"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn runs_the_lifecycle_hooks_around_the_app() -> Result<(), MatetuiError> {
+        let log = Arc::new(Mutex::new(vec![]));
+        let hook = |name: &'static str| {
+            let log = log.clone();
+            move |_: &AppState| log.lock().unwrap().push(name.to_string())
+        };
+        let mut app = App::default()
+            .with_keybindings(kb!["<q>" => Action::Quit])
+            .with_components(components![Notes {
+                log: log.clone(),
+                ..Notes::default()
+            }
+            .as_active()])
+            .on_start(hook("on_start"))
+            .on_quit(hook("on_quit"))
+            .on_quit(hook("on_quit again"));
+
+        let mut tui = Tui::headless(TestBackend::new(20, 2))?;
+        tui.event_tx.send(Event::Key(parse_key_sequence("<q>")?[0])).unwrap();
+        app.run_with(&mut tui).await?;
+        assert_eq!(*log.lock().unwrap(), ["on_start", "quit", "on_quit", "on_quit again"]);
+
+        // the hooks run when the app stops with an error too, and the error is kept
+        log.lock().unwrap().clear();
+        let stopped = app.leave_tui(&mut tui, Err(MatetuiError::NotATerminal));
+        assert!(matches!(stopped, Err(MatetuiError::NotATerminal)));
+        assert_eq!(*log.lock().unwrap(), ["on_quit", "on_quit again"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn addressed_messages_only_reach_their_target() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb![
+                "<l>" => Action::SendTo("left".into(), "drink".into()),
+                "<c>" => Action::SendTo("Counter".into(), "drink".into())
+            ])
+            .with_components(components![Bar::default().as_active()])
+            .test()?;
+
+        app.press("<l>")?;
+        assert!(app.lines()[0].starts_with("mates: 1"));
+        assert!(app.lines()[1].starts_with("mates: 0"));
+
+        // by type name, both counters are targeted
+        app.press("<c>")?;
+        assert!(app.lines()[0].starts_with("mates: 2"));
+        assert!(app.lines()[1].starts_with("mates: 1"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lays_out_the_roots_of_a_new_app() -> Result<(), MatetuiError> {
+        let mut app = App::new(
+            [("<tab>", "FocusNext")],
+            components![Field::default().as_active(), Field::default().as_active()],
+        )
+        .with_layout(Layout::horizontal([Constraint::Length(40), Constraint::Fill(1)]))
+        .test()?;
+
+        app.press("<tab><x>")?;
+        assert_eq!(app.lines()[0].trim_end(), "[<x>]                                   []");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unbound_keys_only_reach_the_focused_component() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<tab>" => Action::FocusNext, "<d>" => "drink"])
+            .with_components(components![
+                Field::default().as_active(),
+                Field::default().as_active()
+            ])
+            .with_layout(Layout::horizontal([Constraint::Length(40), Constraint::Fill(1)]))
+            .test()?;
+
+        // nothing is focused yet
+        app.press("<x>")?;
+        assert_eq!(app.lines()[0].trim_end(), "[]                                      []");
+
+        app.press("<tab><ctrl-k><d><x>")?;
+        assert_eq!(app.lines()[0].trim_end(), "[<ctrl-k>,drink,<x>]                    [drink]");
+
+        // the keys are in the keybindings syntax, F keys and shifted letters included
+        app.press("<f5><shift-a>")?;
+        let heard = "[<ctrl-k>,drink,<x>,<f5>,<shift-a>]     [drink]";
+        assert_eq!(app.lines()[0].trim_end(), heard);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reports_an_invalid_panic_escape() -> Result<(), MatetuiError> {
+        let app = App::default()
+            .with_focus_bindings(true)
+            .with_panic_escape("<ctrl-q")
+            .with_components(components![Field::default().as_active()]);
+        assert_eq!(
+            app.keybinding_conflicts(),
+            [KeyBindingConflict::Invalid {
+                keys: "<ctrl-q".into(),
+                error: "Invalid key sequence".into()
+            }]
+        );
+
+        // there's no panic escape, rather than the default one
+        let mut app = app.test()?;
+        app.press("<tab><ctrl-g>")?;
+        assert_eq!(app.lines()[0].trim_end(), "[<ctrl-g>]");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn holds_back_the_keys_of_unfinished_sequences() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(
+                kb!["<tab>" => Action::FocusNext, "<g><g>" => "top", "<d>" => "drink"],
+            )
+            .with_components(components![Field::default().as_active()])
+            .test()?;
+
+        app.press("<tab><g>")?;
+        assert_eq!(app.lines()[0].trim_end(), "[]");
+        app.press("<g>")?;
+        assert_eq!(app.lines()[0].trim_end(), "[top]");
+
+        // the held back key goes to the component once the sequence fails or times out
+        app.press("<g><x><g><d>")?;
+        assert_eq!(app.lines()[0].trim_end(), "[top,<g>,<x>,<g>,drink]");
+        app.press("<g>")?;
+        app.tick()?;
+        assert_eq!(app.lines()[0].trim_end(), "[top,<g>,<x>,<g>,drink,<g>]");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mouse_events_go_to_the_component_under_the_pointer() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_components(components![
+                Pointer::default().as_active(),
+                Pointer::default().as_active()
+            ])
+            .with_layout(Layout::horizontal([Constraint::Length(40), Constraint::Fill(1)]))
+            .test()?;
+        let mouse = |kind, column, row| {
+            Event::Mouse(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+
+        app.send(mouse(MouseEventKind::Down(MouseButton::Left), 45, 0))?;
+        app.send(mouse(MouseEventKind::Up(MouseButton::Left), 45, 0))?;
+        assert_eq!(
+            app.lines()[0].trim_end(),
+            "[]                                      [down:5,0,up:5,0]"
+        );
+
+        // the drags and the release go to the component the button was pressed on
+        app.send(mouse(MouseEventKind::Down(MouseButton::Left), 10, 1))?;
+        app.send(mouse(MouseEventKind::Drag(MouseButton::Left), 50, 1))?;
+        app.send(mouse(MouseEventKind::Up(MouseButton::Left), 50, 1))?;
+        app.send(mouse(MouseEventKind::Moved, 41, 2))?;
+        assert_eq!(
+            app.lines()[0].trim_end(),
+            "[down:10,1,drag:50,1,up:50,1]           [down:5,0,up:5,0,moved:1,2]"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn clicks_go_to_the_component_under_the_pointer() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_components(components![
+                Pointer::default().as_active(),
+                Pointer::default().as_active()
+            ])
+            .with_layout(Layout::horizontal([Constraint::Length(40), Constraint::Fill(1)]))
+            .test()?;
+        let click = |column, count| {
+            Event::MouseClick(MouseClick {
+                button: MouseButton::Left,
+                column,
+                row: 3,
+                modifiers: KeyModifiers::NONE,
+                count,
+            })
+        };
+
+        app.send(click(42, 1))?;
+        app.send(click(42, 2))?;
+        app.send(click(7, 3))?;
+        assert_eq!(
+            app.lines()[0].trim_end(),
+            "[click3:7,3]                            [click1:2,3,click2:2,3]"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn drags_go_to_the_component_they_started_on() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_components(components![
+                Pointer::default().as_active(),
+                Pointer::default().as_active()
+            ])
+            .with_layout(Layout::horizontal([Constraint::Length(40), Constraint::Fill(1)]))
+            .test()?;
+        let drag = |phase, column| {
+            Event::MouseDrag(MouseDrag {
+                button: MouseButton::Left,
+                phase,
+                origin_column: 42,
+                origin_row: 1,
+                column,
+                row: 1,
+                delta_column: 0,
+                delta_row: 0,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+
+        // the steps past the borders of the component still go to it
+        app.send(drag(DragPhase::Start, 45))?;
+        app.send(drag(DragPhase::Update, 7))?;
+        app.send(drag(DragPhase::End, 7))?;
+        app.send(drag(DragPhase::Update, 45))?;
+        assert_eq!(
+            app.lines()[0].trim_end(),
+            "[]                                      [start:5,1,update:0,1,end:0,1]"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn scrolls_the_component_under_the_pointer_or_the_focused() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb![
+                "<tab>" => Action::FocusNext,
+                "<ctrl-e>" => Action::Scroll(ScrollDirection::Down, 1)
+            ])
+            .with_components(components![
+                Pointer::default().as_active(),
+                Pointer::default().as_active()
+            ])
+            .with_layout(Layout::horizontal([Constraint::Length(40), Constraint::Length(20)]))
+            .with_scroll_amount(2)
+            .test()?;
+        let wheel = |kind, column| {
+            Event::Mouse(MouseEvent {
+                kind,
+                column,
+                row: 0,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+
+        // nothing to scroll outside of the components until one is focused
+        app.send(wheel(MouseEventKind::ScrollDown, 45))?;
+        app.send(wheel(MouseEventKind::ScrollUp, 70))?;
+        app.press("<ctrl-e>")?;
+        assert_eq!(app.lines()[0].trim_end(), "[]                                      [down:2]");
+
+        app.press("<tab><ctrl-e>")?;
+        app.send(wheel(MouseEventKind::ScrollUp, 70))?;
+        assert_eq!(app.lines()[0].trim_end(), "[down:1,up:2]                           [down:2]");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn prints_a_frame_as_plain_text() -> Result<(), MatetuiError> {
+        let quit = Arc::new(Mutex::new(false));
+        let on_quit = quit.clone();
+        let mut app = App::default()
+            .with_components(components![Bar::default().as_active()])
+            .on_quit(move |_| *on_quit.lock().unwrap() = true);
+        app.prepare();
+
+        assert_eq!(app.frame_text(12, 3)?, "mates: 0\nmates: 0\n");
+        let depth = app.state().read(|terminal: &TerminalInfo| terminal.color_depth);
+        assert_eq!(depth, Some(ColorDepth::Mono));
+        assert!(*quit.lock().unwrap());
+        assert!(app.core.cancellation_token.is_cancelled());
+
+        if !std::io::stdout().is_terminal() {
+            let mut app = App::<NoAction>::default();
+            assert!(matches!(app.claim_terminal(), Err(MatetuiError::NotATerminal)));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn steps_in_the_loop_of_a_host() -> Result<(), MatetuiError> {
+        let log = Arc::new(Mutex::new(vec![]));
+        let mut app = App::default().with_components(components![Notes {
+            log: log.clone(),
+            ..Notes::default()
+        }
+        .as_active()]);
+
+        // nothing to step nor to shut down before the app is initialized
+        assert!(!app.step().await?);
+        assert_eq!(app.shutdown()?, None);
+
+        app.prepare();
+        let mut tui = Tui::headless(TestBackend::new(20, 2))?;
+        app.enter_tui(&mut tui)?;
+        app.start(&mut tui)?;
+
+        let (host_tx, mut host_rx) = mpsc::unbounded_channel();
+        for message in ["mate", "quit-soon"] {
+            host_tx.send(message.to_string()).unwrap();
+        }
+        let actions = app.action_sender();
+        let events = tui.event_tx.clone();
+        loop {
+            tokio::select! {
+                running = app.step_on(&mut tui) => {
+                    if !running? {
+                        break;
+                    }
+                }
+                Some(message) = host_rx.recv() => {
+                    actions.send(Action::AppAction(message))?;
+                    events.send(Event::Tick).unwrap();
+                }
+            }
+        }
+        app.leave_tui(&mut tui, Ok(()))?;
+
+        assert_eq!(*log.lock().unwrap(), ["mate", "quit-soon", "save", "quit"]);
+        assert!(app.cancellation_token().is_cancelled());
+        Ok(())
+    }
+
+    /// Start the app on a headless Tui of the given size, to step it by hand.
+    fn start_headless(
+        app: &mut App,
+        width: u16,
+        height: u16,
+    ) -> Result<Tui<TestBackend>, MatetuiError> {
+        app.prepare();
+        let mut tui = Tui::headless(TestBackend::new(width, height))?;
+        app.enter_tui(&mut tui)?;
+        app.start(&mut tui)?;
+        Ok(tui)
+    }
+
+    /// Handle an event and the actions it leads to.
+    fn step(app: &mut App, tui: &mut Tui<TestBackend>, event: Event) -> Result<(), MatetuiError> {
+        app.handle_step(Some((Instant::now(), event)), tui)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn redraws_everything_when_the_screen_might_be_corrupted() -> Result<(), MatetuiError> {
+        let mut app = App::default().with_components(components![Counter::default().as_active()]);
+        let mut tui = start_headless(&mut app, 12, 1)?;
+        let screen = |tui: &Tui<TestBackend>| buffer_to_text(tui.backend().buffer());
+        // another program writing over the screen, behind the back of the app
+        let scribble = |tui: &mut Tui<TestBackend>| {
+            let cell = Cell::new("#");
+            tui.backend_mut().draw([(0, 0, &cell)].into_iter())
+        };
+
+        step(&mut app, &mut tui, Event::Render)?;
+        assert_eq!(screen(&tui), "mates: 0");
+
+        // only the changes are drawn, so the usual frames leave the scribble
+        scribble(&mut tui)?;
+        step(&mut app, &mut tui, Event::Render)?;
+        assert_eq!(screen(&tui), "#ates: 0");
+
+        step(&mut app, &mut tui, Event::FocusGained)?;
+        assert_eq!(screen(&tui), "mates: 0");
+
+        scribble(&mut tui)?;
+        step(&mut app, &mut tui, Event::Resize(12, 1))?;
+        assert_eq!(screen(&tui), "mates: 0");
+
+        scribble(&mut tui)?;
+        app.send(Action::ForceRedraw)?;
+        app.handle_actions(&mut tui)?;
+        assert_eq!(screen(&tui), "mates: 0");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn changes_the_rates_while_running() -> Result<(), MatetuiError> {
+        let history = History::default();
+        let mut app = App::default().with_history(history.clone()).with_frame_rate(30);
+        let mut tui = start_headless(&mut app, 12, 1)?;
+        assert_eq!(tui.frame_rate, 30.0);
+
+        app.send(Action::SetFrameRate(120.0))?;
+        app.send(Action::SetTickRate(0.5))?;
+        app.handle_actions(&mut tui)?;
+        assert_eq!((tui.tick_rate, tui.frame_rate), (0.5, 120.0));
+        assert_eq!((app.core.tick_rate, app.core.frame_rate), (0.5, 120.0));
+
+        // the invalid rates are recorded and ignored
+        app.send(Action::SetFrameRate(0.0))?;
+        app.send(Action::SetTickRate(-1.0))?;
+        app.handle_actions(&mut tui)?;
+        assert_eq!((tui.tick_rate, tui.frame_rate), (0.5, 120.0));
+        let entries = history.entries();
+        let recorded =
+            entries.iter().map(|e| e.value.as_str()).filter(|v| v.starts_with("invalid"));
+        assert_eq!(recorded.collect::<Vec<_>>(), ["invalid frame rate 0", "invalid tick rate -1"]);
+        Ok(())
+    }
+
+    component! {
+        struct Poll {
+            ticks: Vec<String>
+        }
+    }
+
+    impl Component for Poll {
+        fn handle_named_tick_event(&mut self, name: &str) -> Option<Action> {
+            self.ticks.push(name.to_string());
+            None
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("[{}]", self.ticks.join(","))), area);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn delivers_the_named_ticks_to_the_components() -> Result<(), MatetuiError> {
+        let app = || {
+            // shown, so the inactive one is drawn too
+            let mut idle = Poll::default();
+            idle.set_visible(true);
+            App::default()
+                .with_named_tick("slow", 0.2)
+                .with_named_tick("fast", 10)
+                .with_components(components![Poll::default().as_active(), idle])
+        };
+
+        let tui = start_headless(&mut app(), 20, 2)?;
+        assert_eq!(tui.named_ticks, [("slow".to_string(), 0.2), ("fast".to_string(), 10.0)]);
+
+        let mut app = app().with_layout(Layout::vertical([Constraint::Length(1); 2])).test()?;
+        app.send(Event::NamedTick("fast".into()))?;
+        app.send(Event::NamedTick("slow".into()))?;
+        app.tick()?;
+        // the inactive components don't get them
+        assert!(app.lines()[0].starts_with("[fast,slow] "));
+        assert!(app.lines()[1].starts_with("[] "));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sends_the_scheduled_actions_to_the_components() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .every(Duration::from_millis(5), "drink")
+            .after(Duration::from_secs(60), Action::Quit)
+            .with_components(components![Counter::default().as_active()])
+            .test()?;
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        app.settle()?;
+        assert!(!app.lines()[0].starts_with("mates: 0"));
+        assert!(!app.has_quit());
+
+        #[cfg(feature = "cron")]
+        assert!(matches!(
+            App::<NoAction>::default().schedule_action("every hour", "refresh"),
+            Err(MatetuiError::InvalidCron(_))
+        ));
+        Ok(())
+    }
+
+    component! {
+        struct Flaky {}
+    }
+
+    impl Component for Flaky {
+        fn update(&mut self, action: &Action) -> Result<(), ComponentError> {
+            match action {
+                Action::Tick => Err(ComponentError::Message("out of yerba".into())),
+                _ => Ok(()),
+            }
+        }
+
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn shows_the_errors_of_the_components_in_the_overlay() -> Result<(), MatetuiError> {
+        let app = || {
+            App::default().with_keybindings(kb!["<d>" => "drink"]).with_components(components![
+                Counter::default().as_active(),
+                Flaky::default().as_active()
+            ])
+        };
+
+        let err = app().test()?.tick().unwrap_err();
+        assert!(matches!(err, MatetuiError::ComponentError { name, .. } if name == "Flaky"));
+
+        let mut app = app().with_error_overlay(true).test()?;
+        app.tick()?;
+        app.tick()?;
+        assert!(!app.has_quit());
+        let lines = app.lines();
+        let overlay = &lines[lines.len() - 4..];
+        assert!(overlay[0].contains("errors · press any key to dismiss"));
+        for line in &overlay[1..3] {
+            assert!(line.contains("component `Flaky` failed: out of yerba"));
+        }
+
+        // the key press dismissing the overlay isn't handled otherwise
+        app.press("<d>")?;
+        assert!(!app.contains("out of yerba"));
+        assert!(app.lines()[0].starts_with("mates: 0"));
+        app.press("<d>")?;
+        assert!(app.lines()[0].starts_with("mates: 1"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tells_apart_the_enhanced_keys() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keyboard_enhancements(true)
+            .with_keybindings(kb!["<enter>" => "submit", "<shift-enter>" => "newline"])
+            .with_components(components![Field::default().as_active()]);
+        let tui = start_headless(&mut app, 20, 1)?;
+        assert!(tui.keyboard_enhancements);
+
+        let mut app = app.test()?;
+        // what the terminals report for shift+enter once the enhancements are enabled
+        app.send(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT)))?;
+        app.send(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)))?;
+        assert!(app.lines()[0].starts_with("[newline,submit] "));
         Ok(())
     }
 }
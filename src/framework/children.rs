@@ -0,0 +1,179 @@
+use super::{
+    component::Component,
+    events::{NoAction, UserAction},
+};
+
+/// The children of a component, by name.
+///
+/// Unlike a `HashMap`, the children keep the order they were inserted in (the order they're
+/// declared in the [children!](crate::children) macro), so they're drawn, updated and receive the
+/// events in a stable order. Use [Children::move_to] to change it.
+pub struct Children<A: UserAction = NoAction>(Vec<(String, Box<dyn Component<A>>)>);
+
+impl<A: UserAction> Children<A> {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the position of the child with the given name.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.0.iter().position(|(child, _)| child == name)
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    #[allow(clippy::borrowed_box)]
+    pub fn get(&self, name: &str) -> Option<&Box<dyn Component<A>>> {
+        self.position(name).map(|index| &self.0[index].1)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Box<dyn Component<A>>> {
+        self.position(name).map(|index| &mut self.0[index].1)
+    }
+
+    /// Insert a child after the others, returning the child it replaces, if any. A replaced child
+    /// keeps its position.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        child: Box<dyn Component<A>>,
+    ) -> Option<Box<dyn Component<A>>> {
+        let name = name.into();
+        match self.position(&name) {
+            Some(index) => Some(std::mem::replace(&mut self.0[index].1, child)),
+            None => {
+                self.0.push((name, child));
+                None
+            }
+        }
+    }
+
+    /// Remove the child with the given name, keeping the order of the others.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn Component<A>>> {
+        self.position(name).map(|index| self.0.remove(index).1)
+    }
+
+    /// Move the child with the given name to the given position (or the last one, if it's past
+    /// the end), shifting the others. Returns false if there is no such child.
+    pub fn move_to(&mut self, name: &str, index: usize) -> bool {
+        let Some(from) = self.position(name) else {
+            return false;
+        };
+        let child = self.0.remove(from);
+        self.0.insert(index.min(self.0.len()), child);
+        true
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> + '_ {
+        self.0.iter().map(|(name, _)| name)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Box<dyn Component<A>>> + '_ {
+        self.0.iter().map(|(_, child)| child)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn Component<A>>> + '_ {
+        self.0.iter_mut().map(|(_, child)| child)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Box<dyn Component<A>>)> + '_ {
+        self.0.iter().map(|(name, child)| (name, child))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut Box<dyn Component<A>>)> + '_ {
+        self.0.iter_mut().map(|(name, child)| (&*name, child))
+    }
+}
+
+impl<A: UserAction> Default for Children<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: UserAction> std::fmt::Debug for Children<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.keys()).finish()
+    }
+}
+
+impl<A: UserAction, S: Into<String>> Extend<(S, Box<dyn Component<A>>)> for Children<A> {
+    fn extend<I: IntoIterator<Item = (S, Box<dyn Component<A>>)>>(&mut self, children: I) {
+        for (name, child) in children {
+            self.insert(name, child);
+        }
+    }
+}
+
+impl<A: UserAction, S: Into<String>> FromIterator<(S, Box<dyn Component<A>>)> for Children<A> {
+    fn from_iter<I: IntoIterator<Item = (S, Box<dyn Component<A>>)>>(children: I) -> Self {
+        let mut map = Self::new();
+        map.extend(children);
+        map
+    }
+}
+
+impl<A: UserAction> IntoIterator for Children<A> {
+    type Item = (String, Box<dyn Component<A>>);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{component, ComponentAccessors, ComponentError, Frame},
+        ratatui::layout::Rect,
+    };
+
+    component! {
+        struct Leaf {}
+    }
+
+    impl Component for Leaf {
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    fn names(children: &Children) -> Vec<&str> {
+        children.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn keeps_the_children_in_order() {
+        let mut children: Children = crate::children! {
+            "c" => Leaf::default(),
+            "a" => Leaf::default(),
+            "b" => Leaf::default()
+        };
+        assert_eq!(names(&children), ["c", "a", "b"]);
+
+        assert!(children.insert("a", Box::new(Leaf::default().as_active())).is_some());
+        assert_eq!(names(&children), ["c", "a", "b"]);
+        assert!(children.get("a").is_some_and(|a| a.is_active()));
+
+        assert!(children.move_to("b", 0));
+        assert!(children.move_to("c", 10));
+        assert!(!children.move_to("z", 0));
+        assert_eq!(names(&children), ["b", "a", "c"]);
+
+        assert!(children.remove("a").is_some());
+        assert_eq!(names(&children), ["b", "c"]);
+        assert_eq!(format!("{children:?}"), r#"["b", "c"]"#);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{ffi::OsString, io, path::PathBuf};
+
+/// The runtime switches of the framework features, parsed from the command-line arguments so every
+/// matetui app gets the same flags. Passed to [App::with_options](crate::App::with_options):
+///
+/// ```ignore
+/// let options = AppOptions::from_env()?;
+/// if options.help {
+///     println!("usage: mate [options]\n\n{}", AppOptions::HELP);
+///     return Ok(());
+/// }
+///
+/// let mut app = App::default().with_components(components![home]).with_options(options)?;
+/// ```
+///
+/// The arguments that aren't framework flags are kept in [AppOptions::rest], in order, for the
+/// app to parse its own.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AppOptions {
+    /// `--no-color`: draw without colors.
+    pub no_color: bool,
+    /// `--frame-rate <fps>`: the frame rate of the app.
+    pub frame_rate: Option<f64>,
+    /// `--config <path>`: the config file to load (see [Config](crate::Config)).
+    pub config: Option<PathBuf>,
+    /// `--record <path>`: record the input events to a file (see
+    /// [Recording](crate::Recording)).
+    pub record: Option<PathBuf>,
+    /// `--replay <path>`: replay a recording.
+    pub replay: Option<PathBuf>,
+    /// `-h` or `--help`: the app should print its usage and exit.
+    pub help: bool,
+    /// The other arguments.
+    pub rest: Vec<OsString>,
+}
+
+impl AppOptions {
+    /// The description of the flags, to include in the usage of the app.
+    pub const HELP: &'static str = "\
+options:
+  --no-color           draw without colors
+  --frame-rate <fps>   frames drawn per second
+  --config <path>      load the settings from a TOML or JSON config file
+  --record <path>      record the input events to a file
+  --replay <path>      replay a recording of input events
+  -h, --help           print this help";
+
+    /// Parse the arguments the process was started with.
+    pub fn from_env() -> io::Result<Self> {
+        Self::parse(std::env::args_os().skip(1))
+    }
+
+    /// Parse the given arguments (without the name of the program). Values are passed as the
+    /// next argument (`--frame-rate 30`) or after an `=` (`--frame-rate=30`). Everything after a
+    /// `--` argument is left to the app.
+    pub fn parse(args: impl IntoIterator<Item = impl Into<OsString>>) -> io::Result<Self> {
+        let mut options = AppOptions::default();
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.to_str().filter(|arg| arg.starts_with('-')) else {
+                options.rest.push(arg);
+                continue;
+            };
+            let (name, inline) = match flag.split_once('=') {
+                Some((name, value)) => (name, Some(OsString::from(value))),
+                None => (flag, None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| invalid(format!("missing value of `{name}`")))
+            };
+
+            match name {
+                "--" => {
+                    options.rest.push(arg);
+                    options.rest.extend(args.by_ref());
+                }
+                "--no-color" => options.no_color = true,
+                "-h" | "--help" => options.help = true,
+                "--frame-rate" => {
+                    let rate = value()?;
+                    let rate = rate.to_str().and_then(|rate| rate.parse::<f64>().ok());
+                    match rate {
+                        Some(rate) if rate > 0.0 => options.frame_rate = Some(rate),
+                        _ => {
+                            return Err(invalid("`--frame-rate` must be a positive number".into()))
+                        }
+                    }
+                }
+                "--config" => options.config = Some(value()?.into()),
+                "--record" => options.record = Some(value()?.into()),
+                "--replay" => options.replay = Some(value()?.into()),
+                _ => options.rest.push(arg),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, format!("invalid arguments: {message}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_framework_flags_and_keeps_the_rest() {
+        let options = AppOptions::parse([
+            "--frame-rate=30",
+            "notes.md",
+            "--no-color",
+            "--config",
+            "mate.toml",
+            "--verbose",
+            "--",
+            "--replay",
+        ])
+        .unwrap();
+
+        assert_eq!(options.frame_rate, Some(30.0));
+        assert!(options.no_color && !options.help);
+        assert_eq!(options.config, Some("mate.toml".into()));
+        assert_eq!(options.replay, None);
+        assert_eq!(options.rest, ["notes.md", "--verbose", "--", "--replay"]);
+
+        assert!(AppOptions::parse(["--record"]).is_err());
+        assert!(AppOptions::parse(["--frame-rate", "fast"]).is_err());
+    }
+}
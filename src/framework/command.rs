@@ -0,0 +1,52 @@
+use crate::{Action, ActionKind, NoAction, UserAction};
+
+/// A command listed by the command palette, which dispatches its action when picked. Commands
+/// come from the app (see `App::with_commands`) and from the active components (see
+/// [Component::commands](crate::Component::commands)):
+///
+/// ```ignore
+/// fn commands(&self) -> Vec<Command> {
+///     vec![
+///         Command::new("Save file", "editor:save").with_description("write the buffer to disk"),
+///         Command::new("Quit", Action::Quit),
+///     ]
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Command<A: UserAction = NoAction> {
+    name: String,
+    description: Option<String>,
+    action: Action<A>,
+}
+
+impl<A: UserAction> Command<A> {
+    /// Create a new command dispatching the action, e.g. a message like `"editor:save"`.
+    pub fn new(name: impl Into<String>, action: impl Into<ActionKind<A>>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            action: Action::from(action.into()),
+        }
+    }
+
+    /// Set the description of the command, shown next to its name.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// The name of the command, which the palette searches.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The description of the command, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// The action the command dispatches.
+    pub fn action(&self) -> &Action<A> {
+        &self.action
+    }
+}
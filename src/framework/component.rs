@@ -1,16 +1,207 @@
+#[cfg(feature = "trace")]
+use super::instrument::{self, event_kind};
+#[cfg(feature = "metrics")]
+use super::metrics::DrawTimer;
+#[cfg(feature = "persistence")]
+use super::persistence::Snapshot;
 use {
     super::{
-        events::{Action, Event},
+        app::MatetuiError,
+        children::Children,
+        command::Command,
+        events::{Action, Event, MouseClick, MouseDrag, NoAction, ScrollDirection, UserAction},
+        external::External,
+        message::Message,
+        schedule::Schedule,
+        state::{AppCancellation, AppState, ExitValue, RenderRequest, StatusText},
+        terminal::TerminalInfo,
         tui::Frame,
     },
     crossterm::event::{KeyEvent, MouseEvent},
     downcast_rs::{impl_downcast, Downcast},
-    ratatui::layout::{Rect, Size},
-    std::collections::HashMap,
+    ratatui::layout::{Position, Rect, Size},
+    std::{any::Any, sync::atomic::Ordering, time::Duration},
+    thiserror::Error,
     tokio::sync::mpsc::UnboundedSender,
+    tokio_util::sync::CancellationToken,
 };
 
-pub type Children = HashMap<String, Box<dyn Component>>;
+/// The state the framework keeps in every component: whether it's active and focused, the area
+/// it was last drawn into, the state of the app, the sender of the actions and the children.
+///
+/// The [component!](crate::component) macro adds it to the struct as fields. Structs deriving
+/// [ComponentAccessors] (with the `derive` feature) keep it in a field instead, so they can have
+/// any shape:
+///
+/// ```ignore
+/// #[derive(ComponentAccessors)]
+/// pub struct Sidebar {
+///     base: ComponentBase,
+///     width: u16,
+/// }
+///
+/// impl Sidebar {
+///     pub fn new(width: u16) -> Self {
+///         let base = ComponentBase::with_children(children!("tree" => Tree::default()));
+///         Self { base, width }
+///     }
+/// }
+/// ```
+pub struct ComponentBase<A: UserAction = NoAction> {
+    is_active: bool,
+    is_focused: bool,
+    is_shown: Option<bool>,
+    area: Option<Rect>,
+    app_state: AppState,
+    action_sender: Option<UnboundedSender<Action<A>>>,
+    children: Children<A>,
+}
+
+impl<A: UserAction> Default for ComponentBase<A> {
+    fn default() -> Self {
+        Self::with_children(Children::new())
+    }
+}
+
+impl<A: UserAction> std::fmt::Debug for ComponentBase<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComponentBase")
+            .field("is_active", &self.is_active)
+            .field("is_focused", &self.is_focused)
+            .field("is_shown", &self.is_shown)
+            .field("area", &self.area)
+            .field("children", &self.children.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: UserAction> ComponentBase<A> {
+    /// Create a new [ComponentBase] for a component with the given children.
+    pub fn with_children(children: Children<A>) -> Self {
+        Self {
+            is_active: false,
+            is_focused: false,
+            is_shown: None,
+            area: None,
+            app_state: AppState::default(),
+            action_sender: None,
+            children,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    pub fn set_active(&mut self, active: bool) {
+        self.is_active = active;
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.is_shown.unwrap_or(self.is_active)
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.is_shown = Some(visible);
+    }
+
+    pub fn area(&self) -> Option<Rect> {
+        self.area
+    }
+
+    pub fn set_area(&mut self, area: Rect) {
+        self.area = Some(area);
+    }
+
+    pub fn action_sender(&self) -> Option<UnboundedSender<Action<A>>> {
+        self.action_sender.clone()
+    }
+
+    pub fn set_action_sender(&mut self, tx: UnboundedSender<Action<A>>) {
+        self.action_sender = Some(tx);
+    }
+
+    pub fn state(&self) -> &AppState {
+        &self.app_state
+    }
+
+    pub fn set_state(&mut self, state: AppState) {
+        self.app_state = state;
+    }
+
+    /// Send an [Action::AppAction] to the app, if the component was registered in one.
+    pub fn send(&self, action: &str) {
+        self.send_action(Action::AppAction(action.to_string()));
+    }
+
+    /// Send an action to the app, if the component was registered in one.
+    pub fn send_action(&self, action: Action<A>) {
+        if let Some(tx) = &self.action_sender {
+            tx.send(action).unwrap();
+        }
+    }
+
+    pub fn children(&self) -> &Children<A> {
+        &self.children
+    }
+
+    pub fn children_mut(&mut self) -> &mut Children<A> {
+        &mut self.children
+    }
+}
+
+/// An error of a component, returned by [Component::init], [Component::update] and
+/// [Component::draw].
+///
+/// The [App](crate::App) either stops with the error or shows it in an overlay (see
+/// [App::with_error_overlay](crate::App::with_error_overlay)).
+#[derive(Error, Debug)]
+pub enum ComponentError {
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("{0}")]
+    Message(String),
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl ComponentError {
+    /// Create an error from any other error.
+    pub fn other(err: impl std::error::Error + Send + Sync + 'static) -> Self {
+        ComponentError::Other(Box::new(err))
+    }
+}
+
+impl From<String> for ComponentError {
+    fn from(message: String) -> Self {
+        ComponentError::Message(message)
+    }
+}
+
+impl From<&str> for ComponentError {
+    fn from(message: &str) -> Self {
+        ComponentError::Message(message.to_string())
+    }
+}
+
+/// The error of a component is given back as it is, without the name of the component.
+impl From<MatetuiError> for ComponentError {
+    fn from(err: MatetuiError) -> Self {
+        match err {
+            MatetuiError::ComponentError { source, .. } => source,
+            MatetuiError::IoError(err) => ComponentError::IoError(err),
+            err => ComponentError::other(err),
+        }
+    }
+}
 
 /// The ComponentHandler is a wrapper around a component that provides a way to handle the lifecycle
 /// of the components and its children without overloading the component trait with too many
@@ -23,48 +214,173 @@ pub type Children = HashMap<String, Box<dyn Component>>;
 /// constructor) and propagate all the necessary events and function calls to all their children
 /// recursively by using the utility functions in this module ([update], [handle_event_for],
 /// [handle_message], [init]).
-pub(crate) struct ComponentHandler {
-    c: Box<dyn Component>,
+pub(crate) struct ComponentHandler<A: UserAction> {
+    c: Box<dyn Component<A>>,
 }
 
-impl ComponentHandler {
+impl<A: UserAction> ComponentHandler<A> {
     /// Create a new [ComponentHandler] for a specific component.
-    pub fn for_(component: Box<dyn Component>) -> Self {
+    pub fn for_(component: Box<dyn Component<A>>) -> Self {
         Self { c: component }
     }
 
-    pub(crate) fn handle_init(&mut self, area: Size) {
-        init(self.c.as_mut(), area);
+    pub(crate) fn handle_init(&mut self, area: Size) -> Result<(), MatetuiError> {
+        init(self.c.as_mut(), area)
     }
 
-    pub(crate) fn receive_action_handler(&mut self, tx: UnboundedSender<String>) {
+    pub(crate) fn receive_action_handler(&mut self, tx: UnboundedSender<Action<A>>) {
         receive_action_handler(self.c.as_mut(), tx);
     }
 
-    pub(crate) fn handle_events(&mut self, event: Option<Event>) -> Vec<Action> {
+    pub(crate) fn receive_state(&mut self, state: AppState) {
+        receive_state(self.c.as_mut(), state);
+    }
+
+    pub(crate) fn handle_events(&mut self, event: Option<Event>) -> Vec<Action<A>> {
         handle_event_for(event, self.c.as_mut())
     }
 
-    pub(crate) fn handle_update(&mut self, action: Action) {
-        update(self.c.as_mut(), &action);
+    pub(crate) fn handle_update(&mut self, action: Action<A>) -> Result<(), MatetuiError> {
+        update(self.c.as_mut(), &action)
     }
 
     pub(crate) fn handle_message(&mut self, message: String) {
         handle_message(self.c.as_mut(), message);
     }
 
-    pub(crate) fn handle_draw(&mut self, f: &mut Frame<'_>, area: Rect) {
-        if self.c.is_active() {
-            self.c.draw(f, area);
+    pub(crate) fn handle_addressed_message(&mut self, target: &str, message: &str) {
+        handle_addressed_message(self.c.as_mut(), None, target, message);
+    }
+
+    pub(crate) fn handle_typed_message(&mut self, message: &Message) {
+        handle_typed_message(self.c.as_mut(), message);
+    }
+
+    #[cfg(feature = "palette")]
+    pub(crate) fn handle_commands(&mut self, out: &mut Vec<Command<A>>) {
+        commands(self.c.as_mut(), out);
+    }
+
+    pub(crate) fn handle_quit_request(&mut self) -> QuitDecision {
+        quit_requested(self.c.as_mut())
+    }
+
+    pub(crate) fn component_at(&mut self, position: Position) -> Option<Vec<String>> {
+        component_at(self.c.as_mut(), position)
+    }
+
+    /// Deliver a mouse event to the child found through the given keys (see [component_at]),
+    /// with the position of the event relative to the area the child was last drawn into.
+    /// Nothing is delivered if the child went away or was never drawn.
+    pub(crate) fn handle_mouse(&mut self, path: &[String], mouse: MouseEvent) -> Option<Action<A>> {
+        let c = descendant(self.c.as_mut(), path)?;
+        #[cfg(feature = "trace")]
+        let _span = instrument::component(|| c.name(), "mouse");
+        let area = c.area()?;
+        c.handle_mouse_events(MouseEvent {
+            column: mouse.column.saturating_sub(area.x),
+            row: mouse.row.saturating_sub(area.y),
+            ..mouse
+        })
+    }
+
+    /// Deliver a click to the child found through the given keys (see [component_at]), with its
+    /// position relative to the area the child was last drawn into.
+    pub(crate) fn handle_click(&mut self, path: &[String], click: MouseClick) -> Option<Action<A>> {
+        let c = descendant(self.c.as_mut(), path)?;
+        #[cfg(feature = "trace")]
+        let _span = instrument::component(|| c.name(), "mouse-click");
+        let area = c.area()?;
+        c.handle_mouse_click(MouseClick {
+            column: click.column.saturating_sub(area.x),
+            row: click.row.saturating_sub(area.y),
+            ..click
+        })
+    }
+
+    /// Deliver a step of a drag to the child found through the given keys (see [component_at]),
+    /// with its positions relative to the area the child was last drawn into.
+    pub(crate) fn handle_drag(&mut self, path: &[String], drag: MouseDrag) -> Option<Action<A>> {
+        let c = descendant(self.c.as_mut(), path)?;
+        #[cfg(feature = "trace")]
+        let _span = instrument::component(|| c.name(), "mouse-drag");
+        let area = c.area()?;
+        c.handle_mouse_drag(MouseDrag {
+            origin_column: drag.origin_column.saturating_sub(area.x),
+            origin_row: drag.origin_row.saturating_sub(area.y),
+            column: drag.column.saturating_sub(area.x),
+            row: drag.row.saturating_sub(area.y),
+            ..drag
+        })
+    }
+
+    /// Deliver a scroll to the child found through the given keys (see [component_at]).
+    pub(crate) fn handle_scroll(
+        &mut self,
+        path: &[String],
+        direction: ScrollDirection,
+        amount: u16,
+    ) -> Option<Action<A>> {
+        let c = descendant(self.c.as_mut(), path)?;
+        #[cfg(feature = "trace")]
+        let _span = instrument::component(|| c.name(), "scroll");
+        c.handle_scroll_event(direction, amount)
+    }
+
+    #[cfg(feature = "persistence")]
+    pub(crate) fn handle_save_state(&mut self, snapshot: &mut Snapshot) {
+        let path = self.c.name();
+        save_state(self.c.as_mut(), path, snapshot);
+    }
+
+    #[cfg(feature = "persistence")]
+    pub(crate) fn handle_restore_state(&mut self, snapshot: &Snapshot) {
+        let path = self.c.name();
+        restore_state(self.c.as_mut(), path, snapshot);
+    }
+
+    pub(crate) fn handle_draw(
+        &mut self,
+        f: &mut Frame<'_>,
+        area: Rect,
+    ) -> Result<(), MatetuiError> {
+        // inactive components aren't drawn, unless shown explicitly
+        if self.c.is_visible() {
+            self.c.render(f, area).map_err(|err| failed(self.c.as_ref(), err))?;
         }
+        Ok(())
+    }
+
+    /// Get the wrapped component.
+    pub(crate) fn component(&self) -> &dyn Component<A> {
+        self.c.as_ref()
+    }
+
+    /// Get the wrapped component.
+    pub(crate) fn component_mut(&mut self) -> &mut dyn Component<A> {
+        self.c.as_mut()
     }
 }
 
+/// The answer of a component to a request to quit the app (see
+/// [Component::on_quit_requested]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QuitDecision {
+    /// Let the app quit.
+    #[default]
+    Quit,
+    /// Keep the app running, e.g. to ask the user to confirm first.
+    Veto,
+}
+
 /// `Component` is a trait that represents a visual and interactive element of the user interface.
 /// Implementors of this trait can be registered with the main application loop and will be able to
 /// receive events,
 /// update state, and be rendered on the screen.
-pub trait Component: Downcast + ComponentAccessors {
+///
+/// Components are generic over the custom actions of the app (see [UserAction]), which default to
+/// [NoAction] for apps that only use the built-in and string based actions.
+pub trait Component<A: UserAction = NoAction>: Downcast + ComponentAccessors<A> {
     /// Initialize the component with a specified area if necessary. Usefull for components that
     /// need to performe some initialization before the first render.
     ///
@@ -72,7 +388,9 @@ pub trait Component: Downcast + ComponentAccessors {
     ///
     /// * `area` - Rectangular area where the component will be rendered the first time.
     #[allow(unused)]
-    fn init(&mut self, area: Size) {}
+    fn init(&mut self, area: Size) -> Result<(), ComponentError> {
+        Ok(())
+    }
 
     /// Handle key events and produce actions if necessary.
     ///
@@ -84,21 +402,83 @@ pub trait Component: Downcast + ComponentAccessors {
     ///
     /// * `Result<Option<Action>>` - An action to be processed or none.
     #[allow(unused_variables)]
-    fn handle_key_events(&mut self, key: KeyEvent) -> Option<Action> {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Option<Action<A>> {
+        None
+    }
+
+    /// Handle the release of a key and produce actions if necessary. Only called with the key
+    /// releases enabled (see [App::with_key_releases](crate::App::with_key_releases)), e.g. to
+    /// stop moving when an arrow key is let go.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key event of the release.
+    #[allow(unused_variables)]
+    fn handle_key_release_events(&mut self, key: KeyEvent) -> Option<Action<A>> {
         None
     }
 
-    /// Handle mouse events and produce actions if necessary.
+    /// Handle mouse events and produce actions if necessary. The app only delivers the mouse
+    /// events under the area the component was last rendered into (and the drags and release of a
+    /// button pressed there), if no child is under the pointer. The mouse wheel events go to
+    /// [Component::handle_scroll_event] instead.
     ///
     /// # Arguments
     ///
-    /// * `mouse` - A mouse event to be processed.
+    /// * `mouse` - A mouse event to be processed, positioned relative to the area of the
+    ///   component.
     ///
     /// # Returns
     ///
     /// * `Result<Option<Action>>` - An action to be processed or none.
     #[allow(unused_variables)]
-    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Option<Action> {
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Option<Action<A>> {
+        None
+    }
+
+    /// Handle a click of a mouse button on the component and produce actions if necessary. The
+    /// click comes after the [mouse event](Component::handle_mouse_events) of the press of the
+    /// button, and counts the clicks in a row, e.g. to select a word on a double click and a line
+    /// on a triple click.
+    ///
+    /// # Arguments
+    ///
+    /// * `click` - The click, positioned relative to the area of the component.
+    #[allow(unused_variables)]
+    fn handle_mouse_click(&mut self, click: MouseClick) -> Option<Action<A>> {
+        None
+    }
+
+    /// Handle a step of a drag started on the component and produce actions if necessary: its
+    /// [start](crate::DragPhase::Start) on the first move with a button held down, its
+    /// [updates](crate::DragPhase::Update) on the next moves, and its
+    /// [end](crate::DragPhase::End) on the release of the button. The steps keep coming while
+    /// the pointer is outside of the component, and come after the
+    /// [mouse events](Component::handle_mouse_events) they are made of.
+    ///
+    /// # Arguments
+    ///
+    /// * `drag` - The step of the drag, positioned relative to the area of the component.
+    #[allow(unused_variables)]
+    fn handle_mouse_drag(&mut self, drag: MouseDrag) -> Option<Action<A>> {
+        None
+    }
+
+    /// Handle a scroll and produce actions if necessary: a turn of the mouse wheel over the
+    /// component (or over no component while it's focused), or an [Action::Scroll] while it's
+    /// focused. The mouse wheel events come in here instead of [Component::handle_mouse_events].
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The direction to scroll the content towards.
+    /// * `amount` - How much to scroll, e.g. in lines (see
+    ///   [App::with_scroll_amount](crate::App::with_scroll_amount)).
+    #[allow(unused_variables)]
+    fn handle_scroll_event(
+        &mut self,
+        direction: ScrollDirection,
+        amount: u16,
+    ) -> Option<Action<A>> {
         None
     }
 
@@ -112,7 +492,21 @@ pub trait Component: Downcast + ComponentAccessors {
     ///
     /// * `Result<Option<Action>>` - An action to be processed or none.
     #[allow(unused_variables)]
-    fn handle_tick_event(&mut self) -> Option<Action> {
+    fn handle_tick_event(&mut self) -> Option<Action<A>> {
+        None
+    }
+
+    /// Handle the ticks of named tick streams and produce actions if necessary.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the tick stream that emitted the tick.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<Action>>` - An action to be processed or none.
+    #[allow(unused_variables)]
+    fn handle_named_tick_event(&mut self, name: &str) -> Option<Action<A>> {
         None
     }
 
@@ -126,7 +520,25 @@ pub trait Component: Downcast + ComponentAccessors {
     ///
     /// * `Result<Option<Action>>` - An action to be processed or none.
     #[allow(unused_variables)]
-    fn handle_frame_event(&mut self) -> Option<Action> {
+    fn handle_frame_event(&mut self) -> Option<Action<A>> {
+        None
+    }
+
+    /// Handle terminal resize events and produce actions if necessary.
+    ///
+    /// Unlike [Component::init], which only runs before the first render, this is called every
+    /// time the terminal is resized (before the next draw), so layouts that depend on the size of
+    /// the terminal can be computed and cached here instead of on every draw.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The new size of the terminal.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<Action>>` - An action to be processed or none.
+    #[allow(unused_variables)]
+    fn handle_resize_event(&mut self, size: Size) -> Option<Action<A>> {
         None
     }
 
@@ -140,7 +552,7 @@ pub trait Component: Downcast + ComponentAccessors {
     ///
     /// * `Result<Option<Action>>` - An action to be processed or none.
     #[allow(unused_variables)]
-    fn handle_paste_event(&mut self, message: String) -> Option<Action> {
+    fn handle_paste_event(&mut self, message: String) -> Option<Action<A>> {
         None
     }
 
@@ -150,7 +562,9 @@ pub trait Component: Downcast + ComponentAccessors {
     ///
     /// * `action` - An action that may modify the state of the component.
     #[allow(unused_variables)]
-    fn update(&mut self, action: &Action) {}
+    fn update(&mut self, action: &Action<A>) -> Result<(), ComponentError> {
+        Ok(())
+    }
 
     /// Receive a custom message, probably from another component.
     /// # Arguments
@@ -159,6 +573,45 @@ pub trait Component: Downcast + ComponentAccessors {
     #[allow(unused_variables)]
     fn receive_message(&mut self, message: String) {}
 
+    /// The message topics the component receives through [Component::receive_message] and
+    /// [Component::receive_typed_message], so it isn't handed the messages it doesn't care about
+    /// (e.g. every key pressed, in deep trees). An entry ending with `*` matches the topics
+    /// starting with it, e.g. `"app:*"`.
+    ///
+    /// Defaults to `None`: the component receives every message. The subscriptions of a component
+    /// don't apply to its children, and the messages sent to a component by name (see
+    /// [ComponentAccessors::send_to]) are always delivered.
+    ///
+    /// ```ignore
+    /// fn subscriptions(&self) -> Option<Vec<&str>> {
+    ///     Some(vec!["app:drink", "task:*"])
+    /// }
+    /// ```
+    fn subscriptions(&self) -> Option<Vec<&str>> {
+        None
+    }
+
+    /// The commands the component adds to the command palette while it's active (see [Command]),
+    /// e.g. the actions of an editor while it's shown. The commands of the children are added
+    /// too, so parents don't have to forward them.
+    ///
+    /// ```ignore
+    /// fn commands(&self) -> Vec<Command> {
+    ///     vec![Command::new("Save file", "editor:save")]
+    /// }
+    /// ```
+    fn commands(&self) -> Vec<Command<A>> {
+        Vec::new()
+    }
+
+    /// Receive a message with a typed payload, probably from another component (see [Message]).
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message, whose payload can be downcast with [Message::get].
+    #[allow(unused_variables)]
+    fn receive_typed_message(&mut self, message: &Message) {}
+
     /// Render the component on the screen. (REQUIRED)
     ///
     /// # Arguments
@@ -169,7 +622,29 @@ pub trait Component: Downcast + ComponentAccessors {
     /// # Returns
     ///
     /// * `Result<()>` - An Ok result or an error.
-    fn draw(&mut self, f: &mut Frame<'_>, area: Rect);
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError>;
+
+    /// Draw the component, keeping track of the area it was drawn into (see
+    /// [ComponentAccessors::area]).
+    ///
+    /// The framework renders the root components through this method. Parents should prefer it
+    /// over [Component::draw] to draw their children too, so features that depend on the layout
+    /// (like the focus traversal order) know where each component is, and so hidden children (see
+    /// [ComponentAccessors::is_visible]) aren't drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A frame used for rendering.
+    /// * `area` - The area in which the component should be drawn.
+    fn render(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+        if !self.is_visible() {
+            return Ok(());
+        }
+        self.set_area(area);
+        #[cfg(feature = "metrics")]
+        let _timer = DrawTimer::start(|| self.name());
+        self.draw(f, area)
+    }
 
     /// Get a child component by name as a mutable reference.
     ///
@@ -190,8 +665,8 @@ pub trait Component: Downcast + ComponentAccessors {
     /// * `name` - The name of the child component.
     ///
     /// # Returns
-    /// * `Option<&mut Box<dyn Component>>` - A mutable reference to the child component or none.
-    fn child_mut(&mut self, name: &str) -> Option<&mut Box<dyn Component>> {
+    /// * `Option<&mut Box<dyn Component<A>>>` - A mutable reference to the child component or none.
+    fn child_mut(&mut self, name: &str) -> Option<&mut Box<dyn Component<A>>> {
         if let Some(children) = self.get_children() {
             children.get_mut(name)
         } else {
@@ -212,22 +687,85 @@ pub trait Component: Downcast + ComponentAccessors {
     /// }
     /// ```
     ///
-    /// ... or just use the [child_downcast] utility functions.
+    /// ... or just use the [child_downcast] utility functions. Unlike [Component::child_mut], it
+    /// only borrows the component immutably, so it can be used from helpers taking `&self`.
     ///
     /// # Arguments
     /// * `name` - The name of the child component.
     ///
     /// # Returns
-    /// * `Option<&Box<dyn Component>>` - A reference to the child component or none.
+    /// * `Option<&Box<dyn Component<A>>>` - A reference to the child component or none.
     #[allow(clippy::borrowed_box)]
-    fn child(&mut self, name: &str) -> Option<&Box<dyn Component>> {
-        if let Some(children) = self.get_children() {
+    fn child(&self, name: &str) -> Option<&Box<dyn Component<A>>> {
+        if let Some(children) = self.get_children_ref() {
             children.get(name)
         } else {
             None
         }
     }
 
+    /// Add a child component at runtime, after the others, e.g. one per open file. If the
+    /// component is already registered in an app, the child receives its action handler and state
+    /// and is initialized, like the children declared upfront. A child with the same name is
+    /// replaced, keeping its position, and deactivated and unfocused like by
+    /// [Component::remove_child].
+    ///
+    /// ```ignore
+    /// fn update(&mut self, action: &Action) -> Result<(), ComponentError> {
+    ///     if let Action::AppAction(path) = action {
+    ///         self.add_child(path, FileView::new(path).as_active())?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Arguments
+    /// * `name` - The name of the child component.
+    /// * `child` - The child component.
+    ///
+    /// # Returns
+    /// * `Result<Option<Box<dyn Component<A>>>, ComponentError>` - The child with the same name it
+    ///   replaced, if any, or the error of its initialization.
+    fn add_child(
+        &mut self,
+        name: &str,
+        child: impl Component<A>,
+    ) -> Result<Option<Box<dyn Component<A>>>, ComponentError>
+    where
+        Self: Sized,
+    {
+        if self.get_children_ref().is_none() {
+            return Err(format!("{} can't have children", self.name()).into());
+        }
+
+        let mut child: Box<dyn Component<A>> = Box::new(child);
+        if let Some(tx) = self.action_sender() {
+            receive_action_handler(child.as_mut(), tx);
+            receive_state(child.as_mut(), self.state().clone());
+            init(child.as_mut(), self.terminal().size)?;
+        }
+
+        let mut replaced = self.get_children().and_then(|children| children.insert(name, child));
+        if let Some(replaced) = replaced.as_mut() {
+            detach(replaced.as_mut());
+        }
+        Ok(replaced)
+    }
+
+    /// Remove a child component at runtime. The child is deactivated and unfocused first (see
+    /// [Component::on_active_changed]), so it can stop its work.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the child component.
+    ///
+    /// # Returns
+    /// * `Option<Box<dyn Component<A>>>` - The removed child component or none.
+    fn remove_child(&mut self, name: &str) -> Option<Box<dyn Component<A>>> {
+        let mut child = self.get_children()?.remove(name)?;
+        detach(child.as_mut());
+        Some(child)
+    }
+
     /// Notify the component that its active state has changed.
     ///
     /// Whenever the active state of a component changes, the component will be notified through
@@ -238,27 +776,134 @@ pub trait Component: Downcast + ComponentAccessors {
     /// * `active` - The new active state of the component.
     #[allow(unused_variables)]
     fn on_active_changed(&mut self, active: bool) {}
+
+    /// The position of the component in the focus traversal order (see [FocusManager]), or none
+    /// if the component can't be focused (the default).
+    ///
+    /// Focusable components are traversed from the lowest to the highest tab index. Components
+    /// sharing the same tab index are traversed in layout order (top to bottom, left to right),
+    /// based on the area they were last rendered into.
+    ///
+    /// [FocusManager]: crate::FocusManager
+    fn tab_index(&self) -> Option<u16> {
+        None
+    }
+
+    /// Notify the screen component that it became the current screen of the app (see
+    /// [App::with_screen](crate::App::with_screen)), either because it was pushed or because the
+    /// screen on top of it was popped.
+    fn on_screen_enter(&mut self) {}
+
+    /// Notify the screen component that it stopped being the current screen of the app, either
+    /// because another screen was pushed on top of it or because it was popped or replaced.
+    fn on_screen_leave(&mut self) {}
+
+    /// Notify the component that it gained or lost the focus.
+    ///
+    /// # Arguments
+    /// * `focused` - The new focus state of the component.
+    #[allow(unused_variables)]
+    fn on_focus_changed(&mut self, focused: bool) {}
+
+    /// Called when the app is asked to quit with an [Action::Quit], before quitting. Returning
+    /// [QuitDecision::Veto] keeps the app running, e.g. to open an "unsaved changes" dialog
+    /// instead, which quits for real with an [Action::ForceQuit] once confirmed:
+    ///
+    /// ```ignore
+    /// fn on_quit_requested(&mut self) -> QuitDecision {
+    ///     if !self.dirty {
+    ///         return QuitDecision::Quit;
+    ///     }
+    ///     self.send_action(Action::OpenModal("unsaved-changes".into()));
+    ///     QuitDecision::Veto
+    /// }
+    /// ```
+    ///
+    /// Only the active components are asked, until one of them vetoes.
+    fn on_quit_requested(&mut self) -> QuitDecision {
+        QuitDecision::Quit
+    }
+
+    /// The state of the component to remember between runs of the app (e.g. the last selection,
+    /// the size of a split or the history of a prompt), if any. Only called when the app persists
+    /// the state of its components (see [App::with_persistence](crate::App::with_persistence)),
+    /// once it quits.
+    ///
+    /// The state is an opaque string for the app, so components may serialize it the way they
+    /// like, e.g. with `serde_json`:
+    ///
+    /// ```ignore
+    /// fn save_state(&self) -> Option<String> {
+    ///     serde_json::to_string(&self.history).ok()
+    /// }
+    ///
+    /// fn restore_state(&mut self, state: &str) {
+    ///     self.history = serde_json::from_str(state).unwrap_or_default();
+    /// }
+    /// ```
+    fn save_state(&self) -> Option<String> {
+        None
+    }
+
+    /// Restore the state saved by [Component::save_state] on the last run of the app. Called once
+    /// the app starts, after [Component::init], and only if there is a saved state.
+    #[allow(unused_variables)]
+    fn restore_state(&mut self, state: &str) {}
 }
 
-impl_downcast!(Component);
+impl_downcast!(Component<A> where A: UserAction);
+
+/// Attach the name of the component to its error.
+fn failed<A: UserAction, T: Component<A> + ?Sized>(c: &T, err: ComponentError) -> MatetuiError {
+    MatetuiError::ComponentError {
+        name: c.name(),
+        source: err,
+    }
+}
 
 /// Update the component and its childrend recursively, based on a received action.
-fn update<T: Component + ?Sized>(c: &mut T, action: &Action) {
+fn update<A: UserAction, T: Component<A> + ?Sized>(
+    c: &mut T,
+    action: &Action<A>,
+) -> Result<(), MatetuiError> {
     if c.is_active() {
-        c.update(&action);
+        #[cfg(feature = "trace")]
+        let _span = instrument::component(|| c.name(), action);
+        c.update(action).map_err(|err| failed(c, err))?;
 
         if let Some(children) = c.get_children() {
             for child in children.values_mut() {
-                update(child.as_mut(), action);
+                update(child.as_mut(), action)?;
             }
         }
     }
+    Ok(())
+}
+
+/// Returns `true` if the component receives the messages of the topic (see
+/// [Component::subscriptions]).
+fn subscribed<A: UserAction, T: Component<A> + ?Sized>(c: &T, topic: &str) -> bool {
+    c.subscriptions().is_none_or(|topics| {
+        topics.iter().any(|t| match t.strip_suffix('*') {
+            Some(prefix) => topic.starts_with(prefix),
+            None => *t == topic,
+        })
+    })
+}
+
+/// Deliver a message to a component only, if it's active and subscribed to it.
+pub(crate) fn deliver_message<A: UserAction>(c: &mut dyn Component<A>, message: String) {
+    if c.is_active() && subscribed(c, &message) {
+        c.receive_message(message);
+    }
 }
 
 /// Handle a message for a specific component and its children, recursively.
-fn handle_message<T: Component + ?Sized>(c: &mut T, message: String) {
+fn handle_message<A: UserAction, T: Component<A> + ?Sized>(c: &mut T, message: String) {
     if c.is_active() {
-        c.receive_message(message.clone());
+        if subscribed(c, &message) {
+            c.receive_message(message.clone());
+        }
 
         if let Some(children) = c.get_children() {
             for child in children.values_mut() {
@@ -268,19 +913,132 @@ fn handle_message<T: Component + ?Sized>(c: &mut T, message: String) {
     }
 }
 
+/// Handle a message addressed to the components named `target`, among a specific component
+/// (named `key` among its siblings, if it's a child) and its children, recursively.
+fn handle_addressed_message<A: UserAction, T: Component<A> + ?Sized>(
+    c: &mut T,
+    key: Option<&str>,
+    target: &str,
+    message: &str,
+) {
+    if c.is_active() {
+        if key == Some(target) || c.name() == target {
+            c.receive_message(message.to_string());
+        }
+
+        if let Some(children) = c.get_children() {
+            for (name, child) in children.iter_mut() {
+                handle_addressed_message(child.as_mut(), Some(name), target, message);
+            }
+        }
+    }
+}
+
+/// Handle a typed message for a specific component and its children, recursively.
+fn handle_typed_message<A: UserAction, T: Component<A> + ?Sized>(c: &mut T, message: &Message) {
+    if c.is_active() {
+        if subscribed(c, message.topic()) {
+            c.receive_typed_message(message);
+        }
+
+        if let Some(children) = c.get_children() {
+            for child in children.values_mut() {
+                handle_typed_message(child.as_mut(), message);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "palette")]
+/// Collect the commands of the component and its children, if it's active.
+fn commands<A: UserAction, T: Component<A> + ?Sized>(c: &mut T, out: &mut Vec<Command<A>>) {
+    if c.is_active() {
+        out.extend(c.commands());
+        if let Some(children) = c.get_children() {
+            for child in children.values_mut() {
+                commands(child.as_mut(), out);
+            }
+        }
+    }
+}
+
+/// Ask a component and its children, recursively, whether the app can quit. The first veto wins.
+fn quit_requested<A: UserAction, T: Component<A> + ?Sized>(c: &mut T) -> QuitDecision {
+    if !c.is_active() {
+        return QuitDecision::Quit;
+    }
+    if c.on_quit_requested() == QuitDecision::Veto {
+        return QuitDecision::Veto;
+    }
+
+    if let Some(children) = c.get_children() {
+        for child in children.values_mut() {
+            if quit_requested(child.as_mut()) == QuitDecision::Veto {
+                return QuitDecision::Veto;
+            }
+        }
+    }
+    QuitDecision::Quit
+}
+
+/// Save the state of a component (found at `path`, see [Snapshot]) and its children into the
+/// snapshot, recursively.
+#[cfg(feature = "persistence")]
+fn save_state<A: UserAction, T: Component<A> + ?Sized>(
+    c: &mut T,
+    path: String,
+    snapshot: &mut Snapshot,
+) {
+    if let Some(state) = c.save_state() {
+        snapshot.insert(path.clone(), state);
+    }
+
+    if let Some(children) = c.get_children() {
+        for (name, child) in children.iter_mut() {
+            save_state(child.as_mut(), format!("{path}/{name}"), snapshot);
+        }
+    }
+}
+
+/// Restore the state of a component (found at `path`, see [Snapshot]) and its children from the
+/// snapshot, recursively.
+#[cfg(feature = "persistence")]
+fn restore_state<A: UserAction, T: Component<A> + ?Sized>(
+    c: &mut T,
+    path: String,
+    snapshot: &Snapshot,
+) {
+    if let Some(state) = snapshot.get(&path) {
+        c.restore_state(state);
+    }
+
+    if let Some(children) = c.get_children() {
+        for (name, child) in children.iter_mut() {
+            restore_state(child.as_mut(), format!("{path}/{name}"), snapshot);
+        }
+    }
+}
+
 /// Initialize a component and its children recursively.
-fn init<T: Component + ?Sized>(c: &mut T, area: Size) {
-    c.init(area);
+fn init<A: UserAction, T: Component<A> + ?Sized>(
+    c: &mut T,
+    area: Size,
+) -> Result<(), MatetuiError> {
+    c.init(area).map_err(|err| failed(c, err))?;
 
     if let Some(children) = c.get_children() {
         for child in children.values_mut() {
-            init(child.as_mut(), area);
+            init(child.as_mut(), area)?;
         }
     }
+    Ok(())
 }
 
 /// Set the action handler for a component and its children recursively.
-fn receive_action_handler<T: Component + ?Sized>(c: &mut T, tx: UnboundedSender<String>) {
+fn receive_action_handler<A: UserAction, T: Component<A> + ?Sized>(
+    c: &mut T,
+    tx: UnboundedSender<Action<A>>,
+) {
     c.register_action_handler(tx.clone());
 
     if let Some(children) = c.get_children() {
@@ -290,17 +1048,58 @@ fn receive_action_handler<T: Component + ?Sized>(c: &mut T, tx: UnboundedSender<
     }
 }
 
+/// Unfocus and deactivate a child taken out of its parent, so it can stop its work.
+fn detach<A: UserAction, T: Component<A> + ?Sized>(c: &mut T) {
+    if c.is_focused() {
+        c.set_focused(false);
+    }
+    if c.is_active() {
+        c.set_active(false);
+    }
+}
+
+/// Set the shared app state for a component and its children recursively.
+fn receive_state<A: UserAction, T: Component<A> + ?Sized>(c: &mut T, state: AppState) {
+    c.register_state(state.clone());
+
+    if let Some(children) = c.get_children() {
+        for child in children.values_mut() {
+            receive_state(child.as_mut(), state.clone());
+        }
+    }
+}
+
+/// Spawn the schedule of a component, returning the token cancelling it.
+fn spawn_schedule<A: UserAction, T: ComponentAccessors<A> + ?Sized>(
+    c: &T,
+    schedule: Schedule<A>,
+) -> CancellationToken {
+    let token = c.cancellation_token();
+    if let Some(tx) = c.action_sender() {
+        schedule.spawn(tx, token.clone());
+    }
+    token
+}
+
 /// handle event for a specific component and its children, recursively.
-fn handle_event_for<T: Component + ?Sized>(event: Option<Event>, c: &mut T) -> Vec<Action> {
+fn handle_event_for<A: UserAction, T: Component<A> + ?Sized>(
+    event: Option<Event>,
+    c: &mut T,
+) -> Vec<Action<A>> {
     if c.is_active() {
+        #[cfg(feature = "trace")]
+        let _span = event.as_ref().map(|e| instrument::component(|| c.name(), event_kind(e)));
         let mut actions = vec![];
 
         let action = match event {
             Some(Event::Key(key_event)) => c.handle_key_events(key_event),
+            Some(Event::KeyRelease(key_event)) => c.handle_key_release_events(key_event),
             Some(Event::Mouse(mouse_event)) => c.handle_mouse_events(mouse_event),
             Some(Event::Tick) => c.handle_tick_event(),
+            Some(Event::NamedTick(ref name)) => c.handle_named_tick_event(name),
             Some(Event::Render) => c.handle_frame_event(),
             Some(Event::Paste(ref event)) => c.handle_paste_event(event.clone()),
+            Some(Event::Resize(width, height)) => c.handle_resize_event(Size::new(width, height)),
             _ => None,
         };
 
@@ -321,6 +1120,44 @@ fn handle_event_for<T: Component + ?Sized>(event: Option<Event>, c: &mut T) -> V
     }
 }
 
+/// Find the deepest active and visible component under the position, among a specific component
+/// and its children, by the area it was last drawn into. Returns the keys of the children leading
+/// to it, empty for the component itself.
+fn component_at<A: UserAction, T: Component<A> + ?Sized>(
+    c: &mut T,
+    position: Position,
+) -> Option<Vec<String>> {
+    if !c.is_active() || !c.is_visible() {
+        return None;
+    }
+
+    if let Some(children) = c.get_children() {
+        for (name, child) in children.iter_mut() {
+            if let Some(mut path) = component_at(child.as_mut(), position) {
+                path.insert(0, name.clone());
+                return Some(path);
+            }
+        }
+    }
+    c.area().filter(|area| area.contains(position)).map(|_| vec![])
+}
+
+/// Find the active child of a specific component through the given keys (see [component_at]),
+/// or the component itself for no keys.
+fn descendant<'a, A: UserAction>(
+    c: &'a mut dyn Component<A>,
+    path: &[String],
+) -> Option<&'a mut dyn Component<A>> {
+    if !c.is_active() {
+        return None;
+    }
+
+    match path.split_first() {
+        Some((name, rest)) => descendant(c.child_mut(name)?.as_mut(), rest),
+        None => Some(c),
+    }
+}
+
 /// Get a child downcasted to a specific type by name as a mutable reference.
 ///
 /// # Arguments
@@ -328,7 +1165,7 @@ fn handle_event_for<T: Component + ?Sized>(event: Option<Event>, c: &mut T) -> V
 ///
 /// # Returns
 /// * `Option<&mut T>` - A mutable reference to the child component or none.
-pub fn child_downcast_mut<'a, CastTo: Component, This: Component + ?Sized>(
+pub fn child_downcast_mut<'a, CastTo: Component<A>, This: Component<A> + ?Sized, A: UserAction>(
     this: &'a mut This,
     name: &str,
 ) -> Option<&'a mut CastTo> {
@@ -346,8 +1183,8 @@ pub fn child_downcast_mut<'a, CastTo: Component, This: Component + ?Sized>(
 ///
 /// # Returns
 /// * `Option<&T>` - A reference to the child component or none.
-pub fn child_downcast<'a, CastTo: Component, This: Component + ?Sized>(
-    this: &'a mut This,
+pub fn child_downcast<'a, CastTo: Component<A>, This: Component<A> + ?Sized, A: UserAction>(
+    this: &'a This,
     name: &str,
 ) -> Option<&'a CastTo> {
     if let Some(child) = this.child(name) {
@@ -357,7 +1194,7 @@ pub fn child_downcast<'a, CastTo: Component, This: Component + ?Sized>(
     }
 }
 
-pub trait ComponentAccessors {
+pub trait ComponentAccessors<A: UserAction = NoAction> {
     // #region fields g&s
 
     /// returns the name of the component
@@ -369,14 +1206,171 @@ pub trait ComponentAccessors {
     /// sets the active state of the component
     fn set_active(&mut self, active: bool);
 
+    /// returns the focus state of the component
+    fn is_focused(&self) -> bool;
+
+    /// sets the focus state of the component
+    fn set_focused(&mut self, focused: bool);
+
+    /// returns whether the component is drawn (see [Component::render]), which follows the
+    /// active state until [ComponentAccessors::set_visible] is called. Unlike the active state,
+    /// it doesn't stop the events and the messages: a hidden active component (e.g. a background
+    /// poller) keeps receiving them, and an inactive one shown explicitly is drawn but inert.
+    /// Hidden components can't be focused nor clicked.
+    fn is_visible(&self) -> bool;
+
+    /// shows or hides the component
+    fn set_visible(&mut self, visible: bool);
+
+    /// returns the area the component was last rendered into, if any
+    fn area(&self) -> Option<Rect>;
+
+    /// sets the area the component was last rendered into
+    fn set_area(&mut self, area: Rect);
+
     /// registers an action handler that can send actions for processing if necessary
-    fn register_action_handler(&mut self, tx: UnboundedSender<String>);
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action<A>>);
+
+    /// returns the action handler registered by the app, if any
+    fn action_sender(&self) -> Option<UnboundedSender<Action<A>>>;
+
+    /// registers the state shared by all the components of the app
+    fn register_state(&mut self, state: AppState);
+
+    /// returns the state shared by all the components of the app (see [AppState])
+    fn state(&self) -> &AppState;
+
+    /// returns the capabilities of the terminal the app is running in (see [TerminalInfo])
+    fn terminal(&self) -> TerminalInfo {
+        self.state().read(TerminalInfo::clone).unwrap_or_default()
+    }
 
     /// send a message to through the action handler bus
     fn send(&self, action: &str);
 
     /// send a message to through the action handler bus
-    fn send_action(&self, action: Action);
+    fn send_action(&self, action: Action<A>);
+
+    /// send a message only to the components with the given name: their type name (see
+    /// [ComponentAccessors::name]) or their name among the children of their parent, e.g.
+    /// `self.send_to("fps-counter", "reset")`
+    fn send_to(&self, name: &str, message: &str) {
+        self.send_action(Action::SendTo(name.to_string(), message.to_string()));
+    }
+
+    /// publish a message with a typed payload to the components (see [Message])
+    fn publish<T: Any + Send + Sync>(&self, topic: &str, payload: T)
+    where
+        Self: Sized,
+    {
+        self.send_action(Action::Message(Message::new(topic, payload)));
+    }
+
+    /// send a message through the action handler bus once the `delay` elapses, e.g.
+    /// `self.send_after("poll", Duration::from_secs(5))`. The returned token cancels the timer if
+    /// the message isn't needed anymore; it's also cancelled when the app quits (see
+    /// [ComponentAccessors::cancellation_token]).
+    fn send_after(&self, action: &str, delay: Duration) -> CancellationToken {
+        spawn_schedule(self, Schedule::After(delay, Action::AppAction(action.to_string())))
+    }
+
+    /// send a message through the action handler bus every time the `period` elapses, starting one
+    /// `period` from now, until the returned token is cancelled or the app quits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    fn send_every(&self, action: &str, period: Duration) -> CancellationToken {
+        assert!(!period.is_zero(), "`period` must be non-zero");
+        spawn_schedule(self, Schedule::Every(period, Action::AppAction(action.to_string())))
+    }
+
+    /// returns a token cancelled when the app quits, so the tasks spawned by the component can
+    /// stop gracefully instead of being dropped mid-work. It's a child of the token of the app:
+    /// cancelling it only cancels the work of the component. Before the component is registered
+    /// into an app, the token is never cancelled by the app.
+    ///
+    /// ```ignore
+    /// let token = self.cancellation_token();
+    /// tokio::spawn(async move {
+    ///     tokio::select! {
+    ///         _ = token.cancelled() => flush_and_close().await,
+    ///         _ = sync_forever() => {}
+    ///     }
+    /// });
+    /// ```
+    fn cancellation_token(&self) -> CancellationToken {
+        self.state().read(|app: &AppCancellation| app.0.child_token()).unwrap_or_default()
+    }
+
+    /// ask the app to draw the next frame, when it renders on demand (see
+    /// [App::with_render_on_demand](crate::App::with_render_on_demand)). Input events and actions
+    /// already trigger a frame, so this is only needed for changes made on ticks or by background
+    /// tasks.
+    fn request_render(&self) {
+        self.state().read(|request: &RenderRequest| request.0.store(true, Ordering::Relaxed));
+    }
+
+    /// show a status in the status bar of the app, e.g. a hint or the result of a command, until
+    /// another one replaces it (see [App::with_status_bar](crate::App::with_status_bar))
+    fn set_status(&self, status: &str) {
+        self.state().write(|text: &mut StatusText| text.0 = Some(status.to_string()));
+        self.request_render();
+    }
+
+    /// clear the status bar of the app (see [ComponentAccessors::set_status])
+    fn clear_status(&self) {
+        self.state().write(|text: &mut StatusText| text.0 = None);
+        self.request_render();
+    }
+
+    /// switch the theme of the app to the one with the given name (see [Action::SetTheme])
+    fn set_theme(&self, name: &str) {
+        self.send_action(Action::SetTheme(name.to_string()));
+    }
+
+    /// ring the bell to signal an error or the completion of a task (see [Action::Bell])
+    fn bell(&self) {
+        self.send_action(Action::Bell);
+    }
+
+    /// copy the text to the system clipboard (see [Clipboard](crate::clipboard::Clipboard))
+    #[cfg(feature = "clipboard")]
+    fn copy_to_clipboard(&self, text: &str) -> Result<(), ComponentError> {
+        crate::clipboard::Clipboard::copy(text)?;
+        Ok(())
+    }
+
+    /// the text of the system clipboard, or the last text copied by the app, if any (see
+    /// [Clipboard](crate::clipboard::Clipboard))
+    #[cfg(feature = "clipboard")]
+    fn clipboard_text(&self) -> Option<String> {
+        crate::clipboard::Clipboard::text()
+    }
+
+    /// run an external program (e.g. the editor of the user) in the terminal, handing it over
+    /// until the program exits (see [Action::RunExternal])
+    fn run_external(&self, external: External) {
+        self.send_action(Action::RunExternal(external));
+    }
+
+    /// quit the app, making [App::run](crate::App::run) return the given value. The value is
+    /// only returned if its type is the one set with [App::returning](crate::App::returning), and
+    /// it's dropped if a component vetoes the quit (see [Component::on_quit_requested]).
+    fn exit_with<T: Send + Sync + 'static>(&self, value: T)
+    where
+        Self: Sized,
+    {
+        self.state().insert(ExitValue(Some(value)));
+        self.send_action(Action::Quit);
+    }
+
+    /// send a desktop notification through the notification service of the OS (see
+    /// [notify_desktop](crate::notify_desktop))
+    #[cfg(feature = "desktop-notifications")]
+    fn notify_desktop(&self, title: &str, body: &str) -> std::io::Result<()> {
+        crate::notify_desktop(title, body)
+    }
 
     // create a Component as default and active
     #[allow(clippy::wrong_self_convention)]
@@ -388,12 +1382,279 @@ pub trait ComponentAccessors {
     /// used by other functions to have knowledge of the children.
     ///
     /// # Attributes
-    /// * `children`: `HashMap<String, Box<dyn Component>>` - All child components.
+    /// * `children`: [Children] - All child components, in order.
     ///
     /// # Returns
     ///
     /// * `Vec[&mut Box<dyn Component>]` - A vector of mutable references to the child components.
-    fn get_children(&mut self) -> Option<&mut Children>;
+    fn get_children(&mut self) -> Option<&mut Children<A>>;
+
+    /// Get all child components as immutable references, e.g. to inspect them while drawing or in
+    /// tests (see [ComponentAccessors::get_children]).
+    fn get_children_ref(&self) -> Option<&Children<A>>;
 
     // #endregion
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{component, components, kb, App, ComponentHarness},
+        ratatui::widgets::Paragraph,
+    };
+
+    component! {
+        struct Counter {
+            mates: u32
+        }
+    }
+
+    impl Component for Counter {
+        fn receive_message(&mut self, message: String) {
+            if message == "drink" {
+                self.mates += 1;
+            }
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("mates: {}", self.mates)), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Bar {},
+        children => {
+            "left" => Counter::default().as_active(),
+            "right" => Counter::default().as_active()
+        }
+    }
+
+    impl Component for Bar {
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            for (row, name) in ["left", "right"].into_iter().enumerate() {
+                let area = Rect::new(area.x, area.y + row as u16, area.width, 1);
+                self.child_mut(name).unwrap().render(f, area)?;
+            }
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Tab {}
+    }
+
+    impl Component for Tab {
+        fn init(&mut self, _: Size) -> Result<(), ComponentError> {
+            self.send("opened");
+            Ok(())
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new("tab"), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Broken {}
+    }
+
+    impl Component for Broken {
+        fn init(&mut self, _: Size) -> Result<(), ComponentError> {
+            Err("no yerba left".into())
+        }
+
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Tabs {
+            opened: u32
+        }
+    }
+
+    impl Component for Tabs {
+        fn receive_message(&mut self, message: String) {
+            let tabs = self.get_children().map_or(0, |children| children.len());
+            match message.as_str() {
+                "open" => {
+                    let tab = Tab::default().as_active();
+                    self.add_child(&format!("tab-{tabs}"), tab).unwrap();
+                }
+                "close" => {
+                    self.remove_child(&format!("tab-{}", tabs.saturating_sub(1)));
+                }
+                "opened" => self.opened += 1,
+                _ => {}
+            }
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("opened: {}", self.opened)), area);
+            let mut row = area.y;
+            for child in self.get_children().unwrap().values_mut() {
+                row += 1;
+                child.render(f, Rect::new(area.x, row, area.width, 1))?;
+            }
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Tap {
+            heard: Vec<String>
+        }
+    }
+
+    impl Component for Tap {
+        fn subscriptions(&self) -> Option<Vec<&str>> {
+            Some(vec!["app:*", "drink"])
+        }
+
+        fn receive_message(&mut self, message: String) {
+            self.heard.push(message);
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(self.heard.join(",")), area);
+            Ok(())
+        }
+    }
+
+    /// A component of its own shape, deriving its accessors.
+    #[cfg(feature = "derive")]
+    #[derive(Debug, crate::ComponentAccessors)]
+    #[component(actions = A, name = "greeter")]
+    struct Greeter<A: UserAction> {
+        base: crate::ComponentBase<A>,
+        greeting: &'static str,
+        heard: u32,
+    }
+
+    #[cfg(feature = "derive")]
+    impl<A: UserAction> Component<A> for Greeter<A> {
+        fn receive_message(&mut self, _: String) {
+            self.heard += 1;
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("{} x{}", self.greeting, self.heard)), area);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn components_only_receive_their_subscriptions() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<d>" => "drink", "<s>" => "app:save", "<o>" => "other"])
+            .with_components(components![Tap::default().as_active()])
+            .test()?;
+
+        // the keys pressed are messages too
+        app.press("<d><o><s><k>")?;
+        assert_eq!(app.lines()[0].trim_end(), "drink,app:save");
+        Ok(())
+    }
+
+    #[cfg(feature = "derive")]
+    #[tokio::test]
+    async fn derives_the_accessors() -> Result<(), MatetuiError> {
+        let greeter: Greeter<NoAction> = Greeter {
+            base: crate::ComponentBase::default(),
+            greeting: "buen día",
+            heard: 0,
+        };
+        assert!(format!("{greeter:?}").contains("is_active: false"));
+
+        let mut app = App::default()
+            .with_keybindings(kb!["<g>" => Action::SendTo("greeter".into(), "hola".into())])
+            .with_components(components![greeter.as_active()])
+            .test()?;
+        app.press("<g><g>")?;
+        assert_eq!(app.lines()[0].trim_end(), "buen día x2");
+        Ok(())
+    }
+
+    #[test]
+    fn inspects_the_children_immutably() {
+        let bar = Bar::default();
+        let inspect = |bar: &Bar| {
+            let left: Option<&Counter> = crate::child_downcast(bar, "left");
+            (bar.get_children_ref().map(Children::len), left.map(|c| c.mates))
+        };
+        assert_eq!(inspect(&bar), (Some(2), Some(0)));
+        assert!(bar.child("right").is_some_and(|right| right.is_active()));
+        assert!(bar.child("center").is_none());
+    }
+
+    #[tokio::test]
+    async fn adds_and_removes_children_at_runtime() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<o>" => "open", "<c>" => "close"])
+            .with_components(components![Tabs::default().as_active()])
+            .test()?;
+        app.press("<o><o>")?;
+        assert_eq!(
+            app.lines()[..3].iter().map(|l| l.trim_end()).collect::<Vec<_>>(),
+            ["opened: 2", "tab", "tab"]
+        );
+
+        app.press("<c>")?;
+        assert_eq!(app.lines()[2].trim_end(), "");
+        Ok(())
+    }
+
+    #[test]
+    fn deactivates_the_replaced_children() {
+        let mut tabs = ComponentHarness::new(Tabs::default().as_active());
+        let parent = tabs.component_mut();
+        assert!(parent.add_child("tab", Tab::default().as_active()).unwrap().is_none());
+        let replaced = parent.add_child("tab", Tab::default().as_active()).unwrap();
+        assert!(replaced.is_some_and(|tab| !tab.is_active()));
+        assert_eq!(tabs.sent_messages(), ["opened", "opened"]);
+
+        // the error of the initialization of the child is given back as it is
+        let added = tabs.component_mut().add_child("broken", Broken::default());
+        assert!(matches!(added, Err(ComponentError::Message(m)) if m == "no yerba left"));
+    }
+
+    #[test]
+    fn skips_the_inactive_components_that_were_never_hidden_nor_shown() -> Result<(), MatetuiError>
+    {
+        let mut bar = Bar::default().as_active();
+        bar.child_mut("right").unwrap().set_active(false);
+        let mut harness = ComponentHarness::with_size(bar, 12, 2);
+        harness.draw()?;
+        assert!(harness.lines()[0].starts_with("mates: 0"));
+        assert_eq!(harness.lines()[1].trim_end(), "");
+        assert!(!harness.component_mut().child_mut("right").unwrap().is_visible());
+        Ok(())
+    }
+
+    #[test]
+    fn hides_the_components_apart_from_deactivating_them() -> Result<(), MatetuiError> {
+        let mut bar = Bar::default().as_active();
+        bar.child_mut("left").unwrap().set_visible(false);
+        let right = bar.child_mut("right").unwrap();
+        right.set_active(false);
+        right.set_visible(true);
+        let mut harness = ComponentHarness::with_size(bar, 12, 2);
+        harness.message("drink");
+        harness.message("drink");
+        harness.draw()?;
+        assert_eq!(harness.lines()[0].trim_end(), "");
+        assert!(harness.lines()[1].starts_with("mates: 0"));
+
+        let bar = harness.component_mut();
+        bar.child_mut("left").unwrap().set_visible(true);
+        bar.child_mut("right").unwrap().set_visible(false);
+        harness.draw()?;
+        assert!(harness.lines()[0].starts_with("mates: 2"));
+        assert_eq!(harness.lines()[1].trim_end(), "");
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+use {
+    super::keyboard::{parse_key_sequence, KeyGlyphs},
+    crate::theme::Theme,
+    ratatui::style::{Color, Modifier, Style},
+    serde::{Deserialize, Serialize},
+    std::{fs, io, path::Path},
+};
+
+/// User overrides of the settings of an [App](crate::App), loaded from a TOML or JSON file with
+/// [App::with_config_file](crate::App::with_config_file). Every setting is optional, the ones
+/// left out keep the value set by the app:
+///
+/// ```toml
+/// tick_rate = 4
+/// frame_rate = 30
+/// mouse = true
+/// paste = false
+///
+/// [theme]
+/// name = "light"
+/// accent = "lightblue bold"
+/// selection = "black on #ffaf00"
+///
+/// [keybindings]
+/// "<q>" = "quit"
+/// "<ctrl-s>" = "app:save"
+/// ```
+///
+/// ```json
+/// { "frame_rate": 30, "mouse": true, "keybindings": { "<q>": "quit" } }
+/// ```
+///
+/// The keybindings are added to the ones of the app, replacing the bindings of the same key
+/// sequences. Their actions are parsed like the ones of [kb](crate::kb).
+///
+/// The theme starts from the [light](Theme::light) theme if it's named `light`, and from the
+/// [dark](Theme::dark) one otherwise, with the given styles replaced. Styles are written as a
+/// foreground color, `on` and a background color, and modifiers, all optional (e.g. `"red"`,
+/// `"on blue"` or `"white on #1e1e2e bold italic"`). The `border_type` is one of the ratatui
+/// [BorderType](ratatui::widgets::BorderType) names (e.g. `"Rounded"`), and the `key_glyphs` are
+/// `"unicode"` or `"ascii"`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_rate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_rate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mouse: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paste: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "theme_table")]
+    pub theme: Option<Theme>,
+    /// The key sequences (in the keybindings syntax) and the actions bound to them.
+    #[serde(skip_serializing_if = "Vec::is_empty", with = "keybindings_table")]
+    pub keybindings: Vec<(String, String)>,
+}
+
+impl Config {
+    /// Load a config file, as JSON if its extension is `.json` and as TOML otherwise.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        let result = match path.extension().is_some_and(|ext| ext == "json") {
+            true => Self::from_json(&content),
+            false => Self::from_toml(&content),
+        };
+        result.map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))
+    }
+
+    /// Parse a TOML config.
+    pub fn from_toml(s: &str) -> io::Result<Self> {
+        toml::from_str(s).map_err(|e| invalid(e.message()))
+    }
+
+    /// Parse a JSON config.
+    pub fn from_json(s: &str) -> io::Result<Self> {
+        serde_json::from_str(s).map_err(|e| invalid(&e.to_string()))
+    }
+
+    /// Write the config as TOML, in the format read by [Config::from_toml]. The settings left
+    /// out are not written.
+    pub fn to_toml(&self) -> String {
+        // numbers, booleans and tables of strings can always be written as TOML
+        toml::to_string(self).expect("the config is written as TOML")
+    }
+}
+
+/// The `[theme]` table of a config file: the styles of the theme, as strings (see [Config]).
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeTable {
+    name: Option<String>,
+    text: Option<String>,
+    hint: Option<String>,
+    accent: Option<String>,
+    error: Option<String>,
+    warning: Option<String>,
+    success: Option<String>,
+    border: Option<String>,
+    border_focused: Option<String>,
+    selection: Option<String>,
+    shadow: Option<String>,
+    border_type: Option<String>,
+    key_glyphs: Option<String>,
+}
+
+impl From<&Theme> for ThemeTable {
+    fn from(theme: &Theme) -> Self {
+        let style = |style| Some(style_to_string(style));
+        let glyphs = match theme.key_glyphs {
+            KeyGlyphs::Unicode => "unicode",
+            KeyGlyphs::Ascii => "ascii",
+        };
+        Self {
+            name: Some(theme.name.clone()),
+            text: style(theme.text),
+            hint: style(theme.hint),
+            accent: style(theme.accent),
+            error: style(theme.error),
+            warning: style(theme.warning),
+            success: style(theme.success),
+            border: style(theme.border),
+            border_focused: style(theme.border_focused),
+            selection: style(theme.selection),
+            shadow: style(theme.shadow),
+            border_type: Some(theme.border_type.to_string()),
+            key_glyphs: Some(glyphs.into()),
+        }
+    }
+}
+
+impl TryFrom<ThemeTable> for Theme {
+    type Error = String;
+
+    fn try_from(table: ThemeTable) -> Result<Self, String> {
+        let mut theme = match table.name.as_deref() {
+            Some("light") => Theme::light(),
+            _ => Theme::dark(),
+        };
+        if let Some(name) = table.name {
+            theme.name = name;
+        }
+
+        let styles = [
+            ("text", table.text, &mut theme.text),
+            ("hint", table.hint, &mut theme.hint),
+            ("accent", table.accent, &mut theme.accent),
+            ("error", table.error, &mut theme.error),
+            ("warning", table.warning, &mut theme.warning),
+            ("success", table.success, &mut theme.success),
+            ("border", table.border, &mut theme.border),
+            ("border_focused", table.border_focused, &mut theme.border_focused),
+            ("selection", table.selection, &mut theme.selection),
+            ("shadow", table.shadow, &mut theme.shadow),
+        ];
+        for (key, value, style) in styles {
+            if let Some(value) = value {
+                *style = parse_style(&value).map_err(|e| format!("`theme.{key}`: {e}"))?;
+            }
+        }
+
+        if let Some(value) = table.border_type {
+            theme.border_type =
+                value.parse().map_err(|_| format!("invalid border type `{value}`"))?;
+        }
+        if let Some(value) = table.key_glyphs {
+            theme.key_glyphs = match value.to_lowercase().as_str() {
+                "unicode" => KeyGlyphs::Unicode,
+                "ascii" => KeyGlyphs::Ascii,
+                _ => return Err(format!("invalid key glyphs `{value}`")),
+            };
+        }
+        Ok(theme)
+    }
+}
+
+/// Reads and writes the theme of a [Config] as a [ThemeTable].
+mod theme_table {
+    use {
+        super::{Theme, ThemeTable},
+        serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer},
+    };
+
+    pub(super) fn serialize<S: Serializer>(
+        theme: &Option<Theme>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        theme.as_ref().map(ThemeTable::from).serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Theme>, D::Error> {
+        let table = Option::<ThemeTable>::deserialize(deserializer)?;
+        table.map(Theme::try_from).transpose().map_err(D::Error::custom)
+    }
+}
+
+/// Reads and writes the keybindings of a [Config] as a table of key sequences and actions, in
+/// their order.
+mod keybindings_table {
+    use {
+        super::parse_key_sequence,
+        serde::{
+            de::{Error, MapAccess, Visitor},
+            Deserializer, Serializer,
+        },
+        std::fmt,
+    };
+
+    pub(super) fn serialize<S: Serializer>(
+        bindings: &[(String, String)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(bindings.iter().map(|(keys, action)| (keys, action)))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(String, String)>, D::Error> {
+        deserializer.deserialize_map(Bindings)
+    }
+
+    struct Bindings;
+
+    impl<'de> Visitor<'de> for Bindings {
+        type Value = Vec<(String, String)>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a table of key sequences and their actions")
+        }
+
+        fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+            let mut bindings = vec![];
+            while let Some((keys, action)) = map.next_entry::<String, String>()? {
+                if parse_key_sequence(&keys).is_err() {
+                    return Err(M::Error::custom(format!("invalid key sequence `{keys}`")));
+                }
+                bindings.push((keys, action));
+            }
+            Ok(bindings)
+        }
+    }
+}
+
+/// Parse a style like `"white on blue bold"`.
+fn parse_style(s: &str) -> Result<Style, String> {
+    let mut style = Style::default();
+    let mut words = s.split_whitespace();
+    while let Some(word) = words.next() {
+        if word == "on" {
+            let color = words.next().ok_or("expected a color after `on`")?;
+            style = style.bg(color.parse().map_err(|_| format!("invalid color `{color}`"))?);
+        } else if let Some(modifier) = Modifier::from_name(&word.to_uppercase()) {
+            style = style.add_modifier(modifier);
+        } else {
+            let color: Color = word.parse().map_err(|_| format!("invalid color `{word}`"))?;
+            style = style.fg(color);
+        }
+    }
+    Ok(style)
+}
+
+/// Write a style the way [parse_style] reads it.
+pub(crate) fn style_to_string(style: Style) -> String {
+    let colors = [style.fg.map(|fg| fg.to_string()), style.bg.map(|bg| format!("on {bg}"))];
+    let modifiers = style.add_modifier.iter_names().map(|(name, _)| name.to_string());
+    colors.into_iter().flatten().chain(modifiers).collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid config: {message}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crate::framework::keyboard::key_sequence_to_string, ratatui::widgets::BorderType,
+    };
+
+    /// The key sequence as the config export writes it.
+    fn written(raw: &str) -> String {
+        key_sequence_to_string(&parse_key_sequence(raw).unwrap())
+    }
+
+    #[test]
+    fn toml_and_json_configs_are_equivalent() {
+        let toml = Config::from_toml(
+            r#"
+            # my settings
+            frame_rate = 30
+            mouse = true # to click around
+
+            [keybindings]
+            "<q>" = "quit"
+            "<ctrl-s>" = 'app:save'
+            "#,
+        )
+        .unwrap();
+        let json = Config::from_json(
+            r#"{ "frame_rate": 30, "mouse": true,
+                 "keybindings": { "<q>": "quit", "<ctrl-s>": "app:save" } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(toml, json);
+        assert_eq!(toml.frame_rate, Some(30.0));
+        assert_eq!(toml.tick_rate, None);
+        assert_eq!(toml.keybindings[1], ("<ctrl-s>".into(), "app:save".into()));
+
+        assert!(Config::from_toml("fram_rate = 30").is_err());
+        assert!(Config::from_json(r#"{ "mouse": 1 }"#).is_err());
+    }
+
+    #[test]
+    fn toml_export_roundtrips() {
+        let theme = Theme {
+            accent: Style::default().fg(Color::Rgb(255, 175, 0)).add_modifier(Modifier::BOLD),
+            selection: Style::default().fg(Color::Black).bg(Color::Indexed(214)),
+            border_type: BorderType::Double,
+            key_glyphs: KeyGlyphs::Ascii,
+            ..Theme::light().with_name("mate \"light\"")
+        };
+        let config = Config {
+            tick_rate: Some(4.0),
+            frame_rate: Some(29.97),
+            mouse: Some(true),
+            paste: None,
+            theme: Some(theme),
+            keybindings: vec![
+                ("<q>".into(), "quit".into()),
+                ("<ctrl-s>".into(), "app:save".into()),
+                (written("<f1>"), "app:help".into()),
+                (written("<shift-a>"), "app:add".into()),
+            ],
+        };
+
+        let toml = config.to_toml();
+        assert!(toml.contains("selection = \"black on 214\""));
+        let loaded = Config::from_toml(&toml).unwrap();
+        assert_eq!(loaded, config);
+        assert_eq!(loaded.keybindings[2].0, "<f1>");
+        assert_eq!(loaded.keybindings[3].0, "<shift-a>");
+
+        assert_eq!(
+            parse_style("on darkgray dim").unwrap(),
+            Style::default().bg(Color::DarkGray).add_modifier(Modifier::DIM)
+        );
+        assert!(parse_style("white on").is_err());
+    }
+}
@@ -1,23 +1,130 @@
 use {
-    crossterm::event::{KeyEvent, MouseEvent},
-    std::fmt::{Display, Formatter, Result},
+    super::{external::External, message::Message},
+    crossterm::event::{KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
+    std::{
+        fmt::{Debug, Display, Formatter, Result},
+        str::FromStr,
+    },
     strum::EnumString,
 };
 
-#[derive(Debug, PartialEq, Eq, Clone, EnumString)]
+/// The custom actions of an app, carried by [Action::User].
+///
+/// Any enum implementing `Clone`, `Debug`, `PartialEq` and `FromStr` is a `UserAction`, so custom
+/// actions can be matched exhaustively instead of comparing strings:
+///
+/// ```ignore
+/// #[derive(Clone, Debug, PartialEq, strum::EnumString)]
+/// enum MyAction {
+///     DrinkMate,
+///     Refill(u8),
+/// }
+///
+/// let app = App::<MyAction>::default()
+///     .with_keybindings(kb!["<d>" => Action::User(MyAction::DrinkMate)]);
+/// ```
+///
+/// `FromStr` is used to parse the string based actions (e.g. `"drinkmate"` in keybindings or sent
+/// through [ComponentAccessors::send](crate::ComponentAccessors::send)) into custom actions.
+pub trait UserAction: Clone + Debug + PartialEq + FromStr + Send + 'static {}
+
+impl<T> UserAction for T where T: Clone + Debug + PartialEq + FromStr + Send + 'static {}
+
+/// The [UserAction] of the apps that don't define custom actions. It has no variants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoAction {}
+
+impl FromStr for NoAction {
+    type Err = ();
+
+    fn from_str(_: &str) -> std::result::Result<Self, Self::Err> {
+        Err(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, EnumString)]
 #[strum(ascii_case_insensitive)]
-pub enum Action {
+pub enum Action<A = NoAction> {
     Tick,
     Render,
     Resize(u16, u16),
-    // Suspend,
-    // Resume,
+    /// Changes the tick rate (ticks per second) of the running app.
+    SetTickRate(f64),
+    /// Changes the frame rate (frames per second) of the running app.
+    SetFrameRate(f64),
+    /// Switches the current theme to the one with the given name (see
+    /// [App::with_themes](crate::App::with_themes)), restyling the whole app on the next frame.
+    SetTheme(String),
+    Suspend,
+    /// Clears the terminal and redraws every component from scratch. Dispatched automatically on
+    /// resize and when the terminal regains focus.
+    ForceRedraw,
+    /// Moves the focus to the next focusable component (see [FocusManager](crate::FocusManager)).
+    FocusNext,
+    /// Moves the focus to the previous focusable component.
+    FocusPrev,
+    /// Moves the focus up one level in the component hierarchy, to the parent of the focused
+    /// component.
+    FocusParent,
+    /// Moves the focus back to the root component of the focused component.
+    FocusRoot,
+    /// Pushes the screen with the given name on top of the navigation stack (see
+    /// [App::with_screen](crate::App::with_screen)).
+    PushScreen(String),
+    /// Pops the current screen, going back to the previous one.
+    PopScreen,
+    /// Replaces the current screen with the screen with the given name.
+    ReplaceScreen(String),
+    /// Opens the modal with the given name on top of everything (see
+    /// [App::with_modal](crate::App::with_modal)).
+    OpenModal(String),
+    /// Closes the top modal.
+    CloseModal,
+    /// Rings the terminal bell, or runs the bell hook of the app (see
+    /// [App::on_bell](crate::App::on_bell)), e.g. to signal an error or a finished task.
+    Bell,
+    /// Hands the terminal over to an external program (e.g. `$EDITOR`) until it exits, then
+    /// restores the app and tells the components how it went (see [External]).
+    #[strum(disabled)]
+    RunExternal(External),
+    /// Quits the app, unless a component vetoes it (see
+    /// [Component::on_quit_requested](crate::Component::on_quit_requested)).
     Quit,
+    /// Quits the app without asking the components, e.g. once the user confirmed it.
+    ForceQuit,
     AppAction(String),
+    /// A message for the components with the given name only (see
+    /// [ComponentAccessors::send_to](crate::ComponentAccessors::send_to)): the target first,
+    /// then the message.
+    #[strum(disabled)]
+    SendTo(String, String),
+    /// A key no keybinding handled, in the keybindings syntax (e.g. `<ctrl-k>`), received by the
+    /// focused component only, through
+    /// [Component::receive_message](crate::Component::receive_message).
     Key(String),
+    /// Scrolls the focused component by the given amount (e.g. of lines), through
+    /// [Component::handle_scroll_event](crate::Component::handle_scroll_event), like the mouse
+    /// wheel does with the component under the pointer.
+    #[strum(disabled)]
+    Scroll(ScrollDirection, u16),
+    /// A custom action of the app (see [UserAction]).
+    #[strum(disabled)]
+    User(A),
+    /// A message with a typed payload, delivered to the components through
+    /// [Component::receive_typed_message](crate::Component::receive_typed_message).
+    #[strum(disabled)]
+    Message(Message),
+}
+
+impl<A: UserAction> Action<A> {
+    /// Parse a string into a built-in action (e.g. `"quit"`) or, if it isn't one, into a custom
+    /// [UserAction].
+    pub fn parse(s: &str) -> Option<Self> {
+        Action::from_str(s).ok().or_else(|| A::from_str(s).ok().map(Action::User))
+    }
 }
 
-impl Display for Action {
+impl<A: Debug> Display for Action<A> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         let enum_str = write!(f, "{:?}", self);
         enum_str
@@ -30,34 +137,170 @@ pub enum Event {
     Quit,
     Error,
     Tick,
+    /// A tick of one of the named tick streams (see [App::with_named_tick](crate::App::with_named_tick)).
+    NamedTick(String),
     Render,
     FocusGained,
     FocusLost,
     Paste(String),
     Key(KeyEvent),
+    /// The release of a key, only sent with the key releases enabled (see
+    /// [App::with_key_releases](crate::App::with_key_releases)).
+    KeyRelease(KeyEvent),
     Mouse(MouseEvent),
+    /// A click of a mouse button, following the press of the button (see [MouseClick]).
+    MouseClick(MouseClick),
+    /// A step of a drag of the mouse with a button pressed, following the mouse event of the step
+    /// (see [MouseDrag]).
+    MouseDrag(MouseDrag),
     Resize(u16, u16),
+    /// The process was continued (`SIGCONT`) after being stopped, e.g. by [Action::Suspend]. Unix
+    /// only.
+    Resume,
+}
+
+/// A click of a mouse button, counting the clicks in a row on the same cell, so components can
+/// tell double and triple clicks apart (e.g. to select a word or a line). It follows the
+/// [MouseEvent] of the press of the button, which started the click.
+///
+/// Clicks in a row are the ones less than the click interval of the Tui apart (see
+/// [App::with_click_interval](crate::App::with_click_interval)).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MouseClick {
+    pub button: MouseButton,
+    pub column: u16,
+    pub row: u16,
+    pub modifiers: KeyModifiers,
+    /// The number of clicks in a row, `2` for a double click, `3` for a triple click, and so on.
+    pub count: u8,
+}
+
+/// The phase of a [MouseDrag].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DragPhase {
+    /// The pointer moved for the first time since the button was pressed.
+    Start,
+    /// The pointer moved again.
+    Update,
+    /// The button was released.
+    End,
+}
+
+/// A drag of the mouse with a button pressed, from the first move after the press of the button
+/// to its release, so components can move things around (e.g. the divider of a split, or the end
+/// of a selection) without tracking the presses, moves and releases themselves. Releasing the
+/// button without moving is a click instead (see [MouseClick]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MouseDrag {
+    pub button: MouseButton,
+    pub phase: DragPhase,
+    /// The column the button was pressed at.
+    pub origin_column: u16,
+    /// The row the button was pressed at.
+    pub origin_row: u16,
+    pub column: u16,
+    pub row: u16,
+    /// The columns moved since the previous step of the drag, or since the press for the start.
+    pub delta_column: i32,
+    /// The rows moved since the previous step of the drag, or since the press for the start.
+    pub delta_row: i32,
+    pub modifiers: KeyModifiers,
+}
+
+/// The direction of a scroll, of the mouse wheel or of an [Action::Scroll] (see
+/// [Component::handle_scroll_event](crate::Component::handle_scroll_event)).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScrollDirection {
+    Up,
+    Down,
+    Left,
+    Right,
 }
 
-pub enum ActionKind {
+impl ScrollDirection {
+    /// The direction of a mouse wheel event, or none if the event isn't one.
+    pub fn from_mouse(kind: MouseEventKind) -> Option<Self> {
+        match kind {
+            MouseEventKind::ScrollUp => Some(ScrollDirection::Up),
+            MouseEventKind::ScrollDown => Some(ScrollDirection::Down),
+            MouseEventKind::ScrollLeft => Some(ScrollDirection::Left),
+            MouseEventKind::ScrollRight => Some(ScrollDirection::Right),
+            _ => None,
+        }
+    }
+}
+
+pub enum ActionKind<A = NoAction> {
     Stringified(String),
-    Full(Action),
+    Full(Action<A>),
+    /// An action with a description, shown by help screens (see [ActionKind::described]).
+    Described(Box<ActionKind<A>>, String),
 }
 
-impl From<&str> for ActionKind {
+impl<A> ActionKind<A> {
+    /// Describe the action for help screens, e.g. `"<ctrl-s>" => "app:save" => "save the file"` in
+    /// [kb](crate::kb).
+    pub fn described(self, description: impl Into<String>) -> Self {
+        ActionKind::Described(Box::new(self), description.into())
+    }
+
+    /// The description of the action, if any.
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            ActionKind::Described(_, description) => Some(description),
+            _ => None,
+        }
+    }
+}
+
+impl<A> From<&str> for ActionKind<A> {
     fn from(s: &str) -> Self {
         ActionKind::Stringified(s.to_string())
     }
 }
 
-impl From<String> for ActionKind {
+impl<A> From<String> for ActionKind<A> {
     fn from(s: String) -> Self {
         ActionKind::Stringified(s)
     }
 }
 
-impl From<Action> for ActionKind {
-    fn from(a: Action) -> Self {
+impl<A> From<Action<A>> for ActionKind<A> {
+    fn from(a: Action<A>) -> Self {
         ActionKind::Full(a)
     }
 }
+
+impl<A: UserAction> From<ActionKind<A>> for Action<A> {
+    /// Stringified actions are parsed into built-in or custom actions when possible (e.g.
+    /// `"quit"`), or wrapped into an [Action::AppAction] otherwise.
+    fn from(kind: ActionKind<A>) -> Self {
+        match kind {
+            ActionKind::Full(action) => action,
+            ActionKind::Stringified(cmd) => Action::parse(&cmd).unwrap_or(Action::AppAction(cmd)),
+            ActionKind::Described(kind, _) => Action::from(*kind),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, EnumString)]
+    #[strum(ascii_case_insensitive)]
+    enum MyAction {
+        DrinkMate,
+    }
+
+    #[test]
+    fn parse_built_in_and_user_actions() {
+        assert_eq!(Action::<MyAction>::parse("quit"), Some(Action::Quit));
+        assert_eq!(Action::<MyAction>::parse("bell"), Some(Action::Bell));
+        assert_eq!(Action::parse("drinkmate"), Some(Action::User(MyAction::DrinkMate)));
+        assert_eq!(Action::<MyAction>::parse("app:unknown"), None);
+
+        let kind: ActionKind<MyAction> = "app:unknown".into();
+        assert_eq!(Action::from(kind), Action::AppAction("app:unknown".into()));
+    }
+}
@@ -0,0 +1,126 @@
+use std::{path::PathBuf, process::Command};
+
+/// An external program run in the terminal of the app, e.g. the editor of the user (see
+/// [Action::RunExternal](crate::Action::RunExternal)). The app hands the terminal over while it
+/// runs: it leaves the alternate screen and the raw mode, waits for the program to exit, and then
+/// restores the terminal and redraws.
+///
+/// Once the program exits, the components get the message `external:<name>:exited:<code>` (the
+/// code being `signal` if it was killed by a signal), or `external:<name>:failed` if it couldn't
+/// be started. The name is the one of the program, unless set with [External::with_name].
+///
+/// ```ignore
+/// self.run_external(External::editor("notes.md").with_name("notes"));
+///
+/// fn receive_message(&mut self, message: String) {
+///     if message == "external:notes:exited:0" {
+///         self.reload();
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct External {
+    name: String,
+    program: String,
+    args: Vec<String>,
+    dir: Option<PathBuf>,
+}
+
+impl External {
+    /// Create a new [External] running the given program, looked up in the `PATH`.
+    pub fn new(program: impl Into<String>) -> Self {
+        let program = program.into();
+        let name = PathBuf::from(&program)
+            .file_stem()
+            .map_or_else(|| program.clone(), |stem| stem.to_string_lossy().into_owned());
+        Self {
+            name,
+            program,
+            args: Vec::new(),
+            dir: None,
+        }
+    }
+
+    /// Create a new [External] opening the file in the editor of the user: `$VISUAL`, or
+    /// `$EDITOR`, or else `vi` (`notepad` on windows). The variables may hold arguments, like
+    /// `code --wait`.
+    pub fn editor(path: impl Into<String>) -> Self {
+        let editor = ["VISUAL", "EDITOR"]
+            .into_iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|editor| !editor.trim().is_empty())
+            .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+
+        let mut words = editor.split_whitespace().map(str::to_string);
+        let program = words.next().unwrap_or_default();
+        Self::new(program).args(words).arg(path)
+    }
+
+    /// Set the name of the program in the message sent once it exits.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Add an argument to pass to the program.
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Add arguments to pass to the program.
+    pub fn args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the working directory of the program. Defaults to the one of the app.
+    pub fn current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// The name of the program, in the message sent once it exits.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Run the program, inheriting the stdio of the app, and wait for it to exit. Returns the
+    /// message telling how it went.
+    pub(crate) fn run(&self) -> String {
+        let mut command = Command::new(&self.program);
+        command.args(&self.args);
+        if let Some(dir) = &self.dir {
+            command.current_dir(dir);
+        }
+
+        match command.status() {
+            Ok(status) => match status.code() {
+                Some(code) => format!("external:{}:exited:{code}", self.name),
+                None => format!("external:{}:exited:signal", self.name),
+            },
+            Err(_) => format!("external:{}:failed", self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_how_the_program_exited() {
+        let editor = External::editor("notes.md");
+        assert_eq!(editor.args.last().map(String::as_str), Some("notes.md"));
+
+        let missing = External::new("/no/such/program").arg("-h");
+        assert_eq!(missing.name(), "program");
+        assert_eq!(missing.run(), "external:program:failed");
+
+        #[cfg(unix)]
+        assert_eq!(
+            External::new("sh").args(["-c", "exit 3"]).with_name("script").run(),
+            "external:script:exited:3"
+        );
+    }
+}
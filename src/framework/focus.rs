@@ -0,0 +1,319 @@
+use {
+    super::{
+        component::{Component, ComponentHandler},
+        events::UserAction,
+    },
+    ratatui::layout::Rect,
+};
+
+/// Identifies a component in the component tree of the [App](crate::App): the index of the root
+/// component it belongs to and the names of the children leading to it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FocusPath {
+    pub root: usize,
+    pub children: Vec<String>,
+}
+
+impl FocusPath {
+    /// Path of the root component at the given index.
+    pub fn root(root: usize) -> Self {
+        Self {
+            root,
+            children: vec![],
+        }
+    }
+
+    /// Path of the parent component, or none for root components.
+    pub fn parent(&self) -> Option<Self> {
+        let mut children = self.children.clone();
+        children.pop()?;
+        Some(Self {
+            root: self.root,
+            children,
+        })
+    }
+
+    /// Path of the child with the given name of this component.
+    pub fn child(&self, name: impl Into<String>) -> Self {
+        let mut children = self.children.clone();
+        children.push(name.into());
+        Self {
+            root: self.root,
+            children,
+        }
+    }
+}
+
+/// Keeps track of the focused component and moves the focus through the focusable components
+/// (those with a [tab index](Component::tab_index)).
+///
+/// The [App](crate::App) moves the focus on [Action::FocusNext](crate::Action::FocusNext) and
+/// [Action::FocusPrev](crate::Action::FocusPrev), which are usually bound to `<tab>` and
+/// `<backtab>`:
+///
+/// ```ignore
+/// let app = App::default().with_keybindings(kb![
+///     "<tab>" => Action::FocusNext,
+///     "<backtab>" => Action::FocusPrev
+/// ]);
+/// ```
+///
+/// To prevent users from getting stuck inside nested components, [Action::FocusParent] moves the
+/// focus up one level and [Action::FocusRoot] returns it to the root component. See
+/// [App::with_focus_bindings](crate::App::with_focus_bindings) and
+/// [App::with_panic_escape](crate::App::with_panic_escape).
+///
+/// [Action::FocusParent]: crate::Action::FocusParent
+/// [Action::FocusRoot]: crate::Action::FocusRoot
+#[derive(Clone, Debug, Default)]
+pub struct FocusManager {
+    focused: Option<FocusPath>,
+    /// when set, only the tree of the root component at this index can be focused (e.g. an open
+    /// modal)
+    scope: Option<usize>,
+}
+
+impl FocusManager {
+    /// Returns the path of the focused component, if any.
+    pub fn focused(&self) -> Option<&FocusPath> {
+        self.focused.as_ref()
+    }
+
+    /// The focused component, if any.
+    pub(crate) fn focused_component<'a, A: UserAction>(
+        &self,
+        handlers: &'a mut [ComponentHandler<A>],
+    ) -> Option<&'a mut dyn Component<A>> {
+        find(handlers, self.focused.as_ref()?)
+    }
+
+    /// The names of the focused component and of its ancestors, innermost first: both the name
+    /// of each component and the name it was given as a child, if any.
+    pub(crate) fn focused_names<A: UserAction>(
+        &self,
+        handlers: &mut [ComponentHandler<A>],
+    ) -> Vec<String> {
+        let mut names = vec![];
+        let mut path = self.focused.clone();
+        while let Some(p) = path {
+            if let Some(c) = find(handlers, &p) {
+                names.extend(p.children.last().cloned());
+                names.push(c.name());
+            }
+            path = p.parent();
+        }
+        names
+    }
+
+    /// Restrict the focus traversal to the tree of the root component at the given index.
+    pub(crate) fn set_scope(&mut self, scope: Option<usize>) {
+        self.scope = scope;
+    }
+
+    /// Move the focus to the next focusable component, wrapping around.
+    pub(crate) fn focus_next<A: UserAction>(&mut self, handlers: &mut [ComponentHandler<A>]) {
+        self.cycle(handlers, 1);
+    }
+
+    /// Move the focus to the previous focusable component, wrapping around.
+    pub(crate) fn focus_prev<A: UserAction>(&mut self, handlers: &mut [ComponentHandler<A>]) {
+        self.cycle(handlers, -1);
+    }
+
+    /// Move the focus to the parent of the focused component, if any.
+    pub(crate) fn focus_parent<A: UserAction>(&mut self, handlers: &mut [ComponentHandler<A>]) {
+        if let Some(parent) = self.focused.as_ref().and_then(FocusPath::parent) {
+            self.focus(handlers, Some(parent));
+        }
+    }
+
+    /// Move the focus to the root component of the focused component (the first root component if
+    /// nothing is focused).
+    pub(crate) fn focus_root<A: UserAction>(&mut self, handlers: &mut [ComponentHandler<A>]) {
+        let root = self.focused.as_ref().map_or(0, |f| f.root);
+        self.focus(handlers, Some(FocusPath::root(root)));
+    }
+
+    fn cycle<A: UserAction>(&mut self, handlers: &mut [ComponentHandler<A>], delta: isize) {
+        let order = self.traversal_order(handlers);
+        if order.is_empty() {
+            return;
+        }
+
+        let len = order.len() as isize;
+        let next = match self.focused.as_ref().and_then(|f| order.iter().position(|p| p == f)) {
+            Some(current) => (current as isize + delta).rem_euclid(len),
+            None if delta > 0 => 0,
+            None => len - 1,
+        };
+
+        self.focus(handlers, Some(order[next as usize].clone()));
+    }
+
+    /// Focus the component at `path` (or nothing), notifying the components whose focus changed.
+    pub(crate) fn focus<A: UserAction>(
+        &mut self,
+        handlers: &mut [ComponentHandler<A>],
+        path: Option<FocusPath>,
+    ) {
+        if self.focused == path {
+            return;
+        }
+
+        if let Some(c) = self.focused.take().and_then(|p| find(handlers, &p)) {
+            c.set_focused(false);
+        }
+
+        if let Some(c) = path.as_ref().and_then(|p| find(handlers, p)) {
+            c.set_focused(true);
+            self.focused = path;
+        }
+    }
+
+    /// The focusable components sorted by tab index, then by layout position (top to bottom, left
+    /// to right) and finally by path, so the order is stable.
+    pub(crate) fn traversal_order<A: UserAction>(
+        &self,
+        handlers: &mut [ComponentHandler<A>],
+    ) -> Vec<FocusPath> {
+        let mut focusable = vec![];
+        for (root, handler) in handlers.iter_mut().enumerate() {
+            if self.scope.is_some_and(|scope| scope != root) {
+                continue;
+            }
+            collect(handler.component_mut(), FocusPath::root(root), &mut focusable);
+        }
+
+        focusable.sort_by(|(pa, ia, aa), (pb, ib, ab)| {
+            let position = |area: &Option<Rect>| area.map_or((1, 0, 0), |a| (0, a.y, a.x));
+            ia.cmp(ib).then(position(aa).cmp(&position(ab))).then(pa.cmp(pb))
+        });
+
+        focusable.into_iter().map(|(path, ..)| path).collect()
+    }
+}
+
+/// Collect the active and visible focusable components of the tree, with their tab index and area.
+fn collect<A: UserAction>(
+    c: &mut dyn Component<A>,
+    path: FocusPath,
+    out: &mut Vec<(FocusPath, u16, Option<Rect>)>,
+) {
+    if !c.is_active() || !c.is_visible() {
+        return;
+    }
+
+    if let Some(index) = c.tab_index() {
+        out.push((path.clone(), index, c.area()));
+    }
+
+    if let Some(children) = c.get_children() {
+        for (name, child) in children.iter_mut() {
+            collect(child.as_mut(), path.child(name.clone()), out);
+        }
+    }
+}
+
+/// Find the component at `path`.
+fn find<'a, A: UserAction>(
+    handlers: &'a mut [ComponentHandler<A>],
+    path: &FocusPath,
+) -> Option<&'a mut dyn Component<A>> {
+    let mut c = handlers.get_mut(path.root)?.component_mut();
+    for name in &path.children {
+        c = c.child_mut(name)?.as_mut();
+    }
+    Some(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{component, ComponentAccessors, ComponentError, Frame},
+    };
+
+    component! {
+        struct Field {
+            index: Option<u16>
+        }
+    }
+
+    impl Field {
+        fn new(index: Option<u16>, x: u16, y: u16) -> Self {
+            let mut field = Field {
+                index,
+                ..Field::default()
+            };
+            field.set_area(Rect::new(x, y, 1, 1));
+            field.as_active()
+        }
+    }
+
+    impl Component for Field {
+        fn tab_index(&self) -> Option<u16> {
+            self.index
+        }
+
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Form {},
+        children => {
+            "name" => Field::new(Some(0), 0, 2),
+            "email" => Field::new(Some(0), 10, 0),
+            "submit" => Field::new(Some(1), 0, 0),
+            "label" => Field::new(None, 0, 1)
+        }
+    }
+
+    impl Component for Form {
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn traversal_order_by_tab_index_then_layout() {
+        let mut handlers = vec![ComponentHandler::for_(Box::new(Form::default().as_active()))];
+        let names = FocusManager::default()
+            .traversal_order(&mut handlers)
+            .into_iter()
+            .map(|p| p.children.join("/"))
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["email", "name", "submit"]);
+
+        let mut focus = FocusManager::default();
+        focus.focus_prev(&mut handlers);
+        assert_eq!(focus.focused(), Some(&FocusPath::root(0).child("submit")));
+        focus.focus_next(&mut handlers);
+        assert_eq!(focus.focused(), Some(&FocusPath::root(0).child("email")));
+
+        let form = handlers[0].component_mut();
+        assert!(form.child("email").unwrap().is_focused());
+        assert!(!form.child("submit").unwrap().is_focused());
+    }
+
+    #[test]
+    fn focus_escapes_to_parent_and_root() {
+        let mut handlers = vec![ComponentHandler::for_(Box::new(Form::default().as_active()))];
+        let mut focus = FocusManager::default();
+
+        focus.focus_next(&mut handlers);
+        focus.focus_parent(&mut handlers);
+        assert_eq!(focus.focused(), Some(&FocusPath::root(0)));
+        assert!(handlers[0].component_mut().is_focused());
+
+        // already at the top
+        focus.focus_parent(&mut handlers);
+        assert_eq!(focus.focused(), Some(&FocusPath::root(0)));
+
+        focus.focus_next(&mut handlers);
+        focus.focus_root(&mut handlers);
+        assert_eq!(focus.focused(), Some(&FocusPath::root(0)));
+        assert!(!handlers[0].component_mut().child("email").unwrap().is_focused());
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter, Result},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The kind of entry recorded in a [History].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryKind {
+    Event,
+    Action,
+}
+
+impl Display for HistoryKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            HistoryKind::Event => write!(f, "event"),
+            HistoryKind::Action => write!(f, "action"),
+        }
+    }
+}
+
+/// A single entry of the [History].
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Time elapsed since the history was created.
+    pub elapsed: Duration,
+    pub kind: HistoryKind,
+    pub value: String,
+}
+
+#[derive(Debug)]
+struct HistoryInner {
+    started: Instant,
+    capacity: usize,
+    entries: VecDeque<HistoryEntry>,
+}
+
+/// A bounded record of the [Event](crate::Event)s and [Action](crate::Action)s processed by the
+/// [App](crate::App) loop.
+///
+/// The history is a cheap to clone shared handle: give a clone to the app using
+/// [App::with_history](crate::App::with_history) and another one to whatever wants to read it
+/// (e.g. the [EventTrace](crate::components::trace::EventTrace) dev component). Once the capacity
+/// is reached, the oldest entries are discarded.
+#[derive(Clone, Debug)]
+pub struct History(Arc<Mutex<HistoryInner>>);
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(500)
+    }
+}
+
+impl History {
+    /// Create a new history keeping, at most, `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self(Arc::new(Mutex::new(HistoryInner {
+            started: Instant::now(),
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        })))
+    }
+
+    /// Record a new entry.
+    pub fn record(&self, kind: HistoryKind, value: impl Into<String>) {
+        let mut inner = self.0.lock().unwrap();
+        if inner.capacity == 0 {
+            return;
+        }
+
+        if inner.entries.len() == inner.capacity {
+            inner.entries.pop_front();
+        }
+
+        let elapsed = inner.started.elapsed();
+        inner.entries.push_back(HistoryEntry {
+            elapsed,
+            kind,
+            value: value.into(),
+        });
+    }
+
+    /// Returns a snapshot of the recorded entries, from oldest to newest.
+    pub fn entries(&self) -> Vec<HistoryEntry> {
+        self.0.lock().unwrap().entries.iter().cloned().collect()
+    }
+
+    /// Returns the number of recorded entries.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().entries.len()
+    }
+
+    /// Returns `true` if no entries were recorded (or they were cleared).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all the recorded entries.
+    pub fn clear(&self) {
+        self.0.lock().unwrap().entries.clear();
+    }
+}
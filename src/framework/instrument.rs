@@ -0,0 +1,158 @@
+use {crate::Event, std::fmt::Display, tracing::span::EnteredSpan};
+
+/// The target of the spans of the dispatch pipeline, to filter them in the subscriber.
+///
+/// The steps of the dispatch pipeline of the [App](crate::App) (handling an event or an action,
+/// running the handlers of a component, drawing a frame) are `tracing` spans at the trace level,
+/// entered until they are dropped, so the subscriber times them and nests the steps they lead to
+/// (e.g. the components handling an event, and their children). The spans are named after the
+/// step (`event`, `action`, `component` or `render`), with the fields:
+/// - `name`: the kind of the event, the action or the name of the component
+/// - `cause`: for the `component` steps, the event kind or action the component handled
+pub(crate) const TARGET: &str = "matetui::dispatch";
+
+/// Enter the step of handling an event.
+pub(crate) fn event(e: &Event) -> EnteredSpan {
+    tracing::trace_span!(target: TARGET, "event", name = event_kind(e)).entered()
+}
+
+/// Enter the step of handling an action.
+pub(crate) fn action(action: &impl Display) -> EnteredSpan {
+    tracing::trace_span!(target: TARGET, "action", name = %action).entered()
+}
+
+/// Enter the step of drawing a frame.
+pub(crate) fn render() -> EnteredSpan {
+    tracing::trace_span!(target: TARGET, "render", name = "frame").entered()
+}
+
+/// Enter the step of a component handling an event or an action. The name of the component is
+/// only built if the span is enabled.
+pub(crate) fn component(name: impl FnOnce() -> String, cause: impl Display) -> EnteredSpan {
+    tracing::trace_span!(target: TARGET, "component", name = %name(), cause = %cause).entered()
+}
+
+/// The kind of an event, without its data.
+pub(crate) fn event_kind(e: &Event) -> &'static str {
+    match e {
+        Event::Init => "init",
+        Event::Quit => "quit",
+        Event::Error => "error",
+        Event::Tick => "tick",
+        Event::NamedTick(_) => "named-tick",
+        Event::Render => "render",
+        Event::FocusGained => "focus-gained",
+        Event::FocusLost => "focus-lost",
+        Event::Paste(_) => "paste",
+        Event::Key(_) => "key",
+        Event::KeyRelease(_) => "key-release",
+        Event::Mouse(_) => "mouse",
+        Event::MouseClick(_) => "mouse-click",
+        Event::MouseDrag(_) => "mouse-drag",
+        Event::Resize(..) => "resize",
+        Event::Resume => "resume",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{
+            fmt::Debug,
+            sync::{Arc, Mutex},
+        },
+        tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id, Record},
+            subscriber::with_default,
+            Metadata, Subscriber,
+        },
+    };
+
+    /// Keeps the spans of the pipeline, as `step name (cause)`, and whether they were entered.
+    #[derive(Clone, Default)]
+    struct Capture {
+        spans: Arc<Mutex<Vec<String>>>,
+        entered: Arc<Mutex<Vec<u64>>>,
+        interested: bool,
+    }
+
+    #[derive(Default)]
+    struct Fields(Vec<String>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            match field.name() {
+                "cause" => self.0.push(format!("({value:?})")),
+                _ => self.0.push(format!("{value:?}")),
+            }
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            self.interested && metadata.target() == TARGET
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut fields = Fields::default();
+            span.record(&mut fields);
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(format!("{} {}", span.metadata().name(), fields.0.join(" ")));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &tracing::Event<'_>) {}
+
+        fn enter(&self, span: &Id) {
+            self.entered.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn traces_the_steps_with_their_fields() {
+        let capture = Capture {
+            interested: true,
+            ..Capture::default()
+        };
+        with_default(capture.clone(), || {
+            drop(event(&Event::Resize(80, 24)));
+            drop(action(&"Quit"));
+            drop(render());
+            drop(component(|| "Probe".into(), event_kind(&Event::Paste("mate".into()))));
+        });
+
+        assert_eq!(
+            *capture.spans.lock().unwrap(),
+            ["event \"resize\"", "action Quit", "render \"frame\"", "component Probe (paste)"]
+        );
+        assert_eq!(*capture.entered.lock().unwrap(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn builds_the_names_only_for_interested_subscribers() {
+        let built = Arc::new(Mutex::new(0));
+        let name = || {
+            *built.lock().unwrap() += 1;
+            "Probe".to_string()
+        };
+
+        with_default(Capture::default(), || drop(component(name, "paste")));
+        assert_eq!(*built.lock().unwrap(), 0);
+
+        let capture = Capture {
+            interested: true,
+            ..Capture::default()
+        };
+        with_default(capture.clone(), || drop(component(name, "paste")));
+        assert_eq!(*built.lock().unwrap(), 1);
+        assert_eq!(*capture.spans.lock().unwrap(), ["component Probe (paste)"]);
+    }
+}
@@ -1,10 +1,15 @@
 use {
-    super::events::{Action, ActionKind},
+    super::events::{Action, ActionKind, NoAction, UserAction},
+    crate::{theme::Theme, utils::text},
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
-    std::{collections::HashMap, str::FromStr},
+    ratatui::text::Span,
+    std::{
+        collections::HashMap,
+        fmt::{self, Display, Formatter},
+    },
 };
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 /// A struct that holds key bindings
 ///
 /// The key bindings are stored in a hashmap where the key is a vector of
@@ -14,36 +19,305 @@ use {
 /// keys and key sequences (see
 /// [`parse_key_sequence`](crate::tui::utils::keyboard::parse_key_sequence) and
 /// [`Kb`](crate::tui::Kb) for more information).
-pub struct KeyBindings(pub HashMap<Vec<KeyEvent>, Action>);
-
-impl KeyBindings {
-    pub fn new<const N: usize>(raw: [(&str, impl Into<ActionKind>); N]) -> Self {
-        let keybindings = raw
-            .into_iter()
-            .map(|(key_str, cmd)| {
-                let cmd: ActionKind = cmd.into();
-
-                match cmd {
-                    ActionKind::Full(action) => (parse_key_sequence(key_str).unwrap(), action),
-                    ActionKind::Stringified(cmd) => {
-                        let action = Action::from_str(&cmd);
-                        match action {
-                            Ok(action) => (parse_key_sequence(key_str).unwrap(), action),
-                            Err(_) => {
-                                (parse_key_sequence(key_str).unwrap(), Action::AppAction(cmd))
-                            }
-                        }
-                    }
+///
+/// The bindings are iterated sorted by key sequence (see [`KeyBindings::iter`]), so help screens
+/// and config exporters list them in a stable order. Bindings can have a description for help
+/// screens (see [`KeyBindings::description`]).
+pub struct KeyBindings<A = NoAction>(
+    pub(crate) HashMap<Vec<KeyEvent>, Action<A>>,
+    pub(crate) HashMap<Vec<KeyEvent>, String>,
+);
+
+impl<A> Default for KeyBindings<A> {
+    fn default() -> Self {
+        KeyBindings(HashMap::new(), HashMap::new())
+    }
+}
+
+impl<A: UserAction> KeyBindings<A> {
+    pub fn new<const N: usize>(raw: [(&str, impl Into<ActionKind<A>>); N]) -> Self {
+        let mut keybindings = KeyBindings::default();
+        for (key_str, cmd) in raw {
+            keybindings.bind(parse_key_sequence(key_str).unwrap(), cmd.into());
+        }
+        keybindings
+    }
+
+    /// Like [KeyBindings::new], but also returns the conflicts between the keybindings (see
+    /// [KeyBindingConflict]) instead of silently keeping the last of the duplicated ones, and skips
+    /// the key sequences that can't be parsed instead of panicking.
+    pub fn checked<const N: usize>(
+        raw: [(&str, impl Into<ActionKind<A>>); N],
+    ) -> (Self, Vec<KeyBindingConflict>) {
+        let mut keybindings = KeyBindings::default();
+        let mut conflicts = vec![];
+        for (key_str, cmd) in raw {
+            let keys = match parse_key_sequence(key_str) {
+                Ok(keys) => keys,
+                Err(err) => {
+                    let (keys, error) = (key_str.to_string(), err.to_string());
+                    conflicts.push(KeyBindingConflict::Invalid { keys, error });
+                    continue;
                 }
+            };
+            if keybindings.0.contains_key(&keys) {
+                let keys = key_sequence_to_string(&keys);
+                conflicts.push(KeyBindingConflict::Duplicate { keys });
+            }
+            keybindings.bind(keys, cmd.into());
+        }
+
+        conflicts.extend(keybindings.conflicts());
+        (keybindings, conflicts)
+    }
+
+    /// Returns the key sequences that never fire because they start with a shorter bound sequence
+    /// (e.g. `<g><g>` when `<g>` is bound too), sorted.
+    pub fn conflicts(&self) -> Vec<KeyBindingConflict> {
+        let mut conflicts = self
+            .0
+            .keys()
+            .flat_map(|keys| {
+                (1..keys.len()).filter(|len| self.0.contains_key(&keys[..*len])).map(|len| {
+                    KeyBindingConflict::Shadowed {
+                        keys: key_sequence_to_string(keys),
+                        by: key_sequence_to_string(&keys[..len]),
+                    }
+                })
             })
-            .collect();
+            .collect::<Vec<_>>();
+        conflicts.sort();
+        conflicts
+    }
+
+    /// Returns the standard keybindings that every app usually wants:
+    ///
+    /// - `<ctrl-c>` requests the app to quit
+    /// - `<ctrl-z>` suspends the app (unix only)
+    /// - `<ctrl-l>` forces a full redraw of the screen
+    pub fn standard() -> Self {
+        #[cfg(unix)]
+        let kb = KeyBindings::new(crate::kb![
+            "<ctrl-c>" => Action::Quit => "quit",
+            "<ctrl-z>" => Action::Suspend => "suspend",
+            "<ctrl-l>" => Action::ForceRedraw => "redraw the screen"
+        ]);
 
-        KeyBindings(keybindings)
+        #[cfg(not(unix))]
+        let kb = KeyBindings::new(crate::kb![
+            "<ctrl-c>" => Action::Quit => "quit",
+            "<ctrl-l>" => Action::ForceRedraw => "redraw the screen"
+        ]);
+
+        kb
+    }
+
+    /// Returns the focus navigation keybindings:
+    ///
+    /// - `<tab>` moves the focus to the next focusable component
+    /// - `<backtab>` moves the focus to the previous focusable component
+    /// - `<esc>` moves the focus up one level, to the parent of the focused component
+    pub fn focus() -> Self {
+        KeyBindings::new(crate::kb![
+            "<tab>" => Action::FocusNext => "focus the next component",
+            "<backtab>" => Action::FocusPrev => "focus the previous component",
+            "<esc>" => Action::FocusParent => "focus the parent component"
+        ])
+    }
+
+    /// Merges `defaults` into the keybindings. Key sequences that are already bound take
+    /// precedence, so the defaults never override a user defined binding.
+    pub fn merge_under(&mut self, defaults: KeyBindings<A>) {
+        for (keys, action) in defaults.0 {
+            if self.0.contains_key(&keys) {
+                continue;
+            }
+            if let Some(description) = defaults.1.get(&keys) {
+                self.1.insert(keys.clone(), description.clone());
+            }
+            self.0.insert(keys, action);
+        }
     }
 
-    pub fn get(&self, key_events: &[KeyEvent]) -> Option<&Action> {
+    fn bind(&mut self, keys: Vec<KeyEvent>, cmd: ActionKind<A>) {
+        match cmd.description() {
+            Some(description) => self.1.insert(keys.clone(), description.to_string()),
+            None => self.1.remove(&keys),
+        };
+        self.0.insert(keys, Action::from(cmd));
+    }
+
+    pub fn get(&self, key_events: &[KeyEvent]) -> Option<&Action<A>> {
         self.0.get(key_events)
     }
+
+    /// Returns `true` if the keys start a longer bound key sequence, so the next keys might
+    /// complete it.
+    pub fn is_prefix(&self, key_events: &[KeyEvent]) -> bool {
+        self.0.keys().any(|keys| keys.len() > key_events.len() && keys.starts_with(key_events))
+    }
+
+    /// Bind a key sequence to an action, returning the action it was bound to, if any. The
+    /// description of the previous binding is dropped.
+    pub fn insert(&mut self, key_events: Vec<KeyEvent>, action: Action<A>) -> Option<Action<A>> {
+        self.1.remove(&key_events);
+        self.0.insert(key_events, action)
+    }
+
+    /// Unbind a key sequence, returning the action it was bound to, if any.
+    pub fn remove(&mut self, key_events: &[KeyEvent]) -> Option<Action<A>> {
+        self.1.remove(key_events);
+        self.0.remove(key_events)
+    }
+
+    /// The description of the binding of a key sequence, if any (see [kb](crate::kb)).
+    pub fn description(&self, key_events: &[KeyEvent]) -> Option<&str> {
+        self.1.get(key_events).map(String::as_str)
+    }
+
+    /// Describe the binding of a key sequence, for help screens. Does nothing if the key sequence
+    /// isn't bound.
+    pub fn describe(&mut self, key_events: &[KeyEvent], description: impl Into<String>) {
+        if self.0.contains_key(key_events) {
+            self.1.insert(key_events.to_vec(), description.into());
+        }
+    }
+
+    /// The number of bound key sequences.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no key sequence is bound.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the key sequences and their actions, sorted by key sequence (in the
+    /// keybindings syntax, e.g. `<ctrl-c>`).
+    pub fn iter(&self) -> impl Iterator<Item = (&[KeyEvent], &Action<A>)> {
+        let mut bindings = self.0.iter().collect::<Vec<_>>();
+        bindings.sort_by_cached_key(|(keys, _)| key_sequence_to_string(keys));
+        bindings.into_iter().map(|(keys, action)| (keys.as_slice(), action))
+    }
+
+    /// The key sequences bound to the given action, sorted, e.g. to show them in a help screen.
+    pub fn bindings_for_action(&self, action: &Action<A>) -> Vec<&[KeyEvent]> {
+        self.iter().filter(|(_, a)| *a == action).map(|(keys, _)| keys).collect()
+    }
+
+    /// The bindings as listed by help screens, sorted by key sequence, in the given scope (`None`
+    /// for the global keybindings).
+    pub fn help(&self, scope: Option<&str>) -> Vec<KeyBindingHelp> {
+        self.iter()
+            .map(|(keys, action)| KeyBindingHelp {
+                scope: scope.map(str::to_string),
+                keys: keys.to_vec(),
+                action: action_to_config(action).unwrap_or_else(|| action.to_string()),
+                description: self.description(keys).map(str::to_string),
+            })
+            .collect()
+    }
+}
+
+impl<'a, A: UserAction> IntoIterator for &'a KeyBindings<A> {
+    type Item = (&'a [KeyEvent], &'a Action<A>);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+/// A keybinding, as listed by help screens (see [App::keybindings_help](crate::App::keybindings_help)).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBindingHelp {
+    /// The scope of the keybinding (see [ScopedKeyBindings]), `None` for the global ones.
+    pub scope: Option<String>,
+    /// The bound key sequence.
+    pub keys: Vec<KeyEvent>,
+    /// The action, in the keybindings syntax when it can be written as a string (e.g. `quit` or
+    /// `app:save`).
+    pub action: String,
+    /// The description of the binding, if any (see [kb](crate::kb)).
+    pub description: Option<String>,
+}
+
+/// A problem found in a set of keybindings (see [KeyBindings::checked]). The key sequences are
+/// written in the keybindings syntax, e.g. `<ctrl-x><ctrl-s>`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeyBindingConflict {
+    /// The key sequence is bound more than once, only the last binding is kept.
+    Duplicate { keys: String },
+    /// The key sequence never fires, as the shorter sequence it starts with fires first.
+    Shadowed { keys: String, by: String },
+    /// The key sequence can't be parsed, so it isn't bound. The keys are written as given.
+    Invalid { keys: String, error: String },
+}
+
+impl Display for KeyBindingConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyBindingConflict::Duplicate { keys } => write!(f, "{keys} is bound more than once"),
+            KeyBindingConflict::Shadowed { keys, by } => write!(f, "{keys} is shadowed by {by}"),
+            KeyBindingConflict::Invalid { keys, error } => write!(f, "{keys} is invalid: {error}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Keybindings grouped by scope: the bindings of a scope only fire while the component it names
+/// is focused (or one of its children is), usually built with [`kb_scoped`](crate::kb_scoped).
+///
+/// A scope names a component either by its [name](crate::ComponentAccessors::name) or by the
+/// name it was given as a child of its parent. When nothing is focused, the scopes of the active
+/// components apply.
+pub struct ScopedKeyBindings<A = NoAction>(pub(crate) HashMap<String, KeyBindings<A>>);
+
+impl<A> Default for ScopedKeyBindings<A> {
+    fn default() -> Self {
+        ScopedKeyBindings(HashMap::new())
+    }
+}
+
+impl<A: UserAction> ScopedKeyBindings<A> {
+    /// Add the keybindings of a scope, on top of the ones it already had.
+    pub fn insert(&mut self, scope: impl Into<String>, keybindings: KeyBindings<A>) {
+        let scoped = self.0.entry(scope.into()).or_default();
+        for keys in keybindings.0.keys() {
+            scoped.1.remove(keys);
+        }
+        scoped.0.extend(keybindings.0);
+        scoped.1.extend(keybindings.1);
+    }
+
+    /// Returns the action bound to the key sequence in the first of the given scopes that binds
+    /// it.
+    pub fn get(&self, scopes: &[String], key_events: &[KeyEvent]) -> Option<&Action<A>> {
+        scopes.iter().find_map(|scope| self.0.get(scope)?.get(key_events))
+    }
+
+    /// Returns `true` if the keys start a longer key sequence bound in one of the given scopes.
+    pub fn is_prefix(&self, scopes: &[String], key_events: &[KeyEvent]) -> bool {
+        scopes.iter().any(|scope| self.0.get(scope).is_some_and(|kb| kb.is_prefix(key_events)))
+    }
+
+    /// The keybindings of the given scope, if any.
+    pub fn scope(&self, scope: &str) -> Option<&KeyBindings<A>> {
+        self.0.get(scope)
+    }
+
+    /// Iterate over the scopes and their keybindings, sorted by scope.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &KeyBindings<A>)> {
+        let mut scopes = self.0.iter().collect::<Vec<_>>();
+        scopes.sort_by_key(|(scope, _)| scope.as_str());
+        scopes.into_iter().map(|(scope, kb)| (scope.as_str(), kb))
+    }
+
+    /// Returns `true` if no scope has keybindings.
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(|kb| kb.0.is_empty())
+    }
 }
 
 /// `@internal`
@@ -136,7 +410,8 @@ fn parse_key_code_with_modifiers(
     Ok(KeyEvent::new(c, modifiers))
 }
 
-/// Converts a [`KeyEvent`] to a string representation
+/// Converts a [`KeyEvent`] to a string representation, in the keybindings syntax (see
+/// [parse_key_sequence]), e.g. `ctrl-c`, `shift-a` or `f1`
 pub fn key_event_to_string(key_event: &KeyEvent) -> String {
     let char;
     let key_code = match key_event.code {
@@ -155,12 +430,13 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
         KeyCode::Delete => "delete",
         KeyCode::Insert => "insert",
         KeyCode::F(c) => {
-            char = format!("f({c})");
+            char = format!("f{c}");
             &char
         }
         KeyCode::Char(' ') => "space",
+        // the shifted letters are written lowercase, as the keybindings syntax ignores the case
         KeyCode::Char(c) => {
-            char = c.to_string();
+            char = c.to_ascii_lowercase().to_string();
             &char
         }
         KeyCode::Esc => "esc",
@@ -182,7 +458,8 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
         modifiers.push("ctrl");
     }
 
-    if key_event.modifiers.intersects(KeyModifiers::SHIFT) {
+    let uppercase = matches!(key_event.code, KeyCode::Char(c) if c.is_ascii_uppercase());
+    if key_event.modifiers.intersects(KeyModifiers::SHIFT) || uppercase {
         modifiers.push("shift");
     }
 
@@ -190,15 +467,6 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
         modifiers.push("alt");
     }
 
-    // if the modifiers is "shift" and the key code is a letter, we just return the letter
-    // otherwise we return the modifiers joined by a dash and the key code
-    if modifiers.len() == 1
-        && key_code.chars().count() == 1
-        && key_code.chars().all(char::is_alphabetic)
-    {
-        return key_code.to_string();
-    }
-
     let mut key = modifiers.join("-");
 
     if !key.is_empty() {
@@ -210,6 +478,115 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
     key
 }
 
+/// How keys are shown to the user (see [key_event_to_display]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum KeyGlyphs {
+    /// Compact symbols, e.g. `⌃C` or `⇧Tab`.
+    #[default]
+    Unicode,
+    /// Plain text, e.g. `Ctrl+C` or `Shift+Tab`, for terminals or fonts without the symbols.
+    Ascii,
+}
+
+/// Converts a [`KeyEvent`] to a human readable representation, meant to be shown to the user (e.g.
+/// in a help bar), unlike [key_event_to_string].
+pub fn key_event_to_display(key_event: &KeyEvent, glyphs: KeyGlyphs) -> String {
+    let unicode = glyphs == KeyGlyphs::Unicode;
+    let mut modifiers = key_event.modifiers;
+
+    let key = match key_event.code {
+        KeyCode::BackTab => {
+            // backtab is shift-tab for the user
+            modifiers.insert(KeyModifiers::SHIFT);
+            "Tab".to_string()
+        }
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+            c.to_uppercase().to_string()
+        }
+        KeyCode::Char(c) => {
+            // the shift is already part of the character
+            modifiers.remove(KeyModifiers::SHIFT);
+            c.to_string()
+        }
+        KeyCode::F(n) => format!("F{n}"),
+        code => {
+            let (glyph, text) = match code {
+                KeyCode::Backspace => ("⌫", "Backspace"),
+                KeyCode::Enter => ("↵", "Enter"),
+                KeyCode::Left => ("←", "Left"),
+                KeyCode::Right => ("→", "Right"),
+                KeyCode::Up => ("↑", "Up"),
+                KeyCode::Down => ("↓", "Down"),
+                KeyCode::Home => ("Home", "Home"),
+                KeyCode::End => ("End", "End"),
+                KeyCode::PageUp => ("PgUp", "PgUp"),
+                KeyCode::PageDown => ("PgDn", "PgDn"),
+                KeyCode::Tab => ("Tab", "Tab"),
+                KeyCode::Delete => ("⌦", "Del"),
+                KeyCode::Insert => ("Ins", "Ins"),
+                KeyCode::Esc => ("Esc", "Esc"),
+                _ => ("", ""),
+            };
+            if unicode { glyph } else { text }.to_string()
+        }
+    };
+
+    let mut display = String::new();
+    for (modifier, glyph, text) in [
+        (KeyModifiers::CONTROL, "⌃", "Ctrl+"),
+        (KeyModifiers::ALT, "⌥", "Alt+"),
+        (KeyModifiers::SHIFT, "⇧", "Shift+"),
+    ] {
+        if modifiers.intersects(modifier) {
+            display.push_str(if unicode { glyph } else { text });
+        }
+    }
+
+    display.push_str(&key);
+    display
+}
+
+/// Converts a sequence of [`KeyEvent`]s to a human readable representation, separating the keys
+/// with spaces (see [key_event_to_display]).
+pub fn key_sequence_to_display(keys: &[KeyEvent], glyphs: KeyGlyphs) -> String {
+    keys.iter().map(|key| key_event_to_display(key, glyphs)).collect::<Vec<_>>().join(" ")
+}
+
+/// A "chip" showing the given key sequence, styled and displayed (see [KeyGlyphs]) through the
+/// current [Theme](crate::theme::Theme). The chip is truncated to fit in `max_width` columns,
+/// padding included, so its width can be measured with
+/// [text::width](crate::utils::text::width) before rendering it.
+pub fn binding_chip(keys: &[KeyEvent], max_width: usize) -> Span<'static> {
+    let (glyphs, style) = Theme::read(|t| (t.key_glyphs, t.selection));
+    let keys = text::truncate(&key_sequence_to_display(keys, glyphs), max_width.saturating_sub(2));
+
+    if keys.is_empty() {
+        return Span::default();
+    }
+
+    Span::styled(format!(" {keys} "), style)
+}
+
+/// Converts a key sequence back into the keybindings syntax, e.g. `<ctrl-x><ctrl-s>`
+pub(crate) fn key_sequence_to_string(keys: &[KeyEvent]) -> String {
+    keys.iter().map(|key| format!("<{}>", key_event_to_string(key))).collect()
+}
+
+/// The string an action is written as in a keybinding (e.g. in a config file), if it has one: the
+/// message of an [Action::AppAction], or the name of any other action without data (e.g. `quit`).
+/// The actions carrying data (e.g. [Action::PushScreen]) can't be parsed back from a string, so
+/// they have none.
+pub(crate) fn action_to_config<A: UserAction>(action: &Action<A>) -> Option<String> {
+    let name = match action {
+        Action::AppAction(message) => message.clone(),
+        Action::User(action) => format!("{action:?}"),
+        action => format!("{action:?}").to_lowercase(),
+    };
+    let parsed = Action::<A>::from(ActionKind::from(name.as_str()));
+    (parsed == *action).then_some(name)
+}
+
 /// Parses a string into a vector of [`KeyEvent`]
 pub fn parse_key_sequence(raw: &str) -> Result<Vec<KeyEvent>, std::io::Error> {
     if raw.chars().filter(|c| *c == '>').count() != raw.chars().filter(|c| *c == '<').count() {
@@ -237,3 +614,107 @@ pub fn parse_key_sequence(raw: &str) -> Result<Vec<KeyEvent>, std::io::Error> {
 
     sequences.into_iter().map(parse_key_event).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_displayed_in_both_glyph_modes() {
+        let display =
+            |raw: &str, glyphs| key_sequence_to_display(&parse_key_sequence(raw).unwrap(), glyphs);
+
+        assert_eq!(display("<ctrl-c>", KeyGlyphs::Unicode), "⌃C");
+        assert_eq!(display("<ctrl-c>", KeyGlyphs::Ascii), "Ctrl+C");
+        assert_eq!(display("<backtab>", KeyGlyphs::Unicode), "⇧Tab");
+        assert_eq!(display("<alt-enter>", KeyGlyphs::Ascii), "Alt+Enter");
+        assert_eq!(display("<g><g>", KeyGlyphs::Unicode), "g g");
+
+        let chip = binding_chip(&parse_key_sequence("<ctrl-shift-left>").unwrap(), 4);
+        assert_eq!(chip.content, " ⌃… ");
+        assert_eq!(text::width(&chip.content), 4);
+        assert_eq!(key_event_to_string(&parse_key_sequence("<ctrl-c>").unwrap()[0]), "ctrl-c");
+    }
+
+    #[test]
+    fn key_sequences_are_written_back_in_the_keybindings_syntax() {
+        for raw in ["<f1>", "<f12>", "<shift-a>", "<ctrl-shift-a>", "<alt-x>", "<g><shift-g>"] {
+            let keys = parse_key_sequence(raw).unwrap();
+            assert_eq!(key_sequence_to_string(&keys), raw);
+        }
+
+        // some terminals report the shifted letters without the shift modifier
+        let key = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::NONE);
+        assert_eq!(key_event_to_string(&key), "shift-a");
+    }
+
+    #[test]
+    fn detects_duplicated_and_shadowed_sequences() {
+        let (keybindings, conflicts) = KeyBindings::<NoAction>::checked(crate::kb![
+            "<g>" => "top",
+            "<g><g>" => "bottom",
+            "<q>" => "app:quit",
+            "<q>" => "app:close",
+            "<ctrl-g" => "app:cancel"
+        ]);
+
+        assert_eq!(
+            keybindings.get(&parse_key_sequence("<q>").unwrap()),
+            Some(&Action::AppAction("app:close".into()))
+        );
+        assert_eq!(
+            conflicts,
+            [
+                KeyBindingConflict::Duplicate { keys: "<q>".into() },
+                KeyBindingConflict::Invalid {
+                    keys: "<ctrl-g".into(),
+                    error: "Invalid key sequence".into()
+                },
+                KeyBindingConflict::Shadowed {
+                    keys: "<g><g>".into(),
+                    by: "<g>".into()
+                },
+            ]
+        );
+        assert_eq!(conflicts[1].to_string(), "<ctrl-g is invalid: Invalid key sequence");
+        assert_eq!(conflicts[2].to_string(), "<g><g> is shadowed by <g>");
+
+        let top = Action::AppAction("top".into());
+        assert_eq!(keybindings.len(), 3);
+        assert_eq!(keybindings.bindings_for_action(&top), [parse_key_sequence("<g>").unwrap()]);
+        let keys = keybindings.iter().map(|(keys, _)| key_sequence_to_string(keys));
+        assert_eq!(keys.collect::<Vec<_>>(), ["<g>", "<g><g>", "<q>"]);
+    }
+
+    #[test]
+    fn keeps_the_descriptions_of_the_bindings() {
+        let mut keybindings = KeyBindings::<NoAction>::new(crate::kb![
+            "<q>" => Action::Quit => "quit",
+            "<s>" => "app:save"
+        ]);
+        keybindings.merge_under(KeyBindings::focus());
+        let keys = |raw| parse_key_sequence(raw).unwrap();
+
+        assert_eq!(keybindings.description(&keys("<q>")), Some("quit"));
+        assert_eq!(keybindings.description(&keys("<tab>")), Some("focus the next component"));
+        assert_eq!(keybindings.description(&keys("<s>")), None);
+
+        keybindings.insert(keys("<q>"), Action::Suspend);
+        assert_eq!(keybindings.description(&keys("<q>")), None);
+
+        let help = keybindings.help(Some("editor"));
+        let save = help.iter().find(|b| b.keys == keys("<s>")).unwrap();
+        assert_eq!(save.action, "app:save");
+        assert_eq!(save.scope.as_deref(), Some("editor"));
+    }
+
+    #[test]
+    fn writes_the_actions_without_data() {
+        assert_eq!(action_to_config::<NoAction>(&Action::FocusNext).as_deref(), Some("focusnext"));
+        assert_eq!(
+            action_to_config::<NoAction>(&Action::AppAction("app:save".into())).as_deref(),
+            Some("app:save")
+        );
+        assert_eq!(action_to_config::<NoAction>(&Action::PushScreen("help".into())), None);
+    }
+}
@@ -0,0 +1,49 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+/// How long the app takes to react to the input events (keys, mouse, paste and resizes), measured
+/// from the instant the [Tui](crate::Tui) read them from the terminal.
+///
+/// The app keeps it up to date in its [AppState](crate::AppState), and the
+/// [Inspector](crate::components::inspector::Inspector) overlay shows it:
+///
+/// ```ignore
+/// let latency = self.state().read(InputLatency::clone).unwrap_or_default();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InputLatency {
+    /// From the last input event to the end of the processing of the actions it triggered.
+    pub handled: Duration,
+    /// From the oldest input event not drawn yet to the end of the next frame.
+    pub drawn: Duration,
+    /// The longest `drawn` latency so far.
+    pub worst: Duration,
+    /// The number of frames drawn after input events.
+    pub samples: u64,
+}
+
+impl InputLatency {
+    pub(crate) fn handled(&mut self, at: Instant) {
+        self.handled = at.elapsed();
+    }
+
+    pub(crate) fn drawn(&mut self, at: Instant) {
+        self.drawn = at.elapsed();
+        self.worst = self.worst.max(self.drawn);
+        self.samples += 1;
+    }
+}
+
+impl Display for InputLatency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "input→action {:.1}ms · input→draw {:.1}ms · worst {:.1}ms",
+            self.handled.as_secs_f64() * 1000.0,
+            self.drawn.as_secs_f64() * 1000.0,
+            self.worst.as_secs_f64() * 1000.0,
+        )
+    }
+}
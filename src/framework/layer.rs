@@ -0,0 +1,205 @@
+use {
+    ratatui::layout::{Constraint, Flex, Layout, Rect},
+    std::collections::HashMap,
+};
+
+/// A layer of root components of the [App](crate::App) (see
+/// [App::with_layer](crate::App::with_layer)). Layers are drawn from the lowest z-index to the
+/// highest, so the components of a layer are drawn on top of the ones of the layers below.
+///
+/// The components set with [App::with_components](crate::App::with_components), the screens and
+/// the modals are in the [Main](Layer::Main) layer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Layer {
+    /// Drawn below everything else, e.g. for a backdrop.
+    Background,
+    #[default]
+    Main,
+    /// Drawn on top of everything else, open modals included, e.g. for a floating FPS counter
+    /// or status line.
+    Overlay,
+}
+
+impl Layer {
+    /// Every layer, from the bottom to the top.
+    pub const ALL: [Layer; 3] = [Layer::Background, Layer::Main, Layer::Overlay];
+
+    /// The position of the layer in the stack: layers with a higher z-index are drawn on top.
+    pub fn z_index(self) -> i8 {
+        match self {
+            Layer::Background => -1,
+            Layer::Main => 0,
+            Layer::Overlay => 1,
+        }
+    }
+
+    /// Returns `true` if the components of the layer receive the input events (keys, mouse and
+    /// paste) by default. Only the main layer does, the other ones usually just show things.
+    pub fn receives_input(self) -> bool {
+        self == Layer::Main
+    }
+}
+
+/// The point of the frame a [Float] is attached to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    #[default]
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Where a floating component is drawn (see [App::with_floating](crate::App::with_floating)):
+/// its size, the point of the frame it's anchored to and an offset from there.
+///
+/// ```ignore
+/// // a toast, one row above the bottom right corner
+/// let toast = Float::new(Anchor::BottomRight, Constraint::Length(32), Constraint::Length(3))
+///     .with_offset(0, -1);
+///
+/// // a command palette, centered
+/// let palette = Float::centered(Constraint::Percentage(60), Constraint::Length(12));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Float {
+    anchor: Anchor,
+    width: Constraint,
+    height: Constraint,
+    offset: (i16, i16),
+}
+
+impl Float {
+    /// Create a float of the given size, attached to the given point of the frame. The sizes are
+    /// resolved against the frame size (e.g. [Constraint::Percentage] of the frame).
+    pub fn new(anchor: Anchor, width: Constraint, height: Constraint) -> Self {
+        Self {
+            anchor,
+            width,
+            height,
+            offset: (0, 0),
+        }
+    }
+
+    /// Create a float of the given size, centered in the frame.
+    pub fn centered(width: Constraint, height: Constraint) -> Self {
+        Self::new(Anchor::Center, width, height)
+    }
+
+    /// Move the float by the given number of columns and rows from its anchor (negative values
+    /// move it left and up). The float is kept inside the frame anyway.
+    pub fn with_offset(mut self, x: i16, y: i16) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    /// The area of the float in the given frame area.
+    pub fn area(&self, frame: Rect) -> Rect {
+        let resolve = |constraint, total| {
+            let line = Rect::new(0, 0, total, 1);
+            Layout::horizontal([constraint]).flex(Flex::Start).split(line)[0].width
+        };
+        let width = resolve(self.width, frame.width);
+        let height = resolve(self.height, frame.height);
+
+        let (column, row) = match self.anchor {
+            Anchor::Center => (1, 1),
+            Anchor::Top => (1, 0),
+            Anchor::Bottom => (1, 2),
+            Anchor::Left => (0, 1),
+            Anchor::Right => (2, 1),
+            Anchor::TopLeft => (0, 0),
+            Anchor::TopRight => (2, 0),
+            Anchor::BottomLeft => (0, 2),
+            Anchor::BottomRight => (2, 2),
+        };
+        // the start, middle or end of the free space, moved by the offset and kept in the frame
+        let place = |start: u16, free: u16, position: u16, offset: i16| {
+            let at = i32::from(free) * i32::from(position) / 2 + i32::from(offset);
+            start + at.clamp(0, i32::from(free)) as u16
+        };
+        Rect::new(
+            place(frame.x, frame.width - width, column, self.offset.0),
+            place(frame.y, frame.height - height, row, self.offset.1),
+            width,
+            height,
+        )
+    }
+}
+
+/// The layers of the root components of the [App](crate::App), and whether each one receives
+/// the input events.
+#[derive(Debug, Default)]
+pub(crate) struct Layers {
+    /// layer of the component handlers outside of the main layer, by index
+    layers: HashMap<usize, Layer>,
+    /// layers whose input routing was changed from the default
+    input: HashMap<Layer, bool>,
+    /// placement of the floating component handlers, by index
+    floats: HashMap<usize, Float>,
+}
+
+impl Layers {
+    /// Put the component handler at `index` in the given layer.
+    pub(crate) fn register(&mut self, layer: Layer, index: usize) {
+        if layer != Layer::Main {
+            self.layers.insert(index, layer);
+        }
+    }
+
+    /// Make the component handler at `index` a floating one, in the overlay layer.
+    pub(crate) fn register_float(&mut self, float: Float, index: usize) {
+        self.register(Layer::Overlay, index);
+        self.floats.insert(index, float);
+    }
+
+    /// The placement of the component handler at `index`, if it's floating.
+    pub(crate) fn float(&self, index: usize) -> Option<&Float> {
+        self.floats.get(&index)
+    }
+
+    /// The layer of the component handler at `index`.
+    pub(crate) fn layer(&self, index: usize) -> Layer {
+        self.layers.get(&index).copied().unwrap_or_default()
+    }
+
+    /// Set whether the components of the layer receive the input events.
+    pub(crate) fn set_input(&mut self, layer: Layer, input: bool) {
+        self.input.insert(layer, input);
+    }
+
+    /// Returns `true` if the component handler at `index` receives the input events.
+    pub(crate) fn receives_input(&self, index: usize) -> bool {
+        let layer = self.layer(index);
+        self.input.get(&layer).copied().unwrap_or(layer.receives_input())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn places_the_floats_in_the_frame() {
+        let frame = Rect::new(0, 0, 80, 24);
+        let float = |anchor| Float::new(anchor, Constraint::Length(20), Constraint::Length(4));
+
+        assert_eq!(float(Anchor::Center).area(frame), Rect::new(30, 10, 20, 4));
+        assert_eq!(float(Anchor::TopLeft).area(frame), Rect::new(0, 0, 20, 4));
+        assert_eq!(float(Anchor::BottomRight).area(frame), Rect::new(60, 20, 20, 4));
+        assert_eq!(
+            float(Anchor::BottomRight).with_offset(-2, -1).area(frame),
+            Rect::new(58, 19, 20, 4)
+        );
+
+        // kept in the frame, and sized relative to it
+        assert_eq!(float(Anchor::TopLeft).with_offset(-5, 99).area(frame), Rect::new(0, 20, 20, 4));
+        let palette = Float::centered(Constraint::Percentage(50), Constraint::Length(30));
+        assert_eq!(palette.area(Rect::new(10, 5, 80, 24)), Rect::new(30, 5, 40, 24));
+    }
+}
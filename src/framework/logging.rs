@@ -0,0 +1,157 @@
+use {
+    log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError},
+    std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    },
+};
+
+/// A single record captured by a [LogBuffer].
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    /// Time elapsed since the buffer was created.
+    pub elapsed: Duration,
+    pub level: Level,
+    /// The module (or custom target) the record was logged from.
+    pub target: String,
+    pub message: String,
+}
+
+#[derive(Debug)]
+struct LogBufferInner {
+    started: Instant,
+    capacity: usize,
+    level: LevelFilter,
+    entries: VecDeque<LogEntry>,
+}
+
+/// A bounded record of the [log] records of the app, as stdout is taken over by the alternate
+/// screen while the app runs.
+///
+/// Like the [History](crate::History), the buffer is a cheap to clone shared handle: install a
+/// clone as the global logger with [LogBuffer::install] and give another one to whatever shows
+/// the records (e.g. the [LogConsole](crate::components::log_console::LogConsole) component).
+/// Once the capacity is reached, the oldest records are discarded.
+///
+/// ```ignore
+/// let logs = LogBuffer::default();
+/// logs.clone().install(LevelFilter::Debug)?;
+///
+/// log::info!("brewing");
+/// ```
+///
+/// Apps using `tracing` can enable its `log` feature, so its events are captured too.
+#[derive(Clone, Debug)]
+pub struct LogBuffer(Arc<Mutex<LogBufferInner>>);
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(500)
+    }
+}
+
+impl LogBuffer {
+    /// Create a new buffer keeping, at most, `capacity` records.
+    pub fn new(capacity: usize) -> Self {
+        Self(Arc::new(Mutex::new(LogBufferInner {
+            started: Instant::now(),
+            capacity,
+            level: LevelFilter::Trace,
+            entries: VecDeque::with_capacity(capacity),
+        })))
+    }
+
+    /// Make the buffer the global logger of the process, capturing the records up to the given
+    /// level. There is only one global logger, so this fails if one was installed already.
+    pub fn install(self, level: LevelFilter) -> Result<(), SetLoggerError> {
+        self.0.lock().unwrap().level = level;
+        // the global logger lives as long as the process
+        log::set_logger(Box::leak(Box::new(self)))?;
+        log::set_max_level(level);
+        Ok(())
+    }
+
+    /// Record a new entry.
+    pub fn push(&self, level: Level, target: impl Into<String>, message: impl Into<String>) {
+        let mut inner = self.0.lock().unwrap();
+        if inner.capacity == 0 {
+            return;
+        }
+
+        if inner.entries.len() == inner.capacity {
+            inner.entries.pop_front();
+        }
+
+        let elapsed = inner.started.elapsed();
+        inner.entries.push_back(LogEntry {
+            elapsed,
+            level,
+            target: target.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Returns a snapshot of the captured records, from oldest to newest.
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.0.lock().unwrap().entries.iter().cloned().collect()
+    }
+
+    /// Returns the number of captured records.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().entries.len()
+    }
+
+    /// Returns `true` if no records were captured (or they were cleared).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all the captured records.
+    pub fn clear(&self) {
+        self.0.lock().unwrap().entries.clear();
+    }
+}
+
+impl Log for LogBuffer {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.0.lock().unwrap().level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.enabled(record.metadata()) {
+            self.push(record.level(), record.target(), record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_the_records_up_to_the_level() {
+        let logs = LogBuffer::new(2);
+        logs.0.lock().unwrap().level = LevelFilter::Info;
+
+        for (level, message) in
+            [(Level::Info, "one"), (Level::Debug, "two"), (Level::Warn, "three")]
+        {
+            logs.log(
+                &Record::builder()
+                    .level(level)
+                    .target("mate")
+                    .args(format_args!("{message}"))
+                    .build(),
+            );
+        }
+        logs.push(Level::Error, "mate", "four");
+
+        let entries = logs.entries();
+        let messages = entries.iter().map(|e| e.message.as_str()).collect::<Vec<_>>();
+        assert_eq!(messages, ["three", "four"]);
+        assert_eq!(entries[0].target, "mate");
+    }
+}
@@ -0,0 +1,85 @@
+use std::{
+    any::{type_name, Any},
+    fmt::{Debug, Formatter, Result},
+    sync::Arc,
+};
+
+/// A message with a typed payload, published to the components with
+/// [ComponentAccessors::publish](crate::ComponentAccessors::publish) (or an [Action::Message]),
+/// and received through [Component::receive_typed_message](crate::Component::receive_typed_message).
+///
+/// The string messages of [ComponentAccessors::send](crate::ComponentAccessors::send) are fine for
+/// signals, but structured data shouldn't be formatted into strings and parsed back. The payload of
+/// a message can be any value, shared by all the components receiving it:
+///
+/// ```ignore
+/// #[derive(Debug)]
+/// struct Order { mates: u32, sweet: bool }
+///
+/// // in a component
+/// self.publish("order", Order { mates: 2, sweet: false });
+///
+/// // in another one
+/// fn receive_typed_message(&mut self, message: &Message) {
+///     if let Some(order) = message.get::<Order>("order") {
+///         self.pending += order.mates;
+///     }
+/// }
+/// ```
+///
+/// Apps using `serde` can publish `serde_json::Value`s, or their own deserialized types.
+///
+/// [Action::Message]: crate::Action::Message
+#[derive(Clone)]
+pub struct Message {
+    topic: String,
+    payload: Arc<dyn Any + Send + Sync>,
+    payload_type: &'static str,
+}
+
+impl Message {
+    /// Create a new message with the given topic and payload.
+    pub fn new<T: Any + Send + Sync>(topic: impl Into<String>, payload: T) -> Self {
+        Self {
+            topic: topic.into(),
+            payload: Arc::new(payload),
+            payload_type: type_name::<T>(),
+        }
+    }
+
+    /// The topic of the message, telling the receivers what it is about.
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// The payload of the message, if it's a `T`.
+    pub fn payload<T: Any>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+
+    /// The payload of the message, if the message has the given topic and its payload is a `T`.
+    pub fn get<T: Any>(&self, topic: &str) -> Option<&T> {
+        (self.topic == topic).then(|| self.payload()).flatten()
+    }
+}
+
+impl Default for Message {
+    fn default() -> Self {
+        Self::new("", ())
+    }
+}
+
+/// Messages are equal if they have the same topic and share the same payload.
+impl PartialEq for Message {
+    fn eq(&self, other: &Self) -> bool {
+        self.topic == other.topic && Arc::ptr_eq(&self.payload, &other.payload)
+    }
+}
+impl Debug for Message {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Message")
+            .field("topic", &self.topic)
+            .field("payload", &self.payload_type)
+            .finish()
+    }
+}
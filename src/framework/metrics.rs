@@ -0,0 +1,171 @@
+use std::{
+    cell::RefCell,
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+/// The target of the warnings about the slow frames, to filter them in the logger.
+pub(crate) const TARGET: &str = "matetui::metrics";
+
+thread_local! {
+    /// The draws of the components in the frame being drawn, while the app collects them (see
+    /// [collect_draws]).
+    static DRAWS: RefCell<Option<Draws>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct Draws {
+    /// the names of the components and the time they took, in the order they started
+    done: Vec<(String, Duration)>,
+    /// the draws in progress, from the outermost: their index in `done` and the time their
+    /// children took
+    open: Vec<(usize, Duration)>,
+}
+
+/// How long the app takes to draw its frames, and how long each component takes to draw.
+///
+/// The app keeps it up to date in its [AppState](crate::AppState), and warns about the frames
+/// taking longer than a budget, if set (see
+/// [App::with_frame_budget](crate::App::with_frame_budget)):
+///
+/// ```ignore
+/// let metrics = self.state().read(AppMetrics::clone).unwrap_or_default();
+/// if let Some((name, took)) = metrics.slowest_component() {
+///     self.set_status(&format!("{name} took {took:?}"));
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AppMetrics {
+    /// The number of frames drawn.
+    pub frames: u64,
+    /// How long the last frame took to draw.
+    pub last_frame: Duration,
+    /// The longest frame so far.
+    pub worst_frame: Duration,
+    /// The number of frames over the budget.
+    pub slow_frames: u64,
+    /// How long each component took to draw in the last frame, not counting its children, in the
+    /// order they were drawn (parents before their children).
+    pub components: Vec<(String, Duration)>,
+}
+
+impl AppMetrics {
+    /// The component that took the longest to draw in the last frame, not counting its children.
+    pub fn slowest_component(&self) -> Option<(&str, Duration)> {
+        let slowest = self.components.iter().max_by_key(|(_, took)| *took);
+        slowest.map(|(name, took)| (name.as_str(), *took))
+    }
+
+    pub(crate) fn drawn(
+        &mut self,
+        took: Duration,
+        slow: bool,
+        components: Vec<(String, Duration)>,
+    ) {
+        self.frames += 1;
+        self.slow_frames += u64::from(slow);
+        self.last_frame = took;
+        self.worst_frame = self.worst_frame.max(took);
+        self.components = components;
+    }
+}
+
+impl Display for AppMetrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "frame {:.1}ms · worst {:.1}ms · {} of {} slow",
+            self.last_frame.as_secs_f64() * 1000.0,
+            self.worst_frame.as_secs_f64() * 1000.0,
+            self.slow_frames,
+            self.frames,
+        )
+    }
+}
+
+/// Collect the draws of the components (see [DrawTimer]) while drawing a frame, and return them
+/// with the result of the drawing.
+pub(crate) fn collect_draws<T>(draw: impl FnOnce() -> T) -> (T, Vec<(String, Duration)>) {
+    DRAWS.with(|draws| *draws.borrow_mut() = Some(Draws::default()));
+    let result = draw();
+    let draws = DRAWS.with(|draws| draws.borrow_mut().take());
+    (result, draws.map(|draws| draws.done).unwrap_or_default())
+}
+
+/// The warning about a frame taking longer than the budget, naming the slowest components.
+pub(crate) fn warn_slow_frame(took: Duration, budget: Duration, components: &[(String, Duration)]) {
+    let mut slowest: Vec<_> = components.iter().collect();
+    slowest.sort_by_key(|(_, took)| std::cmp::Reverse(*took));
+    let slowest: Vec<_> = slowest.iter().take(3).map(|(name, t)| format!("{name} {t:?}")).collect();
+    log::warn!(
+        target: TARGET,
+        "frame took {took:?}, over the budget of {budget:?}; slowest components: {}",
+        slowest.join(", ")
+    );
+}
+
+/// Times the draw of a component, from its creation to its drop, while the app collects the draws.
+pub(crate) struct DrawTimer {
+    started: Instant,
+}
+
+impl DrawTimer {
+    pub(crate) fn start(name: impl FnOnce() -> String) -> Option<Self> {
+        DRAWS.with(|draws| {
+            let mut draws = draws.borrow_mut();
+            let draws = draws.as_mut()?;
+            draws.open.push((draws.done.len(), Duration::ZERO));
+            draws.done.push((name(), Duration::ZERO));
+            Some(Self {
+                started: Instant::now(),
+            })
+        })
+    }
+}
+
+impl Drop for DrawTimer {
+    fn drop(&mut self) {
+        let took = self.started.elapsed();
+        DRAWS.with(|draws| {
+            let mut draws = draws.borrow_mut();
+            let Some(draws) = draws.as_mut() else {
+                return;
+            };
+            // the timers are dropped in the reverse order they were started
+            if let Some((index, children)) = draws.open.pop() {
+                draws.done[index].1 = took.saturating_sub(children);
+            }
+            if let Some((_, children)) = draws.open.last_mut() {
+                *children += took;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn times_the_components_without_their_children() {
+        let ((), draws) = collect_draws(|| {
+            let _parent = DrawTimer::start(|| "parent".into());
+            std::thread::sleep(Duration::from_millis(5));
+            let _child = DrawTimer::start(|| "child".into());
+            std::thread::sleep(Duration::from_millis(20));
+        });
+        assert_eq!(
+            draws.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(),
+            ["parent", "child"]
+        );
+        assert!(draws[1].1 >= Duration::from_millis(20));
+        assert!(draws[0].1 < draws[1].1);
+
+        let metrics = AppMetrics {
+            components: draws,
+            ..AppMetrics::default()
+        };
+        assert_eq!(metrics.slowest_component().map(|(name, _)| name), Some("child"));
+        assert!(DrawTimer::start(|| "outside".into()).is_none());
+    }
+}
@@ -0,0 +1,133 @@
+use {
+    super::{
+        component::ComponentHandler,
+        events::UserAction,
+        focus::{FocusManager, FocusPath},
+    },
+    std::collections::HashMap,
+};
+
+/// The stack of open modals of the [App](crate::App) (see
+/// [App::with_modal](crate::App::with_modal)).
+///
+/// Modals are root components that are only active while open. They are drawn on top of
+/// everything else, in the order they were opened, and the top one captures all the input events.
+#[derive(Debug, Default)]
+pub(crate) struct Modals {
+    /// index of the component handler of each modal
+    modals: HashMap<String, usize>,
+    /// open modals, with the focus to restore when they are closed
+    stack: Vec<(usize, Option<FocusPath>)>,
+}
+
+impl Modals {
+    /// Register the modal with the given name, drawn by the component handler at `index`.
+    pub(crate) fn register(&mut self, name: String, index: usize) {
+        self.modals.insert(name, index);
+    }
+
+    /// Returns `true` if the component handler at `index` is a modal.
+    pub(crate) fn is_modal(&self, index: usize) -> bool {
+        self.modals.values().any(|i| *i == index)
+    }
+
+    /// Index of the component handler of the top open modal, if any.
+    pub(crate) fn top(&self) -> Option<usize> {
+        self.stack.last().map(|(index, _)| *index)
+    }
+
+    /// Indexes of the component handlers of the open modals, from the bottom to the top.
+    pub(crate) fn open_modals(&self) -> impl Iterator<Item = usize> + '_ {
+        self.stack.iter().map(|(index, _)| *index)
+    }
+
+    /// Open the modal with the given name on top of everything, moving the focus to it. Unknown
+    /// and already open modals are ignored.
+    pub(crate) fn open<A: UserAction>(
+        &mut self,
+        name: &str,
+        handlers: &mut [ComponentHandler<A>],
+        focus: &mut FocusManager,
+    ) {
+        let Some(&index) = self.modals.get(name) else {
+            return;
+        };
+
+        if self.stack.iter().any(|(i, _)| *i == index) {
+            return;
+        }
+
+        self.stack.push((index, focus.focused().cloned()));
+        handlers[index].component_mut().set_active(true);
+        focus.set_scope(Some(index));
+        focus.focus(handlers, Some(FocusPath::root(index)));
+    }
+
+    /// Close the top modal, restoring the focus to where it was when the modal was opened.
+    pub(crate) fn close<A: UserAction>(
+        &mut self,
+        handlers: &mut [ComponentHandler<A>],
+        focus: &mut FocusManager,
+    ) {
+        let Some((index, restore)) = self.stack.pop() else {
+            return;
+        };
+
+        focus.focus(handlers, None);
+        handlers[index].component_mut().set_active(false);
+        focus.set_scope(self.top());
+        focus.focus(handlers, restore);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{component, Component, ComponentAccessors, ComponentError, Frame},
+        ratatui::layout::Rect,
+    };
+
+    component! {
+        struct Focusable {}
+    }
+
+    impl Component for Focusable {
+        fn tab_index(&self) -> Option<u16> {
+            Some(0)
+        }
+
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn open_and_close_restore_focus() {
+        let mut handlers = vec![
+            ComponentHandler::for_(Box::new(Focusable::default().as_active())),
+            ComponentHandler::for_(Box::new(Focusable::default())),
+        ];
+        let mut focus = FocusManager::default();
+        let mut modals = Modals::default();
+        modals.register("confirm".into(), 1);
+
+        focus.focus_next(&mut handlers);
+        assert_eq!(focus.focused(), Some(&FocusPath::root(0)));
+
+        modals.open("confirm", &mut handlers, &mut focus);
+        assert_eq!(modals.top(), Some(1));
+        assert!(handlers[1].component_mut().is_active());
+        assert_eq!(focus.focused(), Some(&FocusPath::root(1)));
+
+        // the focus traversal is trapped inside the modal
+        focus.focus_next(&mut handlers);
+        assert_eq!(focus.focused(), Some(&FocusPath::root(1)));
+
+        modals.close(&mut handlers, &mut focus);
+        assert_eq!(modals.top(), None);
+        assert!(!handlers[1].component_mut().is_active());
+        assert_eq!(focus.focused(), Some(&FocusPath::root(0)));
+        assert!(handlers[0].component_mut().is_focused());
+    }
+}
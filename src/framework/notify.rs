@@ -0,0 +1,71 @@
+use std::{
+    io,
+    process::{Command, Stdio},
+    thread,
+};
+
+/// Send a desktop notification through the notification service of the OS, so users who switched
+/// away from the terminal know something happened (e.g. a long build finished).
+///
+/// The notification is sent with the tools shipped with each OS, without waiting for it to be
+/// shown:
+/// - linux and the BSDs: `notify-send` (from `libnotify`)
+/// - macOS: `osascript`
+/// - windows: a toast, through `powershell`
+///
+/// Components usually send them with
+/// [ComponentAccessors::notify_desktop](crate::ComponentAccessors::notify_desktop).
+///
+/// # Errors
+///
+/// Returns an error if the tool couldn't be started (e.g. `notify-send` isn't installed).
+pub fn notify_desktop(title: &str, body: &str) -> io::Result<()> {
+    let mut child = command(title, body)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // reap the process once it's done, so it doesn't linger as a zombie
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn command(title: &str, body: &str) -> Command {
+    // the texts are passed as arguments to avoid escaping them inside the script
+    let mut command = Command::new("osascript");
+    command
+        .args(["-e", "on run argv"])
+        .args(["-e", "display notification (item 2 of argv) with title (item 1 of argv)"])
+        .args(["-e", "end run"])
+        .args([title, body]);
+    command
+}
+
+#[cfg(windows)]
+fn command(title: &str, body: &str) -> Command {
+    const SCRIPT: &str = "
+        $manager = [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime]
+        $template = $manager::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02)
+        $texts = $template.GetElementsByTagName('text')
+        $texts.Item(0).AppendChild($template.CreateTextNode($env:MATETUI_NOTIFY_TITLE)) | Out-Null
+        $texts.Item(1).AppendChild($template.CreateTextNode($env:MATETUI_NOTIFY_BODY)) | Out-Null
+        $manager::CreateToastNotifier('matetui').Show([Windows.UI.Notifications.ToastNotification]::new($template))
+    ";
+
+    // the texts are passed through the environment to avoid escaping them inside the script
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+        .env("MATETUI_NOTIFY_TITLE", title)
+        .env("MATETUI_NOTIFY_BODY", body);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn command(title: &str, body: &str) -> Command {
+    let mut command = Command::new("notify-send");
+    command.args(["--app-name", "matetui", "--", title, body]);
+    command
+}
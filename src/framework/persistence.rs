@@ -0,0 +1,175 @@
+use {
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::BTreeMap,
+        fs, io,
+        path::{Path, PathBuf},
+    },
+};
+
+/// The states saved by the components of an app (see
+/// [Component::save_state](crate::Component::save_state)), by the path of the component: the
+/// name of the root component, then the keys of the children down to it, separated by `/` (e.g.
+/// `main/sidebar/tree`).
+///
+/// It's stored as a TOML table of strings, one key per component:
+///
+/// ```toml
+/// "main/sidebar" = "width=32"
+/// "main/search" = '{"history":["mate","yerba"]}'
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct Snapshot(BTreeMap<String, String>);
+
+impl Snapshot {
+    /// Load the snapshot stored at `path`. A missing file is an empty snapshot.
+    pub(crate) fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::from_toml(&content),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Store the snapshot at `path`, creating its directory if needed.
+    pub(crate) fn store(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_toml())
+    }
+
+    pub(crate) fn get(&self, path: &str) -> Option<&str> {
+        self.0.get(path).map(String::as_str)
+    }
+
+    pub(crate) fn insert(&mut self, path: String, state: String) {
+        self.0.insert(path, state);
+    }
+
+    fn to_toml(&self) -> String {
+        // a table of strings is always valid TOML
+        toml::to_string(self).expect("the snapshot is written as TOML")
+    }
+
+    fn from_toml(s: &str) -> io::Result<Self> {
+        toml::from_str(s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.message()))
+    }
+}
+
+/// The directory of the config files of the user on this platform: `%APPDATA%` on windows,
+/// `~/Library/Application Support` on macOS, and `$XDG_CONFIG_HOME` or else `~/.config`
+/// elsewhere.
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    let var = |name| std::env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    if cfg!(windows) {
+        var("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        var("XDG_CONFIG_HOME").or_else(|| var("HOME").map(|home| home.join(".config")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            component, components, kb, Action, App, Component, ComponentAccessors, ComponentError,
+            Frame, MatetuiError,
+        },
+        ratatui::{layout::Rect, widgets::Paragraph},
+    };
+
+    #[test]
+    fn snapshot_roundtrips() -> io::Result<()> {
+        let mut snapshot = Snapshot::default();
+        snapshot.insert("main/search".into(), "{\"history\":[\"mate\"]}".into());
+        snapshot.insert("main/sidebar".into(), "width=32\n".into());
+
+        let toml = snapshot.to_toml();
+        assert_eq!(toml.lines().next(), Some(r#""main/search" = '{"history":["mate"]}'"#));
+        assert_eq!(Snapshot::from_toml(&toml)?, snapshot);
+        assert!(Snapshot::from_toml("\"main\" 3").is_err());
+
+        let missing = std::env::temp_dir().join("matetui-no-such-dir/state.toml");
+        assert_eq!(Snapshot::load(&missing)?, Snapshot::default());
+        Ok(())
+    }
+
+    component! {
+        struct Counter {
+            mates: u32
+        }
+    }
+
+    impl Component for Counter {
+        fn receive_message(&mut self, message: String) {
+            if message == "drink" {
+                self.mates += 1;
+            }
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("mates: {}", self.mates)), area);
+            Ok(())
+        }
+
+        fn save_state(&self) -> Option<String> {
+            Some(self.mates.to_string())
+        }
+
+        fn restore_state(&mut self, state: &str) {
+            self.mates = state.parse().unwrap_or_default();
+        }
+    }
+
+    component! {
+        struct Bar {},
+        children => {
+            "left" => Counter::default().as_active(),
+            "right" => Counter::default().as_active()
+        }
+    }
+
+    impl Component for Bar {
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            for (row, name) in ["left", "right"].into_iter().enumerate() {
+                let area = Rect::new(area.x, area.y + row as u16, area.width, 1);
+                self.child_mut(name).unwrap().render(f, area)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn persists_the_state_of_the_components() -> Result<(), MatetuiError> {
+        let path = std::env::temp_dir().join(format!("matetui-{}/state.toml", std::process::id()));
+        let app = || {
+            App::default()
+                .with_keybindings(kb![
+                    "<l>" => Action::SendTo("left".into(), "drink".into()),
+                    "<q>" => Action::Quit
+                ])
+                .with_components(components![Bar::default().as_active()])
+                .with_persistence_file(&path)
+                .test()
+        };
+
+        let mut first = app()?;
+        first.press("<l><l><q>")?;
+        assert!(first.has_quit());
+        let saved = std::fs::read_to_string(&path)?;
+        assert_eq!(saved, "\"Bar/left\" = \"2\"\n\"Bar/right\" = \"0\"\n");
+
+        let mut second = app()?;
+        second.press("<l>")?;
+        assert!(second.lines()[0].starts_with("mates: 3"));
+        assert!(second.lines()[1].starts_with("mates: 0"));
+
+        std::fs::remove_dir_all(path.parent().unwrap())?;
+        Ok(())
+    }
+}
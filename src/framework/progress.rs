@@ -0,0 +1,246 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The state of a task tracked by a [ProgressRegistry].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProgressStatus {
+    Running,
+    Done,
+    /// The task failed with the given message.
+    Failed(String),
+}
+
+/// A snapshot of the progress of a task tracked by a [ProgressRegistry].
+#[derive(Clone, Debug)]
+pub struct ProgressEntry {
+    pub id: String,
+    pub label: String,
+    /// The units of work done so far.
+    pub current: u64,
+    /// The total units of work, if known.
+    pub total: Option<u64>,
+    /// The last message reported by the task, e.g. the file being processed.
+    pub message: Option<String>,
+    pub status: ProgressStatus,
+    /// Time elapsed since the task started, frozen when it ends.
+    pub elapsed: Duration,
+}
+
+impl ProgressEntry {
+    /// The completed fraction of the task, between `0.0` and `1.0`, if its total is known. Tasks
+    /// that are done are always complete.
+    pub fn ratio(&self) -> Option<f64> {
+        if self.status == ProgressStatus::Done {
+            return Some(1.0);
+        }
+        match self.total {
+            Some(0) => Some(1.0),
+            Some(total) => Some((self.current as f64 / total as f64).min(1.0)),
+            None => None,
+        }
+    }
+
+    /// Returns `true` if the task is done or failed.
+    pub fn is_finished(&self) -> bool {
+        self.status != ProgressStatus::Running
+    }
+}
+
+#[derive(Debug)]
+struct Tracked {
+    entry: ProgressEntry,
+    started: Instant,
+}
+
+/// A shared registry where concurrent tasks report their progress by id, to be shown by the
+/// [ProgressView](crate::components::progress::ProgressView) component.
+///
+/// The registry is a cheap to clone shared handle: give a clone to each spawned task and another
+/// one to the component (or read it through the [AppState](crate::AppState)):
+///
+/// ```ignore
+/// let progress = ProgressRegistry::default();
+///
+/// for crate_name in crates {
+///     let task = progress.start(&crate_name, format!("compiling {crate_name}"), Some(100));
+///     tokio::spawn(async move {
+///         for step in build(&crate_name) {
+///             task.advance(1);
+///         }
+///         task.finish();
+///     });
+/// }
+/// ```
+///
+/// Tasks keep their registration order.
+#[derive(Clone, Debug, Default)]
+pub struct ProgressRegistry(Arc<Mutex<Vec<Tracked>>>);
+
+impl ProgressRegistry {
+    /// Start tracking a task, replacing the previous task with the same id, if any. Returns the
+    /// handle the task reports its progress through.
+    pub fn start(
+        &self,
+        id: impl Into<String>,
+        label: impl Into<String>,
+        total: Option<u64>,
+    ) -> ProgressTask {
+        let id = id.into();
+        let tracked = Tracked {
+            entry: ProgressEntry {
+                id: id.clone(),
+                label: label.into(),
+                current: 0,
+                total,
+                message: None,
+                status: ProgressStatus::Running,
+                elapsed: Duration::ZERO,
+            },
+            started: Instant::now(),
+        };
+
+        let mut tasks = self.0.lock().unwrap();
+        match tasks.iter_mut().find(|t| t.entry.id == id) {
+            Some(previous) => *previous = tracked,
+            None => tasks.push(tracked),
+        }
+
+        ProgressTask {
+            registry: self.clone(),
+            id,
+        }
+    }
+
+    /// The handle of the task with the given id, to report its progress.
+    pub fn task(&self, id: impl Into<String>) -> ProgressTask {
+        ProgressTask {
+            registry: self.clone(),
+            id: id.into(),
+        }
+    }
+
+    /// Returns a snapshot of the tracked tasks, in registration order.
+    pub fn entries(&self) -> Vec<ProgressEntry> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|t| {
+                let mut entry = t.entry.clone();
+                if !entry.is_finished() {
+                    entry.elapsed = t.started.elapsed();
+                }
+                entry
+            })
+            .collect()
+    }
+
+    /// The aggregated progress of all the tasks, as the number of finished tasks and the number
+    /// of tasks.
+    pub fn overall(&self) -> (usize, usize) {
+        let tasks = self.0.lock().unwrap();
+        (tasks.iter().filter(|t| t.entry.is_finished()).count(), tasks.len())
+    }
+
+    /// Returns `true` if every task is done or failed.
+    pub fn is_finished(&self) -> bool {
+        let (finished, total) = self.overall();
+        finished == total
+    }
+
+    /// Stop tracking the finished tasks.
+    pub fn clear_finished(&self) {
+        self.0.lock().unwrap().retain(|t| !t.entry.is_finished());
+    }
+
+    /// Update the task with the given id, if it's still running.
+    fn update(&self, id: &str, f: impl FnOnce(&mut ProgressEntry)) {
+        let mut tasks = self.0.lock().unwrap();
+        if let Some(tracked) = tasks.iter_mut().find(|t| t.entry.id == id) {
+            if tracked.entry.is_finished() {
+                return;
+            }
+            f(&mut tracked.entry);
+            if tracked.entry.is_finished() {
+                tracked.entry.elapsed = tracked.started.elapsed();
+            }
+        }
+    }
+}
+
+/// The handle a task reports its progress through (see [ProgressRegistry::start]). Reports made
+/// after the task finished are ignored.
+#[derive(Clone, Debug)]
+pub struct ProgressTask {
+    registry: ProgressRegistry,
+    id: String,
+}
+
+impl ProgressTask {
+    /// The id of the task.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Set the units of work done so far.
+    pub fn set(&self, current: u64) {
+        self.registry.update(&self.id, |e| e.current = current);
+    }
+
+    /// Add to the units of work done so far.
+    pub fn advance(&self, delta: u64) {
+        self.registry.update(&self.id, |e| e.current = e.current.saturating_add(delta));
+    }
+
+    /// Set the total units of work, once they are known.
+    pub fn set_total(&self, total: u64) {
+        self.registry.update(&self.id, |e| e.total = Some(total));
+    }
+
+    /// Report what the task is doing right now.
+    pub fn set_message(&self, message: impl Into<String>) {
+        let message = message.into();
+        self.registry.update(&self.id, |e| e.message = Some(message));
+    }
+
+    /// Mark the task as done.
+    pub fn finish(&self) {
+        self.registry.update(&self.id, |e| e.status = ProgressStatus::Done);
+    }
+
+    /// Mark the task as failed.
+    pub fn fail(&self, error: impl Into<String>) {
+        let error = error.into();
+        self.registry.update(&self.id, |e| e.status = ProgressStatus::Failed(error));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tasks_report_progress_by_id() {
+        let progress = ProgressRegistry::default();
+        let fetch = progress.start("fetch", "fetching", Some(4));
+        let build = progress.start("build", "building", None);
+
+        fetch.advance(3);
+        assert_eq!(progress.entries()[0].ratio(), Some(0.75));
+        assert_eq!(progress.entries()[1].ratio(), None);
+
+        progress.task("build").fail("linker error");
+        build.advance(1);
+        assert_eq!(progress.entries()[1].current, 0);
+        assert_eq!(progress.overall(), (1, 2));
+
+        fetch.finish();
+        assert!(progress.is_finished());
+        assert_eq!(progress.entries()[0].ratio(), Some(1.0));
+
+        progress.clear_finished();
+        assert!(progress.entries().is_empty());
+    }
+}
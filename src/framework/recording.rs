@@ -0,0 +1,444 @@
+use {
+    super::{
+        events::{DragPhase, Event, MouseClick, MouseDrag},
+        tui::EventSender,
+    },
+    crossterm::event::{
+        KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
+    std::{
+        fmt::{self, Display, Formatter},
+        fs::{self, File},
+        io::{self, BufWriter, Write},
+        path::Path,
+        str::FromStr,
+        time::Duration,
+    },
+    tokio::{task::JoinHandle, time::Instant},
+    tokio_util::sync::CancellationToken,
+};
+
+/// The first line of the recording files.
+const HEADER: &str = "# matetui recording v1";
+
+/// A recorded stream of input [Event]s, each one with the time elapsed since the start of the
+/// recording.
+///
+/// Recordings are written by [App::with_recording](crate::App::with_recording) and replayed
+/// through the app with [App::with_replay](crate::App::with_replay), which makes them useful to
+/// reproduce bugs and to script demos:
+///
+/// ```ignore
+/// // record a session...
+/// App::default().with_recording("session.rec").run().await?;
+///
+/// // ...and play it back later
+/// App::default().with_replay(Recording::load("session.rec")?).run().await?;
+/// ```
+///
+/// Only the input events are recorded (keys, mouse, paste, resize and focus changes), the ticks
+/// and renders are produced by the app itself. The file format is plain text, one event per line
+/// with tab separated fields, so recordings can be written by hand too:
+///
+/// ```text
+/// # matetui recording v1
+/// 0       resize  80      24
+/// 1200    key     c:h     0       press
+/// 1350    key     enter   0       press
+/// 2000    paste   hello\nworld
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Recording {
+    events: Vec<(Duration, Event)>,
+}
+
+impl Recording {
+    /// Load a recording from a file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Save the recording to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Add an event at the given time. Returns `false` if the event can't be recorded.
+    pub fn push(&mut self, elapsed: Duration, event: Event) -> bool {
+        let recordable = encode(&event).is_some();
+        if recordable {
+            self.events.push((elapsed, event));
+        }
+        recordable
+    }
+
+    /// The recorded events, with the time elapsed since the start of the recording.
+    pub fn events(&self) -> &[(Duration, Event)] {
+        &self.events
+    }
+
+    /// Spawn a task sending the events through `tx` at their recorded times, until the `token` is
+    /// cancelled.
+    pub(crate) fn replay(&self, tx: EventSender, token: CancellationToken) -> JoinHandle<()> {
+        let events = self.events.clone();
+        let start = Instant::now();
+        tokio::spawn(async move {
+            for (elapsed, event) in events {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = tokio::time::sleep_until(start + elapsed) => {
+                        if tx.send(event).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl Display for Recording {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        for (elapsed, event) in &self.events {
+            if let Some(line) = encode(event) {
+                writeln!(f, "{}\t{line}", elapsed.as_millis())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Recording {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut events = Vec::new();
+        for (index, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid recording line {}: {line:?}", index + 1),
+                )
+            };
+            let (millis, event) = line.split_once('\t').ok_or_else(invalid)?;
+            let millis = millis.parse().map_err(|_| invalid())?;
+            let event = decode(event).ok_or_else(invalid)?;
+            events.push((Duration::from_millis(millis), event));
+        }
+
+        Ok(Self { events })
+    }
+}
+
+/// Writes the input events processed by the app to a file as they happen, so the recording
+/// survives a crash.
+pub(crate) struct Recorder {
+    file: BufWriter<File>,
+    start: Instant,
+}
+
+impl Recorder {
+    pub(crate) fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "{HEADER}")?;
+        Ok(Self {
+            file,
+            start: Instant::now(),
+        })
+    }
+
+    pub(crate) fn record(&mut self, event: &Event) -> io::Result<()> {
+        if let Some(line) = encode(event) {
+            writeln!(self.file, "{}\t{line}", self.start.elapsed().as_millis())?;
+            self.file.flush()?;
+        }
+        Ok(())
+    }
+}
+
+fn encode(event: &Event) -> Option<String> {
+    let line = match event {
+        Event::Key(key) | Event::KeyRelease(key) => format!(
+            "key\t{}\t{}\t{}",
+            encode_key_code(key.code)?,
+            key.modifiers.bits(),
+            match key.kind {
+                KeyEventKind::Press => "press",
+                KeyEventKind::Repeat => "repeat",
+                KeyEventKind::Release => "release",
+            }
+        ),
+        Event::Mouse(mouse) => format!(
+            "mouse\t{}\t{}\t{}\t{}",
+            encode_mouse_kind(mouse.kind),
+            mouse.column,
+            mouse.row,
+            mouse.modifiers.bits()
+        ),
+        Event::MouseClick(click) => format!(
+            "click\t{}\t{}\t{}\t{}\t{}",
+            encode_button(click.button),
+            click.column,
+            click.row,
+            click.modifiers.bits(),
+            click.count
+        ),
+        Event::MouseDrag(drag) => format!(
+            "drag\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            match drag.phase {
+                DragPhase::Start => "start",
+                DragPhase::Update => "update",
+                DragPhase::End => "end",
+            },
+            encode_button(drag.button),
+            drag.origin_column,
+            drag.origin_row,
+            drag.column,
+            drag.row,
+            drag.delta_column,
+            drag.delta_row,
+            drag.modifiers.bits()
+        ),
+        Event::Paste(text) => format!("paste\t{}", escape(text)),
+        Event::Resize(w, h) => format!("resize\t{w}\t{h}"),
+        Event::FocusGained => "focus-gained".to_string(),
+        Event::FocusLost => "focus-lost".to_string(),
+        _ => return None,
+    };
+    Some(line)
+}
+
+fn decode(line: &str) -> Option<Event> {
+    let mut fields = line.split('\t');
+    let event = match fields.next()? {
+        "key" => {
+            let code = decode_key_code(fields.next()?)?;
+            let modifiers = KeyModifiers::from_bits(fields.next()?.parse().ok()?)?;
+            let kind = match fields.next().unwrap_or("press") {
+                "press" => KeyEventKind::Press,
+                "repeat" => KeyEventKind::Repeat,
+                "release" => KeyEventKind::Release,
+                _ => return None,
+            };
+            let key = KeyEvent::new_with_kind_and_state(code, modifiers, kind, KeyEventState::NONE);
+            match kind {
+                KeyEventKind::Release => Event::KeyRelease(key),
+                _ => Event::Key(key),
+            }
+        }
+        "mouse" => Event::Mouse(MouseEvent {
+            kind: decode_mouse_kind(fields.next()?)?,
+            column: fields.next()?.parse().ok()?,
+            row: fields.next()?.parse().ok()?,
+            modifiers: KeyModifiers::from_bits(fields.next()?.parse().ok()?)?,
+        }),
+        "click" => Event::MouseClick(MouseClick {
+            button: decode_button(fields.next()?)?,
+            column: fields.next()?.parse().ok()?,
+            row: fields.next()?.parse().ok()?,
+            modifiers: KeyModifiers::from_bits(fields.next()?.parse().ok()?)?,
+            count: fields.next()?.parse().ok()?,
+        }),
+        "drag" => Event::MouseDrag(MouseDrag {
+            phase: match fields.next()? {
+                "start" => DragPhase::Start,
+                "update" => DragPhase::Update,
+                "end" => DragPhase::End,
+                _ => return None,
+            },
+            button: decode_button(fields.next()?)?,
+            origin_column: fields.next()?.parse().ok()?,
+            origin_row: fields.next()?.parse().ok()?,
+            column: fields.next()?.parse().ok()?,
+            row: fields.next()?.parse().ok()?,
+            delta_column: fields.next()?.parse().ok()?,
+            delta_row: fields.next()?.parse().ok()?,
+            modifiers: KeyModifiers::from_bits(fields.next()?.parse().ok()?)?,
+        }),
+        "paste" => Event::Paste(unescape(fields.next()?)),
+        "resize" => Event::Resize(fields.next()?.parse().ok()?, fields.next()?.parse().ok()?),
+        "focus-gained" => Event::FocusGained,
+        "focus-lost" => Event::FocusLost,
+        _ => return None,
+    };
+    Some(event)
+}
+
+const KEY_CODES: [(KeyCode, &str); 15] = [
+    (KeyCode::Backspace, "backspace"),
+    (KeyCode::Enter, "enter"),
+    (KeyCode::Left, "left"),
+    (KeyCode::Right, "right"),
+    (KeyCode::Up, "up"),
+    (KeyCode::Down, "down"),
+    (KeyCode::Home, "home"),
+    (KeyCode::End, "end"),
+    (KeyCode::PageUp, "pageup"),
+    (KeyCode::PageDown, "pagedown"),
+    (KeyCode::Tab, "tab"),
+    (KeyCode::BackTab, "backtab"),
+    (KeyCode::Delete, "delete"),
+    (KeyCode::Insert, "insert"),
+    (KeyCode::Esc, "esc"),
+];
+
+fn encode_key_code(code: KeyCode) -> Option<String> {
+    match code {
+        KeyCode::Char(c) => Some(format!("c:{}", escape(&c.to_string()))),
+        KeyCode::F(n) => Some(format!("f:{n}")),
+        code => KEY_CODES.iter().find(|(c, _)| *c == code).map(|(_, name)| name.to_string()),
+    }
+}
+
+fn decode_key_code(raw: &str) -> Option<KeyCode> {
+    if let Some(c) = raw.strip_prefix("c:") {
+        return unescape(c).chars().next().map(KeyCode::Char);
+    }
+    if let Some(n) = raw.strip_prefix("f:") {
+        return n.parse().ok().map(KeyCode::F);
+    }
+    KEY_CODES.iter().find(|(_, name)| *name == raw).map(|(code, _)| *code)
+}
+
+const MOUSE_BUTTONS: [(MouseButton, &str); 3] = [
+    (MouseButton::Left, "left"),
+    (MouseButton::Right, "right"),
+    (MouseButton::Middle, "middle"),
+];
+
+fn encode_button(button: MouseButton) -> &'static str {
+    MOUSE_BUTTONS.iter().find(|(b, _)| *b == button).unwrap().1
+}
+
+fn decode_button(raw: &str) -> Option<MouseButton> {
+    MOUSE_BUTTONS.iter().find(|(_, name)| *name == raw).map(|(button, _)| *button)
+}
+
+fn encode_mouse_kind(kind: MouseEventKind) -> String {
+    match kind {
+        MouseEventKind::Down(b) => format!("down:{}", encode_button(b)),
+        MouseEventKind::Up(b) => format!("up:{}", encode_button(b)),
+        MouseEventKind::Drag(b) => format!("drag:{}", encode_button(b)),
+        MouseEventKind::Moved => "moved".to_string(),
+        MouseEventKind::ScrollDown => "scroll-down".to_string(),
+        MouseEventKind::ScrollUp => "scroll-up".to_string(),
+        MouseEventKind::ScrollLeft => "scroll-left".to_string(),
+        MouseEventKind::ScrollRight => "scroll-right".to_string(),
+    }
+}
+
+fn decode_mouse_kind(raw: &str) -> Option<MouseEventKind> {
+    let kind = match raw {
+        "moved" => MouseEventKind::Moved,
+        "scroll-down" => MouseEventKind::ScrollDown,
+        "scroll-up" => MouseEventKind::ScrollUp,
+        "scroll-left" => MouseEventKind::ScrollLeft,
+        "scroll-right" => MouseEventKind::ScrollRight,
+        raw => {
+            let (kind, button) = raw.split_once(':')?;
+            let button = decode_button(button)?;
+            match kind {
+                "down" => MouseEventKind::Down(button),
+                "up" => MouseEventKind::Up(button),
+                "drag" => MouseEventKind::Drag(button),
+                _ => return None,
+            }
+        }
+    };
+    Some(kind)
+}
+
+/// Escape the characters that would break the line based format.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recordings_round_trip() {
+        let events = [
+            Event::Resize(80, 24),
+            Event::Key(KeyEvent::new(KeyCode::Char('\t'), KeyModifiers::CONTROL)),
+            Event::Key(KeyEvent::new(KeyCode::F(5), KeyModifiers::SHIFT | KeyModifiers::ALT)),
+            Event::Paste("a\\b\nc".into()),
+            Event::KeyRelease(KeyEvent::new_with_kind(
+                KeyCode::Up,
+                KeyModifiers::NONE,
+                KeyEventKind::Release,
+            )),
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Drag(MouseButton::Middle),
+                column: 3,
+                row: 7,
+                modifiers: KeyModifiers::NONE,
+            }),
+            Event::MouseClick(MouseClick {
+                button: MouseButton::Left,
+                column: 3,
+                row: 7,
+                modifiers: KeyModifiers::SHIFT,
+                count: 2,
+            }),
+            Event::MouseDrag(MouseDrag {
+                button: MouseButton::Right,
+                phase: DragPhase::Update,
+                origin_column: 3,
+                origin_row: 7,
+                column: 1,
+                row: 9,
+                delta_column: -2,
+                delta_row: 1,
+                modifiers: KeyModifiers::CONTROL,
+            }),
+            Event::FocusLost,
+        ];
+
+        let mut recording = Recording::default();
+        for (i, event) in events.iter().enumerate() {
+            assert!(recording.push(Duration::from_millis(i as u64 * 100), event.clone()));
+        }
+        assert!(!recording.push(Duration::ZERO, Event::Tick));
+
+        let parsed: Recording = recording.to_string().parse().unwrap();
+        assert_eq!(format!("{:?}", parsed.events()), format!("{:?}", recording.events()));
+        assert!("0\tkey\tnope\t0".parse::<Recording>().is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use {
+    super::events::{Action, NoAction, UserAction},
+    std::{future::Future, time::Duration},
+    tokio::sync::mpsc::UnboundedSender,
+    tokio_util::sync::CancellationToken,
+};
+
+/// A retry policy with exponential backoff for flaky async work, like network calls.
+///
+/// When given the action sender of a component (see [Retry::with_sender]), the attempts are
+/// reported to the app as [Action::AppAction] messages, which components receive through
+/// [Component::receive_message](crate::Component::receive_message):
+/// - `task:<name>:retrying:<n>` before the `n`th attempt (so from the second one)
+/// - `task:<name>:done` when an attempt succeeds
+/// - `task:<name>:failed` when the last attempt fails
+///
+/// ```ignore
+/// let retry = Retry::new("fetch").with_max_attempts(5).with_sender(tx.clone());
+///
+/// tokio::spawn(async move {
+///     let body = retry.run(|_attempt| reqwest::get("https://lucode.ar")).await;
+/// });
+/// ```
+///
+/// By default, the work is tried 3 times, waiting 200ms before the first retry and doubling the
+/// wait on each retry, up to 10s.
+#[derive(Clone, Debug)]
+pub struct Retry<A = NoAction> {
+    name: String,
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    tx: Option<UnboundedSender<Action<A>>>,
+    token: Option<CancellationToken>,
+}
+
+impl<A: UserAction> Retry<A> {
+    /// Create a policy for the work with the given name, used in the status messages.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            tx: None,
+            token: None,
+        }
+    }
+
+    /// Set the number of times the work is tried, including the first attempt. Defaults to 3.
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the wait before the first retry, and the longest wait between retries.
+    pub fn with_backoff(mut self, initial_delay: Duration, max_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self.max_delay = max_delay.max(initial_delay);
+        self
+    }
+
+    /// Set the factor the wait grows by on each retry. Defaults to 2, use 1 for a fixed wait.
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Report the attempts to the app through the given action sender.
+    pub fn with_sender(mut self, tx: UnboundedSender<Action<A>>) -> Self {
+        self.tx = Some(tx);
+        self
+    }
+
+    /// Stop retrying once the given token is cancelled (e.g. when the app quits, see
+    /// [ComponentAccessors::cancellation_token](crate::ComponentAccessors::cancellation_token)).
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// The name of the work, used in the status messages.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The wait before the given retry (the first retry is `1`).
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.powi(retry.saturating_sub(1).min(i32::MAX as u32) as i32);
+        self.initial_delay.mul_f64(factor).min(self.max_delay)
+    }
+
+    /// Run the work until it succeeds or the attempts are exhausted, returning its last result.
+    /// The work is given the number of the attempt, starting at `1`.
+    ///
+    /// If the cancellation token is cancelled while waiting for a retry, the last error is
+    /// returned right away.
+    pub async fn run<T, E, F, Fut>(&self, mut work: F) -> Result<T, E>
+    where
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let error = match work(attempt).await {
+                Ok(value) => {
+                    self.report("done");
+                    return Ok(value);
+                }
+                Err(error) => error,
+            };
+
+            if attempt >= self.max_attempts || self.is_cancelled() {
+                self.report("failed");
+                return Err(error);
+            }
+
+            let wait = tokio::time::sleep(self.delay(attempt));
+            match &self.token {
+                Some(token) => tokio::select! {
+                    _ = token.cancelled() => {
+                        self.report("failed");
+                        return Err(error);
+                    }
+                    _ = wait => {}
+                },
+                None => wait.await,
+            }
+
+            attempt += 1;
+            self.report(&format!("retrying:{attempt}"));
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.token.as_ref().is_some_and(CancellationToken::is_cancelled)
+    }
+
+    fn report(&self, status: &str) {
+        if let Some(tx) = &self.tx {
+            // the app may be gone already, there's nobody to tell then
+            let _ = tx.send(Action::AppAction(format!("task:{}:{status}", self.name)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tokio::sync::mpsc};
+
+    #[tokio::test]
+    async fn retries_with_backoff_and_reports_the_attempts() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Action<NoAction>>();
+        let retry = Retry::new("fetch")
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(3))
+            .with_max_attempts(4)
+            .with_sender(tx);
+
+        assert_eq!(retry.delay(1), Duration::from_millis(1));
+        assert_eq!(retry.delay(2), Duration::from_millis(2));
+        assert_eq!(retry.delay(5), Duration::from_millis(3));
+
+        let result = retry
+            .run(|attempt| async move {
+                if attempt < 3 {
+                    Err(attempt)
+                } else {
+                    Ok(attempt)
+                }
+            })
+            .await;
+        assert_eq!(result, Ok(3));
+
+        let failed: Result<(), &str> = retry.run(|_| async { Err("offline") }).await;
+        assert_eq!(failed, Err("offline"));
+
+        let mut messages = vec![];
+        while let Ok(Action::AppAction(message)) = rx.try_recv() {
+            messages.push(message);
+        }
+        assert_eq!(
+            messages,
+            [
+                "task:fetch:retrying:2",
+                "task:fetch:retrying:3",
+                "task:fetch:done",
+                "task:fetch:retrying:2",
+                "task:fetch:retrying:3",
+                "task:fetch:retrying:4",
+                "task:fetch:failed",
+            ]
+        );
+    }
+}
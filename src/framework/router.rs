@@ -0,0 +1,180 @@
+use {
+    super::{component::ComponentHandler, events::UserAction, focus::FocusManager},
+    std::collections::HashMap,
+};
+
+/// The navigation stack of the screens of the [App](crate::App) (see
+/// [App::with_screen](crate::App::with_screen)).
+///
+/// Screens are root components that are only active and visible while they are at the top of
+/// the stack, so only the top screen is drawn and receives events.
+#[derive(Debug, Default)]
+pub(crate) struct Router {
+    /// index of the component handler of each screen
+    screens: HashMap<String, usize>,
+    stack: Vec<String>,
+}
+
+impl Router {
+    /// Register the screen with the given name, drawn by the component handler at `index`.
+    pub(crate) fn register(&mut self, name: String, index: usize) {
+        self.screens.insert(name, index);
+    }
+
+    /// Returns the name of the screen at the top of the stack, if any.
+    pub(crate) fn current(&self) -> Option<&str> {
+        self.stack.last().map(String::as_str)
+    }
+
+    /// Push the screen with the given name on top of the stack. Unknown screens are ignored.
+    pub(crate) fn push<A: UserAction>(
+        &mut self,
+        name: &str,
+        handlers: &mut [ComponentHandler<A>],
+        focus: &mut FocusManager,
+    ) {
+        if !self.screens.contains_key(name) {
+            return;
+        }
+
+        self.leave_current(handlers, focus);
+        self.stack.push(name.to_string());
+        self.enter_current(handlers);
+    }
+
+    /// Pop the screen at the top of the stack, going back to the previous one. The last screen is
+    /// never popped.
+    pub(crate) fn pop<A: UserAction>(
+        &mut self,
+        handlers: &mut [ComponentHandler<A>],
+        focus: &mut FocusManager,
+    ) {
+        if self.stack.len() < 2 {
+            return;
+        }
+
+        self.leave_current(handlers, focus);
+        self.stack.pop();
+        self.enter_current(handlers);
+    }
+
+    /// Replace the screen at the top of the stack with the screen with the given name. Unknown
+    /// screens are ignored.
+    pub(crate) fn replace<A: UserAction>(
+        &mut self,
+        name: &str,
+        handlers: &mut [ComponentHandler<A>],
+        focus: &mut FocusManager,
+    ) {
+        if !self.screens.contains_key(name) {
+            return;
+        }
+
+        self.leave_current(handlers, focus);
+        self.stack.pop();
+        self.stack.push(name.to_string());
+        self.enter_current(handlers);
+    }
+
+    fn current_index(&self) -> Option<usize> {
+        self.current().and_then(|name| self.screens.get(name)).copied()
+    }
+
+    fn leave_current<A: UserAction>(
+        &mut self,
+        handlers: &mut [ComponentHandler<A>],
+        focus: &mut FocusManager,
+    ) {
+        let Some(index) = self.current_index() else {
+            return;
+        };
+
+        if focus.focused().is_some_and(|path| path.root == index) {
+            focus.focus(handlers, None);
+        }
+
+        let screen = handlers[index].component_mut();
+        screen.set_active(false);
+        screen.set_visible(false);
+        screen.on_screen_leave();
+    }
+
+    fn enter_current<A: UserAction>(&mut self, handlers: &mut [ComponentHandler<A>]) {
+        if let Some(index) = self.current_index() {
+            let screen = handlers[index].component_mut();
+            screen.set_visible(true);
+            screen.set_active(true);
+            screen.on_screen_enter();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{component, Component, ComponentAccessors, ComponentError, Frame},
+        ratatui::layout::Rect,
+    };
+
+    component! {
+        struct Screen {
+            entered: u32,
+            left: u32
+        }
+    }
+
+    impl Component for Screen {
+        fn on_screen_enter(&mut self) {
+            self.entered += 1;
+        }
+
+        fn on_screen_leave(&mut self) {
+            self.left += 1;
+        }
+
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    fn screen(
+        handlers: &mut [ComponentHandler<crate::NoAction>],
+        index: usize,
+    ) -> (bool, u32, u32) {
+        let screen = handlers[index].component_mut().downcast_ref::<Screen>().unwrap();
+        assert_eq!(screen.is_visible(), screen.is_active());
+        (screen.is_active(), screen.entered, screen.left)
+    }
+
+    #[test]
+    fn push_pop_and_replace_screens() {
+        let mut handlers =
+            (0..3).map(|_| ComponentHandler::for_(Box::new(Screen::default()))).collect::<Vec<_>>();
+        let mut focus = FocusManager::default();
+        let mut router = Router::default();
+        for (index, name) in ["home", "settings", "about"].into_iter().enumerate() {
+            router.register(name.to_string(), index);
+        }
+
+        router.push("home", &mut handlers, &mut focus);
+        router.push("settings", &mut handlers, &mut focus);
+        router.push("unknown", &mut handlers, &mut focus);
+        assert_eq!(router.current(), Some("settings"));
+        assert_eq!(screen(&mut handlers, 0), (false, 1, 1));
+        assert_eq!(screen(&mut handlers, 1), (true, 1, 0));
+
+        router.replace("about", &mut handlers, &mut focus);
+        assert_eq!(screen(&mut handlers, 1), (false, 1, 1));
+        assert_eq!(screen(&mut handlers, 2), (true, 1, 0));
+
+        router.pop(&mut handlers, &mut focus);
+        assert_eq!(router.current(), Some("home"));
+        assert_eq!(screen(&mut handlers, 0), (true, 2, 1));
+        assert_eq!(screen(&mut handlers, 2), (false, 1, 1));
+
+        // the last screen is never popped
+        router.pop(&mut handlers, &mut focus);
+        assert_eq!(router.current(), Some("home"));
+    }
+}
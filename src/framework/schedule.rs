@@ -0,0 +1,145 @@
+use {
+    super::events::{Action, UserAction},
+    std::time::Duration,
+    tokio::{sync::mpsc::UnboundedSender, task::JoinHandle, time::Instant},
+    tokio_util::sync::CancellationToken,
+};
+
+/// A delayed or recurring action, declared at the app level (see [App::after](crate::App::after),
+/// [App::every](crate::App::every) and `App::schedule_action`) or by a component (see
+/// [ComponentAccessors::send_after](crate::ComponentAccessors::send_after)).
+pub(crate) enum Schedule<A> {
+    /// Send the action once, when the delay elapses.
+    After(Duration, Action<A>),
+    /// Send the action every time the period elapses.
+    Every(Duration, Action<A>),
+    /// Send the action every time the cron expression matches (in local time).
+    #[cfg(feature = "cron")]
+    Cron(Box<croner::Cron>, Action<A>),
+}
+
+impl<A: UserAction> Schedule<A> {
+    /// Spawn a task sending the scheduled action through `tx` until the `token` is cancelled.
+    pub(crate) fn spawn(
+        &self,
+        tx: UnboundedSender<Action<A>>,
+        token: CancellationToken,
+    ) -> JoinHandle<()> {
+        match self {
+            Schedule::After(delay, action) => {
+                let delay = *delay;
+                let action = action.clone();
+                tokio::spawn(async move {
+                    tokio::select! {
+                        biased;
+                        _ = token.cancelled() => {}
+                        _ = tokio::time::sleep(delay) => {
+                            let _ = tx.send(action);
+                        }
+                    }
+                })
+            }
+            Schedule::Every(period, action) => {
+                let mut interval = tokio::time::interval_at(Instant::now() + *period, *period);
+                let action = action.clone();
+                tokio::spawn(async move {
+                    loop {
+                        tokio::select! {
+                            biased;
+                            _ = token.cancelled() => break,
+                            _ = interval.tick() => {
+                                if tx.send(action.clone()).is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                })
+            }
+            #[cfg(feature = "cron")]
+            Schedule::Cron(cron, action) => {
+                let cron = cron.clone();
+                let action = action.clone();
+                tokio::spawn(async move {
+                    loop {
+                        let now = chrono::Local::now();
+                        let Ok(next) = cron.find_next_occurrence(&now, false) else {
+                            break;
+                        };
+                        let delay = (next - now).to_std().unwrap_or_default();
+
+                        tokio::select! {
+                            biased;
+                            _ = token.cancelled() => break,
+                            _ = tokio::time::sleep(delay) => {
+                                if tx.send(action.clone()).is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::NoAction,
+        tokio::sync::mpsc::{self, error::TryRecvError},
+    };
+
+    fn message(message: &str) -> Action<NoAction> {
+        Action::AppAction(message.into())
+    }
+
+    #[tokio::test]
+    async fn sends_the_actions_until_cancelled() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let token = CancellationToken::new();
+        let period = Duration::from_millis(10);
+        Schedule::After(period, message("once")).spawn(tx.clone(), token.clone());
+        let every = Schedule::Every(period, message("again")).spawn(tx, token.clone());
+
+        let mut sent = vec![];
+        while sent.iter().filter(|a| **a == message("again")).count() < 3 {
+            sent.push(rx.recv().await.unwrap());
+        }
+        assert_eq!(sent.iter().filter(|a| **a == message("once")).count(), 1);
+
+        token.cancel();
+        every.await.unwrap();
+        while let Ok(action) = rx.try_recv() {
+            assert_eq!(action, message("again"));
+        }
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    }
+
+    #[tokio::test]
+    async fn skips_the_delayed_actions_cancelled_before() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let token = CancellationToken::new();
+        let after =
+            Schedule::After(Duration::from_millis(10), message("late")).spawn(tx, token.clone());
+        token.cancel();
+        after.await.unwrap();
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[cfg(feature = "cron")]
+    #[tokio::test]
+    async fn sends_the_actions_when_the_cron_matches() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let token = CancellationToken::new();
+        let every_second =
+            croner::Cron::new("* * * * * *").with_seconds_required().parse().unwrap();
+        Schedule::Cron(Box::new(every_second), message("cron")).spawn(tx, token.clone());
+
+        let sent = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await;
+        assert_eq!(sent.unwrap(), Some(message("cron")));
+        token.cancel();
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::{self, Debug},
+    sync::{atomic::AtomicBool, Arc, RwLock},
+};
+
+use tokio_util::sync::CancellationToken;
+
+/// A value shared between the components of the app.
+pub type Shared<T> = Arc<RwLock<T>>;
+
+/// A store of values shared between the components of the app, so sibling components can share
+/// data without serializing it into string messages.
+///
+/// Values are stored by type, so each type can be stored once. The store is registered into every
+/// component of the app (see [App::with_state](crate::App::with_state)) and can be reached from
+/// them through [ComponentAccessors::state](crate::ComponentAccessors::state):
+///
+/// ```ignore
+/// #[derive(Default)]
+/// struct Mates(u32);
+///
+/// let app = App::default().with_state(Mates::default());
+///
+/// // later, in any component
+/// self.state().write(|mates: &mut Mates| mates.0 += 1);
+/// let drank = self.state().read(|mates: &Mates| mates.0).unwrap_or_default();
+/// ```
+#[derive(Clone, Default)]
+pub struct AppState(Arc<RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>>);
+
+impl AppState {
+    /// Store a value, replacing the previous value of the same type, if any. Returns the shared
+    /// handle of the stored value.
+    pub fn insert<T: Send + Sync + 'static>(&self, value: T) -> Shared<T> {
+        let shared = Arc::new(RwLock::new(value));
+        self.0.write().unwrap().insert(TypeId::of::<T>(), Box::new(shared.clone()));
+        shared
+    }
+
+    /// Get the shared handle of the value of type `T`, if any.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<Shared<T>> {
+        self.0.read().unwrap().get(&TypeId::of::<T>())?.downcast_ref::<Shared<T>>().cloned()
+    }
+
+    /// Returns `true` if there is a value of type `T`.
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.0.read().unwrap().contains_key(&TypeId::of::<T>())
+    }
+
+    /// Read the value of type `T`, returning the result of `f`, or none if there is no such value.
+    pub fn read<T: Send + Sync + 'static, R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let shared = self.get::<T>()?;
+        let value = shared.read().unwrap();
+        Some(f(&value))
+    }
+
+    /// Modify the value of type `T`, returning the result of `f`, or none if there is no such
+    /// value.
+    pub fn write<T: Send + Sync + 'static, R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let shared = self.get::<T>()?;
+        let mut value = shared.write().unwrap();
+        Some(f(&mut value))
+    }
+}
+
+/// The value a component exits the app with (see
+/// [ComponentAccessors::exit_with](crate::ComponentAccessors::exit_with)), stored in the
+/// [AppState] until [App::run](crate::App::run) returns it.
+pub(crate) struct ExitValue<T>(pub(crate) Option<T>);
+
+/// The cancellation token of the app, stored in the [AppState] so components can reach it (see
+/// [ComponentAccessors::cancellation_token](crate::ComponentAccessors::cancellation_token)).
+pub(crate) struct AppCancellation(pub(crate) CancellationToken);
+
+/// Whether the app has to be drawn on the next frame, when it renders on demand (see
+/// [App::with_render_on_demand](crate::App::with_render_on_demand)).
+#[derive(Default)]
+pub(crate) struct RenderRequest(pub(crate) AtomicBool);
+
+/// The text of the status bar of the app (see
+/// [ComponentAccessors::set_status](crate::ComponentAccessors::set_status)).
+#[derive(Default)]
+pub(crate) struct StatusText(pub(crate) Option<String>);
+
+impl Debug for AppState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppState").field("len", &self.0.read().unwrap().len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Mates(u32);
+
+    #[test]
+    fn values_are_shared_between_clones() {
+        let state = AppState::default();
+        let other = state.clone();
+        assert_eq!(other.read(|m: &Mates| m.0), None);
+
+        state.insert(Mates(1));
+        other.write(|m: &mut Mates| m.0 += 1);
+        assert_eq!(state.read(|m: &Mates| m.0), Some(2));
+        assert!(other.contains::<Mates>());
+        assert!(!other.contains::<String>());
+    }
+}
@@ -0,0 +1,141 @@
+use {crate::utils::color::ColorDepth, ratatui::layout::Size, std::env};
+
+/// The capabilities of the terminal the app is running in, so components can adapt to it (e.g.
+/// falling back from a braille spinner to an ASCII one).
+///
+/// The [App](crate::App) detects them when it starts and stores them in the [AppState], keeping
+/// the size up to date. Components read them through
+/// [ComponentAccessors::terminal](crate::ComponentAccessors::terminal):
+///
+/// ```ignore
+/// let frames = if self.terminal().unicode { BRAILLE } else { ASCII };
+/// ```
+///
+/// [AppState]: crate::AppState
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TerminalInfo {
+    /// The size of the terminal.
+    pub size: Size,
+    /// The colors the terminal can show.
+    pub color_depth: ColorDepth,
+    /// Whether the terminal can probably show unicode symbols, guessed from the locale.
+    pub unicode: bool,
+    /// Whether the terminal supports the kitty keyboard protocol, which reports key releases and
+    /// disambiguates keys like `<ctrl-i>` and `<tab>`.
+    pub kitty_keyboard: bool,
+    /// Whether the terminal probably supports OSC 8 hyperlinks, guessed from the environment.
+    pub hyperlinks: bool,
+    /// The brightness of the background of the terminal, if known. Guessed from the `COLORFGBG`
+    /// variable some terminals (e.g. konsole, rxvt, iTerm2) set.
+    pub background: Option<Background>,
+}
+
+/// The brightness of the background of the terminal (see [TerminalInfo::background]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Background {
+    Dark,
+    Light,
+}
+
+impl TerminalInfo {
+    /// Detect the capabilities of the terminal. Querying the kitty keyboard protocol support
+    /// requires the terminal to be in raw mode.
+    pub fn detect(size: Size) -> Self {
+        Self {
+            size,
+            color_depth: ColorDepth::detect(),
+            unicode: detect_unicode(),
+            kitty_keyboard: crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false),
+            hyperlinks: detect_hyperlinks(),
+            background: env::var("COLORFGBG").ok().and_then(|value| parse_colorfgbg(&value)),
+        }
+    }
+
+    /// The capabilities of plain text output, like a pipe or a file: no colors, no hyperlinks and
+    /// no keyboard.
+    pub(crate) fn plain(size: Size) -> Self {
+        Self {
+            size,
+            color_depth: ColorDepth::Mono,
+            unicode: detect_unicode(),
+            kitty_keyboard: false,
+            hyperlinks: false,
+            background: None,
+        }
+    }
+}
+
+/// The brightness of the background from the value of `COLORFGBG`, like `15;0`: the foreground
+/// and the background colors, as indices of the terminal palette, sometimes with `default` in
+/// between. Backgrounds 0 to 6 and 8 are dark, the other ones light.
+fn parse_colorfgbg(value: &str) -> Option<Background> {
+    let background = value.rsplit(';').next()?.parse::<u8>().ok()?;
+    match background {
+        0..=6 | 8 => Some(Background::Dark),
+        7 | 9..=15 => Some(Background::Light),
+        _ => None,
+    }
+}
+
+/// Guess whether the terminal can show unicode symbols from the locale variables.
+fn detect_unicode() -> bool {
+    if cfg!(windows) {
+        return true;
+    }
+
+    // the linux console only supports a small set of symbols
+    if env::var("TERM").is_ok_and(|term| term == "linux" || term == "dumb") {
+        return false;
+    }
+
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .is_some_and(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+/// Guess whether the terminal supports OSC 8 hyperlinks from the variables set by the terminals
+/// known to support them. Unsupported terminals usually ignore them, but some print them as text.
+fn detect_hyperlinks() -> bool {
+    if env::var("TERM").is_ok_and(|term| term == "linux" || term == "dumb") {
+        return false;
+    }
+
+    let known_program = env::var("TERM_PROGRAM").is_ok_and(|program| {
+        ["iTerm.app", "WezTerm", "vscode", "ghostty", "Hyper", "Tabby", "rio"].contains(&&*program)
+    });
+    // gnome terminal, tilix and the other VTE based terminals support them since VTE 0.50
+    let vte = env::var("VTE_VERSION")
+        .ok()
+        .and_then(|version| version.parse::<u32>().ok())
+        .is_some_and(|version| version >= 5000);
+    let known_term = env::var("TERM").is_ok_and(|term| {
+        ["kitty", "alacritty", "foot", "wezterm"].iter().any(|t| term.contains(t))
+    });
+
+    known_program
+        || vte
+        || known_term
+        || ["WT_SESSION", "KITTY_WINDOW_ID", "KONSOLE_VERSION"]
+            .into_iter()
+            .any(|var| env::var_os(var).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_the_background_from_colorfgbg() {
+        assert_eq!(parse_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(parse_colorfgbg("0;default;15"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("12;8"), Some(Background::Dark));
+        assert_eq!(parse_colorfgbg("0;7"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("15;default"), None);
+        assert_eq!(parse_colorfgbg(""), None);
+    }
+}
@@ -0,0 +1,977 @@
+use {
+    super::{
+        app::{App, MatetuiError, SCROLL_AMOUNT},
+        component::{Component, ComponentHandler},
+        events::{Action, Event, NoAction, ScrollDirection, UserAction},
+        keyboard::parse_key_sequence,
+        state::{AppState, RenderRequest, StatusText},
+        terminal::TerminalInfo,
+        tui::{EventSender, Tui},
+    },
+    crate::utils::color::ColorDepth,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    ratatui::{
+        backend::TestBackend,
+        buffer::Buffer,
+        layout::{Position, Size},
+        Terminal,
+    },
+    std::marker::PhantomData,
+    tokio::sync::mpsc::{self, UnboundedReceiver},
+};
+
+/// A running [App] drawing to a ratatui [TestBackend] instead of the terminal, to drive it from
+/// unit tests (see [App::test]).
+///
+/// The app goes through its whole event loop, but only processes the events sent by the test:
+/// there are no ticks, renders or terminal events unless the test sends them. After each event,
+/// the pending actions are processed and the app is rendered, so the [buffer](TestApp::buffer)
+/// always shows the current state:
+///
+/// ```ignore
+/// #[tokio::test]
+/// async fn drinks_mate() -> Result<(), MatetuiError> {
+///     let mut app = App::default().with_components(components![Counter::default()]).test()?;
+///
+///     app.press("<d><d>")?;
+///     assert!(app.contains("mates: 2"));
+///
+///     app.press("<q>")?;
+///     assert!(app.has_quit());
+///     Ok(())
+/// }
+/// ```
+///
+/// Like the app itself, it must be used inside a tokio runtime.
+pub struct TestApp<A: UserAction = NoAction, R = ()> {
+    app: App<A, R>,
+    tui: Tui<TestBackend>,
+}
+
+impl<A: UserAction, R: Send + Sync + 'static> TestApp<A, R> {
+    pub(crate) fn new(mut app: App<A, R>, width: u16, height: u16) -> Result<Self, MatetuiError> {
+        app.prepare();
+        let mut tui = Tui::headless(TestBackend::new(width, height))?;
+        app.state().insert(test_terminal(width, height));
+        app.start(&mut tui)?;
+
+        let mut test = Self { app, tui };
+        test.settle()?;
+        Ok(test)
+    }
+
+    /// The sender of the event source of the app, to inject events from other tasks. The events
+    /// are processed on the next [TestApp::settle].
+    pub fn events(&self) -> EventSender {
+        self.tui.event_tx.clone()
+    }
+
+    /// Send an event to the app and process it.
+    pub fn send(&mut self, event: Event) -> Result<(), MatetuiError> {
+        // the receiver lives in the tui, so the send can't fail
+        let _ = self.tui.event_tx.send(event);
+        self.settle()
+    }
+
+    /// Press the keys of a key sequence, e.g. `"<ctrl-a><b>"` (see the keybindings syntax).
+    pub fn press(&mut self, keys: &str) -> Result<(), MatetuiError> {
+        for key in parse_key_sequence(keys)? {
+            self.send(Event::Key(key))?;
+        }
+        Ok(())
+    }
+
+    /// Type the given text, one key press per char.
+    pub fn type_text(&mut self, text: &str) -> Result<(), MatetuiError> {
+        for c in text.chars() {
+            self.send(Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)))?;
+        }
+        Ok(())
+    }
+
+    /// Send a tick to the app.
+    pub fn tick(&mut self) -> Result<(), MatetuiError> {
+        self.send(Event::Tick)
+    }
+
+    /// Resize the terminal of the app.
+    pub fn resize(&mut self, width: u16, height: u16) -> Result<(), MatetuiError> {
+        self.tui.backend_mut().resize(width, height);
+        self.send(Event::Resize(width, height))
+    }
+
+    /// Process the pending events and actions, then render the app (unless it renders on demand
+    /// and nothing changed).
+    ///
+    /// Once the app quits, the actions left are drained like when it runs (see
+    /// [App::run](crate::App::run)), and the app doesn't process events anymore.
+    pub fn settle(&mut self) -> Result<(), MatetuiError> {
+        if self.app.should_quit() {
+            return Ok(());
+        }
+
+        while let Ok((at, event)) = self.tui.event_rx.try_recv() {
+            self.app.handle_event(event, at, &mut self.tui)?;
+            self.app.handle_actions(&mut self.tui)?;
+            if self.app.should_quit() {
+                return self.app.drain_actions();
+            }
+        }
+        self.app.handle_actions(&mut self.tui)?;
+        if self.app.should_quit() {
+            return self.app.drain_actions();
+        }
+        self.app.render_frame(&mut self.tui)
+    }
+
+    /// The buffer the app was last rendered to.
+    pub fn buffer(&self) -> &Buffer {
+        self.tui.backend().buffer()
+    }
+
+    /// The text of each row of the rendered buffer.
+    pub fn lines(&self) -> Vec<String> {
+        buffer_lines(self.buffer())
+    }
+
+    /// Returns `true` if some row of the rendered buffer contains the given text.
+    pub fn contains(&self, text: &str) -> bool {
+        self.lines().iter().any(|line| line.contains(text))
+    }
+
+    /// Returns `true` once the app processed an [Action::Quit](crate::Action::Quit).
+    pub fn has_quit(&self) -> bool {
+        self.app.should_quit()
+    }
+
+    /// Take the value passed to
+    /// [ComponentAccessors::exit_with](crate::ComponentAccessors::exit_with), if any.
+    pub fn exit_value(&self) -> Option<R> {
+        self.app.exit_value()
+    }
+
+    /// The state shared by the components of the app.
+    pub fn state(&self) -> &AppState {
+        self.app.state()
+    }
+}
+
+/// A single component, outside of any [App], to unit test its logic: the harness registers a
+/// capturing action sender into the component (and its children), feeds it events and draws it to
+/// a ratatui [TestBackend].
+///
+/// ```ignore
+/// #[test]
+/// fn saves_on_ctrl_s() -> Result<(), MatetuiError> {
+///     let mut editor = ComponentHarness::new(Editor::default().as_active());
+///
+///     editor.type_text("mate");
+///     editor.press("<ctrl-s>")?;
+///     assert_eq!(editor.sent_messages(), ["editor:save"]);
+///
+///     editor.draw()?;
+///     assert!(editor.contains("mate"));
+///     Ok(())
+/// }
+/// ```
+///
+/// Like in an app, inactive components ignore the events. The actions the component sends, or
+/// returns from its handlers, are only captured: they aren't processed. Unlike [TestApp], the
+/// harness doesn't need a tokio runtime, unless the component spawns tasks.
+pub struct ComponentHarness<C, A: UserAction = NoAction> {
+    handler: ComponentHandler<A>,
+    terminal: Terminal<TestBackend>,
+    state: AppState,
+    action_rx: UnboundedReceiver<Action<A>>,
+    sent: Vec<Action<A>>,
+    component: PhantomData<C>,
+}
+
+impl<A: UserAction, C: Component<A>> ComponentHarness<C, A> {
+    /// Wrap the component, in a terminal of 80x24 cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component fails to initialize.
+    pub fn new(component: C) -> Self {
+        Self::with_size(component, 80, 24)
+    }
+
+    /// Wrap the component, in a terminal of the given size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component fails to initialize.
+    pub fn with_size(component: C, width: u16, height: u16) -> Self {
+        let mut handler = ComponentHandler::for_(Box::new(component));
+        let (action_tx, action_rx) = mpsc::unbounded_channel();
+        let state = AppState::default();
+        state.insert(test_terminal(width, height));
+        state.insert(RenderRequest::default());
+        state.insert(StatusText::default());
+
+        handler.receive_action_handler(action_tx);
+        handler.receive_state(state.clone());
+        handler.handle_init(Size::new(width, height)).unwrap();
+
+        // the backend can't fail
+        let terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        Self {
+            handler,
+            terminal,
+            state,
+            action_rx,
+            sent: vec![],
+            component: PhantomData,
+        }
+    }
+
+    /// The wrapped component.
+    pub fn component(&self) -> &C {
+        // the handler only ever wraps a `C`
+        self.handler.component().downcast_ref().unwrap()
+    }
+
+    /// The wrapped component, to change its state between events.
+    pub fn component_mut(&mut self) -> &mut C {
+        // the handler only ever wraps a `C`
+        self.handler.component_mut().downcast_mut().unwrap()
+    }
+
+    /// The state shared with the component (see [AppState]).
+    pub fn state(&self) -> &AppState {
+        &self.state
+    }
+
+    /// The status the component set (see
+    /// [ComponentAccessors::set_status](crate::ComponentAccessors::set_status)).
+    pub fn status(&self) -> Option<String> {
+        self.state.read(|text: &StatusText| text.0.clone()).flatten()
+    }
+
+    /// Send an event to the component and its children. Mouse events only go to the component
+    /// under the pointer, like in an app, so the component must be [drawn](Self::draw) first.
+    pub fn send(&mut self, event: Event) {
+        let actions = match event {
+            Event::Mouse(mouse) => {
+                let position = Position::new(mouse.column, mouse.row);
+                let path = self.handler.component_at(position);
+                let action = path.and_then(|path| match ScrollDirection::from_mouse(mouse.kind) {
+                    Some(direction) => self.handler.handle_scroll(&path, direction, SCROLL_AMOUNT),
+                    None => self.handler.handle_mouse(&path, mouse),
+                });
+                action.into_iter().collect()
+            }
+            Event::MouseClick(click) => {
+                let path = self.handler.component_at(Position::new(click.column, click.row));
+                let action = path.and_then(|path| self.handler.handle_click(&path, click));
+                action.into_iter().collect()
+            }
+            Event::MouseDrag(drag) => {
+                let origin = Position::new(drag.origin_column, drag.origin_row);
+                let path = self.handler.component_at(origin);
+                let action = path.and_then(|path| self.handler.handle_drag(&path, drag));
+                action.into_iter().collect()
+            }
+            event => self.handler.handle_events(Some(event)),
+        };
+        self.collect();
+        self.sent.extend(actions);
+    }
+
+    /// Press the keys of a key sequence, e.g. `"<ctrl-a><b>"` (see the keybindings syntax).
+    pub fn press(&mut self, keys: &str) -> Result<(), MatetuiError> {
+        for key in parse_key_sequence(keys)? {
+            self.send(Event::Key(key));
+        }
+        Ok(())
+    }
+
+    /// Type the given text, one key press per char.
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.send(Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)));
+        }
+    }
+
+    /// Send `n` ticks to the component.
+    pub fn tick(&mut self, n: usize) {
+        for _ in 0..n {
+            self.send(Event::Tick);
+        }
+    }
+
+    /// Resize the terminal of the component, sending it the resize event.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.terminal.backend_mut().resize(width, height);
+        self.send(Event::Resize(width, height));
+    }
+
+    /// Deliver a message to the component and its children, like when it's broadcast by the app.
+    pub fn message(&mut self, message: &str) {
+        self.handler.handle_message(message.to_string());
+        self.collect();
+    }
+
+    /// Update the component and its children with the given action.
+    pub fn update(&mut self, action: Action<A>) -> Result<(), MatetuiError> {
+        self.handler.handle_update(action)?;
+        self.collect();
+        Ok(())
+    }
+
+    /// Draw the component over the whole terminal.
+    pub fn draw(&mut self) -> Result<&Buffer, MatetuiError> {
+        let mut result = Ok(());
+        let handler = &mut self.handler;
+        self.terminal.draw(|f| result = handler.handle_draw(f, f.area()))?;
+        result?;
+        self.collect();
+        Ok(self.drawn_buffer())
+    }
+
+    /// The buffer the component was last drawn to.
+    pub fn drawn_buffer(&self) -> &Buffer {
+        self.terminal.backend().buffer()
+    }
+
+    /// The text of each row of the drawn buffer.
+    pub fn lines(&self) -> Vec<String> {
+        buffer_lines(self.drawn_buffer())
+    }
+
+    /// Returns `true` if some row of the drawn buffer contains the given text.
+    pub fn contains(&self, text: &str) -> bool {
+        self.lines().iter().any(|line| line.contains(text))
+    }
+
+    /// The actions the component sent, or returned from its handlers, in order.
+    pub fn sent_actions(&mut self) -> &[Action<A>] {
+        self.collect();
+        &self.sent
+    }
+
+    /// The messages the component sent (see
+    /// [ComponentAccessors::send](crate::ComponentAccessors::send)), in order.
+    pub fn sent_messages(&mut self) -> Vec<String> {
+        self.sent_actions()
+            .iter()
+            .filter_map(|action| match action {
+                Action::AppAction(message) => Some(message.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Forget the actions sent so far.
+    pub fn clear_sent(&mut self) {
+        self.collect();
+        self.sent.clear();
+    }
+
+    fn collect(&mut self) {
+        while let Ok(action) = self.action_rx.try_recv() {
+            self.sent.push(action);
+        }
+    }
+}
+
+/// The capabilities of the terminal of the tests.
+fn test_terminal(width: u16, height: u16) -> TerminalInfo {
+    TerminalInfo {
+        size: Size::new(width, height),
+        color_depth: ColorDepth::TrueColor,
+        unicode: true,
+        kitty_keyboard: false,
+        hyperlinks: false,
+        background: None,
+    }
+}
+
+/// The text of each row of a buffer.
+fn buffer_lines(buffer: &Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| (area.left()..area.right()).map(|x| buffer[(x, y)].symbol()).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            component, components, framework::state::AppCancellation, kb, kb_scoped, Action,
+            Component, ComponentAccessors, ComponentError, External, Frame, InputLatency, Layer,
+            Message, QuitDecision,
+        },
+        crossterm::event::{KeyEvent, KeyEventKind},
+        ratatui::{
+            layout::{Constraint, Layout, Rect, Size},
+            widgets::Paragraph,
+        },
+        std::{
+            sync::{Arc, Mutex},
+            time::Duration,
+        },
+        tokio_util::sync::CancellationToken,
+    };
+
+    component! {
+        struct Counter {
+            mates: u32,
+            frames: u32
+        }
+    }
+
+    impl Component for Counter {
+        fn receive_message(&mut self, message: String) {
+            if message == "drink" {
+                self.mates += 1;
+                self.set_status(&format!("{} mates drunk", self.mates));
+            }
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            self.frames += 1;
+            let text = format!("mates: {} frames: {}", self.mates, self.frames);
+            f.render_widget(Paragraph::new(text), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Badge {
+            row: u16,
+            keys: u32
+        }
+    }
+
+    impl Component for Badge {
+        fn handle_key_events(&mut self, _: KeyEvent) -> Option<Action> {
+            self.keys += 1;
+            None
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            let text = format!("[keys: {}]", self.keys);
+            let area = Rect::new(area.x, area.y + self.row, 9, 1).intersection(area);
+            f.render_widget(Paragraph::new(text), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Editor {
+            asked: u32
+        }
+    }
+
+    impl Component for Editor {
+        fn on_quit_requested(&mut self) -> QuitDecision {
+            self.asked += 1;
+            QuitDecision::Veto
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("asked: {}", self.asked)), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Picker {}
+    }
+
+    impl Component for Picker {
+        fn receive_message(&mut self, message: String) {
+            if message == "pick" {
+                self.exit_with("mate".to_string());
+            }
+        }
+
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Grid {
+            columns: u16
+        }
+    }
+
+    impl Component for Grid {
+        fn handle_resize_event(&mut self, size: Size) -> Option<Action> {
+            self.columns = size.width / 10;
+            None
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("columns: {}", self.columns)), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Notes {
+            log: Arc<Mutex<Vec<String>>>
+        }
+    }
+
+    impl Component for Notes {
+        fn receive_message(&mut self, message: String) {
+            if message == "quit-soon" {
+                self.send_action(Action::Quit);
+                self.send("save");
+                self.send_action(Action::FocusNext);
+            }
+            self.log.lock().unwrap().push(message);
+        }
+
+        fn update(&mut self, action: &Action) -> Result<(), ComponentError> {
+            if *action == Action::Quit {
+                self.log.lock().unwrap().push("quit".into());
+            }
+            Ok(())
+        }
+
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Till {
+            mates: u32
+        }
+    }
+
+    impl Component for Till {
+        fn receive_typed_message(&mut self, message: &Message) {
+            if let Some(mates) = message.get::<u32>("order") {
+                self.mates += mates;
+            }
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("ordered: {}", self.mates)), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Launcher {
+            heard: Vec<String>
+        }
+    }
+
+    impl Component for Launcher {
+        fn receive_message(&mut self, message: String) {
+            match message.as_str() {
+                "edit" => self
+                    .run_external(External::new("sh").args(["-c", "exit 3"]).with_name("editor")),
+                _ => self.heard.push(message),
+            }
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(self.heard.join(",")), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Pointer {
+            clicks: Vec<String>
+        }
+    }
+
+    impl Component for Pointer {
+        fn handle_key_release_events(&mut self, key: KeyEvent) -> Option<Action> {
+            self.clicks.push(format!("release:{}", key.code));
+            None
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("[{}]", self.clicks.join(","))), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Poller {
+            polls: u32,
+            timer: Option<CancellationToken>
+        }
+    }
+
+    impl Component for Poller {
+        fn subscriptions(&self) -> Option<Vec<&str>> {
+            Some(vec!["poll", "app:*"])
+        }
+
+        fn receive_message(&mut self, message: String) {
+            match message.as_str() {
+                "app:start" => {
+                    self.timer = Some(self.send_every("poll", Duration::from_millis(10)))
+                }
+                "app:stop" => self.timer.take().unwrap().cancel(),
+                "app:once" => drop(self.send_after("poll", Duration::from_millis(10))),
+                _ => self.polls += 1,
+            }
+        }
+
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("polls: {}", self.polls)), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Pane {
+            index: u16
+        }
+    }
+
+    impl Component for Pane {
+        fn tab_index(&self) -> Option<u16> {
+            Some(self.index)
+        }
+
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Panes {},
+        children => {
+            "editor" => Pane { index: 0, ..Pane::default() }.as_active(),
+            "list" => Pane { index: 1, ..Pane::default() }.as_active()
+        }
+    }
+
+    impl Component for Panes {
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn drives_the_app_headless() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<d>" => "drink", "<q>" => Action::Quit])
+            .with_components(components![Counter::default().as_active()])
+            .test()?;
+        assert_eq!(app.lines()[0].trim_end(), "mates: 0 frames: 1");
+
+        app.press("<d><d>")?;
+        assert!(app.contains("mates: 2"));
+        assert_eq!(app.state().read(|l: &InputLatency| l.samples), Some(2));
+
+        app.resize(20, 2)?;
+        assert_eq!(app.lines().len(), 2);
+        assert!(app.contains("mates: 2"));
+
+        let token = app.state().read(|c: &AppCancellation| c.0.child_token()).unwrap();
+        app.press("<q>")?;
+        assert!(app.has_quit());
+        assert!(token.is_cancelled());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn runs_on_a_custom_backend() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<d>" => "drink", "<q>" => Action::Quit])
+            .with_components(components![Counter::default().as_active()]);
+
+        let mut tui = Tui::headless(TestBackend::new(20, 2))?;
+        let key = |keys| Event::Key(parse_key_sequence(keys).unwrap()[0]);
+        for event in [key("<d>"), key("<d>"), Event::Render, key("<q>")] {
+            tui.event_tx.send(event).unwrap();
+        }
+
+        assert_eq!(app.run_with(&mut tui).await?, None);
+        assert!(buffer_lines(tui.backend().buffer())[0].starts_with("mates: 2"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn renders_on_demand() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<d>" => "drink"])
+            .with_components(components![Counter::default().as_active()])
+            .with_render_on_demand(true)
+            .test()?;
+        assert!(app.contains("frames: 1"));
+
+        app.tick()?;
+        app.send(Event::Render)?;
+        assert!(app.contains("frames: 1"));
+
+        app.press("<d>")?;
+        assert!(app.contains("mates: 1 frames: 2"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn draws_the_status_bar_below_the_components() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<d>" => "drink"])
+            .with_components(components![Badge {
+                row: 1,
+                ..Badge::default()
+            }
+            .as_active()])
+            .with_layer(Layer::Overlay, components![Counter::default().as_active()])
+            .with_status_bar(true)
+            .test()?;
+        app.resize(20, 2)?;
+        assert!(app.lines()[0].starts_with("mates: 0"));
+        assert_eq!(app.lines()[1].trim(), "");
+
+        // the badge on the second row doesn't fit above the status bar
+        app.press("<d><d>")?;
+        assert_eq!(app.lines()[1], "2 mates drunk       ");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn scoped_keybindings_follow_the_focus() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<d>" => "drink", "<tab>" => Action::FocusNext])
+            .with_scoped_keybindings(kb_scoped! { "list" => { "<d>" => "spill" } })
+            .with_components(components![
+                Counter::default().as_active(),
+                Panes::default().as_active()
+            ])
+            .test()?;
+
+        // the editor has no scoped keybindings, so the global one fires
+        app.press("<tab><d>")?;
+        assert!(app.contains("mates: 1"));
+
+        app.press("<tab><d>")?;
+        assert!(app.contains("mates: 1"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn layers_are_drawn_in_order_and_route_input() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<d>" => "drink"])
+            .with_layer(Layer::Overlay, components![Badge::default().as_active()])
+            .with_components(components![Counter::default().as_active()])
+            .with_layer(
+                Layer::Background,
+                components![Badge {
+                    row: 1,
+                    ..Badge::default()
+                }
+                .as_active()],
+            )
+            .with_layer_input(Layer::Background, true)
+            .test()?;
+
+        // the overlay is drawn over the counter, and only the background receives the keys
+        app.press("<d>")?;
+        assert_eq!(app.lines()[0].trim_end(), "[keys: 0]frames: 2");
+        assert_eq!(app.lines()[1].trim_end(), "[keys: 1]");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn components_can_veto_quitting() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<q>" => Action::Quit, "<ctrl-q>" => Action::ForceQuit])
+            .with_components(components![Editor::default().as_active()])
+            .test()?;
+
+        app.press("<q>")?;
+        assert!(!app.has_quit());
+        assert!(app.contains("asked: 1"));
+
+        app.press("<ctrl-q>")?;
+        assert!(app.has_quit());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn drops_the_exit_value_of_a_vetoed_quit() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .returning::<String>()
+            .with_keybindings(kb!["<p>" => "pick", "<ctrl-q>" => Action::ForceQuit])
+            .with_components(components![
+                Picker::default().as_active(),
+                Editor::default().as_active()
+            ])
+            .test()?;
+
+        app.press("<p>")?;
+        assert!(!app.has_quit());
+
+        app.press("<ctrl-q>")?;
+        assert!(app.has_quit());
+        assert_eq!(app.exit_value(), None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn components_recompute_their_layout_on_resize() -> Result<(), MatetuiError> {
+        let mut app =
+            App::default().with_components(components![Grid::default().as_active()]).test()?;
+        assert!(app.contains("columns: 0"));
+
+        app.resize(40, 2)?;
+        assert!(app.contains("columns: 4"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delivers_typed_messages() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb![
+                "<o>" => Action::Message(Message::new("order", 2u32)),
+                "<x>" => Action::Message(Message::new("order", "two"))
+            ])
+            .with_components(components![Till::default().as_active()])
+            .test()?;
+
+        app.press("<o><x><o>")?;
+        assert!(app.contains("ordered: 4"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lays_out_the_root_components() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<d>" => Action::SendTo("Counter".into(), "drink".into())])
+            .with_components(components![
+                Counter::default().as_active(),
+                Grid::default().as_active()
+            ])
+            .with_layout(Layout::horizontal([Constraint::Length(24), Constraint::Fill(1)]))
+            .test()?;
+
+        app.press("<d>")?;
+        assert_eq!(app.lines()[0].trim_end(), "mates: 1 frames: 2      columns: 0");
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_external_programs() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<e>" => "edit"])
+            .with_components(components![Launcher::default().as_active()])
+            .test()?;
+
+        app.press("<e>")?;
+        assert_eq!(app.lines()[0].trim_end(), "external:editor:exited:3");
+        Ok(())
+    }
+
+    #[cfg(feature = "metrics")]
+    #[tokio::test]
+    async fn measures_the_frames() -> Result<(), MatetuiError> {
+        use crate::AppMetrics;
+
+        let mut app = App::default()
+            .with_keybindings(kb!["<d>" => "drink"])
+            .with_components(components![Counter::default().as_active()])
+            .with_frame_budget(Duration::ZERO)
+            .test()?;
+        app.press("<d>")?;
+
+        let metrics = app.state().read(AppMetrics::clone).unwrap();
+        assert_eq!(metrics.frames, 2);
+        assert_eq!(metrics.slow_frames, 2);
+        assert_eq!(metrics.slowest_component().map(|(name, _)| name), Some("Counter"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn repeats_and_releases_the_keys() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<d>" => "drink"])
+            .with_components(components![
+                Counter::default().as_active(),
+                Pointer::default().as_active()
+            ])
+            .with_layout(Layout::horizontal([Constraint::Length(40), Constraint::Fill(1)]))
+            .with_key_releases(true)
+            .test()?;
+        let key = |kind| KeyEvent::new_with_kind(KeyCode::Char('d'), KeyModifiers::NONE, kind);
+
+        app.send(Event::Key(key(KeyEventKind::Press)))?;
+        app.send(Event::Key(key(KeyEventKind::Repeat)))?;
+        app.send(Event::KeyRelease(key(KeyEventKind::Release)))?;
+        assert!(app.contains("mates: 2"));
+        assert!(app.contains("[release:d]"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sends_delayed_and_recurring_actions() -> Result<(), MatetuiError> {
+        let mut app = App::default()
+            .with_keybindings(kb!["<s>" => "app:start", "<x>" => "app:stop", "<o>" => "app:once"])
+            .with_components(components![Poller::default().as_active()])
+            .after(Duration::from_millis(10), "poll")
+            .test()?;
+        let wait = || tokio::time::sleep(Duration::from_millis(50));
+
+        wait().await;
+        app.settle()?;
+        assert!(app.contains("polls: 1"));
+
+        app.press("<s>")?;
+        wait().await;
+        app.settle()?;
+        app.press("<x>")?;
+        let polls: u32 = app.lines()[0].trim_end()[7..].parse().unwrap();
+        assert!(polls >= 3, "polled {polls} times");
+
+        app.press("<o>")?;
+        wait().await;
+        app.settle()?;
+        assert!(app.contains(&format!("polls: {}", polls + 1)));
+        Ok(())
+    }
+
+    #[test]
+    fn unit_tests_a_single_component() -> Result<(), MatetuiError> {
+        let mut notes = ComponentHarness::with_size(Notes::default().as_active(), 20, 1);
+        notes.message("quit-soon");
+        assert_eq!(notes.sent_messages(), ["save"]);
+        assert_eq!(notes.sent_actions()[2], Action::FocusNext);
+        assert_eq!(*notes.component().log.lock().unwrap(), ["quit-soon"]);
+
+        notes.clear_sent();
+        notes.update(Action::Quit)?;
+        assert!(notes.sent_actions().is_empty());
+
+        let mut counter = ComponentHarness::with_size(Counter::default().as_active(), 20, 1);
+        counter.message("drink");
+        counter.component_mut().mates += 1;
+        counter.draw()?;
+        assert_eq!(counter.lines(), ["mates: 2 frames: 1  "]);
+        assert_eq!(counter.status().as_deref(), Some("1 mates drunk"));
+
+        // inactive, the editor is only drawn once shown, and doesn't receive the keys
+        let mut editor = ComponentHarness::new(Editor::default());
+        editor.press("<q>")?;
+        editor.draw()?;
+        assert!(!editor.contains("asked"));
+
+        editor.component_mut().set_visible(true);
+        editor.draw()?;
+        assert!(editor.contains("asked: 0"));
+
+        editor.component_mut().set_visible(false);
+        editor.draw()?;
+        assert!(!editor.contains("asked"));
+        Ok(())
+    }
+}
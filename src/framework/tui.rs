@@ -1,27 +1,56 @@
 use {
-    super::events::Event,
+    super::events::{DragPhase, Event, MouseClick, MouseDrag},
     crossterm::{
-        cursor,
+        cursor::{self, SetCursorStyle},
         event::{
-            DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
-            Event as CrosstermEvent, KeyEventKind,
+            DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+            EnableFocusChange, EnableMouseCapture, Event as CrosstermEvent, KeyEventKind,
+            KeyboardEnhancementFlags, MouseButton, MouseEvent, MouseEventKind,
+            PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
         },
-        terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen, SetTitle},
+        Command,
     },
-    futures::{FutureExt, StreamExt},
-    ratatui::backend::CrosstermBackend as Backend,
+    futures::{
+        stream::{self, BoxStream, SelectAll},
+        FutureExt, StreamExt,
+    },
+    ratatui::backend::{Backend, CrosstermBackend},
     std::{
         ops::{Deref, DerefMut},
-        time::Duration,
+        sync::Arc,
+        time::{Duration, Instant},
     },
     tokio::{
-        sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+        sync::{
+            mpsc::{self, error::SendError, UnboundedReceiver, UnboundedSender},
+            watch, Notify,
+        },
         task::JoinHandle,
     },
     tokio_util::sync::CancellationToken,
 };
 
 pub type IO = std::io::Stdout;
+
+/// How long [Tui::stop] waits for the event loop to finish before aborting it.
+const STOP_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The default of [Tui::click_interval].
+pub(crate) const DEFAULT_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The sending half of the event channel of a [Tui]. Each event is stamped with the (monotonic)
+/// instant it was sent, so the app can measure how long it takes to react to it (see
+/// [InputLatency](crate::InputLatency)).
+#[derive(Clone, Debug)]
+pub struct EventSender(UnboundedSender<(Instant, Event)>);
+
+impl EventSender {
+    /// Send an event, stamped with the current instant.
+    pub fn send(&self, event: Event) -> Result<(), SendError<Event>> {
+        self.0.send((Instant::now(), event)).map_err(|SendError((_, event))| SendError(event))
+    }
+}
 fn io() -> IO {
     std::io::stdout()
 }
@@ -35,28 +64,74 @@ pub type Frame<'a> = ratatui::Frame<'a>;
 ///   [raw mode](https://docs.rs/crossterm/0.28.1/crossterm/terminal/index.html#raw-mode)
 /// - Mapping of crossterm events to [Event]s
 /// - Emits [Event::Tick] and [Event::Render] events at a specified rate
-pub struct Tui {
-    pub terminal: ratatui::Terminal<Backend<IO>>,
+///
+/// It draws through crossterm by default, or through any backend given to [Tui::with_backend]. A
+/// [headless](Tui::headless) Tui draws to any backend (e.g. ratatui's `TestBackend`) without
+/// touching the real terminal, and only emits the events sent through its `event_tx`.
+pub struct Tui<B: Backend = CrosstermBackend<IO>> {
+    pub terminal: ratatui::Terminal<B>,
     pub task: JoinHandle<()>,
     pub cancellation_token: CancellationToken,
-    pub event_rx: UnboundedReceiver<Event>,
-    pub event_tx: UnboundedSender<Event>,
+    pub event_rx: UnboundedReceiver<(Instant, Event)>,
+    pub event_tx: EventSender,
     pub frame_rate: f64,
     pub tick_rate: f64,
     pub mouse: bool,
     pub paste: bool,
+    /// Whether the keys are reported with the kitty keyboard protocol (see
+    /// [Tui::keyboard_enhancements]).
+    pub keyboard_enhancements: bool,
+    /// Whether the repeats and the releases of the keys are sent too (see [Tui::key_releases]).
+    pub key_releases: bool,
+    /// The title of the terminal window while the Tui is entered, if set (see [Tui::title]).
+    pub title: Option<String>,
+    /// The shape of the cursor while the Tui is entered, if set (see [Tui::cursor_style]).
+    pub cursor_style: Option<SetCursorStyle>,
+    pub named_ticks: Vec<(String, f64)>,
+    /// The frame rate used after some time without input events, if any.
+    pub idle_frame_rate: Option<(f64, Duration)>,
+    /// The bounds of the frame rate, when it adapts to the activity of the app.
+    pub adaptive_frame_rate: Option<(f64, f64)>,
+    /// The longest time between two clicks counted as a double (or triple) click.
+    pub click_interval: Duration,
+    activity: Arc<Notify>,
+    rates_tx: watch::Sender<(f64, f64)>,
+    headless: bool,
 }
 
 impl Tui {
     pub fn new() -> Result<Self, std::io::Error> {
+        Self::build(CrosstermBackend::new(io()), false)
+    }
+}
+
+impl<B: Backend> Tui<B> {
+    /// Creates a Tui drawing to the given backend without touching the real terminal: it doesn't
+    /// enter the raw mode nor read the terminal events, and doesn't emit ticks or renders by
+    /// itself, so the only events are the ones sent through `event_tx`. Meant for tests.
+    pub fn headless(backend: B) -> Result<Self, std::io::Error> {
+        Self::build(backend, true)
+    }
+
+    /// Creates a Tui drawing to the given backend (e.g. a termwiz or a custom backend) instead of
+    /// the crossterm one. The events are still read from the terminal, and [Tui::enter] and
+    /// [Tui::exit] still set the terminal modes through crossterm, so the backend only has to
+    /// draw.
+    pub fn with_backend(backend: B) -> Result<Self, std::io::Error> {
+        Self::build(backend, false)
+    }
+
+    fn build(backend: B, headless: bool) -> Result<Self, std::io::Error> {
         let tick_rate = 4.0;
         let frame_rate = 60.0;
-        let terminal = ratatui::Terminal::new(Backend::new(io()))?;
+        let terminal = ratatui::Terminal::new(backend)?;
         let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let event_tx = EventSender(event_tx);
         let cancellation_token = CancellationToken::new();
         let task = tokio::spawn(async {});
         let mouse = false;
         let paste = false;
+        let (rates_tx, _) = watch::channel((tick_rate, frame_rate));
         Ok(Self {
             terminal,
             task,
@@ -67,6 +142,17 @@ impl Tui {
             tick_rate,
             mouse,
             paste,
+            keyboard_enhancements: false,
+            key_releases: false,
+            title: None,
+            cursor_style: None,
+            named_ticks: Vec::new(),
+            idle_frame_rate: None,
+            adaptive_frame_rate: None,
+            click_interval: DEFAULT_CLICK_INTERVAL,
+            activity: Arc::new(Notify::new()),
+            rates_tx,
+            headless,
         })
     }
 
@@ -94,70 +180,213 @@ impl Tui {
         self
     }
 
+    /// Adds a named tick stream, emitting [Event::NamedTick] events with the given `name` at the
+    /// given `rate` (ticks per second), independently of the main tick rate.
+    ///
+    /// Useful for components that need to poll something at a different pace than the rest of
+    /// the application (e.g. a disk usage monitor that refreshes every 5 seconds).
+    pub fn named_tick(mut self, name: impl Into<String>, rate: f64) -> Self {
+        self.named_ticks.push((name.into(), rate));
+        self
+    }
+
+    /// Drops the frame rate to `idle_rate` once no input event (key, mouse, paste, resize or focus)
+    /// was received for `after`, and restores it on the next one. Idle apps are still drawn, e.g.
+    /// to show the progress of background tasks, but at a fraction of the cost.
+    pub fn idle_frame_rate(mut self, idle_rate: f64, after: Duration) -> Self {
+        self.idle_frame_rate = Some((idle_rate, after));
+        self
+    }
+
+    /// Adapts the frame rate to the activity of the app, instead of using a fixed one: it jumps to
+    /// `max` on input events (and on [Tui::notify_activity], e.g. while animating), and then
+    /// halves every second without activity, down to `min`. Takes precedence over the frame rate
+    /// and the [idle frame rate](Tui::idle_frame_rate).
+    pub fn adaptive_frame_rate(mut self, min: f64, max: f64) -> Self {
+        self.adaptive_frame_rate = Some((min, max));
+        self
+    }
+
+    /// Tells an [adaptive](Tui::adaptive_frame_rate) Tui that something is changing on screen, so
+    /// it draws at its highest frame rate for a while.
+    pub fn notify_activity(&self) {
+        self.activity.notify_one();
+    }
+
+    /// Changes the tick rate of a running Tui, without restarting its event loop.
+    ///
+    /// Returns `false` (and ignores the change) if the rate is not a positive finite number.
+    pub fn set_tick_rate(&mut self, tick_rate: f64) -> bool {
+        if !is_valid_rate(tick_rate) {
+            return false;
+        }
+        self.tick_rate = tick_rate;
+        self.rates_tx.send_replace((self.tick_rate, self.frame_rate));
+        true
+    }
+
+    /// Changes the frame rate of a running Tui, without restarting its event loop.
+    ///
+    /// Returns `false` (and ignores the change) if the rate is not a positive finite number.
+    pub fn set_frame_rate(&mut self, frame_rate: f64) -> bool {
+        if !is_valid_rate(frame_rate) {
+            return false;
+        }
+        self.frame_rate = frame_rate;
+        self.rates_tx.send_replace((self.tick_rate, self.frame_rate));
+        true
+    }
+
     /// Sets whether the Tui should capture mouse events. The default is false.
     pub fn mouse(mut self, mouse: bool) -> Self {
         self.mouse = mouse;
         self
     }
 
+    /// Sets the longest time between two clicks of a mouse button on the same cell for them to
+    /// count as a double (or triple) click (see [MouseClick]). The default is 500 milliseconds.
+    pub fn click_interval(mut self, interval: Duration) -> Self {
+        self.click_interval = interval;
+        self
+    }
+
     /// Sets whether the Tui should capture paste events. The default is false.
     pub fn paste(mut self, paste: bool) -> Self {
         self.paste = paste;
         self
     }
 
+    /// Sets whether the Tui should ask the terminal to report the keys with the kitty keyboard
+    /// protocol, which tells apart the keys the legacy encoding mixes up: `Shift+Enter`,
+    /// `Ctrl+Enter` and `Enter`, `Ctrl+I` and `Tab`, `Esc` and `Alt` combinations... The terminals
+    /// that don't support the protocol (see crossterm's `supports_keyboard_enhancement`) ignore
+    /// the request and keep the legacy encoding. The default is false.
+    pub fn keyboard_enhancements(mut self, enabled: bool) -> Self {
+        self.keyboard_enhancements = enabled;
+        self
+    }
+
+    /// Sets whether the Tui should send the repeats of the keys held down, as [Event::Key]s of the
+    /// [Repeat](KeyEventKind::Repeat) kind, and their releases, as [Event::KeyRelease]s. Only the
+    /// presses are sent otherwise. Most terminals only report the releases with the kitty
+    /// keyboard protocol, which this enables too (see [Tui::keyboard_enhancements]). The default
+    /// is false.
+    pub fn key_releases(mut self, enabled: bool) -> Self {
+        self.key_releases = enabled;
+        self
+    }
+
+    /// Sets the title of the terminal window while the Tui is entered. The title the window had
+    /// is restored on exit, on the terminals keeping a stack of titles (most of them, but not the
+    /// windows console). The default is to leave the title alone.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the shape of the cursor (e.g. `SetCursorStyle::SteadyBar` for text inputs) while the
+    /// Tui is entered. The shape of the user is restored on exit. The default is to leave the
+    /// shape alone.
+    pub fn cursor_style(mut self, style: SetCursorStyle) -> Self {
+        self.cursor_style = Some(style);
+        self
+    }
+
+    /// Changes the title of the terminal window, right away if the Tui is entered.
+    pub fn set_title(&mut self, title: &str) -> Result<(), std::io::Error> {
+        self.title = Some(title.to_string());
+        if self.headless || !crossterm::terminal::is_raw_mode_enabled()? {
+            return Ok(());
+        }
+        crossterm::execute!(io(), SetTitle(title))
+    }
+
+    /// Shows the cursor at the given position of the terminal, e.g. where a text input is edited.
+    ///
+    /// The frames drawn afterwards hide the cursor again, unless they set its position too with
+    /// `Frame::set_cursor_position`, which is how components drawing a text input keep showing it.
+    pub fn show_cursor_at(&mut self, x: u16, y: u16) -> Result<(), std::io::Error> {
+        self.terminal.set_cursor_position((x, y))?;
+        self.terminal.show_cursor()
+    }
+
+    /// The keyboard enhancement flags to push on the terminal, if any.
+    fn keyboard_flags(&self) -> Option<KeyboardEnhancementFlags> {
+        let mut flags = KeyboardEnhancementFlags::empty();
+        if self.keyboard_enhancements || self.key_releases {
+            flags |= KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES;
+        }
+        if self.key_releases {
+            flags |= KeyboardEnhancementFlags::REPORT_EVENT_TYPES;
+        }
+        (!flags.is_empty()).then_some(flags)
+    }
+
     /// Starts the Tui event loop.
     pub fn start(&mut self) {
+        if self.headless {
+            return;
+        }
+
         let tick_delay = std::time::Duration::from_secs_f64(1.0 / self.tick_rate);
         let render_delay = std::time::Duration::from_secs_f64(1.0 / self.frame_rate);
         self.cancel();
         self.cancellation_token = CancellationToken::new();
         let _cancellation_token = self.cancellation_token.clone();
         let _event_tx = self.event_tx.clone();
+        let mut rates_rx = self.rates_tx.subscribe();
+        let named_ticks = self.named_ticks.clone();
+        let idle = self.idle_frame_rate.filter(|(rate, _)| is_valid_rate(*rate));
+        let adaptive = self
+            .adaptive_frame_rate
+            .filter(|(min, max)| is_valid_rate(*min) && is_valid_rate(*max) && min <= max);
+        let activity = self.activity.clone();
+        let click_interval = self.click_interval;
+        let key_releases = self.key_releases;
+        let mut frame_rate = self.frame_rate;
+        let mut render_rate = frame_rate;
         self.task = tokio::spawn(async move {
             let mut reader = crossterm::event::EventStream::new();
             let mut tick_interval = tokio::time::interval(tick_delay);
             let mut render_interval = tokio::time::interval(render_delay);
+            let mut named_ticks = named_tick_streams(named_ticks);
+            let mut continued = continued_signals();
+            let mut mapper = EventMapper::new(click_interval, key_releases);
+            let mut last_input = Instant::now();
+            let mut last_activity = Instant::now();
             _event_tx.send(Event::Init).unwrap();
             loop {
                 let tick_delay = tick_interval.tick();
                 let render_delay = render_interval.tick();
                 let crossterm_event = reader.next().fuse();
+                let mut rates_changed = false;
                 tokio::select! {
-                    _ = _cancellation_token.cancelled() => {
-                        println!("Tui task cancelled");
-                        break;
+                    _ = _cancellation_token.cancelled() => break,
+                    Ok(()) = rates_rx.changed() => {
+                        rates_changed = true;
+                    }
+                    _ = activity.notified() => {
+                        last_activity = Instant::now();
+                    }
+                    Some(name) = named_ticks.next() => {
+                        _event_tx.send(Event::NamedTick(name)).unwrap();
+                    }
+                    Some(()) = continued.next() => {
+                        _event_tx.send(Event::Resume).unwrap();
                     }
                     maybe_event = crossterm_event => {
                         match maybe_event {
-                        Some(Ok(evt)) => {
-                            match evt {
-                                CrosstermEvent::Key(key) => {
-                                    if key.kind == KeyEventKind::Press {
-                                        _event_tx.send(Event::Key(key)).unwrap();
-                                    }
-                                },
-                                CrosstermEvent::Mouse(mouse) => {
-                                    _event_tx.send(Event::Mouse(mouse)).unwrap();
-                                },
-                                CrosstermEvent::Resize(x, y) => {
-                                    _event_tx.send(Event::Resize(x, y)).unwrap();
-                                },
-                                CrosstermEvent::FocusLost => {
-                                    _event_tx.send(Event::FocusLost).unwrap();
-                                },
-                                CrosstermEvent::FocusGained => {
-                                    _event_tx.send(Event::FocusGained).unwrap();
-                                },
-                                CrosstermEvent::Paste(s) => {
-                                    _event_tx.send(Event::Paste(s)).unwrap();
-                                },
+                            Some(Ok(evt)) => {
+                                last_input = Instant::now();
+                                last_activity = last_input;
+                                for event in mapper.map(evt, last_input) {
+                                    _event_tx.send(event).unwrap();
+                                }
                             }
-                        }
-                        Some(Err(_)) => {
-                            _event_tx.send(Event::Error).unwrap();
-                        }
-                        None => {},
+                            Some(Err(_)) => {
+                                _event_tx.send(Event::Error).unwrap();
+                            }
+                            None => {},
                         }
                     },
                     _ = tick_delay => {
@@ -167,53 +396,107 @@ impl Tui {
                         _event_tx.send(Event::Render).unwrap();
                     },
                 }
+
+                if rates_changed {
+                    let (tick_rate, rate) = *rates_rx.borrow_and_update();
+                    tick_interval = tokio::time::interval(Duration::from_secs_f64(1.0 / tick_rate));
+                    frame_rate = rate;
+                }
+
+                // checked at least once per frame, as each render wakes the loop up
+                let rate = match (adaptive, idle) {
+                    (Some((min, max)), _) => adaptive_rate(min, max, last_activity.elapsed()),
+                    (None, Some((idle_rate, after))) if last_input.elapsed() >= after => idle_rate,
+                    _ => frame_rate,
+                };
+                if rates_changed || rate != render_rate {
+                    render_rate = rate;
+                    render_interval = tokio::time::interval(Duration::from_secs_f64(1.0 / rate));
+                }
             }
         });
     }
 
-    /// Stops the Tui event loop.
-    pub fn stop(&self) {
+    /// Stops the Tui event loop, waiting for it to finish. It's aborted if it doesn't finish in
+    /// 100 milliseconds (with a warning, with the `logging` feature), so a slow loop doesn't keep
+    /// the app from quitting. A panic of the loop is reported as an error.
+    pub async fn stop(&mut self) -> Result<(), std::io::Error> {
         self.cancel();
-        let mut counter = 0;
-        while !self.task.is_finished() {
-            std::thread::sleep(Duration::from_millis(1));
-            counter += 1;
-            if counter > 50 {
-                self.task.abort();
-            }
-            if counter > 100 {
-                break;
+        let task = std::mem::replace(&mut self.task, tokio::spawn(async {}));
+        let abort = task.abort_handle();
+        match tokio::time::timeout(STOP_TIMEOUT, task).await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(err)) if err.is_cancelled() => Ok(()),
+            Ok(Err(err)) => Err(std::io::Error::other(err)),
+            Err(_) => {
+                abort.abort();
+                #[cfg(feature = "logging")]
+                log::warn!("the event loop didn't stop in {STOP_TIMEOUT:?}, it was aborted");
+                Ok(())
             }
         }
     }
 
+    /// Cancels the event loop and aborts it, without waiting for it to finish. For the places
+    /// that can't wait (see [Tui::stop]), like [Tui::exit].
+    fn halt(&self) {
+        self.cancel();
+        self.task.abort();
+    }
+
     /// Enables cross-term raw mode and enters the alternate screen.
     pub fn enter(&mut self) -> Result<(), std::io::Error> {
-        crossterm::terminal::enable_raw_mode()?;
-        crossterm::execute!(io(), EnterAlternateScreen, cursor::Hide)?;
-        if self.mouse {
-            crossterm::execute!(io(), EnableMouseCapture)?;
-        }
-        if self.paste {
-            crossterm::execute!(io(), EnableBracketedPaste)?;
+        if self.headless {
+            return Ok(());
         }
+
+        // enables the virtual terminal sequences of the windows console, where available; the
+        // legacy console falls back to the console API
+        #[cfg(windows)]
+        crossterm::ansi_support::supports_ansi();
+
+        self.enter_terminal(&mut Crossterm)?;
         self.start();
         Ok(())
     }
 
-    /// Disables cross-term raw mode and exits the alternate screen.
+    /// Makes the changes of [Tui::enter] to the terminal.
+    fn enter_terminal(&self, terminal: &mut impl Terminal) -> Result<(), std::io::Error> {
+        for mode in TerminalMode::for_tui(self.mouse, self.paste, self.keyboard_flags()) {
+            mode.set(true, terminal)?;
+        }
+        if let Some(title) = &self.title {
+            save_title(true, terminal)?;
+            terminal.execute(SetTitle(title))?;
+        }
+        if let Some(style) = self.cursor_style {
+            terminal.execute(style)?;
+        }
+        Ok(())
+    }
+
+    /// Disables cross-term raw mode and exits the alternate screen. The event loop is aborted if
+    /// it wasn't [stopped](Tui::stop) already.
     pub fn exit(&mut self) -> Result<(), std::io::Error> {
-        self.stop();
-        if crossterm::terminal::is_raw_mode_enabled()? {
-            self.flush()?;
-            if self.paste {
-                crossterm::execute!(io(), DisableBracketedPaste)?;
-            }
-            if self.mouse {
-                crossterm::execute!(io(), DisableMouseCapture)?;
-            }
-            crossterm::execute!(io(), LeaveAlternateScreen, cursor::Show)?;
-            crossterm::terminal::disable_raw_mode()?;
+        self.halt();
+        if !self.headless && crossterm::terminal::is_raw_mode_enabled()? {
+            self.exit_terminal(&mut Crossterm)?;
+        }
+        Ok(())
+    }
+
+    /// Undoes the changes of [Tui::enter] to the terminal, in the reverse order.
+    fn exit_terminal(&mut self, terminal: &mut impl Terminal) -> Result<(), std::io::Error> {
+        self.flush()?;
+        if self.cursor_style.is_some() {
+            terminal.execute(SetCursorStyle::DefaultUserShape)?;
+        }
+        if self.title.is_some() {
+            save_title(false, terminal)?;
+        }
+        let modes = TerminalMode::for_tui(self.mouse, self.paste, self.keyboard_flags());
+        for mode in modes.into_iter().rev() {
+            mode.set(false, terminal)?;
         }
         Ok(())
     }
@@ -222,22 +505,448 @@ impl Tui {
         self.cancellation_token.cancel();
     }
 
+    /// Exits the terminal and, on unix, stops the process by raising `SIGTSTP`. The call returns
+    /// once the process is continued (e.g. by `fg`), after which [Tui::resume] should be called.
+    ///
+    /// If the process is stopped by other means, the event loop emits [Event::Resume] when it's
+    /// continued, so the terminal can be restored with [Tui::restore].
     pub fn suspend(&mut self) -> Result<(), std::io::Error> {
-        self.exit()
+        self.exit()?;
+        #[cfg(unix)]
+        if !self.headless {
+            signal_hook::low_level::raise(signal_hook::consts::signal::SIGTSTP)?;
+        }
+        Ok(())
     }
 
     pub fn resume(&mut self) -> Result<(), std::io::Error> {
         self.enter()
     }
 
+    /// Exits and enters the terminal again, restoring the raw mode and the alternate screen, e.g.
+    /// after the shell reset the terminal while the process was stopped.
+    pub fn restore(&mut self) -> Result<(), std::io::Error> {
+        self.exit()?;
+        self.enter()
+    }
+
+    /// Rings the terminal bell (`BEL`). Depending on the terminal settings, it plays a sound,
+    /// flashes the screen or does nothing.
+    pub fn bell(&mut self) -> Result<(), std::io::Error> {
+        use std::io::Write;
+
+        if self.headless {
+            return Ok(());
+        }
+
+        let mut io = io();
+        io.write_all(b"\x07")?;
+        io.flush()
+    }
+
+    /// Returns `true` if the Tui is [headless](Tui::headless).
+    pub fn is_headless(&self) -> bool {
+        self.headless
+    }
+
     /// Returns the next event from the event channel.
     pub async fn next(&mut self) -> Option<Event> {
+        self.next_stamped().await.map(|(_, event)| event)
+    }
+
+    /// Returns the next event from the event channel, with the instant it was sent at.
+    pub async fn next_stamped(&mut self) -> Option<(Instant, Event)> {
         self.event_rx.recv().await
     }
 }
 
-impl Deref for Tui {
-    type Target = ratatui::Terminal<Backend<IO>>;
+/// Restores the terminal to its normal state, whatever state a [Tui] left it in: disables the raw
+/// mode, the mouse capture, the bracketed paste and the keyboard enhancements, and leaves the
+/// alternate screen. Meant for
+/// places where the [Tui] itself isn't reachable, like a panic hook (see
+/// [App::with_panic_hook](crate::App::with_panic_hook)).
+pub fn restore_terminal() -> Result<(), std::io::Error> {
+    let keyboard = Some(KeyboardEnhancementFlags::empty());
+    for mode in TerminalMode::for_tui(true, true, keyboard).into_iter().rev() {
+        mode.set(false, &mut Crossterm)?;
+    }
+    Ok(())
+}
+
+/// Saves the title of the terminal window on the stack of titles of the terminal, or restores it
+/// from there. The windows console doesn't keep such a stack, so it's skipped there.
+fn save_title(save: bool, terminal: &mut impl Terminal) -> Result<(), std::io::Error> {
+    if cfg!(windows) {
+        return Ok(());
+    }
+    terminal.execute(TitleStack(save))
+}
+
+/// The command pushing the title of the terminal window on the stack of titles (`true`), or
+/// popping it from there (`false`).
+struct TitleStack(bool);
+
+impl Command for TitleStack {
+    fn write_ansi(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        f.write_str(if self.0 { "\x1b[22;0t" } else { "\x1b[23;0t" })
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> Result<(), std::io::Error> {
+        Ok(())
+    }
+}
+
+/// Where [Tui::enter] and [Tui::exit] make their changes: the real terminal (see [Crossterm]), or
+/// a recording of them in the tests.
+trait Terminal {
+    fn set_raw_mode(&mut self, enabled: bool) -> Result<(), std::io::Error>;
+
+    fn execute(&mut self, command: impl Command) -> Result<(), std::io::Error>;
+}
+
+/// The real terminal, changed through crossterm.
+struct Crossterm;
+
+impl Terminal for Crossterm {
+    fn set_raw_mode(&mut self, enabled: bool) -> Result<(), std::io::Error> {
+        match enabled {
+            true => crossterm::terminal::enable_raw_mode(),
+            false => crossterm::terminal::disable_raw_mode(),
+        }
+    }
+
+    fn execute(&mut self, command: impl Command) -> Result<(), std::io::Error> {
+        crossterm::execute!(io(), command)
+    }
+}
+
+/// A change to the terminal made by [Tui::enter] and undone, in the reverse order, by
+/// [Tui::exit].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TerminalMode {
+    RawMode,
+    AlternateScreen,
+    FocusChange,
+    HiddenCursor,
+    MouseCapture,
+    BracketedPaste,
+    KeyboardEnhancements(KeyboardEnhancementFlags),
+}
+
+impl TerminalMode {
+    /// The changes made to the terminal, in the order they are made.
+    fn for_tui(
+        mouse: bool,
+        paste: bool,
+        keyboard: Option<KeyboardEnhancementFlags>,
+    ) -> Vec<TerminalMode> {
+        let mut modes = vec![
+            TerminalMode::RawMode,
+            TerminalMode::AlternateScreen,
+            TerminalMode::FocusChange,
+            TerminalMode::HiddenCursor,
+        ];
+        modes.extend(mouse.then_some(TerminalMode::MouseCapture));
+        modes.extend(paste.then_some(TerminalMode::BracketedPaste));
+        modes.extend(keyboard.map(TerminalMode::KeyboardEnhancements));
+        modes
+    }
+
+    /// Enable or disable the mode. The legacy windows console doesn't support the bracketed
+    /// paste nor the keyboard enhancements, which are skipped there (pastes arrive as key presses
+    /// instead).
+    fn set(self, enabled: bool, terminal: &mut impl Terminal) -> Result<(), std::io::Error> {
+        let result = match (self, enabled) {
+            (TerminalMode::RawMode, enabled) => terminal.set_raw_mode(enabled),
+            (TerminalMode::AlternateScreen, true) => terminal.execute(EnterAlternateScreen),
+            (TerminalMode::AlternateScreen, false) => terminal.execute(LeaveAlternateScreen),
+            (TerminalMode::FocusChange, true) => terminal.execute(EnableFocusChange),
+            (TerminalMode::FocusChange, false) => terminal.execute(DisableFocusChange),
+            (TerminalMode::HiddenCursor, true) => terminal.execute(cursor::Hide),
+            (TerminalMode::HiddenCursor, false) => terminal.execute(cursor::Show),
+            (TerminalMode::MouseCapture, true) => terminal.execute(EnableMouseCapture),
+            (TerminalMode::MouseCapture, false) => terminal.execute(DisableMouseCapture),
+            (TerminalMode::BracketedPaste, true) => terminal.execute(EnableBracketedPaste),
+            (TerminalMode::BracketedPaste, false) => terminal.execute(DisableBracketedPaste),
+            (TerminalMode::KeyboardEnhancements(flags), true) => {
+                terminal.execute(PushKeyboardEnhancementFlags(flags))
+            }
+            (TerminalMode::KeyboardEnhancements(_), false) => {
+                terminal.execute(PopKeyboardEnhancementFlags)
+            }
+        };
+
+        match result {
+            Err(e)
+                if matches!(
+                    self,
+                    TerminalMode::BracketedPaste | TerminalMode::KeyboardEnhancements(_)
+                ) && e.kind() == std::io::ErrorKind::Unsupported =>
+            {
+                Ok(())
+            }
+            result => result,
+        }
+    }
+}
+
+/// Maps a crossterm event to an [Event], if the app handles it: key releases and repeats (unless
+/// `key_releases`), and the resizes that don't change the size of the terminal, are dropped.
+fn map_event(
+    event: CrosstermEvent,
+    resizes: &mut ResizeFilter,
+    key_releases: bool,
+) -> Option<Event> {
+    match event {
+        CrosstermEvent::Key(key) => match key.kind {
+            KeyEventKind::Press => Some(Event::Key(key)),
+            KeyEventKind::Repeat => key_releases.then_some(Event::Key(key)),
+            KeyEventKind::Release => key_releases.then_some(Event::KeyRelease(key)),
+        },
+        CrosstermEvent::Mouse(mouse) => Some(Event::Mouse(mouse)),
+        CrosstermEvent::Resize(x, y) => resizes.resized((x, y)).map(|(x, y)| Event::Resize(x, y)),
+        CrosstermEvent::FocusLost => Some(Event::FocusLost),
+        CrosstermEvent::FocusGained => Some(Event::FocusGained),
+        CrosstermEvent::Paste(s) => Some(Event::Paste(s)),
+    }
+}
+
+/// Maps the crossterm events to [Event]s (see [map_event]), following the mouse events with the
+/// gestures they make: the [MouseClick] of the presses of the buttons, and the [MouseDrag] steps.
+#[derive(Debug)]
+struct EventMapper {
+    resizes: ResizeFilter,
+    clicks: ClickCounter,
+    drags: DragTracker,
+    key_releases: bool,
+}
+
+impl EventMapper {
+    fn new(click_interval: Duration, key_releases: bool) -> Self {
+        Self {
+            resizes: ResizeFilter::default(),
+            clicks: ClickCounter::new(click_interval),
+            drags: DragTracker::default(),
+            key_releases,
+        }
+    }
+
+    /// The events of a crossterm event read at the given instant.
+    fn map(&mut self, event: CrosstermEvent, at: Instant) -> Vec<Event> {
+        let Some(event) = map_event(event, &mut self.resizes, self.key_releases) else {
+            return vec![];
+        };
+        let mut events = vec![event];
+        if let Event::Mouse(mouse) = events[0] {
+            events.extend(self.clicks.click(&mouse, at).map(Event::MouseClick));
+            events.extend(self.drags.drag(&mouse).map(Event::MouseDrag));
+        }
+        events
+    }
+}
+
+/// A mouse button held down, for the [DragTracker].
+#[derive(Clone, Copy, Debug)]
+struct Press {
+    button: MouseButton,
+    origin: (u16, u16),
+    /// where the pointer was last seen
+    last: (u16, u16),
+    dragging: bool,
+}
+
+/// Turns the presses, drags and releases of the mouse buttons into [MouseDrag] steps.
+#[derive(Debug, Default)]
+struct DragTracker {
+    press: Option<Press>,
+}
+
+impl DragTracker {
+    /// The step of the drag made by the mouse event, if any.
+    fn drag(&mut self, mouse: &MouseEvent) -> Option<MouseDrag> {
+        let position = (mouse.column, mouse.row);
+        let (button, phase) = match mouse.kind {
+            MouseEventKind::Down(button) => {
+                self.press = Some(Press {
+                    button,
+                    origin: position,
+                    last: position,
+                    dragging: false,
+                });
+                return None;
+            }
+            MouseEventKind::Drag(button) => {
+                // a press the app didn't see (e.g. made before it started) starts here
+                let press = self.press.filter(|press| press.button == button);
+                let dragging = press.is_some_and(|press| press.dragging);
+                self.press = Some(press.unwrap_or(Press {
+                    button,
+                    origin: position,
+                    last: position,
+                    dragging,
+                }));
+                (
+                    button,
+                    if dragging {
+                        DragPhase::Update
+                    } else {
+                        DragPhase::Start
+                    },
+                )
+            }
+            MouseEventKind::Up(button) if self.press.is_some_and(|p| p.button == button) => {
+                let dragging = self.press.is_some_and(|press| press.dragging);
+                if !dragging {
+                    self.press = None;
+                    return None;
+                }
+                (button, DragPhase::End)
+            }
+            _ => return None,
+        };
+
+        // the press is set by now
+        let press = self.press.as_mut()?;
+        let drag = MouseDrag {
+            button,
+            phase,
+            origin_column: press.origin.0,
+            origin_row: press.origin.1,
+            column: mouse.column,
+            row: mouse.row,
+            delta_column: i32::from(mouse.column) - i32::from(press.last.0),
+            delta_row: i32::from(mouse.row) - i32::from(press.last.1),
+            modifiers: mouse.modifiers,
+        };
+        press.last = position;
+        press.dragging = true;
+        if phase == DragPhase::End {
+            self.press = None;
+        }
+        Some(drag)
+    }
+}
+
+/// Counts the clicks in a row of a mouse button on the same cell, less than the interval apart.
+#[derive(Debug)]
+struct ClickCounter {
+    interval: Duration,
+    last: Option<(MouseClick, Instant)>,
+}
+
+impl ClickCounter {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: None,
+        }
+    }
+
+    /// The click started by the mouse event read at the given instant, if it's the press of a
+    /// button.
+    fn click(&mut self, mouse: &MouseEvent, at: Instant) -> Option<MouseClick> {
+        let MouseEventKind::Down(button) = mouse.kind else {
+            return None;
+        };
+
+        let mut click = MouseClick {
+            button,
+            column: mouse.column,
+            row: mouse.row,
+            modifiers: mouse.modifiers,
+            count: 1,
+        };
+        if let Some((last, last_at)) = self.last {
+            let same = (last.button, last.column, last.row) == (button, mouse.column, mouse.row);
+            if same && at.saturating_duration_since(last_at) <= self.interval {
+                click.count = last.count.saturating_add(1);
+            }
+        }
+        self.last = Some((click, at));
+        Some(click)
+    }
+}
+
+/// Filters the resize events of the terminal, dropping the ones that don't change its size.
+///
+/// The windows console reports the size of its screen buffer in resize events, rather than the
+/// size of the window, and reports it again on unrelated changes (e.g. when scrolling the
+/// buffer), so the size of the window is queried instead there.
+#[derive(Debug)]
+struct ResizeFilter {
+    last: Option<(u16, u16)>,
+    /// The size of the window, when the reported one can't be trusted.
+    window_size: fn() -> Option<(u16, u16)>,
+}
+
+impl Default for ResizeFilter {
+    fn default() -> Self {
+        Self {
+            last: None,
+            window_size: match cfg!(windows) {
+                true => || crossterm::terminal::size().ok(),
+                false => || None,
+            },
+        }
+    }
+}
+
+impl ResizeFilter {
+    /// The new size of the terminal after a resize event reporting `reported`, or none if the
+    /// size didn't change.
+    fn resized(&mut self, reported: (u16, u16)) -> Option<(u16, u16)> {
+        let size = (self.window_size)().unwrap_or(reported);
+        (self.last.replace(size) != Some(size)).then_some(size)
+    }
+}
+
+/// Merges the named ticks intervals into a single stream yielding the name of the tick stream
+/// each time one of them ticks.
+fn named_tick_streams(named_ticks: Vec<(String, f64)>) -> SelectAll<BoxStream<'static, String>> {
+    stream::select_all(named_ticks.into_iter().filter(|(_, rate)| is_valid_rate(*rate)).map(
+        |(name, rate)| {
+            let interval = tokio::time::interval(Duration::from_secs_f64(1.0 / rate));
+            stream::unfold((interval, name), |(mut interval, name)| async move {
+                interval.tick().await;
+                Some((name.clone(), (interval, name)))
+            })
+            .boxed()
+        },
+    ))
+}
+
+/// Stream yielding each time the process is continued (`SIGCONT`) after being stopped. Never
+/// yields on non-unix platforms.
+fn continued_signals() -> BoxStream<'static, ()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        if let Ok(signals) = signal(SignalKind::from_raw(signal_hook::consts::signal::SIGCONT)) {
+            return stream::unfold(signals, |mut signals| async move {
+                signals.recv().await.map(|()| ((), signals))
+            })
+            .boxed();
+        }
+    }
+
+    stream::pending().boxed()
+}
+
+fn is_valid_rate(rate: f64) -> bool {
+    rate.is_finite() && rate > 0.0
+}
+
+/// The frame rate of an adaptive Tui: `max` during the first second after some activity, then
+/// halved every second, down to `min`.
+fn adaptive_rate(min: f64, max: f64, since_activity: Duration) -> f64 {
+    let halvings = since_activity.as_secs().min(64) as i32;
+    (max / 2f64.powi(halvings)).max(min)
+}
+
+impl<B: Backend> Deref for Tui<B> {
+    type Target = ratatui::Terminal<B>;
 
     fn deref(&self) -> &Self::Target {
         // deref Tui as Terminal
@@ -245,16 +954,299 @@ impl Deref for Tui {
     }
 }
 
-impl DerefMut for Tui {
+impl<B: Backend> DerefMut for Tui<B> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         // deref Tui as Terminal mutably
         &mut self.terminal
     }
 }
 
-impl Drop for Tui {
+impl<B: Backend> Drop for Tui<B> {
     fn drop(&mut self) {
-        // Ensure that the terminal is cleaned up when the Tui is dropped
-        self.exit().unwrap();
+        // Ensure that the terminal is cleaned up when the Tui is dropped, as well as possible: the
+        // event loop is aborted instead of awaited, and the errors have nowhere to go
+        let _ = self.exit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adaptive_rate_decays_to_the_minimum() {
+        assert_eq!(adaptive_rate(2.0, 60.0, Duration::from_millis(900)), 60.0);
+        assert_eq!(adaptive_rate(2.0, 60.0, Duration::from_millis(1500)), 30.0);
+        assert_eq!(adaptive_rate(2.0, 60.0, Duration::from_secs(3)), 7.5);
+        assert_eq!(adaptive_rate(2.0, 60.0, Duration::from_secs(3600)), 2.0);
+    }
+
+    #[test]
+    fn terminal_modes_are_undone_in_reverse() {
+        use TerminalMode::*;
+
+        let modes = TerminalMode::for_tui(true, false, None);
+        assert_eq!(modes, [RawMode, AlternateScreen, FocusChange, HiddenCursor, MouseCapture]);
+        assert_eq!(TerminalMode::for_tui(false, true, None).last(), Some(&BracketedPaste));
+        let flags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES;
+        assert_eq!(
+            TerminalMode::for_tui(false, true, Some(flags)).last(),
+            Some(&KeyboardEnhancements(flags))
+        );
+    }
+
+    #[test]
+    fn drops_the_resizes_that_dont_change_the_size() {
+        let mut resizes = ResizeFilter {
+            last: None,
+            window_size: || None,
+        };
+        assert_eq!(resizes.resized((80, 24)), Some((80, 24)));
+        assert_eq!(resizes.resized((80, 24)), None);
+        assert_eq!(resizes.resized((100, 30)), Some((100, 30)));
+
+        // like the windows console, reporting the size of its screen buffer
+        let mut resizes = ResizeFilter {
+            last: None,
+            window_size: || Some((120, 40)),
+        };
+        assert_eq!(resizes.resized((120, 9001)), Some((120, 40)));
+        assert_eq!(resizes.resized((120, 3000)), None);
+    }
+
+    /// A terminal recording the changes made to it.
+    #[derive(Default)]
+    struct Recording(Vec<String>);
+
+    impl Terminal for Recording {
+        fn set_raw_mode(&mut self, enabled: bool) -> Result<(), std::io::Error> {
+            self.0.push(format!("raw mode {enabled}"));
+            Ok(())
+        }
+
+        fn execute(&mut self, command: impl Command) -> Result<(), std::io::Error> {
+            let mut ansi = String::new();
+            command.write_ansi(&mut ansi).map_err(std::io::Error::other)?;
+            self.0.push(ansi);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn exits_the_terminal_in_the_reverse_order_it_entered() -> Result<(), std::io::Error> {
+        use ratatui::backend::TestBackend;
+
+        let mut tui = Tui::headless(TestBackend::new(10, 2))?
+            .mouse(true)
+            .title("mate")
+            .cursor_style(SetCursorStyle::SteadyBar);
+        let mut terminal = Recording::default();
+        tui.enter_terminal(&mut terminal)?;
+        tui.exit_terminal(&mut terminal)?;
+
+        let mut expected = vec![
+            "raw mode true",
+            "\x1b[?1049h",
+            "\x1b[?1004h",
+            "\x1b[?25l",
+            "\x1b[?1000h\x1b[?1002h\x1b[?1003h\x1b[?1015h\x1b[?1006h",
+            "\x1b[22;0t",
+            "\x1b]0;mate\x07",
+            "\x1b[6 q",
+            "\x1b[0 q",
+            "\x1b[23;0t",
+            "\x1b[?1006l\x1b[?1015l\x1b[?1003l\x1b[?1002l\x1b[?1000l",
+            "\x1b[?25h",
+            "\x1b[?1004l",
+            "\x1b[?1049l",
+            "raw mode false",
+        ];
+        // the windows console doesn't keep the titles
+        if cfg!(windows) {
+            expected.retain(|change| !change.ends_with(";0t"));
+        }
+        assert_eq!(terminal.0, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn counts_the_clicks_in_a_row() {
+        use crossterm::event::{KeyModifiers, MouseButton};
+
+        let mut mapper = EventMapper::new(Duration::from_millis(500), false);
+        let start = Instant::now();
+        let mut press = |kind, column, ms| {
+            let mouse = MouseEvent {
+                kind,
+                column,
+                row: 1,
+                modifiers: KeyModifiers::NONE,
+            };
+            let events =
+                mapper.map(CrosstermEvent::Mouse(mouse), start + Duration::from_millis(ms));
+            assert!(matches!(events[0], Event::Mouse(m) if m == mouse));
+            events.get(1).map(|event| match event {
+                Event::MouseClick(click) => click.count,
+                _ => panic!("not a click: {event:?}"),
+            })
+        };
+        let left = MouseEventKind::Down(MouseButton::Left);
+
+        assert_eq!(press(left, 4, 0), Some(1));
+        assert_eq!(press(MouseEventKind::Up(MouseButton::Left), 4, 100), None);
+        assert_eq!(press(left, 4, 300), Some(2));
+        assert_eq!(press(left, 4, 700), Some(3));
+        // too late, on another cell, or with another button
+        assert_eq!(press(left, 4, 1300), Some(1));
+        assert_eq!(press(left, 5, 1400), Some(1));
+        assert_eq!(press(MouseEventKind::Down(MouseButton::Right), 5, 1500), Some(1));
+    }
+
+    #[tokio::test]
+    async fn controls_the_title_and_the_cursor() -> Result<(), std::io::Error> {
+        use ratatui::{backend::TestBackend, layout::Position};
+
+        let mut tui = Tui::headless(TestBackend::new(10, 2))?.title("mate");
+        tui.set_title("mate cocido")?;
+        assert_eq!(tui.title.as_deref(), Some("mate cocido"));
+
+        tui.hide_cursor()?;
+        tui.show_cursor_at(3, 1)?;
+        assert_eq!(tui.get_cursor_position()?, Position::new(3, 1));
+        tui.backend_mut().assert_cursor_position((3, 1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stops_the_event_loop() -> Result<(), std::io::Error> {
+        use ratatui::backend::TestBackend;
+
+        let mut tui = Tui::headless(TestBackend::new(10, 2))?;
+        let token = tui.cancellation_token.clone();
+        tui.task = tokio::spawn(async move { token.cancelled().await });
+        tui.stop().await?;
+        assert!(tui.cancellation_token.is_cancelled());
+        // stopping twice is fine
+        tui.stop().await?;
+
+        // a loop that doesn't stop in time is aborted
+        tui.task = tokio::spawn(std::future::pending());
+        let stuck = tui.task.abort_handle();
+        tui.stop().await?;
+        tokio::task::yield_now().await;
+        assert!(stuck.is_finished());
+
+        tui.task = tokio::spawn(async { panic!("the loop broke") });
+        assert!(tui.stop().await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asks_for_the_keyboard_enhancements_on_demand() -> Result<(), std::io::Error> {
+        use ratatui::backend::TestBackend;
+
+        let tui = || Tui::headless(TestBackend::new(10, 2));
+        assert_eq!(tui()?.keyboard_flags(), None);
+        let disambiguate = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES;
+        assert_eq!(tui()?.keyboard_enhancements(true).keyboard_flags(), Some(disambiguate));
+        assert_eq!(
+            tui()?.key_releases(true).keyboard_flags(),
+            Some(disambiguate | KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ticks_each_named_tick_at_its_rate() {
+        let ticks = vec![("fast".into(), 100.0), ("broken".into(), 0.0), ("slow".into(), 0.2)];
+        let mut streams = named_tick_streams(ticks);
+
+        // the intervals tick right away, then at their own rates
+        let mut first = vec![streams.next().await.unwrap(), streams.next().await.unwrap()];
+        first.sort();
+        assert_eq!(first, ["fast", "slow"]);
+        for _ in 0..3 {
+            let next = tokio::time::timeout(Duration::from_secs(1), streams.next()).await;
+            assert_eq!(next.unwrap().as_deref(), Some("fast"));
+        }
+    }
+
+    #[tokio::test]
+    async fn hands_the_new_rates_to_the_event_loop() -> Result<(), std::io::Error> {
+        use ratatui::backend::TestBackend;
+
+        let mut tui = Tui::headless(TestBackend::new(10, 2))?;
+        let mut rates = tui.rates_tx.subscribe();
+        assert!(tui.set_frame_rate(120.0));
+        assert!(tui.set_tick_rate(0.5));
+        assert_eq!(*rates.borrow_and_update(), (0.5, 120.0));
+
+        assert!(!tui.set_frame_rate(0.0));
+        assert!(!tui.set_tick_rate(f64::NAN));
+        assert!(!rates.has_changed().unwrap());
+        assert_eq!((tui.tick_rate, tui.frame_rate), (0.5, 120.0));
+        Ok(())
+    }
+
+    #[test]
+    fn maps_the_key_releases_on_demand() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let key = |kind| {
+            CrosstermEvent::Key(KeyEvent::new_with_kind(KeyCode::Up, KeyModifiers::NONE, kind))
+        };
+        let mut resizes = ResizeFilter::default();
+        let mut map = |kind, key_releases| map_event(key(kind), &mut resizes, key_releases);
+
+        assert!(matches!(map(KeyEventKind::Press, false), Some(Event::Key(_))));
+        assert!(map(KeyEventKind::Repeat, false).is_none());
+        assert!(map(KeyEventKind::Release, false).is_none());
+        assert!(matches!(
+            map(KeyEventKind::Repeat, true),
+            Some(Event::Key(k)) if k.kind == KeyEventKind::Repeat
+        ));
+        assert!(matches!(map(KeyEventKind::Release, true), Some(Event::KeyRelease(_))));
+    }
+
+    #[test]
+    fn follows_the_drags() {
+        use crossterm::event::{KeyModifiers, MouseButton};
+
+        let mut drags = DragTracker::default();
+        let mut mouse = |kind, column, row| {
+            drags
+                .drag(&MouseEvent {
+                    kind,
+                    column,
+                    row,
+                    modifiers: KeyModifiers::NONE,
+                })
+                .map(|d| (d.phase, (d.origin_column, d.origin_row), (d.delta_column, d.delta_row)))
+        };
+        let left = MouseButton::Left;
+
+        // a press and a release in place aren't a drag
+        assert_eq!(mouse(MouseEventKind::Down(left), 2, 2), None);
+        assert_eq!(mouse(MouseEventKind::Up(left), 2, 2), None);
+
+        assert_eq!(mouse(MouseEventKind::Down(left), 4, 3), None);
+        assert_eq!(
+            mouse(MouseEventKind::Drag(left), 6, 3),
+            Some((DragPhase::Start, (4, 3), (2, 0)))
+        );
+        assert_eq!(
+            mouse(MouseEventKind::Drag(left), 5, 5),
+            Some((DragPhase::Update, (4, 3), (-1, 2)))
+        );
+        assert_eq!(mouse(MouseEventKind::Up(MouseButton::Right), 5, 5), None);
+        assert_eq!(mouse(MouseEventKind::Up(left), 5, 6), Some((DragPhase::End, (4, 3), (0, 1))));
+
+        // a drag whose press went unseen starts where it's first seen
+        let right = MouseButton::Right;
+        assert_eq!(
+            mouse(MouseEventKind::Drag(right), 1, 1),
+            Some((DragPhase::Start, (1, 1), (0, 0)))
+        );
+        assert_eq!(mouse(MouseEventKind::Up(right), 1, 1), Some((DragPhase::End, (1, 1), (0, 0))));
     }
 }
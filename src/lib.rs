@@ -3,41 +3,166 @@
     html_logo_url = "https://raw.githubusercontent.com/lucodear/matetui/master/.github/media/logo.svg"
 )]
 
+// allows the crate's own macros (which refer to `matetui::*`) to be used internally
+extern crate self as matetui;
+
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
 pub mod macros;
+pub mod prelude;
+#[cfg(feature = "scaffold")]
+pub mod scaffold;
+pub mod styles;
+pub mod theme;
 
 mod framework {
+    pub mod animation;
     pub mod app;
+    pub mod children;
+    #[cfg(feature = "cli")]
+    pub mod cli;
+    pub mod command;
     pub mod component;
+    #[cfg(feature = "config")]
+    pub mod config;
     pub mod events;
+    pub mod external;
+    pub mod focus;
+    pub mod history;
+    #[cfg(feature = "trace")]
+    pub mod instrument;
     pub mod keyboard;
+    pub mod latency;
+    pub mod layer;
+    #[cfg(feature = "logging")]
+    pub mod logging;
+    pub mod message;
+    #[cfg(feature = "metrics")]
+    pub mod metrics;
+    pub mod modal;
+    #[cfg(feature = "desktop-notifications")]
+    pub mod notify;
+    #[cfg(feature = "persistence")]
+    pub mod persistence;
+    pub mod progress;
+    pub mod recording;
+    pub mod retry;
+    pub mod router;
+    pub mod schedule;
+    pub mod state;
+    pub mod terminal;
+    pub mod testing;
     pub mod tui;
 }
 
 pub use framework::{
-    app::App,
-    component::{child_downcast, child_downcast_mut, Children, Component, ComponentAccessors},
-    events::{Action, ActionKind, Event},
-    keyboard::KeyBindings,
-    tui::{Frame, Tui, IO},
+    animation::{Easing, Interpolate, Tween},
+    app::{App, MatetuiError},
+    children::Children,
+    command::Command,
+    component::{
+        child_downcast, child_downcast_mut, Component, ComponentAccessors, ComponentBase,
+        ComponentError, QuitDecision,
+    },
+    events::{
+        Action, ActionKind, DragPhase, Event, MouseClick, MouseDrag, NoAction, ScrollDirection,
+        UserAction,
+    },
+    external::External,
+    focus::{FocusManager, FocusPath},
+    history::{History, HistoryEntry, HistoryKind},
+    keyboard::{KeyBindingConflict, KeyBindingHelp, KeyBindings, ScopedKeyBindings},
+    latency::InputLatency,
+    layer::{Anchor, Float, Layer},
+    message::Message,
+    progress::{ProgressEntry, ProgressRegistry, ProgressStatus, ProgressTask},
+    recording::Recording,
+    retry::Retry,
+    state::{AppState, Shared},
+    terminal::{Background, TerminalInfo},
+    testing::{ComponentHarness, TestApp},
+    tui::{restore_terminal, EventSender, Frame, Tui, IO},
 };
 
+#[cfg(feature = "cli")]
+pub use framework::cli::AppOptions;
+
+#[cfg(feature = "config")]
+pub use framework::config::Config;
+
+#[cfg(feature = "derive")]
+pub use matetui_derive::ComponentAccessors;
+
+#[cfg(feature = "logging")]
+pub use framework::logging::{LogBuffer, LogEntry};
+
+#[cfg(feature = "desktop-notifications")]
+pub use framework::notify::notify_desktop;
+
+#[cfg(feature = "metrics")]
+pub use framework::metrics::AppMetrics;
+
 pub mod utils {
     pub mod keyboard {
-        pub use super::super::framework::keyboard::{key_event_to_string, parse_key_sequence};
+        pub use super::super::framework::keyboard::{
+            binding_chip, key_event_to_display, key_event_to_string, key_sequence_to_display,
+            parse_key_sequence, KeyGlyphs,
+        };
     }
+
+    pub mod color;
+    pub mod component;
+    pub mod fill;
+    pub mod layout;
+    pub mod text;
+}
+
+#[cfg(any(
+    feature = "devtools",
+    feature = "help",
+    feature = "palette",
+    feature = "passthrough",
+    feature = "prompt",
+    feature = "progress",
+    feature = "logging"
+))]
+pub mod components {
+    #[cfg(feature = "help")]
+    pub mod help;
+    #[cfg(feature = "devtools")]
+    pub mod inspector;
+    #[cfg(feature = "logging")]
+    pub mod log_console;
+    #[cfg(feature = "palette")]
+    pub mod palette;
+    #[cfg(feature = "passthrough")]
+    pub mod passthrough;
+    #[cfg(feature = "progress")]
+    pub mod progress;
+    #[cfg(feature = "prompt")]
+    pub mod prompt;
+    #[cfg(feature = "devtools")]
+    pub mod theme_editor;
+    #[cfg(feature = "devtools")]
+    pub mod trace;
 }
 
-#[cfg(feature = "widget-gridselector")]
-#[cfg(feature = "widget-textarea")]
-#[cfg(feature = "widget-switch")]
+#[cfg(any(
+    feature = "widget-gridselector",
+    feature = "widget-textarea",
+    feature = "widget-switch",
+    feature = "widget-panel",
+    feature = "widget-link"
+))]
 pub mod widgets {
     #[cfg(feature = "widget-gridselector")]
     pub mod gridselector {
+        mod loader;
         mod selector;
         mod state;
         mod widget;
 
-        pub use {selector::*, state::*};
+        pub use {loader::GridLoader, selector::*, state::*};
     }
 
     #[cfg(feature = "widget-textarea")]
@@ -48,8 +173,22 @@ pub mod widgets {
         mod widget;
         pub use widget::*;
     }
+
+    #[cfg(feature = "widget-panel")]
+    pub mod panel {
+        mod widget;
+        pub use widget::*;
+    }
+
+    #[cfg(feature = "widget-link")]
+    pub mod link {
+        mod widget;
+        pub use widget::*;
+    }
 }
 
+pub use tokio_util::sync::CancellationToken;
+
 // re-export ratatui
 pub mod ratatui {
     pub use ratatui::*;
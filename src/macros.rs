@@ -11,7 +11,7 @@
 /// # }
 /// #
 /// # impl Component for FpsComponent {
-/// #    fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) {}
+/// #    fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) -> Result<(), matetui::ComponentError> { Ok(()) }
 /// # }
 ///
 /// let components = components![
@@ -21,22 +21,19 @@
 #[macro_export]
 macro_rules! components {
     ( $( $x:expr $( => $t:ty )* ),* ) => {
-        {
-            let mut temp_vec = Vec::new();
+        vec![
             $(
-                temp_vec.push(
-                    Box::new($x)
-                        as Box<dyn matetui::Component $( $t + )* >
-                );
-            )*
-            temp_vec
-        }
+                Box::new($x)
+                    as Box<dyn matetui::Component<_> $( $t + )* >
+            ),*
+        ]
     };
 }
 
-/// Creates a hashmap of children components.
+/// Creates the [`Children`](crate::Children) of a component.
 ///
-/// Each child will be converted into a `Box<dyn Component>`.
+/// Each child will be converted into a `Box<dyn Component>`. The children are drawn and receive
+/// the events in the order they're given.
 ///
 /// ## Example
 ///
@@ -47,7 +44,7 @@ macro_rules! components {
 /// # }
 /// #
 /// # impl Component for FpsComponent {
-/// #    fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) {}
+/// #    fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) -> Result<(), matetui::ComponentError> { Ok(()) }
 /// # }
 ///
 /// let children = children! {
@@ -58,14 +55,15 @@ macro_rules! components {
 macro_rules! children {
     ( $( $name:expr => $value:expr ),* ) => {
         {
-            let mut map = std::collections::HashMap::new();
+            #[allow(unused_mut)]
+            let mut children = matetui::Children::new();
             $(
-                map.insert(
+                children.insert(
                     $name.to_string(),
-                    Box::new($value) as Box<dyn matetui::Component>
+                    Box::new($value) as Box<dyn matetui::Component<_>>
                 );
             )*
-            map
+            children
         }
     };
 }
@@ -86,10 +84,10 @@ macro_rules! children {
 /// # }
 /// #
 /// # impl Component for FpsComponent {
-/// #   fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) {}
+/// #   fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) -> Result<(), matetui::ComponentError> { Ok(()) }
 /// # }
 /// # impl Component for MainComponent {
-/// #   fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) {}
+/// #   fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) -> Result<(), matetui::ComponentError> { Ok(()) }
 /// # }
 ///
 /// component! (
@@ -103,52 +101,124 @@ macro_rules! children {
 ///       "fps-counter" => FpsComponent::default()
 ///   }
 /// );
+/// ```
+///
+/// Components of apps with custom actions (see [`UserAction`](crate::UserAction)) declare them
+/// before the children:
+///
+/// ```rust
+/// # use matetui::{component, Component};
+/// # #[derive(Clone, Debug, PartialEq, strum::EnumString)]
+/// # enum MyAction { DrinkMate }
+/// component! (
+///   pub struct MateCounter {
+///       counter: u32
+///   },
+///   actions => MyAction
+/// );
+/// # impl Component<MyAction> for MateCounter {
+/// #   fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) -> Result<(), matetui::ComponentError> { Ok(()) }
+/// # }
+/// ```
+///
+/// Reusable components that work with any kind of actions can be generic over them instead:
+///
+/// ```rust
+/// # use matetui::{component, Component, UserAction};
+/// component! (
+///   pub struct Clock<A> {
+///       seconds: u64
+///   }
+/// );
+/// # impl<A: UserAction> Component<A> for Clock<A> {
+/// #   fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) -> Result<(), matetui::ComponentError> { Ok(()) }
+/// # }
+/// ```
+///
+/// Structs with other generics (bounded type parameters, lifetimes, consts, defaults...) take
+/// `NoAction` as their actions, unless given with `actions =>`. The framework downcasts the
+/// components, so they must be `'static`: implement `Component` for the `'static` lifetimes.
+///
+/// ```rust
+/// # use matetui::{component, Component, UserAction};
+/// # use ratatui::backend::Backend;
+/// component! (
+///   pub struct Preview<'a, B: Backend + 'static, A: UserAction = matetui::NoAction> {
+///       title: &'a str = "preview",
+///       backend: Option<B>
+///   },
+///   actions => A
+/// );
+/// # impl<B: Backend + 'static, A: UserAction> Component<A> for Preview<'static, B, A> {
+/// #   fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) -> Result<(), matetui::ComponentError> { Ok(()) }
+/// # }
+/// ```
+///
+/// Fields are initialized with their `Default` value, unless given an initializer, so fields of
+/// types without a default (channels, instants, ...) don't need an `Option` wrapper:
+///
+/// ```rust
+/// # use matetui::{component, Component};
+/// # use std::time::Instant;
+/// component! (
+///   pub struct Uptime {
+///       started: Instant = Instant::now(),
+///       label: String = "up".to_string(),
+///       ticks: u64
+///   }
+/// );
+/// # impl Component for Uptime {
+/// #   fn draw(&mut self, _: &mut matetui::Frame<'_>, _: matetui::ratatui::prelude::Rect) -> Result<(), matetui::ComponentError> { Ok(()) }
+/// # }
+/// ```
 #[macro_export]
 macro_rules! component {
-    // Entry point: struct definition without children
-    (
-        $(#[$outer:meta])*
-        $vis:vis struct $name:ident { $($fieldname:ident: $ty:ty),* $(,)? }
-    ) => {
-        // Call the inner macro with an empty children section
-        component! {
-            $(#[$outer])*
-            $vis struct $name { $($fieldname: $ty),* },
-            children => {}
-        }
-    };
-
-    // Entry point: struct definition with children
+    // Internal rule: expand the struct definition and the trait implementations. The bracketed
+    // sections hold the generics of the struct declaration, the impl blocks and the type, and the
+    // where clause of the accessors.
     (
+        @expand
         $(#[$outer:meta])*
-        $vis:vis struct $name:ident { $($fieldname:ident: $ty:ty),* $(,)? },
+        $vis:vis struct $name:ident [$($decl:tt)*] [$($impl:tt)*] [$($use:tt)*] [$($where:tt)*]
+        { $($fieldname:ident: $ty:ty $(= $init:expr)?),* $(,)? },
+        actions => $action:ty,
         children => {
             $($childname:literal => $childval:expr),* $(,)?
         }
     ) => {
         // Expand the struct with fields and children
         $(#[$outer])*
-        $vis struct $name {
+        $vis struct $name $($decl)* {
             is_active: bool,
-            action_sender: Option<tokio::sync::mpsc::UnboundedSender<String>>,
-            children: matetui::Children,
+            is_focused: bool,
+            // like the field of `ComponentBase`, not `visible`, which components showing and
+            // hiding themselves (e.g. overlays) may name their own field after
+            is_shown: Option<bool>,
+            area: Option<matetui::ratatui::layout::Rect>,
+            app_state: matetui::AppState,
+            action_sender: Option<tokio::sync::mpsc::UnboundedSender<matetui::Action<$action>>>,
+            children: matetui::Children<$action>,
             $($fieldname: $ty),*
         }
 
         // Implement the default trait for the struct
-        impl Default for $name {
+        impl $($impl)* Default for $name $($use)* {
             fn default() -> Self {
                 Self {
                     is_active: false,
+                    is_focused: false,
+                    is_shown: None,
+                    area: None,
+                    app_state: matetui::AppState::default(),
                     action_sender: None,
                     children: matetui::children!( $($childname => $childval),* ),
-                    $($fieldname: Default::default()),*
+                    $($fieldname: matetui::component!(@init $($init)?)),*
                 }
             }
         }
 
         // Implement the ComponentAccessors trait
-        impl matetui::ComponentAccessors for $name {
+        impl $($impl)* matetui::ComponentAccessors<$action> for $name $($use)* $($where)* {
             fn name(&self) -> String {
                 stringify!($name).to_string()
             }
@@ -159,17 +229,48 @@ macro_rules! component {
                 self.is_active = active;
                 self.on_active_changed(active);
             }
-            fn register_action_handler(&mut self, tx: tokio::sync::mpsc::UnboundedSender<String>) {
+            fn is_focused(&self) -> bool {
+                self.is_focused
+            }
+            fn set_focused(&mut self, focused: bool) {
+                self.is_focused = focused;
+                self.on_focus_changed(focused);
+            }
+            fn is_visible(&self) -> bool {
+                self.is_shown.unwrap_or(self.is_active)
+            }
+            fn set_visible(&mut self, visible: bool) {
+                self.is_shown = Some(visible);
+            }
+            fn area(&self) -> Option<matetui::ratatui::layout::Rect> {
+                self.area
+            }
+            fn set_area(&mut self, area: matetui::ratatui::layout::Rect) {
+                self.area = Some(area);
+            }
+            fn register_action_handler(
+                &mut self,
+                tx: tokio::sync::mpsc::UnboundedSender<matetui::Action<$action>>
+            ) {
                 self.action_sender = Some(tx.clone());
             }
+            fn action_sender(&self) -> Option<tokio::sync::mpsc::UnboundedSender<matetui::Action<$action>>> {
+                self.action_sender.clone()
+            }
+            fn register_state(&mut self, state: matetui::AppState) {
+                self.app_state = state;
+            }
+            fn state(&self) -> &matetui::AppState {
+                &self.app_state
+            }
             fn send(&self, action: &str) {
                 if let Some(tx) = &self.action_sender {
-                    tx.send(action.to_string()).unwrap();
+                    tx.send(matetui::Action::AppAction(action.to_string())).unwrap();
                 }
             }
-            fn send_action(&self, action: matetui::Action) {
+            fn send_action(&self, action: matetui::Action<$action>) {
                 if let Some(tx) = &self.action_sender {
-                    tx.send(action.to_string()).unwrap();
+                    tx.send(action).unwrap();
                 }
             }
             fn as_active(mut self) -> Self {
@@ -177,9 +278,250 @@ macro_rules! component {
                 self.on_active_changed(true);
                 self
             }
-            fn get_children(&mut self) -> Option<&mut matetui::Children> {
+            fn get_children(&mut self) -> Option<&mut matetui::Children<$action>> {
                 Some(&mut self.children)
             }
+            fn get_children_ref(&self) -> Option<&matetui::Children<$action>> {
+                Some(&self.children)
+            }
+        }
+    };
+
+    // Internal rule: the initial value of a field, its default unless given
+    (@init) => {
+        Default::default()
+    };
+    (@init $init:expr) => {
+        $init
+    };
+
+    // Entry point: struct definition without children
+    (
+        $(#[$outer:meta])*
+        $vis:vis struct $name:ident { $($fieldname:ident: $ty:ty $(= $init:expr)?),* $(,)? }
+        $(, actions => $action:ty)? $(,)?
+    ) => {
+        // Call the inner macro with an empty children section
+        component! {
+            $(#[$outer])*
+            $vis struct $name { $($fieldname: $ty $(= $init)?),* },
+            $(actions => $action,)?
+            children => {}
+        }
+    };
+
+    // Entry point: struct definition with children and without custom actions
+    (
+        $(#[$outer:meta])*
+        $vis:vis struct $name:ident { $($fieldname:ident: $ty:ty $(= $init:expr)?),* $(,)? },
+        children => {
+            $($childname:literal => $childval:expr),* $(,)?
+        }
+    ) => {
+        component! {
+            $(#[$outer])*
+            $vis struct $name { $($fieldname: $ty $(= $init)?),* },
+            actions => matetui::NoAction,
+            children => { $($childname => $childval),* }
+        }
+    };
+
+    // Entry point: struct definition with custom actions and children
+    (
+        $(#[$outer:meta])*
+        $vis:vis struct $name:ident { $($fieldname:ident: $ty:ty $(= $init:expr)?),* $(,)? },
+        actions => $action:ty,
+        children => {
+            $($childname:literal => $childval:expr),* $(,)?
+        }
+    ) => {
+        component! {
+            @expand
+            $(#[$outer])*
+            $vis struct $name [] [] [] [] { $($fieldname: $ty $(= $init)?),* },
+            actions => $action,
+            children => { $($childname => $childval),* }
+        }
+    };
+
+    // Entry point: struct definition generic over the actions, without children
+    (
+        $(#[$outer:meta])*
+        $vis:vis struct $name:ident<$generic:ident> {
+            $($fieldname:ident: $ty:ty $(= $init:expr)?),* $(,)?
+        }
+    ) => {
+        component! {
+            $(#[$outer])*
+            $vis struct $name<$generic> { $($fieldname: $ty $(= $init)?),* },
+            children => {}
+        }
+    };
+
+    // Entry point: struct definition generic over the actions, with children
+    (
+        $(#[$outer:meta])*
+        $vis:vis struct $name:ident<$generic:ident> {
+            $($fieldname:ident: $ty:ty $(= $init:expr)?),* $(,)?
+        },
+        children => {
+            $($childname:literal => $childval:expr),* $(,)?
+        }
+    ) => {
+        component! {
+            @expand
+            $(#[$outer])*
+            $vis struct $name
+                [<$generic: matetui::UserAction = matetui::NoAction>]
+                [<$generic: matetui::UserAction>]
+                [<$generic>]
+                []
+            { $($fieldname: $ty $(= $init)?),* },
+            actions => $generic,
+            children => { $($childname => $childval),* }
+        }
+    };
+
+    // Entry point: struct definition with any generics (bounded type parameters, lifetimes,
+    // consts...), split one token at a time into the generics of the declaration, of the impl
+    // blocks (without the defaults) and of the type (only the names)
+    (
+        $(#[$outer:meta])*
+        $vis:vis struct $name:ident < $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$(#[$outer])* $vis struct $name] [] [] [] [] @start $($rest)*
+        }
+    };
+
+    // Internal rules: the start of a generic parameter, its name
+    (@generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] [] @start > $($rest:tt)*) => {
+        component! { @generics [$($head)*] [$($d)*] [$($i)*] [$($u)*] [] @done $($rest)* }
+    };
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] []
+        @start const $param:ident $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)* const $param] [$($i)* const $param] [$($u)* $param] []
+            @param $($rest)*
+        }
+    };
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] []
+        @start $param:tt $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)* $param] [$($i)* $param] [$($u)* $param] []
+            @param $($rest)*
+        }
+    };
+
+    // Internal rules: the bounds of a generic parameter (`@param`) and its default (`@default`,
+    // left out of the impl blocks), keeping track of the nested angle brackets (splitting `>>`)
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] []
+        @$mode:ident , $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)* ,] [$($i)* ,] [$($u)* ,] [] @start $($rest)*
+        }
+    };
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] []
+        @$mode:ident > $($rest:tt)*
+    ) => {
+        component! { @generics [$($head)*] [$($d)*] [$($i)*] [$($u)*] [] @done $($rest)* }
+    };
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] [$($depth:tt)*]
+        @$mode:ident >> $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)*] [$($i)*] [$($u)*] [$($depth)*] @$mode > > $($rest)*
+        }
+    };
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] []
+        @param = $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)* =] [$($i)*] [$($u)*] [] @default $($rest)*
+        }
+    };
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] [$($depth:tt)*]
+        @param $token:tt $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)* $token] [$($i)* $token] [$($u)*]
+            [$($depth)*] @nest $token @param $($rest)*
+        }
+    };
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] [$($depth:tt)*]
+        @default $token:tt $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)* $token] [$($i)*] [$($u)*]
+            [$($depth)*] @nest $token @default $($rest)*
+        }
+    };
+
+    // Internal rules: the depth of the angle brackets after a token of the bounds or the default
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] [$($depth:tt)*]
+        @nest < @$mode:ident $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)*] [$($i)*] [$($u)*] [< $($depth)*] @$mode $($rest)*
+        }
+    };
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] [< $($depth:tt)*]
+        @nest > @$mode:ident $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)*] [$($i)*] [$($u)*] [$($depth)*] @$mode $($rest)*
+        }
+    };
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] [$($depth:tt)*]
+        @nest $token:tt @$mode:ident $($rest:tt)*
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)*] [$($i)*] [$($u)*] [$($depth)*] @$mode $($rest)*
+        }
+    };
+
+    // Internal rules: the generics are split, expand the struct with its actions and children
+    (
+        @generics [$(#[$outer:meta])* $vis:vis struct $name:ident]
+        [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] [] @done
+        { $($fieldname:ident: $ty:ty $(= $init:expr)?),* $(,)? },
+        actions => $action:ty
+        $(, children => { $($childname:literal => $childval:expr),* $(,)? })? $(,)?
+    ) => {
+        component! {
+            @expand
+            $(#[$outer])*
+            $vis struct $name [<$($d)*>] [<$($i)*>] [<$($u)*>]
+                [where Self: matetui::Component<$action>]
+            { $($fieldname: $ty $(= $init)?),* },
+            actions => $action,
+            children => { $($($childname => $childval),*)? }
+        }
+    };
+    (
+        @generics [$($head:tt)*] [$($d:tt)*] [$($i:tt)*] [$($u:tt)*] [] @done
+        { $($fields:tt)* }
+        $(, children => { $($children:tt)* })? $(,)?
+    ) => {
+        component! {
+            @generics [$($head)*] [$($d)*] [$($i)*] [$($u)*] [] @done
+            { $($fields)* },
+            actions => matetui::NoAction
+            $(, children => { $($children)* })?
         }
     };
 }
@@ -211,12 +553,23 @@ macro_rules! component {
 /// ```
 ///
 /// Each action will be converted into an `ActionKind`.
+///
+/// With the first syntax, the actions can be described for help screens (e.g. the `Help`
+/// component):
+///
+/// ```rust
+/// # use matetui::{kb, Action};
+/// let keybindings = kb![
+///     "<q>" => Action::Quit => "quit",
+///     "<d>" => "app:drink-mate" => "drink a mate"
+/// ];
+/// ```
 #[macro_export]
 macro_rules! kb {
-    // Accepts "<key>" => <action> syntax
-    ($($key:expr => $action:expr),* $(,)?) => {
+    // Accepts "<key>" => <action> syntax, optionally followed by "=> <description>"
+    ($($key:expr => $action:expr $(=> $description:expr)?),* $(,)?) => {
         [
-            $(($key, $crate::ActionKind::from($action))),*
+            $(($key, $crate::ActionKind::from($action)$(.described($description))?)),*
         ]
     };
 
@@ -227,3 +580,120 @@ macro_rules! kb {
         ]
     };
 }
+
+/// Creates [`ScopedKeyBindings`](crate::ScopedKeyBindings): keybindings that only fire while the
+/// component named by their scope is focused (see
+/// [`App::with_scoped_keybindings`](crate::App::with_scoped_keybindings)).
+///
+/// ```rust
+/// # use matetui::{kb_scoped, Action, ScopedKeyBindings};
+/// let keybindings: ScopedKeyBindings = kb_scoped! {
+///     "editor" => {
+///         "<ctrl-s>" => "app:save",
+///         "<ctrl-z>" => "app:undo"
+///     },
+///     "list" => {
+///         "<d>" => "app:delete",
+///         "<q>" => Action::Quit
+///     }
+/// };
+/// ```
+#[macro_export]
+macro_rules! kb_scoped {
+    ($($scope:expr => { $($key:expr => $action:expr $(=> $description:expr)?),* $(,)? }),* $(,)?) => {{
+        let mut scoped = $crate::ScopedKeyBindings::default();
+        $(
+            scoped.insert(
+                $scope,
+                $crate::KeyBindings::new($crate::kb![$($key => $action $(=> $description)?),*]),
+            );
+        )*
+        scoped
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            Component, ComponentAccessors, ComponentError, ComponentHarness, Frame, MatetuiError,
+            NoAction, UserAction,
+        },
+        ratatui::{layout::Rect, widgets::Paragraph},
+        std::time::Instant,
+    };
+
+    component! {
+        struct Uptime {
+            since: Instant = Instant::now(),
+            label: &'static str = "up",
+            ticks: u64
+        }
+    }
+
+    impl Component for Uptime {
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(format!("{} {}", self.label, self.ticks)), area);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn initializes_the_fields_of_the_components() -> Result<(), MatetuiError> {
+        let before = Instant::now();
+        let uptime = Uptime::default();
+        assert!(uptime.since >= before);
+        assert_eq!((uptime.label, uptime.ticks), ("up", 0));
+
+        let mut harness = ComponentHarness::with_size(uptime.as_active(), 10, 1);
+        harness.draw()?;
+        assert_eq!(harness.lines()[0].trim_end(), "up 0");
+        Ok(())
+    }
+
+    component! {
+        struct Gauge<T: Copy + Default + Into<f64> + 'static, const N: usize = 3> {
+            values: [T; N] = [T::default(); N]
+        }
+    }
+
+    impl<T: Copy + Default + Into<f64> + 'static, const N: usize> Component for Gauge<T, N> {
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            let total: f64 = self.values.iter().map(|value| (*value).into()).sum();
+            f.render_widget(Paragraph::new(format!("{N} {total}")), area);
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Label<'a, A: UserAction = NoAction> {
+            text: &'a str = "hola"
+        },
+        actions => A
+    }
+
+    impl<A: UserAction> Component<A> for Label<'static, A> {
+        fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+            f.render_widget(Paragraph::new(self.text), area);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn expands_the_generic_components() -> Result<(), MatetuiError> {
+        let gauge = Gauge::<u8> {
+            values: [1, 2, 3],
+            ..Gauge::default()
+        };
+        let mut harness = ComponentHarness::with_size(gauge.as_active(), 10, 1);
+        harness.draw()?;
+        assert_eq!(harness.lines()[0].trim_end(), "3 6");
+
+        let label: Label<'static> = Label::default();
+        assert_eq!(ComponentAccessors::<NoAction>::name(&label), "Label");
+        let mut harness = ComponentHarness::with_size(label.as_active(), 10, 1);
+        harness.draw()?;
+        assert_eq!(harness.lines()[0].trim_end(), "hola");
+        Ok(())
+    }
+}
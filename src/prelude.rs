@@ -0,0 +1,51 @@
+//! # Prelude
+//!
+//! The items most component files need, so they can start with a single import:
+//!
+//! ```ignore
+//! use matetui::prelude::*;
+//!
+//! component! {
+//!     pub struct Home {}
+//! }
+//!
+//! impl Component for Home {
+//!     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {
+//!         f.render_widget(Paragraph::new("matetui").block(Block::bordered()), area);
+//!         Ok(())
+//!     }
+//! }
+//! ```
+//!
+//! The whole ratatui prelude is available as [`rat`] (e.g. `rat::Direction`), so it doesn't
+//! collect every ratatui name into the scope of the glob import.
+
+pub use {
+    crate::{
+        children, component, components, kb, kb_scoped, styles, Action, App, Component,
+        ComponentAccessors, ComponentError, DragPhase, Easing, Event, Frame, KeyBindings,
+        MouseClick, MouseDrag, NoAction, ScrollDirection, Tween, UserAction,
+    },
+    ratatui::{
+        layout::{Alignment, Constraint, Layout, Margin, Rect},
+        style::{Color, Modifier, Style, Stylize},
+        text::{Line, Span, Text},
+        widgets::{
+            Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Wrap,
+        },
+    },
+};
+
+#[cfg(feature = "widget-gridselector")]
+pub use crate::widgets::gridselector::{GridItem, GridSelector, GridSelectorState};
+#[cfg(feature = "widget-link")]
+pub use crate::widgets::link::Link;
+#[cfg(feature = "widget-panel")]
+pub use crate::widgets::panel::Panel;
+#[cfg(feature = "widget-switch")]
+pub use crate::widgets::switch::Switch;
+#[cfg(feature = "widget-textarea")]
+pub use crate::widgets::textarea::TextArea;
+
+/// The ratatui prelude, namespaced so its names don't clash with the ones above.
+pub use ratatui::prelude as rat;
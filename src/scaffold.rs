@@ -0,0 +1,266 @@
+//! # Scaffold
+//!
+//! Generates a starter matetui project: a `Cargo.toml`, a `main.rs` setting up the [`App`], a root
+//! component and a `config.toml` with the theme, ready for `cargo run`:
+//!
+//! ```ignore
+//! let project = Scaffold::new("mate-counter")
+//!     .with_component("Counter")
+//!     .with_keybinding("<d>", "app:drink")
+//!     .with_theme(Theme::light())
+//!     .write(".")?;
+//! ```
+//!
+//! The generated app loads its settings from the `config.toml` next to it (see
+//! [`App::with_config_file`]), so the theme and keybindings can be customized without touching
+//! the code.
+//!
+//! [`App`]: crate::App
+//! [`App::with_config_file`]: crate::App::with_config_file
+
+use {
+    crate::{theme::Theme, Config},
+    std::{
+        fs, io,
+        path::{Path, PathBuf},
+    },
+};
+
+/// A starter project, generated with [`Scaffold::files`] or written to disk with
+/// [`Scaffold::write`].
+#[derive(Clone, Debug)]
+pub struct Scaffold {
+    name: String,
+    component: String,
+    keybindings: Vec<(String, String)>,
+    theme: Theme,
+    mouse: bool,
+}
+
+impl Scaffold {
+    /// Create a project with the given crate name (e.g. `mate-counter`).
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            component: "Home".into(),
+            keybindings: vec![("<q>".into(), "quit".into())],
+            theme: Theme::default(),
+            mouse: false,
+        }
+    }
+
+    /// Set the name of the root component, in `PascalCase`. Defaults to `Home`.
+    pub fn with_component(mut self, name: impl Into<String>) -> Self {
+        self.component = name.into();
+        self
+    }
+
+    /// Bind a key sequence to an action, in the syntax of the [kb](crate::kb) macro. The app
+    /// quits on `<q>` by default.
+    pub fn with_keybinding(mut self, keys: impl Into<String>, action: impl Into<String>) -> Self {
+        self.keybindings.push((keys.into(), action.into()));
+        self
+    }
+
+    /// Set the theme written to the `config.toml` of the project. Defaults to the dark theme.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Enable the mouse in the generated app.
+    pub fn with_mouse(mut self, mouse: bool) -> Self {
+        self.mouse = mouse;
+        self
+    }
+
+    /// The files of the project, with their paths relative to the project directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the crate or component name isn't valid.
+    pub fn files(&self) -> io::Result<Vec<(PathBuf, String)>> {
+        let valid_name = self.name.starts_with(|c: char| c.is_ascii_lowercase())
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "-_".contains(c));
+        if !valid_name {
+            return Err(invalid(format!("invalid crate name `{}`", self.name)));
+        }
+
+        let valid_component = self.component.starts_with(|c: char| c.is_ascii_uppercase())
+            && self.component.chars().all(|c| c.is_ascii_alphanumeric());
+        if !valid_component {
+            return Err(invalid(format!("invalid component name `{}`", self.component)));
+        }
+
+        let module = snake_case(&self.component);
+        Ok(vec![
+            ("Cargo.toml".into(), self.cargo_toml()),
+            ("config.toml".into(), self.config_toml()),
+            (Path::new("src").join("main.rs"), self.main_rs(&module)),
+            (Path::new("src").join(format!("{module}.rs")), self.component_rs()),
+        ])
+    }
+
+    /// Write the project to a new directory named after it inside `dir`, returning its path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the names aren't valid, the directory already exists or the files
+    /// can't be written.
+    pub fn write(&self, dir: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let files = self.files()?;
+        let root = dir.as_ref().join(&self.name);
+        if root.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", root.display()),
+            ));
+        }
+
+        for (path, content) in files {
+            let path = root.join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, content)?;
+        }
+        Ok(root)
+    }
+
+    fn cargo_toml(&self) -> String {
+        format!(
+            r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+matetui = "{version}"
+tokio = {{ version = "1", features = ["macros", "rt-multi-thread"] }}
+"#,
+            name = self.name,
+            version = env!("CARGO_PKG_VERSION"),
+        )
+    }
+
+    fn config_toml(&self) -> String {
+        let config = Config {
+            theme: Some(self.theme.clone()),
+            ..Config::default()
+        };
+        format!("# settings of {}, loaded when it starts\n\n{}", self.name, config.to_toml())
+    }
+
+    fn main_rs(&self, module: &str) -> String {
+        let keybindings = self
+            .keybindings
+            .iter()
+            .map(|(keys, action)| format!("            {keys:?} => {action:?},\n"))
+            .collect::<String>();
+
+        format!(
+            r#"mod {module};
+
+use {{
+    matetui::{{components, kb, App, ComponentAccessors}},
+    {module}::{component},
+}};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {{
+    let mut app = App::default()
+        .with_components(components![{component}::default().as_active()])
+        .with_standard_bindings(true)
+        .with_mouse({mouse})
+        .with_keybindings(kb![
+{keybindings}        ])
+        .with_config_file("config.toml")?;
+
+    app.run().await?;
+    Ok(())
+}}
+"#,
+            component = self.component,
+            mouse = self.mouse,
+        )
+    }
+
+    fn component_rs(&self) -> String {
+        let hint = self
+            .keybindings
+            .iter()
+            .map(|(keys, action)| format!("{keys} {action}"))
+            .collect::<Vec<_>>()
+            .join(" · ");
+
+        format!(
+            r#"use matetui::prelude::*;
+
+component! {{
+    pub struct {component} {{}}
+}}
+
+impl Component for {component} {{
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), ComponentError> {{
+        let lines = vec![
+            Line::styled({name:?}, styles::accent()),
+            Line::styled({hint:?}, styles::hint()),
+        ];
+        let block = Block::bordered().border_style(styles::border());
+        f.render_widget(Paragraph::new(lines).block(block), area);
+        Ok(())
+    }}
+}}
+"#,
+            component = self.component,
+            name = self.name,
+        )
+    }
+}
+
+/// `PascalCase` to `snake_case`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_the_project_files() {
+        let files = Scaffold::new("mate-counter")
+            .with_component("DrinkCounter")
+            .with_keybinding("<d>", "app:drink")
+            .with_theme(Theme::light())
+            .files()
+            .unwrap();
+
+        let paths = files.iter().map(|(path, _)| path.clone()).collect::<Vec<_>>();
+        assert_eq!(paths[3], Path::new("src").join("drink_counter.rs"));
+
+        let main = &files[2].1;
+        assert!(main.contains("mod drink_counter;"));
+        assert!(main.contains(r#""<d>" => "app:drink","#));
+
+        let config = Config::from_toml(&files[1].1).unwrap();
+        assert_eq!(config.theme, Some(Theme::light()));
+
+        assert!(Scaffold::new("Mate").files().is_err());
+        assert!(Scaffold::new("mate").with_component("home").files().is_err());
+    }
+}
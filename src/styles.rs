@@ -0,0 +1,148 @@
+//! # Styles
+//!
+//! Semantic style tokens, resolved through the current [`Theme`], and the [`StyleExt`] helpers to
+//! compose them:
+//!
+//! ```ignore
+//! use matetui::styles::{self, StyleExt};
+//!
+//! let style = styles::accent().dim_if(!self.is_focused());
+//! let error = styles::error().merge(Style::new().underlined());
+//! ```
+
+use {
+    crate::theme::Theme,
+    ratatui::style::{Modifier, Style},
+};
+
+/// Style of regular text.
+pub fn text() -> Style {
+    Theme::read(|t| t.text)
+}
+
+/// Style of secondary text, like hints and placeholders.
+pub fn hint() -> Style {
+    Theme::read(|t| t.hint)
+}
+
+/// Style of highlighted elements.
+pub fn accent() -> Style {
+    Theme::read(|t| t.accent)
+}
+
+/// Style of errors.
+pub fn error() -> Style {
+    Theme::read(|t| t.error)
+}
+
+/// Style of warnings.
+pub fn warning() -> Style {
+    Theme::read(|t| t.warning)
+}
+
+/// Style of successful outcomes.
+pub fn success() -> Style {
+    Theme::read(|t| t.success)
+}
+
+/// Style of the borders of unfocused elements.
+pub fn border() -> Style {
+    Theme::read(|t| t.border)
+}
+
+/// Style of the borders of focused elements.
+pub fn border_focused() -> Style {
+    Theme::read(|t| t.border_focused)
+}
+
+/// Style of the borders of an element, depending on whether it's focused.
+pub fn border_for(focused: bool) -> Style {
+    if focused {
+        border_focused()
+    } else {
+        border()
+    }
+}
+
+/// Style of selected items.
+pub fn selection() -> Style {
+    Theme::read(|t| t.selection)
+}
+
+/// Style of drop shadows.
+pub fn shadow() -> Style {
+    Theme::read(|t| t.shadow)
+}
+
+/// Composition helpers for [`Style`].
+pub trait StyleExt {
+    /// Apply `other` on top of this style: its colors and modifiers win over the ones of this
+    /// style.
+    fn merge(self, other: Style) -> Style;
+
+    /// Add the dim modifier if `condition` is true.
+    fn dim_if(self, condition: bool) -> Style;
+
+    /// Add the bold modifier if `condition` is true.
+    fn bold_if(self, condition: bool) -> Style;
+
+    /// Add the reversed modifier if `condition` is true.
+    fn reversed_if(self, condition: bool) -> Style;
+}
+
+impl StyleExt for Style {
+    fn merge(self, other: Style) -> Style {
+        self.patch(other)
+    }
+
+    fn dim_if(self, condition: bool) -> Style {
+        add_modifier_if(self, Modifier::DIM, condition)
+    }
+
+    fn bold_if(self, condition: bool) -> Style {
+        add_modifier_if(self, Modifier::BOLD, condition)
+    }
+
+    fn reversed_if(self, condition: bool) -> Style {
+        add_modifier_if(self, Modifier::REVERSED, condition)
+    }
+}
+
+fn add_modifier_if(style: Style, modifier: Modifier, condition: bool) -> Style {
+    if condition {
+        style.add_modifier(modifier)
+    } else {
+        style
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        ratatui::style::{Color, Stylize},
+    };
+
+    #[test]
+    fn tokens_follow_the_current_theme() {
+        Theme::set(Theme::dark().with_name("custom"));
+        assert_eq!(accent(), Theme::dark().accent);
+
+        Theme::set(Theme {
+            accent: Style::new().magenta(),
+            ..Theme::dark()
+        });
+        assert_eq!(accent(), Style::new().magenta());
+        assert_eq!(Theme::current().name, "dark");
+    }
+
+    #[test]
+    fn style_ext_helpers() {
+        let style = Style::new().red().merge(Style::new().bg(Color::Blue).bold());
+        assert_eq!(style, Style::new().red().on_blue().bold());
+
+        assert_eq!(Style::new().dim_if(false), Style::new());
+        assert_eq!(Style::new().dim_if(true), Style::new().dim());
+        assert_eq!(Style::new().bold_if(true).reversed_if(true), Style::new().bold().reversed());
+    }
+}
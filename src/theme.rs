@@ -0,0 +1,127 @@
+//! # Theme
+//!
+//! The [`Theme`] holds the semantic styles used by the built-in components and widgets, and by the
+//! apps through the [`styles`](crate::styles) tokens, so everything is styled consistently without
+//! hardcoded colors.
+//!
+//! There is one current theme per process, set with [`Theme::set`] (or
+//! [`App::with_theme`](crate::App::with_theme)). Styles are resolved when drawing, so changing the
+//! current theme restyles the whole app on the next frame.
+
+use {
+    crate::utils::keyboard::KeyGlyphs,
+    ratatui::{
+        style::{Color, Modifier, Style},
+        widgets::BorderType,
+    },
+    std::sync::RwLock,
+};
+
+static CURRENT: RwLock<Option<Theme>> = RwLock::new(None);
+
+/// A set of semantic styles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    /// regular text
+    pub text: Style,
+    /// secondary text, like hints and placeholders
+    pub hint: Style,
+    /// highlighted elements
+    pub accent: Style,
+    pub error: Style,
+    pub warning: Style,
+    pub success: Style,
+    /// borders of unfocused elements
+    pub border: Style,
+    /// borders of focused elements
+    pub border_focused: Style,
+    /// selected items
+    pub selection: Style,
+    /// drop shadows, like the one of the [Panel](crate::widgets::panel::Panel)
+    pub shadow: Style,
+    /// the border type of panels
+    pub border_type: BorderType,
+    /// how keys are shown, e.g. in binding chips
+    pub key_glyphs: KeyGlyphs,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    /// The default theme, for terminals with a dark background.
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".into(),
+            text: Style::default(),
+            hint: Style::default().fg(Color::DarkGray),
+            accent: Style::default().fg(Color::Cyan),
+            error: Style::default().fg(Color::Red),
+            warning: Style::default().fg(Color::Yellow),
+            success: Style::default().fg(Color::Green),
+            border: Style::default().fg(Color::DarkGray),
+            border_focused: Style::default().fg(Color::Cyan),
+            selection: Style::default().fg(Color::Black).bg(Color::Cyan),
+            shadow: Style::default().bg(Color::Black),
+            border_type: BorderType::Rounded,
+            key_glyphs: KeyGlyphs::Unicode,
+        }
+    }
+
+    /// A theme for terminals with a light background.
+    pub fn light() -> Self {
+        Self {
+            name: "light".into(),
+            text: Style::default(),
+            hint: Style::default().fg(Color::Gray),
+            accent: Style::default().fg(Color::Blue),
+            error: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            warning: Style::default().fg(Color::Magenta),
+            success: Style::default().fg(Color::Green),
+            border: Style::default().fg(Color::Gray),
+            border_focused: Style::default().fg(Color::Blue),
+            selection: Style::default().fg(Color::White).bg(Color::Blue),
+            shadow: Style::default().bg(Color::Gray),
+            border_type: BorderType::Plain,
+            key_glyphs: KeyGlyphs::Unicode,
+        }
+    }
+
+    /// The built-in theme with the given name, `dark` or `light`, if any.
+    pub fn builtin(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            _ => None,
+        }
+    }
+
+    /// builder method to set the name of the theme
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Get a copy of the current theme.
+    pub fn current() -> Theme {
+        Theme::read(Theme::clone)
+    }
+
+    /// Set the current theme.
+    pub fn set(theme: Theme) {
+        *CURRENT.write().unwrap() = Some(theme);
+    }
+
+    /// Read the current theme, returning the result of `f`.
+    pub fn read<R>(f: impl FnOnce(&Theme) -> R) -> R {
+        let current = CURRENT.read().unwrap();
+        match current.as_ref() {
+            Some(theme) => f(theme),
+            None => f(&Theme::default()),
+        }
+    }
+}
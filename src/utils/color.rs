@@ -0,0 +1,159 @@
+//! # Color utilities
+//!
+//! Detection of the color depth supported by the terminal, and degradation of true colors to the
+//! closest color the terminal can show.
+
+use {ratatui::style::Color, std::env};
+
+/// The colors a terminal can show, from the richest to the poorest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorDepth {
+    /// No colors, e.g. `NO_COLOR` is set or the terminal is dumb.
+    Mono,
+    /// The 16 ANSI colors.
+    Ansi16,
+    /// The 256 colors palette.
+    Ansi256,
+    /// 24-bit RGB colors.
+    #[default]
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Guess the color depth of the terminal from the environment (`NO_COLOR`, `COLORTERM` and
+    /// `TERM`).
+    pub fn detect() -> Self {
+        if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return ColorDepth::Mono;
+        }
+
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+
+        match env::var("TERM").unwrap_or_default() {
+            term if term == "dumb" => ColorDepth::Mono,
+            term if term.contains("256color") => ColorDepth::Ansi256,
+            _ => ColorDepth::Ansi16,
+        }
+    }
+
+    /// The closest color to `color` this depth can show. Named colors are kept as they are, except
+    /// for [ColorDepth::Mono], which resets every color.
+    pub fn degrade(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::Mono, _) => Color::Reset,
+            (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(rgb_to_ansi256(r, g, b)),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => rgb_to_ansi16(r, g, b),
+            (ColorDepth::Ansi16, Color::Indexed(i)) => {
+                let (r, g, b) = ansi256_to_rgb(i);
+                rgb_to_ansi16(r, g, b)
+            }
+            _ => color,
+        }
+    }
+}
+
+/// The RGB components of `color`, if known. Named colors use the xterm default palette.
+pub fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(i) => Some(ansi256_to_rgb(i)),
+        Color::Reset => None,
+        named => ANSI16.iter().find(|(c, _)| *c == named).map(|(_, rgb)| *rgb),
+    }
+}
+
+/// Linear interpolation between the colors `from` and `to`, with `t` between `0.0` and `1.0`.
+/// Colors without known RGB components aren't interpolated: the closest one is returned.
+pub fn lerp(from: Color, to: Color, t: f64) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    match (to_rgb(from), to_rgb(to)) {
+        (Some((r1, g1, b1)), Some((r2, g2, b2))) => {
+            let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ if t < 0.5 => from,
+        _ => to,
+    }
+}
+
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// levels of each component in the 6x6x6 cube of the 256 colors palette
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn ansi256_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16[index as usize].1,
+        16..=231 => {
+            let i = index - 16;
+            (CUBE[(i / 36) as usize], CUBE[(i / 6 % 6) as usize], CUBE[(i % 6) as usize])
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let level =
+        |c: u8| (0..CUBE.len()).min_by_key(|i| (CUBE[*i] as i32 - c as i32).abs()).unwrap() as u8;
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+
+    [cube, gray].into_iter().min_by_key(|i| distance(ansi256_to_rgb(*i), (r, g, b))).unwrap()
+}
+
+fn rgb_to_ansi16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16.iter().min_by_key(|(_, rgb)| distance(*rgb, (r, g, b))).unwrap().0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_degrade_to_the_closest_supported_color() {
+        let orange = Color::Rgb(255, 135, 0);
+        assert_eq!(ColorDepth::TrueColor.degrade(orange), orange);
+        assert_eq!(ColorDepth::Ansi256.degrade(orange), Color::Indexed(208));
+        assert_eq!(ColorDepth::Ansi256.degrade(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+        assert_eq!(ColorDepth::Ansi16.degrade(Color::Rgb(250, 10, 10)), Color::LightRed);
+        assert_eq!(ColorDepth::Ansi16.degrade(Color::Indexed(21)), Color::Blue);
+        assert_eq!(ColorDepth::Ansi16.degrade(Color::Cyan), Color::Cyan);
+        assert_eq!(ColorDepth::Mono.degrade(orange), Color::Reset);
+
+        assert_eq!(
+            lerp(Color::Rgb(0, 0, 0), Color::Rgb(255, 100, 10), 0.5),
+            Color::Rgb(128, 50, 5)
+        );
+    }
+}
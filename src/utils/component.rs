@@ -0,0 +1,133 @@
+//! # Component utilities
+//!
+//! Helpers for groups of children of which only one is active at a time, e.g. the fields of a
+//! form or the tabs of a panel: they move the active child through the children, in their order
+//! (see [Children](crate::Children)), deactivating the current one.
+
+use crate::{Component, UserAction};
+
+/// Activate the child after the active one (the first one if none is active), wrapping around
+/// after the last one, and deactivate the others. Returns the name of the activated child, or
+/// `None` if the component has no children.
+///
+/// ```ignore
+/// fn handle_key_events(&mut self, key: KeyEvent) -> Option<Action> {
+///     match key.code {
+///         KeyCode::Tab => focus_next_child(self),
+///         KeyCode::BackTab => focus_prev_child(self),
+///         _ => return None,
+///     };
+///     None
+/// }
+/// ```
+pub fn focus_next_child<A: UserAction, C: Component<A> + ?Sized>(c: &mut C) -> Option<String> {
+    step(c, |current, len| current.map_or(0, |index| (index + 1) % len))
+}
+
+/// Activate the child before the active one (the last one if none is active), wrapping around
+/// before the first one, and deactivate the others. Returns the name of the activated child, or
+/// `None` if the component has no children.
+pub fn focus_prev_child<A: UserAction, C: Component<A> + ?Sized>(c: &mut C) -> Option<String> {
+    step(c, |current, len| current.map_or(len - 1, |index| (index + len - 1) % len))
+}
+
+/// Activate the child with the given name and deactivate the others. Returns `false`, leaving the
+/// children untouched, if there is no such child.
+pub fn focus_child<A: UserAction, C: Component<A> + ?Sized>(c: &mut C, name: &str) -> bool {
+    let Some(index) = c.get_children_ref().and_then(|children| children.position(name)) else {
+        return false;
+    };
+    activate(c, index);
+    true
+}
+
+/// Activate the child at the position picked from the position of the active one (if any) and
+/// the number of children.
+fn step<A: UserAction, C: Component<A> + ?Sized>(
+    c: &mut C,
+    pick: impl FnOnce(Option<usize>, usize) -> usize,
+) -> Option<String> {
+    let children = c.get_children_ref().filter(|children| !children.is_empty())?;
+    let current = children.values().position(|child| child.is_active());
+    let index = pick(current, children.len());
+    activate(c, index)
+}
+
+/// Activate the child at `index` and deactivate the others, returning its name.
+fn activate<A: UserAction, C: Component<A> + ?Sized>(c: &mut C, index: usize) -> Option<String> {
+    let mut activated = None;
+    for (position, (name, child)) in c.get_children()?.iter_mut().enumerate() {
+        let active = position == index;
+        // deactivate first, so the active child is the last one to hear about it
+        if !active && child.is_active() {
+            child.set_active(false);
+        }
+        if active {
+            activated = Some(name.clone());
+        }
+    }
+
+    let name = activated?;
+    let child = c.child_mut(&name)?;
+    if !child.is_active() {
+        child.set_active(true);
+    }
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{component, ComponentAccessors, ComponentError, Frame},
+        ratatui::layout::Rect,
+    };
+
+    component! {
+        struct Field {}
+    }
+
+    impl Component for Field {
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    component! {
+        struct Form {},
+        children => {
+            "name" => Field::default(),
+            "email" => Field::default(),
+            "submit" => Field::default()
+        }
+    }
+
+    impl Component for Form {
+        fn draw(&mut self, _: &mut Frame<'_>, _: Rect) -> Result<(), ComponentError> {
+            Ok(())
+        }
+    }
+
+    fn active(form: &Form) -> Vec<&str> {
+        let children = form.get_children_ref().unwrap();
+        children.iter().filter(|(_, c)| c.is_active()).map(|(name, _)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn moves_the_active_child_through_the_children() {
+        let mut form = Form::default();
+        assert_eq!(focus_next_child(&mut form).as_deref(), Some("name"));
+        assert_eq!(focus_next_child(&mut form).as_deref(), Some("email"));
+        assert_eq!(active(&form), ["email"]);
+
+        assert_eq!(focus_prev_child(&mut form).as_deref(), Some("name"));
+        assert_eq!(focus_prev_child(&mut form).as_deref(), Some("submit"));
+        assert_eq!(focus_next_child(&mut form).as_deref(), Some("name"));
+
+        assert!(focus_child(&mut form, "submit"));
+        assert!(!focus_child(&mut form, "phone"));
+        assert_eq!(active(&form), ["submit"]);
+
+        assert_eq!(focus_next_child(&mut Field::default()), None);
+    }
+}
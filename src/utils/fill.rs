@@ -0,0 +1,240 @@
+//! # Fills
+//!
+//! Widgets that fill an area with a color [`Gradient`] or a [`Pattern`], for headers, progress
+//! bars, splash screens and the like:
+//!
+//! ```ignore
+//! let header = Gradient::horizontal([Color::Rgb(255, 95, 0), Color::Rgb(175, 0, 255)]);
+//! f.render_widget(header, area);
+//! f.render_widget(PatternFill::new(Pattern::Checker).with_style(styles::hint()), background);
+//! ```
+//!
+//! Colors are degraded to the [`ColorDepth`] of the terminal (detected from the environment by
+//! default), so gradients turn into bands of the closest colors the terminal can show.
+
+use {
+    super::color::{self, ColorDepth},
+    ratatui::{
+        buffer::Buffer,
+        layout::Rect,
+        style::{Color, Style},
+        widgets::Widget,
+    },
+};
+
+/// The direction of a [`Gradient`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// From left to right.
+    #[default]
+    Horizontal,
+    /// From top to bottom.
+    Vertical,
+}
+
+/// A widget that fills an area with a color gradient between evenly spaced color stops.
+///
+/// By default it colors the background of the area, keeping what's already drawn there. Use
+/// [`Gradient::with_foreground`] to color the text instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gradient {
+    stops: Vec<Color>,
+    direction: Direction,
+    foreground: bool,
+    depth: Option<ColorDepth>,
+}
+
+impl Gradient {
+    pub fn new(stops: impl IntoIterator<Item = Color>, direction: Direction) -> Self {
+        Self {
+            stops: stops.into_iter().collect(),
+            direction,
+            foreground: false,
+            depth: None,
+        }
+    }
+
+    /// A gradient from left to right.
+    pub fn horizontal(stops: impl IntoIterator<Item = Color>) -> Self {
+        Self::new(stops, Direction::Horizontal)
+    }
+
+    /// A gradient from top to bottom.
+    pub fn vertical(stops: impl IntoIterator<Item = Color>) -> Self {
+        Self::new(stops, Direction::Vertical)
+    }
+
+    /// Color the foreground (the text) instead of the background.
+    pub fn with_foreground(mut self, foreground: bool) -> Self {
+        self.foreground = foreground;
+        self
+    }
+
+    /// Use the given color depth instead of the detected one.
+    pub fn with_color_depth(mut self, depth: ColorDepth) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// The color at `t`, between `0.0` (the first stop) and `1.0` (the last stop).
+    pub fn color_at(&self, t: f64) -> Color {
+        match self.stops.len() {
+            0 => Color::Reset,
+            1 => self.stops[0],
+            len => {
+                let position = t.clamp(0.0, 1.0) * (len - 1) as f64;
+                let segment = (position.floor() as usize).min(len - 2);
+                color::lerp(self.stops[segment], self.stops[segment + 1], position - segment as f64)
+            }
+        }
+    }
+}
+
+impl Widget for Gradient {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        let depth = self.depth.unwrap_or_else(ColorDepth::detect);
+        let steps = match self.direction {
+            Direction::Horizontal => area.width,
+            Direction::Vertical => area.height,
+        };
+
+        for step in 0..steps {
+            let t = if steps > 1 {
+                step as f64 / (steps - 1) as f64
+            } else {
+                0.0
+            };
+            let color = depth.degrade(self.color_at(t));
+            let style = if self.foreground {
+                Style::default().fg(color)
+            } else {
+                Style::default().bg(color)
+            };
+
+            let line = match self.direction {
+                Direction::Horizontal => Rect::new(area.x + step, area.y, 1, area.height),
+                Direction::Vertical => Rect::new(area.x, area.y + step, area.width, 1),
+            };
+            buf.set_style(line, style);
+        }
+    }
+}
+
+/// A repeating pattern of symbols.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    /// `░`
+    LightShade,
+    /// `▒`
+    MediumShade,
+    /// `▓`
+    DarkShade,
+    /// alternating full and empty cells
+    Checker,
+    /// a dot every other cell of every other row
+    Dots,
+    /// diagonal stripes
+    Diagonal,
+}
+
+impl Pattern {
+    /// The symbol of the pattern at the given position.
+    pub fn symbol_at(&self, x: u16, y: u16) -> &'static str {
+        match self {
+            Pattern::LightShade => "░",
+            Pattern::MediumShade => "▒",
+            Pattern::DarkShade => "▓",
+            Pattern::Checker if (x + y) % 2 == 0 => "█",
+            Pattern::Dots if x % 2 == 0 && y % 2 == 0 => "·",
+            Pattern::Diagonal => ["╲", " ", " "][((x + 3 - y % 3) % 3) as usize],
+            _ => " ",
+        }
+    }
+}
+
+/// A widget that fills an area with a [`Pattern`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternFill {
+    pattern: Pattern,
+    style: Style,
+    depth: Option<ColorDepth>,
+}
+
+impl PatternFill {
+    pub fn new(pattern: Pattern) -> Self {
+        Self {
+            pattern,
+            style: Style::default(),
+            depth: None,
+        }
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Use the given color depth instead of the detected one.
+    pub fn with_color_depth(mut self, depth: ColorDepth) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+}
+
+impl Widget for PatternFill {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        let depth = self.depth.unwrap_or_else(ColorDepth::detect);
+        let mut style = self.style;
+        style.fg = style.fg.map(|c| depth.degrade(c));
+        style.bg = style.bg.map(|c| depth.degrade(c));
+
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                // patterns are anchored to the area, so they don't shift when it moves
+                let symbol = self.pattern.symbol_at(x - area.x, y - area.y);
+                buf[(x, y)].set_symbol(symbol).set_style(style);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_spans_the_stops() {
+        let black = Color::Rgb(0, 0, 0);
+        let white = Color::Rgb(255, 255, 255);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 5, 2));
+        Gradient::horizontal([black, white])
+            .with_color_depth(ColorDepth::TrueColor)
+            .render(buf.area, &mut buf);
+
+        assert_eq!(buf[(0, 0)].bg, black);
+        assert_eq!(buf[(2, 1)].bg, Color::Rgb(128, 128, 128));
+        assert_eq!(buf[(4, 1)].bg, white);
+
+        let gradient = Gradient::vertical([black, Color::Rgb(255, 0, 0), white]);
+        assert_eq!(gradient.color_at(0.5), Color::Rgb(255, 0, 0));
+        assert_eq!(gradient.color_at(0.75), Color::Rgb(255, 128, 128));
+    }
+
+    #[test]
+    fn patterns_fill_the_area() {
+        let filled = |pattern| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 4, 3));
+            PatternFill::new(pattern).render(buf.area, &mut buf);
+            buf
+        };
+
+        assert_eq!(filled(Pattern::LightShade), Buffer::with_lines(["░░░░"; 3]));
+        assert_eq!(filled(Pattern::MediumShade), Buffer::with_lines(["▒▒▒▒"; 3]));
+        assert_eq!(filled(Pattern::DarkShade), Buffer::with_lines(["▓▓▓▓"; 3]));
+        assert_eq!(filled(Pattern::Checker), Buffer::with_lines(["█ █ ", " █ █", "█ █ "]));
+        assert_eq!(filled(Pattern::Dots), Buffer::with_lines(["· · ", "    ", "· · "]));
+        assert_eq!(filled(Pattern::Diagonal), Buffer::with_lines(["╲  ╲", " ╲  ", "  ╲ "]));
+    }
+}
@@ -0,0 +1,59 @@
+//! # Layout utilities
+//!
+//! Helpers for widgets rendered into areas they don't control, e.g. after a resize to a tiny
+//! terminal, or nested in layouts that hand out areas partly outside of the buffer.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Block, Widget},
+};
+
+/// Fit a widget into the buffer: render its block (if any) into the area, clipped to the buffer,
+/// and return the area left inside of it for the content, or `None` when there is no room for it
+/// (e.g. a bordered block 2 rows high). Some ratatui widgets (like `Paragraph`) panic when rendered
+/// outside of the buffer, so widgets render their content into the returned area only:
+///
+/// ```ignore
+/// let Some(inner) = fit_block(self.block.as_ref(), area, buf) else {
+///     return;
+/// };
+/// Paragraph::new(text).render(inner, buf);
+/// ```
+pub fn fit_block(block: Option<&Block<'_>>, area: Rect, buf: &mut Buffer) -> Option<Rect> {
+    let area = area.intersection(buf.area);
+    if area.is_empty() {
+        return None;
+    }
+
+    let inner = match block {
+        Some(block) => {
+            block.render(area, buf);
+            block.inner(area)
+        }
+        None => area,
+    };
+    (!inner.is_empty()).then_some(inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, ratatui::widgets::Borders};
+
+    #[test]
+    fn fits_blocks_into_the_buffer() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 5));
+        let block = Block::default().borders(Borders::ALL);
+
+        assert_eq!(fit_block(None, Rect::new(8, 3, 5, 5), &mut buf), Some(Rect::new(8, 3, 2, 2)));
+        assert_eq!(
+            fit_block(Some(&block), Rect::new(0, 0, 4, 4), &mut buf),
+            Some(Rect::new(1, 1, 2, 2))
+        );
+        assert_eq!(fit_block(None, Rect::new(12, 0, 5, 5), &mut buf), None);
+
+        // the borders are drawn, but there is no room inside of them
+        assert_eq!(fit_block(Some(&block), Rect::new(5, 0, 5, 2), &mut buf), None);
+        assert_eq!(buf[(5, 1)].symbol(), "└");
+    }
+}
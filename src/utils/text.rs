@@ -0,0 +1,218 @@
+//! # Text utilities
+//!
+//! Width-aware text helpers: truncation, padding, centering and wrapping, and fuzzy matching. All the widths are
+//! measured in terminal columns, taking into account wide (e.g. CJK, emoji) and zero-width
+//! characters.
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// The ellipsis used by the truncation helpers.
+pub const ELLIPSIS: &str = "…";
+
+/// Width of the text in terminal columns.
+pub fn width(text: &str) -> usize {
+    text.width()
+}
+
+fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Take the longest prefix of `text` that fits in `max` columns.
+fn take_width(text: &str, max: usize) -> &str {
+    let mut used = 0;
+    for (i, c) in text.char_indices() {
+        used += char_width(c);
+        if used > max {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+/// Take the longest suffix of `text` that fits in `max` columns.
+fn take_width_end(text: &str, max: usize) -> &str {
+    let mut used = 0;
+    for (i, c) in text.char_indices().rev() {
+        used += char_width(c);
+        if used > max {
+            return &text[i + c.len_utf8()..];
+        }
+    }
+    text
+}
+
+/// Truncate the text to fit in `max` columns, ending it with an ellipsis if it doesn't fit.
+///
+/// ```ignore
+/// assert_eq!(truncate("hello world", 8), "hello w…");
+/// ```
+pub fn truncate(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+
+    if max == 0 {
+        return String::new();
+    }
+
+    format!("{}{ELLIPSIS}", take_width(text, max - 1))
+}
+
+/// Truncate the text to fit in `max` columns, replacing its middle with an ellipsis if it doesn't
+/// fit, so both the start and the end are kept. Useful for paths.
+///
+/// ```ignore
+/// assert_eq!(truncate_middle("/home/user/projects/matetui/src/lib.rs", 20), "/home/use…src/lib.rs");
+/// ```
+pub fn truncate_middle(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+
+    if max == 0 {
+        return String::new();
+    }
+
+    let available = max - 1;
+    // the end (e.g. the file name) gets the larger half
+    let tail = take_width_end(text, available - available / 2);
+    let head = take_width(text, available - width(tail));
+    format!("{head}{ELLIPSIS}{tail}")
+}
+
+/// Pad the text with spaces on the right up to `target` columns.
+pub fn pad_right(text: &str, target: usize) -> String {
+    format!("{text}{}", " ".repeat(target.saturating_sub(width(text))))
+}
+
+/// Pad the text with spaces on the left up to `target` columns.
+pub fn pad_left(text: &str, target: usize) -> String {
+    format!("{}{text}", " ".repeat(target.saturating_sub(width(text))))
+}
+
+/// Center the text in `target` columns, padding it with spaces on both sides. If the padding
+/// can't be evenly split, the extra space goes to the right.
+pub fn center(text: &str, target: usize) -> String {
+    let padding = target.saturating_sub(width(text));
+    let left = padding / 2;
+    format!("{}{text}{}", " ".repeat(left), " ".repeat(padding - left))
+}
+
+/// Wrap the text in lines of at most `max` columns, breaking on whitespace when possible and
+/// splitting the words that don't fit in a line on their own. Existing line breaks are kept.
+pub fn wrap(text: &str, max: usize) -> Vec<String> {
+    let max = max.max(1);
+    let mut lines = vec![];
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+
+        for word in paragraph.split_whitespace() {
+            let mut word = word;
+            let separator = usize::from(!line.is_empty());
+
+            if width(&line) + separator + width(word) <= max {
+                if separator == 1 {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+
+            // words longer than a line are split
+            while width(word) > max {
+                let mut head = take_width(word, max);
+                if head.is_empty() {
+                    // a single character wider than the line
+                    head = &word[..word.chars().next().map_or(0, char::len_utf8)];
+                }
+                lines.push(head.to_string());
+                word = &word[head.len()..];
+            }
+            line.push_str(word);
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Match the query against the text as a subsequence, ignoring case: the chars of the query must
+/// all appear in the text, in order. Returns the score of the match, higher when the chars match
+/// at the start of words or next to each other, and the indices of the matched chars in the text
+/// (e.g. to highlight them).
+///
+/// ```ignore
+/// assert_eq!(fuzzy_match("sf", "Save file").map(|(_, chars)| chars), Some(vec![0, 5]));
+/// assert_eq!(fuzzy_match("fs", "Save file"), None);
+/// ```
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut matched = vec![];
+    let mut score = 0;
+    let mut from = 0;
+
+    for q in query.chars().map(lower) {
+        let i = from + chars[from..].iter().position(|c| lower(*c) == q)?;
+        let word_start = i == 0 || !chars[i - 1].is_alphanumeric();
+        let adjacent = matched.last().is_some_and(|last| last + 1 == i);
+        score += 1 + 8 * i64::from(word_start) + 4 * i64::from(adjacent);
+        score -= (i - from) as i64;
+        matched.push(i);
+        from = i + 1;
+    }
+
+    Some((score, matched))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncation_is_width_aware() {
+        assert_eq!(truncate("hello world", 20), "hello world");
+        assert_eq!(truncate("hello world", 8), "hello w…");
+        assert_eq!(truncate("日本語テキスト", 7), "日本語…");
+        assert_eq!(truncate("hello", 0), "");
+
+        let path = "/home/user/projects/matetui/src/lib.rs";
+        let truncated = truncate_middle(path, 20);
+        assert_eq!(truncated, "/home/use…src/lib.rs");
+        assert_eq!(width(&truncated), 20);
+    }
+
+    #[test]
+    fn padding_and_centering() {
+        assert_eq!(pad_right("日本", 6), "日本  ");
+        assert_eq!(pad_left("ab", 4), "  ab");
+        assert_eq!(center("ab", 5), " ab  ");
+        assert_eq!(center("abcdef", 3), "abcdef");
+    }
+
+    #[test]
+    fn wrapping_respects_widths() {
+        assert_eq!(wrap("the quick brown fox", 10), ["the quick", "brown fox"]);
+        assert_eq!(wrap("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("日本語テキスト", 5), ["日本", "語テ", "キス", "ト"]);
+        assert_eq!(wrap("one\n\ntwo", 10), ["one", "", "two"]);
+    }
+
+    #[test]
+    fn fuzzy_matching_prefers_word_starts() {
+        assert_eq!(fuzzy_match("sf", "Save file").map(|(_, chars)| chars), Some(vec![0, 5]));
+        assert_eq!(fuzzy_match("fs", "Save file"), None);
+        assert_eq!(fuzzy_match("", "Save file"), Some((0, vec![])));
+
+        let score = |query, text| fuzzy_match(query, text).unwrap().0;
+        assert!(score("sf", "Save file") > score("sf", "misfire"));
+        assert!(score("save", "Save file") > score("save", "Shave and leave"));
+    }
+}
@@ -0,0 +1,112 @@
+//! # Grid Selector Loader
+//!
+//! This module contains the [`GridLoader`], used by the [`GridSelectorState`] to load more items
+//! on demand when the user navigates near the end of the grid.
+//!
+//! [`GridSelectorState`]: super::GridSelectorState
+
+use {
+    super::GridItem,
+    futures::future::BoxFuture,
+    std::{
+        fmt::{self, Debug},
+        future::Future,
+        sync::{Arc, Mutex},
+    },
+};
+
+type LoadFn = Arc<dyn Fn(usize, usize) -> BoxFuture<'static, Vec<GridItem>> + Send + Sync>;
+
+/// Loads pages of items asynchronously, given an `offset` and a `limit`.
+///
+/// The load futures are spawned on the current tokio runtime; if there is none, nothing is loaded.
+#[derive(Clone)]
+pub struct GridLoader {
+    load: LoadFn,
+    limit: usize,
+    threshold: usize,
+    pending: Option<Arc<Mutex<Option<Vec<GridItem>>>>>,
+    exhausted: bool,
+}
+
+impl GridLoader {
+    pub(crate) fn new<F, Fut, T>(limit: usize, load: F) -> Self
+    where
+        F: Fn(usize, usize) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Vec<T>> + Send + 'static,
+        T: Into<GridItem>,
+    {
+        let load: LoadFn = Arc::new(move |offset, limit| {
+            let fut = load(offset, limit);
+            Box::pin(async move { fut.await.into_iter().map(Into::into).collect() })
+        });
+
+        Self {
+            load,
+            limit,
+            threshold: 1,
+            pending: None,
+            exhausted: false,
+        }
+    }
+
+    pub(crate) fn set_threshold(&mut self, rows: usize) {
+        self.threshold = rows;
+    }
+
+    pub(crate) fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns `true` while a page is being loaded.
+    pub(crate) fn is_loading(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Returns `true` once the loader returned an empty page.
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Take the loaded page if the pending load finished.
+    pub(crate) fn take_loaded(&mut self) -> Option<Vec<GridItem>> {
+        let loaded = self.pending.as_ref()?.lock().unwrap().take()?;
+        self.pending = None;
+        if loaded.is_empty() {
+            self.exhausted = true;
+        }
+        Some(loaded)
+    }
+
+    /// Start loading the page starting at `offset`, unless a load is already in progress.
+    pub(crate) fn load(&mut self, offset: usize) {
+        if self.is_loading() || self.exhausted {
+            return;
+        }
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let slot = Arc::new(Mutex::new(None));
+        let fut = (self.load)(offset, self.limit);
+        let task_slot = slot.clone();
+        runtime.spawn(async move {
+            let items = fut.await;
+            *task_slot.lock().unwrap() = Some(items);
+        });
+
+        self.pending = Some(slot);
+    }
+}
+
+impl Debug for GridLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GridLoader")
+            .field("limit", &self.limit)
+            .field("threshold", &self.threshold)
+            .field("loading", &self.is_loading())
+            .field("exhausted", &self.exhausted)
+            .finish()
+    }
+}
@@ -15,6 +15,7 @@ pub struct GridSelector {
     color: Color,
     hovered_color: Color,
     selected_color: Color,
+    section_color: Color,
 }
 
 impl Default for GridSelector {
@@ -23,6 +24,7 @@ impl Default for GridSelector {
             color: Color::Reset,
             hovered_color: Color::Blue,
             selected_color: Color::Green,
+            section_color: Color::DarkGray,
         }
     }
 }
@@ -48,6 +50,16 @@ impl GridSelector {
         self
     }
 
+    /// Set the color of the section headers in the grid.
+    pub fn with_section_color(mut self, color: Color) -> Self {
+        self.section_color = color;
+        self
+    }
+
+    pub(crate) fn get_section_color(&self) -> Color {
+        self.section_color
+    }
+
     pub(crate) fn get_color(&self, for_idx: usize, state: &GridSelectorState) -> Color {
         let mut color = self.color;
 
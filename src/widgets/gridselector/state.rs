@@ -5,11 +5,15 @@
 //! The state is used to keep track of the items, the selected item, and the hovered item and
 //! encapsulates the navigation logic for the grid selector.
 
-use ratatui::text::Text;
+use {super::GridLoader, ratatui::text::Text, std::future::Future};
 
 // If Text has a lifetime parameter, specify it in the implementation.
 #[derive(Clone, Debug)]
-pub struct GridItem(String);
+pub struct GridItem {
+    value: String,
+    span: usize,
+    section: bool,
+}
 
 impl GridItem {
     // accept both String and &str
@@ -17,42 +21,76 @@ impl GridItem {
     where
         S: Into<String>,
     {
-        GridItem(value.into())
+        GridItem {
+            value: value.into(),
+            span: 1,
+            section: false,
+        }
+    }
+
+    /// Create a section header. Sections group the items that follow them under a label, are
+    /// rendered as full-width separator rows and are skipped by the navigation.
+    pub fn section<S>(label: S) -> Self
+    where
+        S: Into<String>,
+    {
+        GridItem {
+            section: true,
+            ..GridItem::new(label)
+        }
+    }
+
+    /// Returns `true` if the item is a section header.
+    pub fn is_section(&self) -> bool {
+        self.section
+    }
+
+    /// builder method to make the item span multiple columns of the grid (1 by default)
+    ///
+    /// The span is clamped to the number of columns of the grid.
+    pub fn span(mut self, span: usize) -> Self {
+        self.span = span.max(1);
+        self
+    }
+
+    /// Get the number of columns the item spans.
+    pub fn get_span(&self) -> usize {
+        self.span
     }
 }
 
 // convert Label into &str
 impl AsRef<str> for GridItem {
     fn as_ref(&self) -> &str {
-        &self.0
+        &self.value
     }
 }
 
 // convert Label into String
 impl From<GridItem> for String {
     fn from(val: GridItem) -> Self {
-        val.0.clone()
+        val.value
     }
 }
 
 // Specify the lifetime for the implementation
 impl<'a> From<GridItem> for Text<'a> {
     fn from(val: GridItem) -> Self {
-        Text::from(val.0)
+        Text::from(val.value)
     }
 }
 
 // implement a way to convert String into Label
 impl From<String> for GridItem {
     fn from(value: String) -> Self {
-        GridItem(value)
+        GridItem::new(value)
     }
 }
 
 // implement a way to convert &str into Label
 impl<'a> From<&'a str> for GridItem {
     fn from(value: &'a str) -> Self {
-        GridItem(value.to_string())
+        GridItem::new(value)
     }
 }
 
@@ -65,6 +103,9 @@ pub struct GridSelectorState {
     pub selected: Option<usize>,
     pub hovered: Option<usize>,
     pub(crate) columns: usize,
+    /// first row rendered in the last render, used to keep the hovered row visible
+    pub(crate) offset: usize,
+    pub(crate) loader: Option<GridLoader>,
 }
 
 impl GridSelectorState {
@@ -75,11 +116,14 @@ impl GridSelectorState {
         T: Into<GridItem>, // Accept anything that can be converted into Label
     {
         let items: Vec<GridItem> = items.into_iter().map(Into::into).collect();
+        let hovered = items.iter().position(|item| !item.section).or(Some(0));
         Self {
             items,
             selected: None,
-            hovered: Some(0),
+            hovered,
             columns: 5,
+            offset: 0,
+            loader: None,
         }
     }
 
@@ -89,6 +133,79 @@ impl GridSelectorState {
         self
     }
 
+    /// builder method to load more items on demand, `limit` items at a time.
+    ///
+    /// The `loader` receives the `offset` (the number of items already in the state) and the
+    /// `limit` and is called every time the hovered item gets near the end of the grid (see
+    /// [`GridSelectorState::load_threshold`]), until it returns an empty page. Useful for pickers
+    /// backed by a remote source:
+    ///
+    /// ```ignore
+    /// let state = GridSelectorState::new(Vec::<GridItem>::new())
+    ///     .with_loader(20, |offset, limit| async move { fetch_names(offset, limit).await });
+    /// ```
+    ///
+    /// The load runs on the current tokio runtime and the loaded items are appended on the next
+    /// render or navigation. If there is no runtime, nothing is loaded.
+    pub fn with_loader<F, Fut, T>(mut self, limit: usize, loader: F) -> Self
+    where
+        F: Fn(usize, usize) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Vec<T>> + Send + 'static,
+        T: Into<GridItem>,
+    {
+        self.loader = Some(GridLoader::new(limit, loader));
+        self.load_more();
+        self
+    }
+
+    /// builder method to set how many rows before the last one the hovered item has to be to
+    /// trigger a load (1 by default). Only relevant with [`GridSelectorState::with_loader`].
+    pub fn load_threshold(mut self, rows: usize) -> Self {
+        if let Some(loader) = self.loader.as_mut() {
+            loader.set_threshold(rows);
+        }
+        self
+    }
+
+    /// Returns `true` while the loader is loading more items.
+    pub fn is_loading(&self) -> bool {
+        self.loader.as_ref().is_some_and(GridLoader::is_loading)
+    }
+
+    /// Returns `true` once the loader has no more items to load.
+    pub fn is_exhausted(&self) -> bool {
+        self.loader.as_ref().is_some_and(GridLoader::is_exhausted)
+    }
+
+    /// Append the items loaded by the loader, if any, and start loading the next page if the
+    /// hovered item is near the end of the grid.
+    ///
+    /// This is called on every render and navigation, so there is usually no need to call it.
+    pub fn load_more(&mut self) {
+        let Some(loader) = self.loader.as_mut() else {
+            return;
+        };
+
+        if let Some(loaded) = loader.take_loaded() {
+            self.items.extend(loaded);
+            if self.hovered.is_none_or(|idx| idx >= self.items.len() || self.items[idx].section) {
+                self.hovered = self.items.iter().position(|item| !item.section).or(Some(0));
+            }
+        }
+
+        let threshold = self.loader.as_ref().map_or(0, GridLoader::threshold);
+        let rows = self.rows();
+        let row =
+            self.hovered.and_then(|idx| rows.iter().position(|r| r.contains(&idx))).unwrap_or(0);
+
+        if rows.len().saturating_sub(1) <= row + threshold {
+            let offset = self.items.len();
+            if let Some(loader) = self.loader.as_mut() {
+                loader.load(offset);
+            }
+        }
+    }
+
     /// Get the selected item.
     pub fn selected(&self) -> Option<GridItem> {
         self.selected.map(|i| self.items[i].clone())
@@ -104,158 +221,159 @@ impl GridSelectorState {
         self.hovered.map(|i| self.items[i].clone())
     }
 
-    /// Move the hovered item right +1
+    /// Move the hovered item right +1, skipping section headers
     ///
     ///  Returns `true` if the hovered item was moved, `false` otherwise.
     pub fn move_right(&mut self) -> bool {
-        self.hovered = if let Some(hovered) = self.hovered {
-            let next = hovered + 1;
-            if next < self.items.len() {
-                Some(next)
-            } else {
-                Some(0)
-            }
-        } else {
-            Some(0)
-        };
+        let len = self.items.len();
+        let start = self.hovered.unwrap_or(len.saturating_sub(1));
+        self.hovered = (1..=len)
+            .map(|step| (start + step) % len)
+            .find(|i| !self.items[*i].section)
+            .or(Some(0));
+        self.load_more();
 
         true
     }
 
-    /// Move the hovered item left -1
+    /// Move the hovered item left -1, skipping section headers
     ///
     /// Returns `true` if the hovered item was moved,
     /// `false` otherwise.
     pub fn move_left(&mut self) -> bool {
-        self.hovered = if let Some(hovered) = self.hovered {
-            if hovered > 0 {
-                Some(hovered - 1)
-            } else {
-                Some(self.items.len() - 1)
-            }
-        } else {
-            Some(0)
-        };
+        let len = self.items.len();
+        let start = self.hovered.unwrap_or(1 % len.max(1));
+        self.hovered = (1..=len)
+            .map(|step| (start + len - step) % len)
+            .find(|i| !self.items[*i].section)
+            .or(Some(0));
         true
     }
 
     /// Move the hovered item down by one row.
     ///
+    /// The item right below the hovered one is hovered next, taking into account that shorter rows
+    /// are centered and that items can span multiple columns.
+    ///
     /// Returns `true` if the hovered item was moved, `false` otherwise.
     pub fn move_down(&mut self) -> bool {
-        if let Some(hovered) = self.hovered {
-            let items_per_row = self.columns;
-            let num_items = self.items.len();
-            let current_row = hovered / items_per_row;
-            let next_row_start = (current_row + 1) * items_per_row;
-            let last_item_index = num_items - 1;
-
-            // If we are in the last row, we can't go down
-            if next_row_start > last_item_index {
-                return false;
-            }
-
-            let mut next_index = std::cmp::min(hovered + items_per_row, last_item_index);
-
-            // Handle the case where the next row has fewer items
-            let next_row_count = std::cmp::min(items_per_row, last_item_index - next_row_start + 1);
-
-            // check if both the next_row_count and the self.columns are odd numbers (3,5,7, etc)
-            // and next_row_count is less than self.columns
-            if next_row_count % 2 != 0 && items_per_row % 2 != 0 && next_row_count < items_per_row {
-                let shift = (items_per_row - next_row_count) / 2;
-                // if we are in the shifted range (left or right) of the row, adjust the hovered
-                // index accordingly
-                if hovered % items_per_row >= shift
-                    && hovered % items_per_row < items_per_row - shift
-                {
-                    next_index = hovered + items_per_row - shift;
-                } else {
-                    // if we are in the left part of the shifted range, set next to the first item
-                    // in the last row and if we are in the right part of the shifted range, set
-                    // next to the last item in the last row
-                    next_index = if hovered % items_per_row < shift {
-                        next_row_start
-                    } else {
-                        last_item_index
-                    };
-                }
-            }
-
-            self.hovered = Some(std::cmp::min(next_index, last_item_index));
-            return true;
-        }
-
-        false
+        let moved = self.move_vertically(1);
+        self.load_more();
+        moved
     }
 
     /// Move the hovered item up by one row.
     ///
+    /// The item right above the hovered one is hovered next, taking into account that shorter rows
+    /// are centered and that items can span multiple columns.
+    ///
     /// Returns `true` if the hovered item was moved, `false` otherwise.
     pub fn move_up(&mut self) -> bool {
-        if let Some(hovered) = self.hovered {
-            let row_number = hovered / self.columns;
-
-            // If we are in the first row, we can't go up
-            if row_number == 0 {
-                return false;
-            }
-
-            let mut next_index = hovered.saturating_sub(self.columns);
+        self.move_vertically(-1)
+    }
 
-            // Handle case where the current index is in the last row
-            // let is_last_row = hovered >= self.items.len().saturating_sub(self.columns);
-            let last_row_start = (self.items.len() / self.columns) * self.columns;
-            let is_last_row = hovered >= last_row_start;
+    fn move_vertically(&mut self, delta: isize) -> bool {
+        let Some(hovered) = self.hovered else {
+            return false;
+        };
 
-            if is_last_row {
-                let last_row_count = self.items.len() % self.columns;
+        let rows = self.rows();
+        let Some(row) = rows.iter().position(|r| r.contains(&hovered)) else {
+            return false;
+        };
 
-                // If the last_row_count and self.columns are odd numbers (3,5,7, etc)
-                // and last_row_count is less than self.columns we need to adjust the next index
-                // to go to the cell just above the current hovered cell
-                if last_row_count % 2 != 0 && self.columns % 2 != 0 && last_row_count < self.columns
-                {
-                    let shift = (self.columns - last_row_count) / 2;
-                    next_index = hovered.saturating_sub(self.columns - shift);
-                }
+        // If we are in the first/last row, we can't go further. Section rows are skipped
+        let mut next_row = row;
+        loop {
+            match next_row.checked_add_signed(delta).filter(|r| *r < rows.len()) {
+                Some(r) if self.is_section_row(&rows[r]) => next_row = r,
+                Some(r) => break next_row = r,
+                None => return false,
             }
-
-            // Ensure next_index stays within bounds
-            self.hovered = Some(std::cmp::min(next_index, self.items.len() - 1));
-            return true;
         }
 
-        false
+        let center = self.center_of(&rows[row], hovered);
+        let closest = rows[next_row].iter().min_by(|a, b| {
+            let da = (self.center_of(&rows[next_row], **a) - center).abs();
+            let db = (self.center_of(&rows[next_row], **b) - center).abs();
+            da.total_cmp(&db)
+        });
+
+        self.hovered = closest.copied();
+        true
     }
 
     /// Move the hovered item to the first item in the current row.
     ///
     /// Returns `true` if the hovered item was moved, `false` otherwise.
     pub fn move_to_row_start(&mut self) -> bool {
-        if let Some(hovered) = self.hovered {
-            let row_start = (hovered / self.columns) * self.columns;
-            self.hovered = Some(row_start);
-            true
-        } else {
-            false
-        }
+        self.move_in_row(|row| row.first())
     }
 
     /// Move the hovered item to the last item in the current row.
     ///
     /// Returns `true` if the hovered item was moved, `false` otherwise.
     pub fn move_to_row_end(&mut self) -> bool {
+        self.move_in_row(|row| row.last())
+    }
+
+    fn move_in_row(&mut self, pick: impl Fn(&Vec<usize>) -> Option<&usize>) -> bool {
         if let Some(hovered) = self.hovered {
-            let row_end = std::cmp::min(
-                (hovered / self.columns + 1) * self.columns - 1,
-                self.items.len() - 1,
-            );
-            self.hovered = Some(row_end);
-            true
-        } else {
-            false
+            let rows = self.rows();
+            if let Some(row) = rows.iter().find(|r| r.contains(&hovered)) {
+                self.hovered = pick(row).copied();
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Get the number of columns spanned by the item at `idx`, clamped to the number of columns.
+    pub(crate) fn span_of(&self, idx: usize) -> usize {
+        self.items[idx].span.clamp(1, self.columns.max(1))
+    }
+
+    /// Distribute the items in rows, wrapping to a new row every time an item doesn't fit in the
+    /// remaining columns of the current one. Section headers always take a whole row. Each row
+    /// contains the indexes of its items.
+    pub(crate) fn rows(&self) -> Vec<Vec<usize>> {
+        let mut rows: Vec<Vec<usize>> = vec![];
+        let mut used = self.columns;
+
+        for idx in 0..self.items.len() {
+            let span = if self.items[idx].section {
+                self.columns
+            } else {
+                self.span_of(idx)
+            };
+
+            if used + span > self.columns || self.items[idx].section {
+                rows.push(vec![]);
+                used = 0;
+            }
+            rows.last_mut().unwrap().push(idx);
+            used = if self.items[idx].section {
+                self.columns
+            } else {
+                used + span
+            };
         }
+
+        rows
+    }
+
+    /// Returns `true` if the row is a section header row.
+    pub(crate) fn is_section_row(&self, row: &[usize]) -> bool {
+        row.first().is_some_and(|idx| self.items[*idx].section)
+    }
+
+    /// Horizontal center of the item at `idx` (in column units) inside its centered row.
+    fn center_of(&self, row: &[usize], idx: usize) -> f32 {
+        let row_span: usize = row.iter().map(|i| self.span_of(*i)).sum();
+        let offset = (self.columns as f32 - row_span as f32) / 2.0;
+        let start: usize = row.iter().take_while(|i| **i != idx).map(|i| self.span_of(*i)).sum();
+        offset + start as f32 + self.span_of(idx) as f32 / 2.0
     }
 
     /// Select the hovered item.
@@ -270,3 +388,152 @@ impl GridSelectorState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(items: Vec<GridItem>, columns: usize, hovered: usize) -> GridSelectorState {
+        let mut state = GridSelectorState::new(items).columns(columns);
+        state.hovered = Some(hovered);
+        state
+    }
+
+    fn plain(count: usize) -> Vec<GridItem> {
+        (0..count).map(|i| GridItem::new(i.to_string())).collect()
+    }
+
+    #[test]
+    fn renders_into_any_area() {
+        use {
+            crate::widgets::gridselector::GridSelector,
+            ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget},
+        };
+
+        let mut state = state(plain(6), 3, 4);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 10));
+        for (x, y, width, height) in [(0, 0, 1, 1), (0, 0, 10, 2), (4, 6, 12, 8), (12, 0, 4, 4)] {
+            let area = Rect::new(x, y, width, height);
+            StatefulWidget::render(&GridSelector::default(), area, &mut buf, &mut state);
+        }
+    }
+
+    #[test]
+    fn rows_wrap_on_spans() {
+        let items = vec![
+            GridItem::new("a").span(2),
+            GridItem::new("b"),
+            GridItem::new("c").span(2),
+            GridItem::new("d").span(9),
+        ];
+        let state = state(items, 3, 0);
+        assert_eq!(state.rows(), vec![vec![0, 1], vec![2], vec![3]]);
+    }
+
+    #[test]
+    fn move_down_to_centered_shorter_row() {
+        // 5 columns, last row has 3 items centered below columns 1..4
+        for (from, to) in [(5, 10), (6, 10), (7, 11), (8, 12), (9, 12)] {
+            let mut s = state(plain(13), 5, from);
+            assert!(s.move_down());
+            assert_eq!(s.hovered, Some(to), "down from {from}");
+        }
+    }
+
+    #[test]
+    fn move_up_from_centered_shorter_row() {
+        for (from, to) in [(10, 6), (11, 7), (12, 8)] {
+            let mut s = state(plain(13), 5, from);
+            assert!(s.move_up());
+            assert_eq!(s.hovered, Some(to), "up from {from}");
+        }
+    }
+
+    #[test]
+    fn move_vertically_across_spans() {
+        let items = vec![
+            GridItem::new("header").span(3),
+            GridItem::new("a"),
+            GridItem::new("b"),
+            GridItem::new("c"),
+        ];
+
+        let mut s = state(items.clone(), 3, 0);
+        assert!(s.move_down());
+        assert_eq!(s.hovered, Some(2));
+        assert!(!s.move_down());
+
+        let mut s = state(items, 3, 3);
+        assert!(s.move_up());
+        assert_eq!(s.hovered, Some(0));
+        assert!(!s.move_up());
+    }
+
+    #[test]
+    fn navigation_skips_sections() {
+        let items = vec![
+            GridItem::section("first"),
+            GridItem::new("a"),
+            GridItem::new("b"),
+            GridItem::section("second"),
+            GridItem::new("c"),
+        ];
+
+        let mut s = GridSelectorState::new(items).columns(2);
+        assert_eq!(s.rows(), vec![vec![0], vec![1, 2], vec![3], vec![4]]);
+        assert_eq!(s.hovered, Some(1));
+
+        assert!(s.move_down());
+        assert_eq!(s.hovered, Some(4));
+        assert!(s.move_up());
+        assert_eq!(s.hovered, Some(1));
+        assert!(!s.move_up());
+
+        s.hovered = Some(2);
+        assert!(s.move_right());
+        assert_eq!(s.hovered, Some(4));
+        assert!(s.move_right());
+        assert_eq!(s.hovered, Some(1));
+        assert!(s.move_left());
+        assert_eq!(s.hovered, Some(4));
+    }
+
+    #[test]
+    fn loader_appends_pages_near_the_end() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let _guard = runtime.enter();
+
+        let mut s = GridSelectorState::new(plain(5)).columns(5).with_loader(
+            5,
+            |offset, limit| async move {
+                (offset..(offset + limit).min(12)).map(|i| i.to_string()).collect::<Vec<_>>()
+            },
+        );
+        assert!(s.is_loading());
+
+        for expected in [10, 12] {
+            runtime.block_on(tokio::task::yield_now());
+            s.load_more();
+            assert_eq!(s.items.len(), expected);
+        }
+        // the hovered item is far from the end, nothing else is loaded
+        assert!(!s.is_loading());
+        assert_eq!(s.hovered, Some(0));
+
+        assert!(s.move_down());
+        assert!(s.is_loading());
+        runtime.block_on(tokio::task::yield_now());
+        s.load_more();
+        assert_eq!(s.items.len(), 12);
+        assert!(s.is_exhausted());
+    }
+
+    #[test]
+    fn move_to_row_bounds() {
+        let mut s = state(plain(13), 5, 11);
+        assert!(s.move_to_row_start());
+        assert_eq!(s.hovered, Some(10));
+        assert!(s.move_to_row_end());
+        assert_eq!(s.hovered, Some(12));
+    }
+}
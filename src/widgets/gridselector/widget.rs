@@ -1,29 +1,90 @@
 use {
     super::{GridSelector, GridSelectorState},
+    crate::utils::layout::fit_block,
     ratatui::{
         buffer::Buffer,
         layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
-        style::Style,
+        style::{Modifier, Style},
         widgets::{Block, Borders, Paragraph, StatefulWidget, Widget},
     },
     std::rc::Rc,
     unicode_width::UnicodeWidthStr,
 };
 
+impl GridSelector {
+    /// The selector bound to its state as a plain ratatui [`Widget`], to compose it inside code
+    /// that only deals with stateless widgets (e.g. a `Paragraph` next to it in a custom widget).
+    pub fn widget<'a>(&'a self, state: &'a mut GridSelectorState) -> impl Widget + 'a {
+        StatefulGridSelector {
+            selector: self,
+            state,
+        }
+    }
+}
+
+struct StatefulGridSelector<'a> {
+    selector: &'a GridSelector,
+    state: &'a mut GridSelectorState,
+}
+
+impl Widget for StatefulGridSelector<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        StatefulWidget::render(self.selector, area, buf, self.state);
+    }
+}
+
 impl StatefulWidget for GridSelector {
     type State = GridSelectorState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut GridSelectorState) {
-        let rows_layout = rows_layout(state, area);
-        let largest_item = largest_item(state);
+        StatefulWidget::render(&self, area, buf, state);
+    }
+}
+
+impl StatefulWidget for &GridSelector {
+    type State = GridSelectorState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut GridSelectorState) {
+        // the cells are laid out inside the area, so they can't end up outside of the buffer
+        let Some(area) = fit_block(None, area, buf) else {
+            return;
+        };
+        state.load_more();
+        let rows = state.rows();
+        let cell_width = cell_width(state);
+        let heights = rows.iter().map(|r| row_height(state, r)).collect::<Vec<_>>();
+
+        state.offset = scroll_offset(state, &rows, &heights, area.height);
+
+        // when scrolled into a group, its section header sticks to the top of the grid
+        let mut area = area;
+        if let Some(section) = sticky_section(state, &rows) {
+            let [header, rest] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+            self.render_section(section, header, buf, state);
+            area = rest;
+        }
+
+        let visible = (state.offset..rows.len())
+            .scan(0, |used, row| {
+                *used += heights[row];
+                (*used <= area.height).then_some(row)
+            })
+            .collect::<Vec<_>>();
+        let rows_layout = rows_layout(visible.iter().map(|row| heights[*row]), area);
+
+        for (row, row_layout) in visible.iter().map(|row| &rows[*row]).zip(rows_layout.iter()) {
+            if state.is_section_row(row) {
+                self.render_section(row[0], *row_layout, buf, state);
+                continue;
+            }
 
-        for (i, row) in rows_layout.iter().enumerate() {
-            let row_items = state.items.iter().skip(i * state.columns).take(state.columns);
-            let columns_layout = columns_layout(row, row_items.len(), largest_item);
+            let spans = row.iter().map(|idx| state.span_of(*idx));
+            let columns_layout = columns_layout(row_layout, spans, cell_width);
 
-            for (j, item) in row_items.enumerate() {
-                let main_index = i * state.columns + j;
-                let color = self.get_color(main_index, state);
+            for (j, main_index) in row.iter().enumerate() {
+                let item = &state.items[*main_index];
+                let color = self.get_color(*main_index, state);
 
                 let type_block =
                     Block::default().borders(Borders::ALL).border_style(Style::default().fg(color));
@@ -38,26 +99,98 @@ impl StatefulWidget for GridSelector {
     }
 }
 
-fn rows_layout(state: &GridSelectorState, area: Rect) -> Rc<[Rect]> {
-    let row_count = (state.items.len() as f32 / state.columns as f32).ceil() as usize;
+impl GridSelector {
+    fn render_section(&self, idx: usize, area: Rect, buf: &mut Buffer, state: &GridSelectorState) {
+        Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(self.get_section_color()))
+            .title(format!(" {} ", state.items[idx].as_ref()))
+            .title_style(Style::default().fg(self.get_section_color()).add_modifier(Modifier::BOLD))
+            .render(area, buf);
+    }
+}
+
+fn row_height(state: &GridSelectorState, row: &[usize]) -> u16 {
+    if state.is_section_row(row) {
+        1
+    } else {
+        3
+    }
+}
+
+/// Compute the first row to render so that the hovered row is visible, starting from the previous
+/// offset to avoid jumping around while navigating.
+fn scroll_offset(state: &GridSelectorState, rows: &[Vec<usize>], heights: &[u16], h: u16) -> usize {
+    let Some(hovered_row) =
+        state.hovered.and_then(|idx| rows.iter().position(|r| r.contains(&idx)))
+    else {
+        return 0;
+    };
+
+    let mut offset = state.offset.min(rows.len().saturating_sub(1));
+    if hovered_row < offset {
+        offset = hovered_row;
+        // show the section header of the hovered row if it's right above it
+        if offset > 0 && state.is_section_row(&rows[offset - 1]) {
+            offset -= 1;
+        }
+    }
+
+    loop {
+        let sticky = u16::from(sticky_section_at(state, rows, offset).is_some());
+        let used: u16 = heights[offset..=hovered_row].iter().sum::<u16>() + sticky;
+        if used <= h || offset >= hovered_row {
+            break offset;
+        }
+        offset += 1;
+    }
+}
+
+fn sticky_section(state: &GridSelectorState, rows: &[Vec<usize>]) -> Option<usize> {
+    sticky_section_at(state, rows, state.offset)
+}
+
+/// The section header that groups the first visible row, if the header itself is not visible.
+fn sticky_section_at(
+    state: &GridSelectorState,
+    rows: &[Vec<usize>],
+    offset: usize,
+) -> Option<usize> {
+    if rows.get(offset).is_none_or(|row| state.is_section_row(row)) {
+        return None;
+    }
+
+    rows[..offset].iter().rev().find(|row| state.is_section_row(row)).map(|row| row[0])
+}
 
+fn rows_layout(heights: impl Iterator<Item = u16>, area: Rect) -> Rc<[Rect]> {
     Layout::default()
         .direction(Direction::Vertical)
-        .constraints((0..row_count).map(|_| Constraint::Length(3)).collect::<Vec<_>>())
+        .constraints(heights.map(Constraint::Length).collect::<Vec<_>>())
         .spacing(0)
         .split(area)
 }
 
-fn columns_layout(row: &Rect, row_item_count: usize, largest_item: u16) -> Rc<[Rect]> {
+fn columns_layout(row: &Rect, spans: impl Iterator<Item = usize>, cell_width: u16) -> Rc<[Rect]> {
     Layout::default()
         .direction(Direction::Horizontal)
         .flex(Flex::Center)
-        .constraints(
-            (0..row_item_count).map(|_| Constraint::Length(largest_item + 3)).collect::<Vec<_>>(),
-        )
+        .constraints(spans.map(|span| Constraint::Length(cell_width * span as u16)))
         .split(*row)
 }
 
-fn largest_item(state: &GridSelectorState) -> u16 {
-    state.items.iter().map(|item| UnicodeWidthStr::width(item.as_ref())).max().unwrap_or(0) as u16
+/// Width of a single column: wide enough for every item (plus its borders and padding) to fit in
+/// the columns it spans.
+fn cell_width(state: &GridSelectorState) -> u16 {
+    state
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| !item.is_section())
+        .map(|(idx, item)| {
+            let width = UnicodeWidthStr::width(item.as_ref()) + 3;
+            width.div_ceil(state.span_of(idx))
+        })
+        .max()
+        .unwrap_or(0) as u16
 }
@@ -0,0 +1,147 @@
+use {
+    ratatui::{
+        buffer::Buffer,
+        layout::{Alignment, Rect},
+        style::Style,
+        text::Line,
+        widgets::Widget,
+    },
+    unicode_width::UnicodeWidthStr,
+};
+
+/// Wrap `text` in an OSC 8 escape sequence, so terminals supporting it show it as a link to
+/// `url`. Meant for text written straight to the terminal (e.g. after the app exits); use a
+/// [`Link`] to render links inside the app.
+pub fn hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// A single line of text rendered as a clickable OSC 8 hyperlink, in the terminals that support
+/// them (see [`TerminalInfo::hyperlinks`](crate::TerminalInfo::hyperlinks)):
+///
+/// ```ignore
+/// let link = "docs.rs/matetui".link("https://docs.rs/matetui");
+/// f.render_widget(link.with_enabled(self.terminal().hyperlinks), area);
+/// ```
+///
+/// Ratatui measures the escape sequences as regular text, so the link is emitted in chunks of two
+/// columns, each one a link on its own. A single column left over (e.g. at the end of a text with
+/// an odd width) is shown as plain text.
+#[derive(Clone, Debug)]
+pub struct Link<'a> {
+    text: Line<'a>,
+    url: String,
+    enabled: bool,
+}
+
+impl<'a> Link<'a> {
+    pub fn new(text: impl Into<Line<'a>>, url: impl Into<String>) -> Self {
+        Link {
+            text: text.into(),
+            url: url.into(),
+            enabled: true,
+        }
+    }
+
+    /// Set the style of the text.
+    pub fn with_style(mut self, style: impl Into<Style>) -> Self {
+        self.text = self.text.style(style);
+        self
+    }
+
+    /// Set whether the text is emitted as a hyperlink (`true` by default). Disable it on terminals
+    /// that don't support them, which might print the escape sequences.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// The URL the link points to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Widget for Link<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+impl Widget for &Link<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if area.is_empty() {
+            return;
+        }
+
+        let width = (self.text.width() as u16).min(area.width);
+        let offset = match self.text.alignment {
+            Some(Alignment::Center) => (area.width - width) / 2,
+            Some(Alignment::Right) => area.width - width,
+            _ => 0,
+        };
+        (&self.text).render(area, buf);
+
+        if !self.enabled {
+            return;
+        }
+
+        let (y, end) = (area.y, area.x + offset + width);
+        let mut x = area.x + offset;
+        while x < end {
+            let symbol = buf[(x, y)].symbol().to_string();
+            let chunk = match symbol.width() {
+                // a wide char fills the chunk on its own
+                2 => Some(symbol),
+                1 if x + 1 < end && buf[(x + 1, y)].symbol().width() == 1 => {
+                    Some(format!("{symbol}{}", buf[(x + 1, y)].symbol()))
+                }
+                _ => None,
+            };
+
+            match chunk {
+                Some(chunk) => {
+                    // the next cell is covered by this one, and skipped when drawn
+                    buf[(x, y)].set_symbol(&hyperlink(&chunk, &self.url));
+                    x += 2;
+                }
+                None => x += 1,
+            }
+        }
+    }
+}
+
+/// Turn text into a [`Link`]: `"docs".link("https://docs.rs/matetui")`.
+pub trait ToLink<'a> {
+    fn link(self, url: impl Into<String>) -> Link<'a>;
+}
+
+impl<'a, T: Into<Line<'a>>> ToLink<'a> for T {
+    fn link(self, url: impl Into<String>) -> Link<'a> {
+        Link::new(self, url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_the_text_as_two_column_links() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 1));
+        "abcde".link("https://lucode.ar").render(buf.area, &mut buf);
+
+        let link = |text| hyperlink(text, "https://lucode.ar");
+        assert_eq!(buf[(0, 0)].symbol(), link("ab"));
+        assert_eq!(buf[(1, 0)].symbol(), "b");
+        assert_eq!(buf[(2, 0)].symbol(), link("cd"));
+        assert_eq!(buf[(4, 0)].symbol(), "e");
+
+        // only the first cell of each chunk is drawn
+        let previous = Buffer::empty(buf.area);
+        let drawn = previous.diff(&buf).into_iter().map(|(x, ..)| x).collect::<Vec<_>>();
+        assert!([0, 2, 4].iter().all(|x| drawn.contains(x)));
+        assert!(!drawn.contains(&1) && !drawn.contains(&3));
+    }
+}
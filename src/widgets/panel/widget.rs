@@ -0,0 +1,193 @@
+use {
+    crate::{styles, theme::Theme},
+    ratatui::{
+        buffer::Buffer,
+        layout::{Alignment, Rect},
+        style::Style,
+        text::Line,
+        widgets::{
+            block::{Position, Title},
+            Block, BorderType, Borders, Clear, Widget,
+        },
+    },
+};
+
+/// A bordered panel
+///
+/// This widget wraps a [`Block`] styled through the current [`Theme`], with an optional title,
+/// subtitle (at the bottom border) and drop shadow. It gives modals and dialogs a consistent look:
+///
+/// ```ignore
+/// let panel = Panel::dialog().with_title("Confirm").with_subtitle("<enter> ok · <esc> cancel");
+/// let inner = panel.inner(area);
+/// f.render_widget(panel, area);
+/// f.render_widget(Paragraph::new("Quit?"), inner);
+/// ```
+///
+/// The panel clears the area it's rendered into, so it can be drawn on top of other widgets.
+#[derive(Clone, Debug, Default)]
+pub struct Panel<'a> {
+    title: Option<Line<'a>>,
+    subtitle: Option<Line<'a>>,
+    title_alignment: Alignment,
+    /// The border type (the theme's border type by default)
+    border_type: Option<BorderType>,
+    /// The style of the border (the theme's border style by default)
+    border_style: Option<Style>,
+    /// The style of the whole panel
+    style: Style,
+    focused: bool,
+    shadow: bool,
+}
+
+impl<'a> Panel<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Preset for dialogs: centered title and a drop shadow.
+    pub fn dialog() -> Self {
+        Self::new().with_title_alignment(Alignment::Center).with_shadow(true)
+    }
+
+    pub fn with_title(mut self, title: impl Into<Line<'a>>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the subtitle, shown at the bottom border.
+    pub fn with_subtitle(mut self, subtitle: impl Into<Line<'a>>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Set the alignment of both the title and the subtitle (left by default).
+    pub fn with_title_alignment(mut self, alignment: Alignment) -> Self {
+        self.title_alignment = alignment;
+        self
+    }
+
+    pub fn with_border_type(mut self, border_type: BorderType) -> Self {
+        self.border_type = Some(border_type);
+        self
+    }
+
+    pub fn with_border_style(mut self, style: Style) -> Self {
+        self.border_style = Some(style);
+        self
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Use the focused border style of the theme.
+    pub fn with_focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    /// Draw a drop shadow below and to the right of the panel. The shadow takes one row and one
+    /// column of the area the panel is rendered into.
+    pub fn with_shadow(mut self, shadow: bool) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
+    /// The area inside the borders of the panel, when rendered into `area`.
+    pub fn inner(&self, area: Rect) -> Rect {
+        self.block().inner(self.panel_area(area))
+    }
+
+    /// The area of the panel itself, excluding the shadow.
+    fn panel_area(&self, area: Rect) -> Rect {
+        if self.shadow && area.width > 1 && area.height > 1 {
+            Rect {
+                width: area.width - 1,
+                height: area.height - 1,
+                ..area
+            }
+        } else {
+            area
+        }
+    }
+
+    fn block(&self) -> Block<'a> {
+        let border_type = self.border_type.unwrap_or_else(|| Theme::read(|t| t.border_type));
+        let border_style = self.border_style.unwrap_or_else(|| styles::border_for(self.focused));
+
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(border_type)
+            .border_style(border_style)
+            .style(self.style)
+            .title_alignment(self.title_alignment);
+
+        if let Some(title) = self.title.clone() {
+            block = block.title(title);
+        }
+
+        if let Some(subtitle) = self.subtitle.clone() {
+            block = block.title(Title::from(subtitle).position(Position::Bottom));
+        }
+
+        block
+    }
+}
+
+impl Widget for Panel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+impl Widget for &Panel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let panel = self.panel_area(area);
+
+        if panel != area {
+            let shadow = styles::shadow();
+            // right column, starting one row below the top
+            buf.set_style(Rect::new(panel.right(), area.y + 1, 1, area.height - 1), shadow);
+            // bottom row, starting one column after the left
+            buf.set_style(Rect::new(area.x + 1, panel.bottom(), area.width - 1, 1), shadow);
+        }
+
+        Clear.render(panel, buf);
+        self.block().render(panel, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, ratatui::style::Color};
+
+    #[test]
+    fn renders_title_subtitle_and_shadow() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 12, 5));
+        Panel::new()
+            .with_border_type(BorderType::Plain)
+            .with_title("top")
+            .with_subtitle("end")
+            .with_shadow(true)
+            .render(buf.area, &mut buf);
+
+        let expected = Buffer::with_lines([
+            "┌top──────┐ ",
+            "│         │ ",
+            "│         │ ",
+            "└end──────┘ ",
+            "            ",
+        ]);
+        let symbols =
+            |b: &Buffer| b.content().iter().map(|c| c.symbol().to_string()).collect::<Vec<_>>();
+        assert_eq!(symbols(&buf), symbols(&expected));
+
+        assert_eq!(buf[(0, 0)].fg, styles::border().fg.unwrap_or(Color::Reset));
+        assert_eq!(buf[(11, 2)].bg, styles::shadow().bg.unwrap_or(Color::Reset));
+        // the shadow is offset by one cell
+        assert_eq!(buf[(0, 4)].bg, Color::Reset);
+        assert_eq!(Panel::new().with_shadow(true).inner(buf.area), Rect::new(1, 1, 9, 2));
+    }
+}
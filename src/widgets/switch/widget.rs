@@ -1,8 +1,11 @@
-use ratatui::{
-    buffer::Buffer,
-    layout::{Constraint, Layout, Rect},
-    style::{Color, Style},
-    widgets::{Block, Widget},
+use {
+    crate::utils::layout::fit_block,
+    ratatui::{
+        buffer::Buffer,
+        layout::{Constraint, Layout, Rect},
+        style::{Color, Style},
+        widgets::{Block, Widget},
+    },
 };
 
 /// A switch widget
@@ -77,6 +80,15 @@ impl Switch {
 
 impl Widget for Switch {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+impl Widget for &Switch {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(area) = fit_block(None, area, buf) else {
+            return;
+        };
         let (left, right) = self.get_layout(area);
 
         let left_block = Block::default().style(Style::default().bg(self.get_left_color()));
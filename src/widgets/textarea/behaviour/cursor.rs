@@ -1,8 +1,4 @@
-use {
-    super::util::{find_word_start_backward, find_word_start_forward},
-    crate::widgets::textarea::core::widget::Viewport,
-    std::cmp,
-};
+use {super::word::WordBoundaries, crate::widgets::textarea::core::widget::Viewport, std::cmp};
 
 /// Specify how to move the cursor.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -23,7 +19,7 @@ pub enum CursorMove {
     End,
     /// Move cursor forward by one word. Word boundary appears at spaces, punctuations, and others. For example
     /// `fn foo(a)` consists of words `fn`, `foo`, `(`, `a`, `)`. When the cursor is at the end of line, it moves to the
-    /// head of next line.
+    /// head of next line. See [`WordBoundaries`] to change how lines are split into words.
     WordForward,
     /// Move cursor backward by one word.  Word boundary appears at spaces, punctuations, and others. For example
     /// `fn foo(a)` consists of words `fn`, `foo`, `(`, `a`, `)`.When the cursor is at the head of line, it moves to
@@ -37,6 +33,9 @@ pub enum CursorMove {
     ///
     /// This is useful when you moved a cursor but you don't want to move the viewport.
     InViewport,
+    /// Move cursor to the given (row, col) position. When the position is out of the text, the
+    /// cursor is moved to the closest position in it.
+    Jump(u16, u16),
 }
 
 impl CursorMove {
@@ -45,6 +44,7 @@ impl CursorMove {
         (row, col): (usize, usize),
         lines: &[String],
         viewport: &Viewport,
+        words: WordBoundaries,
     ) -> Option<(usize, usize)> {
         use CursorMove::*;
 
@@ -70,7 +70,7 @@ impl CursorMove {
             Head => Some((row, 0)),
             End => Some((row, lines[row].chars().count())),
             WordForward => {
-                if let Some(col) = find_word_start_forward(&lines[row], col) {
+                if let Some(col) = words.next_word_start(&lines[row], col) {
                     Some((row, col))
                 } else if row + 1 < lines.len() {
                     Some((row + 1, 0))
@@ -79,7 +79,7 @@ impl CursorMove {
                 }
             }
             WordBack => {
-                if let Some(col) = find_word_start_backward(&lines[row], col) {
+                if let Some(col) = words.prev_word_start(&lines[row], col) {
                     Some((row, col))
                 } else if row > 0 {
                     Some((row - 1, lines[row - 1].chars().count()))
@@ -97,6 +97,10 @@ impl CursorMove {
 
                 Some((row, col))
             }
+            Jump(row, col) => {
+                let row = cmp::min(*row as usize, lines.len() - 1);
+                Some((row, fit_col(*col as usize, &lines[row])))
+            }
         }
     }
 }
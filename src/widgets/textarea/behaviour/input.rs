@@ -222,6 +222,12 @@ impl Input {
         self.key == Key::End
     }
 
+    /// Returns `true` if the Input is ctrl+`c`, without alt
+    #[inline]
+    pub fn is_ctrl_char(&self, c: char) -> bool {
+        self.key == Key::Char(c) && self.ctrl && !self.alt
+    }
+
     /// Returns `true` if the Input is ctrl+left
     #[inline]
     pub fn is_ctrl_left(&self) -> bool {
@@ -254,6 +260,11 @@ impl Input {
             i if i.is_end() => ":end",
             i if i.is_ctrl_left() => ":word-left",
             i if i.is_ctrl_right() => ":word-right",
+            i if i.key == Key::Copy => ":copy",
+            i if i.key == Key::Cut => ":cut",
+            i if i.key == Key::Paste => ":paste",
+            i if i.is_ctrl_char('z') => ":undo",
+            i if i.is_ctrl_char('y') => ":redo",
             // char with modifiers probably, that wasn't handled above
             // e.g. alt+64... better to handle it as a last resort than not handling it at all
             i if i.is_char_raw() => ":char",
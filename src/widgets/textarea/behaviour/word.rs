@@ -0,0 +1,136 @@
+#[cfg(feature = "unicode-words")]
+use unicode_segmentation::UnicodeSegmentation;
+
+/// How the textarea splits lines into words, for [`CursorMove::WordForward`],
+/// [`CursorMove::WordBack`] and [`TextArea::select_word`].
+///
+/// [`CursorMove::WordForward`]: super::cursor::CursorMove::WordForward
+/// [`CursorMove::WordBack`]: super::cursor::CursorMove::WordBack
+/// [`TextArea::select_word`]: crate::widgets::textarea::TextArea::select_word
+#[derive(Clone, Copy, Debug, Default)]
+pub enum WordBoundaries {
+    /// Words are runs of ASCII punctuation, or of any other chars but spaces: `fn foo(a)` consists
+    /// of `fn`, `foo`, `(`, `a`, `)`. Words in other scripts are kept whole, but a line of CJK text
+    /// (which doesn't use spaces) is a single word.
+    #[default]
+    Ascii,
+    /// Words are runs of the chars accepted by the predicate (e.g. `|c| c.is_alphanumeric() ||
+    /// c == '-'`, to keep `kebab-case` names whole), and runs of other chars but spaces.
+    Custom(fn(char) -> bool),
+    /// Words follow the Unicode word boundaries (UAX #29): accented words, `snake_case` names and
+    /// contractions like `can't` are a single word, and each CJK ideograph is a word.
+    #[cfg(feature = "unicode-words")]
+    Unicode,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum CharKind {
+    Space,
+    Punct,
+    Other,
+}
+
+impl CharKind {
+    fn new(c: char, is_word: Option<fn(char) -> bool>) -> Self {
+        match is_word {
+            _ if c.is_whitespace() => Self::Space,
+            Some(is_word) if is_word(c) => Self::Other,
+            Some(_) => Self::Punct,
+            None if c.is_ascii_punctuation() => Self::Punct,
+            None => Self::Other,
+        }
+    }
+}
+
+impl WordBoundaries {
+    /// The words of the line, as ranges of columns (chars), in order.
+    fn words(self, line: &str) -> Vec<(usize, usize)> {
+        let is_word = match self {
+            Self::Ascii => None,
+            Self::Custom(is_word) => Some(is_word),
+            #[cfg(feature = "unicode-words")]
+            Self::Unicode => return unicode_words(line),
+        };
+
+        let mut words: Vec<(usize, usize)> = vec![];
+        let mut prev = CharKind::Space;
+        for (col, c) in line.chars().enumerate() {
+            let kind = CharKind::new(c, is_word);
+            match words.last_mut() {
+                Some((_, end)) if kind == prev && kind != CharKind::Space => *end = col + 1,
+                _ if kind != CharKind::Space => words.push((col, col + 1)),
+                _ => {}
+            }
+            prev = kind;
+        }
+        words
+    }
+
+    /// The column of the start of the next word after `col`, if any.
+    pub(crate) fn next_word_start(self, line: &str, col: usize) -> Option<usize> {
+        self.words(line).into_iter().map(|(start, _)| start).find(|start| *start > col)
+    }
+
+    /// The column of the start of the word before `col`, if any.
+    pub(crate) fn prev_word_start(self, line: &str, col: usize) -> Option<usize> {
+        self.words(line).into_iter().map(|(start, _)| start).rfind(|start| *start < col)
+    }
+
+    /// The range of columns of the word at `col`, or ending at it.
+    pub(crate) fn word_at(self, line: &str, col: usize) -> Option<(usize, usize)> {
+        let words = self.words(line);
+        let at = words.iter().find(|(start, end)| (*start..*end).contains(&col));
+        at.or_else(|| words.iter().find(|(_, end)| *end == col)).copied()
+    }
+}
+
+#[cfg(feature = "unicode-words")]
+fn unicode_words(line: &str) -> Vec<(usize, usize)> {
+    let mut col = 0;
+    let mut words = vec![];
+    for segment in line.split_word_bounds() {
+        let len = segment.chars().count();
+        if !segment.chars().all(char::is_whitespace) {
+            words.push((col, col + len));
+        }
+        col += len;
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn starts(boundaries: WordBoundaries, line: &str) -> Vec<usize> {
+        let mut starts = vec![];
+        let mut col = 0;
+        while let Some(start) = boundaries.next_word_start(line, col) {
+            starts.push(start);
+            col = start;
+        }
+        starts
+    }
+
+    #[test]
+    fn splits_words() {
+        let line = "fn foo_bar(a) mate-cocido";
+        assert_eq!(starts(WordBoundaries::Ascii, line), [3, 6, 7, 10, 11, 12, 14, 18, 19]);
+
+        let kebab = WordBoundaries::Custom(|c| c.is_alphanumeric() || c == '-');
+        assert_eq!(starts(kebab, line), [3, 6, 7, 10, 11, 12, 14]);
+        assert_eq!(kebab.prev_word_start(line, 14), Some(12));
+        assert_eq!(kebab.word_at(line, 16), Some((14, 25)));
+        assert_eq!(kebab.word_at(line, 25), Some((14, 25)));
+        assert_eq!(kebab.word_at("a  b", 2), None);
+    }
+
+    #[cfg(feature = "unicode-words")]
+    #[test]
+    fn splits_unicode_words() {
+        let line = "can't café_con_leche 日本語";
+        assert_eq!(starts(WordBoundaries::Unicode, line), [6, 21, 22, 23]);
+        assert_eq!(WordBoundaries::Unicode.prev_word_start(line, 21), Some(6));
+        assert_eq!(WordBoundaries::Unicode.word_at(line, 2), Some((0, 5)));
+    }
+}
@@ -1,5 +1,5 @@
 use {
-    super::{validation::ValidatorFn, TextArea},
+    super::{validation::ValidatorFn, TextArea, WordBoundaries},
     ratatui::{layout::Alignment, style::Style, widgets::Block},
 };
 
@@ -16,6 +16,13 @@ impl<'a> TextArea<'a> {
         self
     }
 
+    /// Set how lines are split into words by the word motions and [`TextArea::select_word`]. The
+    /// default is [`WordBoundaries::Ascii`].
+    pub fn with_word_boundaries(mut self, boundaries: WordBoundaries) -> Self {
+        self.word_boundaries = boundaries;
+        self
+    }
+
     /// Set the placeholder text. The text is set in the textarea when no text is input. Setting a
     /// non-empty string `""` enables the placeholder. The default value is an empty string so the
     /// placeholder is disabled by default. To customize the text style, see
@@ -53,6 +60,22 @@ impl<'a> TextArea<'a> {
         self
     }
 
+    /// Restrict the textarea to a single line, like a text input. Newlines are ignored when typed
+    /// and replaced by spaces when pasted.
+    pub fn with_single_line(mut self, single_line: bool) -> Self {
+        self.single_line = single_line;
+        self
+    }
+
+    /// Copy and cut the text to the system clipboard too, and paste the text of the system
+    /// clipboard (see [`Clipboard`](crate::clipboard::Clipboard)). Disabled by default, so the text
+    /// only goes to the yank buffer of the textarea.
+    #[cfg(feature = "clipboard")]
+    pub fn with_system_clipboard(mut self, system_clipboard: bool) -> Self {
+        self.system_clipboard = system_clipboard;
+        self
+    }
+
     pub fn with_validations(
         mut self,
         validations: impl IntoIterator<
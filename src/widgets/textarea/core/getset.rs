@@ -1,5 +1,5 @@
 use {
-    super::TextArea,
+    super::{TextArea, WordBoundaries},
     crate::widgets::textarea::behaviour::{cursor::CursorMove, scroll::Scrolling},
     ratatui::{layout::Alignment, style::Style, widgets::Block},
 };
@@ -78,6 +78,16 @@ impl<'a> TextArea<'a> {
         self.alignment
     }
 
+    /// Get how lines are split into words (see [`TextArea::with_word_boundaries`]).
+    pub fn word_boundaries(&self) -> WordBoundaries {
+        self.word_boundaries
+    }
+
+    /// Check if the textarea is restricted to a single line.
+    pub fn is_single_line(&self) -> bool {
+        self.single_line
+    }
+
     /// Check if the textarea has a empty content.
     pub fn is_empty(&self) -> bool {
         self.lines == [""]
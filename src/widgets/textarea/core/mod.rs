@@ -9,6 +9,7 @@ use {
         highlight::LineHighlighter,
         input::Input,
         util::{spaces, Pos},
+        word::WordBoundaries,
     },
     ratatui::{
         layout::Alignment,
@@ -52,6 +53,16 @@ impl From<Vec<String>> for YankText {
     }
 }
 
+/// The text and cursor before an edit, restored by [`TextArea::undo`].
+#[derive(Debug, Clone)]
+struct Snapshot {
+    lines: Vec<String>,
+    cursor: (usize, usize),
+}
+
+/// The number of edits [`TextArea::undo`] can revert.
+const MAX_HISTORY: usize = 100;
+
 impl fmt::Display for YankText {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -80,6 +91,13 @@ pub struct TextArea<'a> {
     selection_start: Option<(usize, usize)>,
     select_style: Style,
     validators: Vec<ValidatorFn>,
+    single_line: bool,
+    word_boundaries: WordBoundaries,
+    #[cfg(feature = "clipboard")]
+    system_clipboard: bool,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    editing: bool,
 }
 
 impl<'a, I> From<I> for TextArea<'a>
@@ -131,6 +149,13 @@ impl<'a> TextArea<'a> {
             selection_start: None,
             select_style: Style::default().bg(Color::LightBlue),
             validators: Vec::new(),
+            single_line: false,
+            word_boundaries: WordBoundaries::default(),
+            #[cfg(feature = "clipboard")]
+            system_clipboard: false,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            editing: false,
         }
     }
 
@@ -161,27 +186,22 @@ impl<'a> TextArea<'a> {
             ":end" => self.move_cursor_with_shift(CursorMove::End, input.shift),
             ":word-right" => self.move_cursor_with_shift(CursorMove::WordForward, input.shift),
             ":word-left" => self.move_cursor_with_shift(CursorMove::WordBack, input.shift),
+            ":copy" => {
+                self.copy();
+                false
+            }
+            ":cut" => self.cut(),
+            ":paste" => self.paste(),
+            ":undo" => self.undo(),
+            ":redo" => self.redo(),
             _ => false,
         };
 
-        // Check invariants
-        debug_assert!(!self.lines.is_empty(), "no line after {:?}", input);
-        let (r, c) = self.cursor;
-        debug_assert!(
-            self.lines.len() > r,
-            "cursor {:?} exceeds max lines {} after {:?}",
-            self.cursor,
-            self.lines.len(),
-            input,
-        );
-        debug_assert!(
-            self.lines[r].chars().count() >= c,
-            "cursor {:?} exceeds max col {} at line {:?} after {:?}",
-            self.cursor,
-            self.lines[r].chars().count(),
-            self.lines[r],
-            input,
-        );
+        if cfg!(debug_assertions) {
+            if let Err(violation) = self.check_invariants() {
+                panic!("{violation} after {input:?}");
+            }
+        }
 
         modified
     }
@@ -193,32 +213,59 @@ impl<'a> TextArea<'a> {
             return;
         }
 
-        self.delete_selection(false);
-        let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        let i = line.char_indices().nth(col).map(|(i, _)| i).unwrap_or(line.len());
-        line.insert(i, c);
-        self.cursor.1 += 1;
+        self.edit(|t| {
+            t.delete_selection(false);
+            let (row, col) = t.cursor;
+            let line = &mut t.lines[row];
+            let i = line.char_indices().nth(col).map(|(i, _)| i).unwrap_or(line.len());
+            line.insert(i, c);
+            t.cursor.1 += 1;
+            true
+        });
     }
 
     /// Insert a string at current cursor position. This method returns if some text was inserted or
     /// not in the textarea. Both `\n` and `\r\n` are recognized as newlines but `\r` isn't.
     pub fn insert_str<S: AsRef<str>>(&mut self, s: S) -> bool {
-        let modified = self.delete_selection(false);
-        let mut lines: Vec<_> =
-            s.as_ref().split('\n').map(|s| s.strip_suffix('\r').unwrap_or(s).to_string()).collect();
-        match lines.len() {
-            0 => modified,
-            1 => self.insert_piece(lines.remove(0)),
-            _ => self.insert_chunk(lines),
-        }
+        self.edit(|t| {
+            if t.single_line {
+                let s = s.as_ref().replace("\r\n", " ").replace(['\n', '\r'], " ");
+                let modified = t.delete_selection(false);
+                return t.insert_piece(s) || modified;
+            }
+
+            let modified = t.delete_selection(false);
+            let mut lines: Vec<_> = s
+                .as_ref()
+                .split('\n')
+                .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
+                .collect();
+            match lines.len() {
+                0 => modified,
+                1 => t.insert_piece(lines.remove(0)),
+                _ => t.insert_chunk(lines),
+            }
+        })
     }
 
     fn insert_chunk(&mut self, chunk: Vec<String>) -> bool {
         debug_assert!(chunk.len() > 1, "Chunk size must be > 1: {:?}", chunk);
 
-        let (row, _col) = self.cursor;
-        let (row, col) = (row + chunk.len() - 1, chunk[chunk.len() - 1].chars().count());
+        let (row, col) = self.cursor;
+        let line = &mut self.lines[row];
+        let i = line.char_indices().nth(col).map(|(i, _)| i).unwrap_or(line.len());
+        let end = line.split_off(i);
+
+        let mut chunk = chunk.into_iter();
+        // the chunk has at least two pieces: the end of the line and a new line
+        line.push_str(&chunk.next().unwrap());
+        let mut inserted: Vec<String> = chunk.collect();
+        let last = inserted.last_mut().unwrap();
+        let col = last.chars().count();
+        last.push_str(&end);
+
+        let row = row + inserted.len();
+        self.lines.splice(self.cursor.0 + 1..self.cursor.0 + 1, inserted);
         self.cursor = (row, col);
         true
     }
@@ -273,153 +320,169 @@ impl<'a> TextArea<'a> {
     /// characters, not a byte length of the string. Newlines at the end of lines are counted in the
     /// number. This method returns if some text was deleted or not.
     pub fn delete_str(&mut self, chars: usize) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
-        if chars == 0 {
-            return false;
-        }
-
-        let (start_row, start_col) = self.cursor;
+        self.edit(|t| {
+            if t.delete_selection(false) {
+                return true;
+            }
+            if chars == 0 {
+                return false;
+            }
 
-        let mut remaining = chars;
-        let mut find_end = move |line: &str| {
-            let mut col = 0usize;
-            for (i, _) in line.char_indices() {
+            let (start_row, start_col) = t.cursor;
+
+            let mut remaining = chars;
+            let mut find_end = move |line: &str| {
+                let mut col = 0usize;
+                for (i, _) in line.char_indices() {
+                    if remaining == 0 {
+                        return Some((i, col));
+                    }
+                    col += 1;
+                    remaining -= 1;
+                }
                 if remaining == 0 {
-                    return Some((i, col));
+                    Some((line.len(), col))
+                } else {
+                    remaining -= 1;
+                    None
                 }
-                col += 1;
-                remaining -= 1;
-            }
-            if remaining == 0 {
-                Some((line.len(), col))
-            } else {
-                remaining -= 1;
-                None
+            };
+
+            let line = &t.lines[start_row];
+            let start_offset =
+                { line.char_indices().nth(start_col).map(|(i, _)| i).unwrap_or(line.len()) };
+
+            // First line
+            if let Some((offset_delta, _col_delta)) = find_end(&line[start_offset..]) {
+                let end_offset = start_offset + offset_delta;
+                let removed =
+                    t.lines[start_row].drain(start_offset..end_offset).as_str().to_string();
+                t.yank = removed.clone().into();
+                return true;
             }
-        };
 
-        let line = &self.lines[start_row];
-        let start_offset =
-            { line.char_indices().nth(start_col).map(|(i, _)| i).unwrap_or(line.len()) };
+            let mut r = start_row + 1;
+            let mut offset = 0;
+            let mut col = 0;
 
-        // First line
-        if let Some((offset_delta, _col_delta)) = find_end(&line[start_offset..]) {
-            let end_offset = start_offset + offset_delta;
-            let removed =
-                self.lines[start_row].drain(start_offset..end_offset).as_str().to_string();
-            self.yank = removed.clone().into();
-            return true;
-        }
-
-        let mut r = start_row + 1;
-        let mut offset = 0;
-        let mut col = 0;
-
-        while r < self.lines.len() {
-            let line = &self.lines[r];
-            if let Some((o, c)) = find_end(line) {
-                offset = o;
-                col = c;
-                break;
+            while r < t.lines.len() {
+                let line = &t.lines[r];
+                if let Some((o, c)) = find_end(line) {
+                    offset = o;
+                    col = c;
+                    break;
+                }
+                r += 1;
             }
-            r += 1;
-        }
 
-        let start = Pos::new(start_row, start_col, start_offset);
-        let end = Pos::new(r, col, offset);
-        self.delete_range(start, end, true);
-        true
+            let start = Pos::new(start_row, start_col, start_offset);
+            let end = Pos::new(r, col, offset);
+            t.delete_range(start, end, true);
+            true
+        })
     }
 
     /// Insert a tab at current cursor position. Note that this method does nothing when the tab
     /// length is 0. This method returns if a tab string was inserted or not in the textarea.
     pub fn insert_tab(&mut self) -> bool {
-        let modified = self.delete_selection(false);
-        if self.tab_len == 0 {
-            return modified;
-        }
+        self.edit(|t| {
+            let modified = t.delete_selection(false);
+            if t.tab_len == 0 {
+                return modified;
+            }
 
-        let (row, col) = self.cursor;
-        let width: usize = self.lines[row].chars().take(col).map(|c| c.width().unwrap_or(0)).sum();
-        let len = self.tab_len - (width % self.tab_len as usize) as u8;
-        self.insert_piece(spaces(len).to_string())
+            let (row, col) = t.cursor;
+            let width: usize = t.lines[row].chars().take(col).map(|c| c.width().unwrap_or(0)).sum();
+            let len = t.tab_len - (width % t.tab_len as usize) as u8;
+            t.insert_piece(spaces(len).to_string())
+        })
     }
 
-    /// Insert a newline at current cursor position.
+    /// Insert a newline at current cursor position. Does nothing in single-line mode.
     pub fn insert_newline(&mut self) -> bool {
-        self.delete_selection(false);
+        self.edit(|t| {
+            if t.single_line {
+                return false;
+            }
 
-        let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        let offset = line.char_indices().nth(col).map(|(i, _)| i).unwrap_or(line.len());
-        let next_line = line[offset..].to_string();
-        line.truncate(offset);
+            t.delete_selection(false);
 
-        self.lines.insert(row + 1, next_line);
-        self.cursor = (row + 1, 0);
-        true
+            let (row, col) = t.cursor;
+            let line = &mut t.lines[row];
+            let offset = line.char_indices().nth(col).map(|(i, _)| i).unwrap_or(line.len());
+            let next_line = line[offset..].to_string();
+            line.truncate(offset);
+
+            t.lines.insert(row + 1, next_line);
+            t.cursor = (row + 1, 0);
+            true
+        })
     }
 
     /// Delete a newline from **head** of current cursor line. This method returns if a newline was
     /// deleted or not in the textarea. When some text is selected, it is deleted instead.
     pub fn delete_newline(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
+        self.edit(|t| {
+            if t.delete_selection(false) {
+                return true;
+            }
 
-        let (row, _) = self.cursor;
-        if row == 0 {
-            return false;
-        }
+            let (row, _) = t.cursor;
+            if row == 0 {
+                return false;
+            }
 
-        let line = self.lines.remove(row);
-        let prev_line = &mut self.lines[row - 1];
+            let line = t.lines.remove(row);
+            let prev_line = &mut t.lines[row - 1];
 
-        self.cursor = (row - 1, prev_line.chars().count());
-        prev_line.push_str(&line);
-        true
+            t.cursor = (row - 1, prev_line.chars().count());
+            prev_line.push_str(&line);
+            true
+        })
     }
 
     /// Delete one character before cursor. When the cursor is at head of line, the newline before
     /// the cursor will be removed. This method returns if some text was deleted or not in the
     /// textarea. When some text is selected, it is deleted instead.
     pub fn delete_char(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
+        self.edit(|t| {
+            if t.delete_selection(false) {
+                return true;
+            }
 
-        let (row, col) = self.cursor;
-        if col == 0 {
-            return self.delete_newline();
-        }
+            let (row, col) = t.cursor;
+            if col == 0 {
+                return t.delete_newline();
+            }
 
-        let line = &mut self.lines[row];
-        if let Some((offset, _c)) = line.char_indices().nth(col - 1) {
-            line.remove(offset);
-            self.cursor.1 -= 1;
-            true
-        } else {
-            false
-        }
+            let line = &mut t.lines[row];
+            if let Some((offset, _c)) = line.char_indices().nth(col - 1) {
+                line.remove(offset);
+                t.cursor.1 -= 1;
+                true
+            } else {
+                false
+            }
+        })
     }
 
     /// Delete one character next to cursor. When the cursor is at end of line, the newline next to
     /// the cursor will be removed. This method returns if a character was deleted or not in the
     /// textarea.
     pub fn delete_next_char(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
+        self.edit(|t| {
+            if t.delete_selection(false) {
+                return true;
+            }
 
-        let before = self.cursor;
-        self.move_cursor_with_shift(CursorMove::Forward, false);
-        if before == self.cursor {
-            return false; // Cursor didn't move, meant no character at next of cursor.
-        }
+            let before = t.cursor;
+            t.move_cursor_with_shift(CursorMove::Forward, false);
+            if before == t.cursor {
+                return false; // Cursor didn't move, meant no character at next of cursor.
+            }
 
-        self.delete_char()
+            t.delete_char()
+        })
     }
 
     /// Start text selection at the cursor position. If text selection is already ongoing, the start
@@ -433,6 +496,21 @@ impl<'a> TextArea<'a> {
         self.selection_start = None;
     }
 
+    /// Select the word under the cursor (or ending at it), e.g. on a double click, moving the
+    /// cursor to its end. See [`WordBoundaries`] for how lines are split into words. This method
+    /// returns if a word was selected or not.
+    pub fn select_word(&mut self) -> bool {
+        let (row, col) = self.cursor;
+        match self.word_boundaries.word_at(&self.lines[row], col) {
+            Some((start, end)) => {
+                self.selection_start = Some((row, start));
+                self.cursor = (row, end);
+                true
+            }
+            None => false,
+        }
+    }
+
     fn line_offset(&self, row: usize, col: usize) -> usize {
         let line = self.lines.get(row).unwrap_or(&self.lines[self.lines.len() - 1]);
         line.char_indices().nth(col).map(|(i, _)| i).unwrap_or(line.len())
@@ -475,8 +553,145 @@ impl<'a> TextArea<'a> {
         false
     }
 
+    /// Copy the selected text into the yank buffer, ending the selection. Does nothing when no
+    /// text is selected.
+    pub fn copy(&mut self) {
+        if let Some((s, e)) = self.take_selection_positions() {
+            self.yank = if s.row == e.row {
+                self.lines[s.row][s.offset..e.offset].to_string().into()
+            } else {
+                let mut copied = vec![self.lines[s.row][s.offset..].to_string()];
+                copied.extend(self.lines[s.row + 1..e.row].iter().cloned());
+                copied.push(self.lines[e.row][..e.offset].to_string());
+                YankText::Chunk(copied)
+            };
+            self.copy_to_system_clipboard();
+        }
+    }
+
+    /// Cut the selected text into the yank buffer. This method returns if some text was deleted or
+    /// not in the textarea.
+    pub fn cut(&mut self) -> bool {
+        let cut = self.edit(|t| t.delete_selection(true));
+        if cut {
+            self.copy_to_system_clipboard();
+        }
+        cut
+    }
+
+    /// Paste the yanked text at the cursor position, replacing the selected text. Multiple lines
+    /// are joined with spaces in single-line mode. This method returns if the text was modified or
+    /// not in the textarea.
+    ///
+    /// With the system clipboard (see [`TextArea::with_system_clipboard`]), the text of the
+    /// clipboard is pasted instead.
+    pub fn paste(&mut self) -> bool {
+        #[cfg(feature = "clipboard")]
+        if self.system_clipboard {
+            if let Some(text) = crate::clipboard::Clipboard::text() {
+                return self.insert_str(text);
+            }
+        }
+        let text = self.yank.to_string();
+        self.insert_str(text)
+    }
+
+    fn copy_to_system_clipboard(&self) {
+        #[cfg(feature = "clipboard")]
+        if self.system_clipboard {
+            // best effort, the text is in the yank buffer anyway
+            let _ = crate::clipboard::Clipboard::copy(&self.yank.to_string());
+        }
+    }
+
+    /// Undo the last edit of the text, restoring the cursor where it was before it. This method
+    /// returns if there was an edit to undo or not. Every call to an editing method is an edit, up
+    /// to the last 100 ones.
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some(snapshot) => {
+                let current = self.restore(snapshot);
+                self.redo.push(current);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redo the last undone edit. This method returns if there was an edit to redo or not. Editing
+    /// the text after undoing discards the edits left to redo.
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(snapshot) => {
+                let current = self.restore(snapshot);
+                self.undo.push(current);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        self.selection_start = None;
+        Snapshot {
+            lines: std::mem::replace(&mut self.lines, snapshot.lines),
+            cursor: std::mem::replace(&mut self.cursor, snapshot.cursor),
+        }
+    }
+
+    /// Run an editing operation, recording the text before it in the undo history if it changed
+    /// the text. Operations nested in another one (e.g. [`TextArea::paste`] inserting the yanked
+    /// text) are recorded once, by the outer one.
+    fn edit(&mut self, f: impl FnOnce(&mut Self) -> bool) -> bool {
+        if self.editing {
+            return f(self);
+        }
+
+        let before = Snapshot {
+            lines: self.lines.clone(),
+            cursor: self.cursor,
+        };
+        self.editing = true;
+        let modified = f(self);
+        self.editing = false;
+
+        if self.lines != before.lines {
+            if self.undo.len() == MAX_HISTORY {
+                self.undo.remove(0);
+            }
+            self.undo.push(before);
+            self.redo.clear();
+        }
+        modified
+    }
+
+    /// Move the cursor. See [`CursorMove`] for the possible movements. Moving the cursor cancels
+    /// the current text selection.
+    pub fn move_cursor(&mut self, m: CursorMove) {
+        self.move_cursor_with_shift(m, false);
+    }
+
+    /// Replace the whole text of the textarea, moving the cursor to its end. In single-line mode,
+    /// newlines are replaced by spaces.
+    pub fn set_text(&mut self, text: impl AsRef<str>) {
+        self.edit(|t| {
+            let text = text.as_ref();
+            t.lines = if t.single_line {
+                vec![text.replace("\r\n", " ").replace(['\n', '\r'], " ")]
+            } else {
+                text.split('\n').map(|s| s.strip_suffix('\r').unwrap_or(s).to_string()).collect()
+            };
+            t.selection_start = None;
+            let row = t.lines.len() - 1;
+            t.cursor = (row, t.lines[row].chars().count());
+            true
+        });
+    }
+
     fn move_cursor_with_shift(&mut self, m: CursorMove, shift: bool) -> bool {
-        if let Some(cursor) = m.next_cursor(self.cursor, &self.lines, &self.viewport) {
+        if let Some(cursor) =
+            m.next_cursor(self.cursor, &self.lines, &self.viewport, self.word_boundaries)
+        {
             if shift {
                 if self.selection_start.is_none() {
                     self.start_selection();
@@ -489,6 +704,45 @@ impl<'a> TextArea<'a> {
         false
     }
 
+    /// Check the invariants of the textarea, panicking with the violated one: the text has at least
+    /// one line (exactly one in single-line mode) and no line contains a newline, and the cursor and
+    /// the start of the selection are within the text. Only checked in debug builds.
+    ///
+    /// Editing methods keep these invariants, so this is meant for tests and fuzzers driving the
+    /// textarea with their own input mappings (see [fuzz](crate::widgets::textarea::fuzz)).
+    pub fn debug_assert_invariants(&self) {
+        if cfg!(debug_assertions) {
+            if let Err(violation) = self.check_invariants() {
+                panic!("{violation}");
+            }
+        }
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        if self.lines.is_empty() {
+            return Err("no line".into());
+        }
+        if self.single_line && self.lines.len() > 1 {
+            return Err(format!("{} lines in single-line mode", self.lines.len()));
+        }
+        if let Some(row) = self.lines.iter().position(|line| line.contains('\n')) {
+            return Err(format!("newline in line {row}: {:?}", self.lines[row]));
+        }
+
+        let in_bounds = |(row, col): (usize, usize)| {
+            self.lines.get(row).is_some_and(|line| line.chars().count() >= col)
+        };
+        if !in_bounds(self.cursor) {
+            return Err(format!("cursor {:?} out of the text {:?}", self.cursor, self.lines));
+        }
+        match self.selection_start {
+            Some(start) if !in_bounds(start) => {
+                Err(format!("selection start {start:?} out of the text {:?}", self.lines))
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub(crate) fn line_spans<'b>(&'b self, line: &'b str, row: usize) -> Line<'b> {
         let mut hl = LineHighlighter::new(
             line,
@@ -509,3 +763,93 @@ impl<'a> TextArea<'a> {
         hl.into_spans()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::widgets::textarea::Key};
+
+    #[test]
+    fn pastes_multiple_lines_over_the_selection() {
+        let mut textarea = TextArea::from(["mate amargo", "con yerba"]);
+        textarea.set_yank_text("dulce\ncocido");
+        textarea.move_cursor(CursorMove::Jump(0, 5));
+        textarea.start_selection();
+        textarea.move_cursor_with_shift(CursorMove::End, true);
+
+        assert!(textarea.paste());
+        assert_eq!(textarea.lines(), ["mate dulce", "cocido", "con yerba"]);
+        assert_eq!(textarea.cursor(), (1, 6));
+        textarea.debug_assert_invariants();
+
+        let mut textarea = TextArea::default().with_single_line(true);
+        textarea.set_yank_text("mate\ncocido");
+        textarea.paste();
+        assert_eq!(textarea.lines(), ["mate cocido"]);
+    }
+
+    #[test]
+    fn cuts_copies_and_pastes() {
+        let mut textarea = TextArea::from(["uno", "dos", "tres"]);
+        textarea.move_cursor(CursorMove::Jump(0, 1));
+        textarea.start_selection();
+        textarea.move_cursor_with_shift(CursorMove::Jump(2, 2), true);
+
+        textarea.copy();
+        assert_eq!(textarea.yank_text(), "no\ndos\ntr");
+        assert_eq!(textarea.selection_range(), None);
+        assert_eq!(textarea.lines(), ["uno", "dos", "tres"]);
+
+        textarea.start_selection();
+        textarea.move_cursor_with_shift(CursorMove::Jump(0, 1), true);
+        assert!(textarea.cut());
+        assert_eq!(textarea.lines(), ["ues"]);
+        assert!(textarea.paste());
+        assert_eq!(textarea.lines(), ["uno", "dos", "tres"]);
+        assert_eq!(textarea.cursor(), (2, 2));
+    }
+
+    #[test]
+    fn selects_words_with_the_word_boundaries() {
+        let mut textarea = TextArea::from(["git commit --no-verify"])
+            .with_word_boundaries(WordBoundaries::Custom(|c| c.is_alphanumeric() || c == '-'));
+        textarea.move_cursor(CursorMove::Jump(0, 16));
+        assert!(textarea.select_word());
+        assert_eq!(textarea.selection_range(), Some(((0, 11), (0, 22))));
+
+        textarea.move_cursor(CursorMove::WordBack);
+        textarea.move_cursor(CursorMove::WordBack);
+        assert_eq!(textarea.cursor(), (0, 4));
+    }
+
+    #[test]
+    fn undoes_and_redoes_edits() {
+        let mut textarea = TextArea::from(["mate"]);
+        textarea.move_cursor(CursorMove::End);
+        textarea.insert_str(" cocido\ncon leche");
+        textarea.insert_char('!');
+        textarea.delete_next_char(); // nothing to delete, not an edit
+
+        assert!(textarea.undo());
+        assert_eq!(textarea.lines(), ["mate cocido", "con leche"]);
+        assert!(textarea.undo());
+        assert_eq!(textarea.lines(), ["mate"]);
+        assert_eq!(textarea.cursor(), (0, 4));
+        assert!(!textarea.undo());
+
+        assert!(textarea.redo());
+        assert_eq!(textarea.lines(), ["mate cocido", "con leche"]);
+
+        // editing discards the edits left to redo
+        textarea.input(Input {
+            key: Key::Backspace,
+            ..Input::default()
+        });
+        assert!(!textarea.redo());
+        textarea.input(Input {
+            key: Key::Char('z'),
+            ctrl: true,
+            ..Input::default()
+        });
+        assert_eq!(textarea.lines(), ["mate cocido", "con leche"]);
+    }
+}
@@ -1,5 +1,6 @@
 use {
     super::TextArea,
+    crate::utils::layout::fit_block,
     ratatui::{
         buffer::Buffer,
         layout::Rect,
@@ -114,13 +115,27 @@ impl<'a> TextArea<'a> {
     }
 }
 
+impl<'a> TextArea<'a> {
+    /// The textarea as a plain ratatui [`Widget`], to render it from code that takes widgets by
+    /// value. Rendering `&textarea` directly works too.
+    pub fn widget(&self) -> impl Widget + '_ {
+        self
+    }
+}
+
+impl Widget for TextArea<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
 impl Widget for &TextArea<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let Rect { width, height, .. } = if let Some(b) = self.block() {
-            b.inner(area)
-        } else {
-            area
+        // the block is rendered first, so the text can only be rendered into the room left inside
+        let Some(text_area) = fit_block(self.block(), area, buf) else {
+            return;
         };
+        let Rect { width, height, .. } = text_area;
 
         let (top_row, top_col) = self.viewport.scroll_top();
         let top_row = self.scroll_top_row(top_row, height);
@@ -134,15 +149,7 @@ impl Widget for &TextArea<'_> {
 
         // To get fine control over the text color and the surrrounding block they have to be
         // rendered separately / see https://github.com/ratatui-org/ratatui/issues/144
-        let mut text_area = area;
         let mut inner = Paragraph::new(text).style(style).alignment(self.alignment());
-        if let Some(b) = self.block() {
-            text_area = b.inner(area);
-            // ratatui does not need `clone()` call because `Block` implements `WidgetRef` and `&T`
-            // implements `Widget` where `T: WidgetRef`. So `b.render` internally calls
-            // `b.render_ref` and it doesn't move out `self`.
-            b.render(area, buf)
-        }
         if top_col != 0 {
             inner = inner.scroll((0, top_col));
         }
@@ -0,0 +1,248 @@
+//! # Fuzzing
+//!
+//! Random edit sequences for the [`TextArea`], to catch editing bugs (e.g. a cursor left out of
+//! the text) that only show up after an unusual sequence of edits. Each [`Edit`] is applied
+//! through the public API, and the invariants of the textarea are checked after it (see
+//! [`TextArea::debug_assert_invariants`]).
+//!
+//! The sequences come either from a seed, for property tests:
+//!
+//! ```ignore
+//! for seed in 0..1000 {
+//!     let mut textarea = TextArea::default();
+//!     fuzz::run(&mut textarea, &fuzz::random_edits(seed, 200));
+//! }
+//! ```
+//!
+//! or from raw bytes, for coverage-guided fuzzers like `cargo fuzz`, whose target only has to
+//! forward its input:
+//!
+//! ```ignore
+//! fuzz_target!(|data: &[u8]| {
+//!     fuzz::run(&mut TextArea::default(), &fuzz::edits_from_bytes(data));
+//! });
+//! ```
+//!
+//! Apps mapping their own keys to the textarea can check their mappings the same way, calling
+//! [`TextArea::debug_assert_invariants`] after each input.
+
+use {
+    super::{CursorMove, Input, Key, TextArea},
+    ratatui::{buffer::Buffer, layout::Rect, widgets::Widget},
+};
+
+/// An edit of a [`TextArea`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Edit {
+    /// A key input, mapped by [`TextArea::input`].
+    Input(Input),
+    /// [`TextArea::insert_str`], with newlines.
+    InsertStr(String),
+    /// [`TextArea::delete_str`].
+    DeleteStr(usize),
+    /// [`TextArea::move_cursor`].
+    Move(CursorMove),
+    /// [`TextArea::start_selection`].
+    StartSelection,
+    /// [`TextArea::cancel_selection`].
+    CancelSelection,
+    /// [`TextArea::scroll`], by rows and columns.
+    Scroll(i16, i16),
+    /// [`TextArea::set_text`].
+    SetText(String),
+    /// [`TextArea::undo`].
+    Undo,
+    /// [`TextArea::redo`].
+    Redo,
+    /// Render the textarea into a buffer of the given width and height.
+    Render(u16, u16),
+}
+
+/// Apply the edits to the textarea, checking its invariants after each one.
+///
+/// # Panics
+///
+/// Panics when an edit panics, or breaks an invariant of the textarea (in debug builds).
+pub fn run(textarea: &mut TextArea<'_>, edits: &[Edit]) {
+    for edit in edits {
+        apply(textarea, edit);
+        textarea.debug_assert_invariants();
+    }
+}
+
+/// Apply an edit to the textarea.
+pub fn apply(textarea: &mut TextArea<'_>, edit: &Edit) {
+    match edit {
+        Edit::Input(input) => {
+            textarea.input(input.clone());
+        }
+        Edit::InsertStr(s) => {
+            textarea.insert_str(s);
+        }
+        Edit::DeleteStr(chars) => {
+            textarea.delete_str(*chars);
+        }
+        Edit::Move(m) => textarea.move_cursor(*m),
+        Edit::StartSelection => textarea.start_selection(),
+        Edit::CancelSelection => textarea.cancel_selection(),
+        Edit::Scroll(rows, cols) => textarea.scroll((*rows, *cols)),
+        Edit::SetText(text) => textarea.set_text(text),
+        Edit::Undo => {
+            textarea.undo();
+        }
+        Edit::Redo => {
+            textarea.redo();
+        }
+        Edit::Render(width, height) => {
+            let area = Rect::new(0, 0, *width, *height);
+            textarea.widget().render(area, &mut Buffer::empty(area));
+        }
+    }
+}
+
+/// A sequence of `len` random edits, always the same for a given seed.
+pub fn random_edits(seed: u64, len: usize) -> Vec<Edit> {
+    // xorshift, whose state must not be zero
+    let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+    let mut bytes = Vec::with_capacity(len * 16);
+    while bytes.len() < len * 16 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        bytes.extend(state.to_le_bytes());
+    }
+
+    let mut edits = edits_from_bytes(&bytes);
+    edits.truncate(len);
+    edits
+}
+
+/// Decode arbitrary bytes into edits: the first byte of each edit picks its kind, and the next
+/// ones its arguments, so any input is a valid sequence.
+pub fn edits_from_bytes(data: &[u8]) -> Vec<Edit> {
+    let mut bytes = data.iter().copied();
+    let mut edits = vec![];
+    while let Some(kind) = bytes.next() {
+        let mut arg = || bytes.next().unwrap_or_default();
+        let edit = match kind % 16 {
+            0..=4 => Edit::Input(Input {
+                key: Key::Char(char_from(arg())),
+                ..Input::default()
+            }),
+            5 => Edit::Input(Input {
+                key: KEYS[arg() as usize % KEYS.len()],
+                ctrl: false,
+                alt: false,
+                shift: arg() % 2 == 0,
+            }),
+            6 => Edit::InsertStr((0..arg() % 8).map(|_| char_from(arg())).collect()),
+            7 => Edit::DeleteStr(arg() as usize % 12),
+            8 => Edit::Move(match arg() % 11 {
+                0 => CursorMove::Forward,
+                1 => CursorMove::Back,
+                2 => CursorMove::Up,
+                3 => CursorMove::Down,
+                4 => CursorMove::Head,
+                5 => CursorMove::End,
+                6 => CursorMove::WordForward,
+                7 => CursorMove::WordBack,
+                8 => CursorMove::InViewport,
+                _ => CursorMove::Jump(arg() as u16 % 8, arg() as u16 % 16),
+            }),
+            9 => Edit::StartSelection,
+            10 => Edit::CancelSelection,
+            11 => Edit::Scroll(arg() as i8 as i16, arg() as i8 as i16),
+            12 if arg() % 8 == 0 => {
+                Edit::SetText((0..arg() % 24).map(|_| char_from(arg())).collect())
+            }
+            13 if arg() % 2 == 0 => Edit::Undo,
+            13 if arg() % 2 == 0 => Edit::Redo,
+            12 | 13 => Edit::Input(Input {
+                key: Key::Enter,
+                ..Input::default()
+            }),
+            _ => Edit::Render(arg() as u16 % 40, arg() as u16 % 12),
+        };
+        edits.push(edit);
+    }
+    edits
+}
+
+/// The keys other than chars the textarea maps.
+const KEYS: [Key; 14] = [
+    Key::Backspace,
+    Key::Delete,
+    Key::Tab,
+    Key::Left,
+    Key::Right,
+    Key::Up,
+    Key::Down,
+    Key::Home,
+    Key::End,
+    Key::Enter,
+    Key::Esc,
+    Key::Copy,
+    Key::Cut,
+    Key::Paste,
+];
+
+/// A char for the byte, mostly ASCII, with some newlines, tabs and wide or multi-byte chars.
+fn char_from(byte: u8) -> char {
+    match byte {
+        0..=9 => '\n',
+        10..=14 => '\t',
+        15..=19 => '\r',
+        20..=24 => '🧉',
+        25..=29 => 'ñ',
+        30..=34 => '漢',
+        _ => (b' ' + byte % 95) as char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_edits_keep_the_invariants() {
+        for seed in 0..300 {
+            for single_line in [false, true] {
+                let mut textarea = TextArea::default().with_single_line(single_line);
+                run(&mut textarea, &random_edits(seed, 200));
+            }
+        }
+    }
+
+    #[test]
+    fn inserts_multiline_strings() {
+        let mut textarea = TextArea::from(["mate amargo"]);
+        textarea.move_cursor(CursorMove::Jump(0, 5));
+        run(&mut textarea, &[Edit::InsertStr("dulce\nmate\r\n".into())]);
+        assert_eq!(textarea.lines(), ["mate dulce", "mate", "amargo"]);
+        assert_eq!(textarea.cursor(), (2, 0));
+    }
+
+    #[test]
+    fn renders_into_any_area() {
+        let mut textarea = TextArea::from(["mate", "amargo"])
+            .with_block(ratatui::widgets::Block::bordered())
+            .with_placeholder("mate");
+        textarea.move_cursor(CursorMove::Jump(1, 3));
+
+        // areas too small for the borders, or sticking out of the buffer
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 10));
+        for (x, y, width, height) in [(0, 0, 1, 1), (0, 0, 10, 2), (8, 8, 6, 6), (12, 0, 4, 4)] {
+            textarea.widget().render(Rect::new(x, y, width, height), &mut buf);
+        }
+        textarea.debug_assert_invariants();
+    }
+
+    #[test]
+    fn decodes_any_bytes() {
+        assert_eq!(edits_from_bytes(&[]), []);
+        assert_eq!(edits_from_bytes(&[9, 10]), [Edit::StartSelection, Edit::CancelSelection]);
+        // missing arguments are zeros
+        assert_eq!(edits_from_bytes(&[11]), [Edit::Scroll(0, 0)]);
+        assert_eq!(random_edits(7, 50), random_edits(7, 50));
+    }
+}
@@ -4,12 +4,18 @@ pub(super) mod behaviour {
     pub(super) mod input;
     pub(super) mod scroll;
     pub(super) mod util;
+    pub(super) mod word;
 }
 
 mod core;
+pub mod fuzz;
 
 pub use {
-    behaviour::input::{Input, Key},
+    behaviour::{
+        cursor::CursorMove,
+        input::{Input, Key},
+        word::WordBoundaries,
+    },
     core::{
         validation::{validators, ValidationResult},
         TextArea,